@@ -17,6 +17,7 @@ mod debug_box_ball2;
 mod heightfield2;
 mod joints2;
 mod kinematic2;
+mod polyline2;
 mod pyramid2;
 mod sensor2;
 mod stress_joint_ball2;
@@ -62,6 +63,7 @@ pub fn main() {
         ("Heightfield", heightfield2::init_world),
         ("Joints", joints2::init_world),
         ("Kinematic", kinematic2::init_world),
+        ("Polyline", polyline2::init_world),
         ("Pyramid", pyramid2::init_world),
         ("Sensor", sensor2::init_world),
         ("(Debug) box ball", debug_box_ball2::init_world),
@@ -1,26 +1,22 @@
 use na::Point2;
-use rapier2d::dynamics::{BallJoint, BodyStatus, JointSet, RigidBodyBuilder, RigidBodySet};
+use rapier2d::dynamics::{
+    BallJoint, BodyStatus, DistanceJoint, JointSet, RevoluteJoint, RigidBodyBuilder, RigidBodySet,
+};
 use rapier2d::geometry::{ColliderBuilder, ColliderSet};
 use rapier_testbed2d::Testbed;
 
-pub fn init_world(testbed: &mut Testbed) {
-    /*
-     * World
-     */
-    let mut bodies = RigidBodySet::new();
-    let mut colliders = ColliderSet::new();
-    let mut joints = JointSet::new();
-
-    /*
-     * Create the balls
-     */
-    // Build the rigid body.
-    // NOTE: a smaller radius (e.g. 0.1) breaks Box2D so
+fn create_ball_joints(
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    joints: &mut JointSet,
+    num: usize,
+) {
+    // NOTE: a smaller radius (e.g. 0.1) breaks Box2D so
     // in order to be able to compare rapier with Box2D,
     // we set it to 0.4.
     let rad = 0.4;
-    let numi = 100; // Num vertical nodes.
-    let numk = 100; // Num horizontal nodes.
+    let numi = num; // Num vertical nodes.
+    let numk = num; // Num horizontal nodes.
     let shift = 1.0;
 
     let mut body_handles = Vec::new();
@@ -41,13 +37,13 @@ pub fn init_world(testbed: &mut Testbed) {
                 .build();
             let child_handle = bodies.insert(rigid_body);
             let collider = ColliderBuilder::ball(rad).density(1.0).build();
-            colliders.insert(collider, child_handle, &mut bodies);
+            colliders.insert(collider, child_handle, bodies);
 
             // Vertical joint.
             if i > 0 {
                 let parent_handle = *body_handles.last().unwrap();
                 let joint = BallJoint::new(Point2::origin(), Point2::new(0.0, shift));
-                joints.insert(&mut bodies, parent_handle, child_handle, joint);
+                joints.insert(bodies, parent_handle, child_handle, joint);
             }
 
             // Horizontal joint.
@@ -55,18 +51,182 @@ pub fn init_world(testbed: &mut Testbed) {
                 let parent_index = body_handles.len() - numi;
                 let parent_handle = body_handles[parent_index];
                 let joint = BallJoint::new(Point2::origin(), Point2::new(-shift, 0.0));
-                joints.insert(&mut bodies, parent_handle, child_handle, joint);
+                joints.insert(bodies, parent_handle, child_handle, joint);
             }
 
             body_handles.push(child_handle);
         }
     }
+}
+
+fn create_revolute_joints(
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    joints: &mut JointSet,
+    origin: Point2<f32>,
+    num: usize,
+) {
+    let rad = 0.4;
+    let shift = 1.0;
+
+    let ground = RigidBodyBuilder::new_static()
+        .translation(origin.x, origin.y)
+        .build();
+    let mut curr_parent = bodies.insert(ground);
+    let collider = ColliderBuilder::cuboid(rad, rad).build();
+    colliders.insert(collider, curr_parent, bodies);
+
+    // A horizontal chain that can only bend a little at each joint.
+    for i in 0..num {
+        let x = origin.x - (i + 1) as f32 * shift;
+        let rigid_body = RigidBodyBuilder::new_dynamic()
+            .translation(x, origin.y)
+            .build();
+        let curr_child = bodies.insert(rigid_body);
+        let collider = ColliderBuilder::cuboid(rad, rad).density(1.0).build();
+        colliders.insert(collider, curr_child, bodies);
+
+        let mut revolute = RevoluteJoint::new(Point2::origin(), Point2::new(shift, 0.0));
+        revolute.limits_enabled = true;
+        revolute.limits = [-0.2, 0.2];
+        joints.insert(bodies, curr_parent, curr_child, revolute);
+
+        curr_parent = curr_child;
+    }
+}
+
+fn create_motorized_revolute_joints(
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    joints: &mut JointSet,
+    origin: Point2<f32>,
+) {
+    /*
+     * A paddle spinning at a constant angular velocity.
+     */
+    let ground = RigidBodyBuilder::new_static()
+        .translation(origin.x, origin.y)
+        .build();
+    let ground_handle = bodies.insert(ground);
+
+    let rigid_body = RigidBodyBuilder::new_dynamic()
+        .translation(origin.x, origin.y)
+        .build();
+    let paddle_handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::cuboid(2.0, 0.2).density(1.0).build();
+    colliders.insert(collider, paddle_handle, bodies);
+
+    let mut revolute = RevoluteJoint::new(Point2::origin(), Point2::origin());
+    revolute.configure_motor_velocity(1.0, 0.5);
+    joints.insert(bodies, ground_handle, paddle_handle, revolute);
+
+    /*
+     * An arm held by a spring toward a target angle, with some balls dropped on it.
+     */
+    let arm_length = 1.5;
+    let y = origin.y - 6.0;
+    let ground = RigidBodyBuilder::new_static()
+        .translation(origin.x, y)
+        .build();
+    let ground_handle = bodies.insert(ground);
+
+    let rigid_body = RigidBodyBuilder::new_dynamic()
+        .translation(origin.x + arm_length, y)
+        .build();
+    let arm_handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::cuboid(arm_length, 0.1)
+        .density(1.0)
+        .build();
+    colliders.insert(collider, arm_handle, bodies);
+
+    let mut revolute = RevoluteJoint::new(Point2::origin(), Point2::new(-arm_length, 0.0));
+    revolute.configure_motor_position(0.5, 10.0, 1.0);
+    revolute.limits_enabled = true;
+    revolute.limits = [-1.0, 1.0];
+    joints.insert(bodies, ground_handle, arm_handle, revolute);
+
+    for i in 0..3 {
+        let rigid_body = RigidBodyBuilder::new_dynamic()
+            .translation(origin.x + 1.0 + i as f32 * 0.6, y + 3.0 + i as f32)
+            .build();
+        let handle = bodies.insert(rigid_body);
+        let collider = ColliderBuilder::ball(0.25).density(1.0).build();
+        colliders.insert(collider, handle, bodies);
+    }
+}
+
+fn create_distance_joints(
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    joints: &mut JointSet,
+    origin: Point2<f32>,
+) {
+    let rad = 0.4;
+
+    let ground = RigidBodyBuilder::new_static()
+        .translation(origin.x, origin.y)
+        .build();
+    let ground_handle = bodies.insert(ground);
+    let collider = ColliderBuilder::cuboid(4.0, 0.1).build();
+    colliders.insert(collider, ground_handle, bodies);
+
+    /*
+     * A rope, a rigid rod, and a spring, each holding a box below the ground.
+     */
+    let rope = DistanceJoint::rope(Point2::new(-3.0, 0.0), Point2::origin(), 3.0);
+    let rod = DistanceJoint::rod(Point2::origin(), Point2::origin(), 3.0);
+    let mut spring = DistanceJoint::new(Point2::new(3.0, 0.0), Point2::origin());
+    // The mass of each box is (2 * rad)^2 since their density is 1.
+    spring.configure_spring_frequency(3.0, 1.0, 0.1, 4.0 * rad * rad);
+
+    for (i, joint) in [rope, rod, spring].iter().enumerate() {
+        // Start the boxes a bit to the side so they swing.
+        let x = origin.x - 3.0 + i as f32 * 3.0 + 1.0;
+        let rigid_body = RigidBodyBuilder::new_dynamic()
+            .translation(x, origin.y - 2.0)
+            .build();
+        let handle = bodies.insert(rigid_body);
+        let collider = ColliderBuilder::cuboid(rad, rad).density(1.0).build();
+        colliders.insert(collider, handle, bodies);
+
+        joints.insert(bodies, ground_handle, handle, *joint);
+    }
+}
+
+pub fn init_world(testbed: &mut Testbed) {
+    /*
+     * World
+     */
+    let mut bodies = RigidBodySet::new();
+    let mut colliders = ColliderSet::new();
+    let mut joints = JointSet::new();
+
+    create_ball_joints(&mut bodies, &mut colliders, &mut joints, 100);
+    create_revolute_joints(
+        &mut bodies,
+        &mut colliders,
+        &mut joints,
+        Point2::new(-5.0, 0.0),
+        10,
+    );
+    create_motorized_revolute_joints(
+        &mut bodies,
+        &mut colliders,
+        &mut joints,
+        Point2::new(-10.0, -15.0),
+    );
+    create_distance_joints(
+        &mut bodies,
+        &mut colliders,
+        &mut joints,
+        Point2::new(-10.0, -25.0),
+    );
 
     /*
      * Set up the testbed.
      */
     testbed.set_world(bodies, colliders, joints);
-    testbed.look_at(Point2::new(numk as f32 * rad, numi as f32 * -rad), 5.0);
+    testbed.look_at(Point2::new(40.0, -40.0), 5.0);
 }
 
 fn main() {
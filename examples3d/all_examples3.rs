@@ -13,6 +13,10 @@ use std::cmp::Ordering;
 mod balls3;
 mod boxes3;
 mod capsules3;
+mod character_controller3;
+mod compound3;
+mod convex_polyhedron3;
+mod cylinders3;
 mod debug_boxes3;
 mod debug_triangle3;
 mod domino3;
@@ -20,6 +24,7 @@ mod heightfield3;
 mod joints3;
 mod kinematic3;
 mod pyramid3;
+mod round_boxes3;
 mod sensor3;
 mod stacks3;
 mod stress_joint_ball3;
@@ -28,6 +33,7 @@ mod stress_joint_prismatic3;
 mod stress_joint_revolute3;
 mod stress_keva3;
 mod trimesh3;
+mod voxels3;
 
 fn demo_name_from_command_line() -> Option<String> {
     let mut args = std::env::args();
@@ -69,14 +75,20 @@ pub fn main() {
         ("Balls", balls3::init_world),
         ("Boxes", boxes3::init_world),
         ("Capsules", capsules3::init_world),
+        ("Character controller", character_controller3::init_world),
+        ("Compound", compound3::init_world),
+        ("Convex polyhedron", convex_polyhedron3::init_world),
+        ("Cylinders", cylinders3::init_world),
         ("Domino", domino3::init_world),
         ("Heightfield", heightfield3::init_world),
         ("Joints", joints3::init_world),
         ("Kinematic", kinematic3::init_world),
         ("Stacks", stacks3::init_world),
         ("Pyramid", pyramid3::init_world),
+        ("Round boxes", round_boxes3::init_world),
         ("Sensor", sensor3::init_world),
         ("Trimesh", trimesh3::init_world),
+        ("Voxels", voxels3::init_world),
         ("(Debug) boxes", debug_boxes3::init_world),
         ("(Debug) triangle", debug_triangle3::init_world),
         ("(Stress test) joint ball", stress_joint_ball3::init_world),
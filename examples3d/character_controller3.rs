@@ -0,0 +1,142 @@
+use na::{Isometry3, Point3, Vector3};
+use rapier3d::control::KinematicCharacterController;
+use rapier3d::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
+use rapier3d::geometry::{ColliderBuilder, ColliderSet};
+use rapier3d::pipeline::QueryPipeline;
+use rapier_testbed3d::Testbed;
+
+pub fn init_world(testbed: &mut Testbed) {
+    /*
+     * World
+     */
+    let mut bodies = RigidBodySet::new();
+    let mut colliders = ColliderSet::new();
+    let joints = JointSet::new();
+
+    /*
+     * Ground.
+     */
+    let ground_size = 20.0;
+    let ground_height = 0.1;
+
+    let rigid_body = RigidBodyBuilder::new_static()
+        .translation(0.0, -ground_height, 0.0)
+        .build();
+    let ground_handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::cuboid(ground_size, ground_height, ground_size).build();
+    colliders.insert(collider, ground_handle, &mut bodies);
+
+    /*
+     * Stairs.
+     */
+    let step_height = 0.2;
+    let step_width = 0.5;
+
+    for i in 0..10 {
+        let height = step_height * (i + 1) as f32;
+        let collider = ColliderBuilder::cuboid(step_width / 2.0, height / 2.0, 2.0)
+            .delta(Isometry3::translation(
+                6.0 + i as f32 * step_width,
+                height / 2.0,
+                0.0,
+            ))
+            .build();
+        colliders.insert(collider, ground_handle, &mut bodies);
+    }
+
+    /*
+     * A slope that can be climbed, and one that is too steep.
+     */
+    let slopes = [(-6.0, 0.4), (-1.0, 1.2)];
+
+    for (z, angle) in slopes.iter() {
+        let collider = ColliderBuilder::cuboid(2.0, 0.1, 2.0)
+            .delta(Isometry3::new(
+                Vector3::new(-6.0, 0.0, *z),
+                Vector3::z() * *angle,
+            ))
+            .build();
+        colliders.insert(collider, ground_handle, &mut bodies);
+    }
+
+    /*
+     * Some boxes the character can push.
+     */
+    let rad = 0.3;
+
+    for i in 0..5 {
+        let rigid_body = RigidBodyBuilder::new_dynamic()
+            .translation(0.0, rad, -4.0 + i as f32 * rad * 3.0)
+            .build();
+        let handle = bodies.insert(rigid_body);
+        let collider = ColliderBuilder::cuboid(rad, rad, rad).density(1.0).build();
+        colliders.insert(collider, handle, &mut bodies);
+    }
+
+    /*
+     * The character.
+     */
+    let character_body = RigidBodyBuilder::new_kinematic()
+        .translation(0.0, 1.0, 0.0)
+        .build();
+    let character_handle = bodies.insert(character_body);
+    let collider = ColliderBuilder::capsule_y(0.3, 0.15).build();
+    let character_collider = colliders.insert(collider, character_handle, &mut bodies);
+
+    /*
+     * Setup a callback that makes the character run in circles.
+     */
+    let controller = KinematicCharacterController::default();
+    let mut queries = QueryPipeline::new();
+    let mut vertical_speed = 0.0;
+
+    testbed.add_callback(move |bodies, colliders, _, _, time| {
+        let dt = 0.016;
+        let speed = 3.0;
+        let horizontal_dir = Vector3::new((time * 0.3).cos(), 0.0, (time * 0.3).sin());
+        vertical_speed -= 9.81 * dt;
+        let desired_translation = horizontal_dir * speed * dt + Vector3::y() * vertical_speed * dt;
+
+        queries.update(colliders);
+        let mut hits = Vec::new();
+        let movement = controller.move_shape(
+            &queries,
+            colliders,
+            character_collider,
+            &desired_translation,
+            |collision| hits.push(collision),
+        );
+
+        if movement.grounded {
+            vertical_speed = 0.0;
+        }
+
+        // Push the dynamic bodies hit by the character.
+        for hit in hits {
+            let parent = colliders[hit.handle].parent();
+
+            if let Some(mut body) = bodies.get_mut(parent) {
+                if body.is_dynamic() {
+                    let impulse = hit.translation_remaining * body.mass() * 0.5;
+                    body.apply_impulse(impulse);
+                }
+            }
+        }
+
+        let mut character = bodies.get_mut(character_handle).unwrap();
+        let mut next_pos = character.position;
+        next_pos.translation.vector += movement.translation;
+        character.set_next_kinematic_position(next_pos);
+    });
+
+    /*
+     * Set up the testbed.
+     */
+    testbed.set_world(bodies, colliders, joints);
+    testbed.look_at(Point3::new(15.0, 10.0, 15.0), Point3::origin());
+}
+
+fn main() {
+    let testbed = Testbed::from_builders(0, vec![("Character controller", init_world)]);
+    testbed.run()
+}
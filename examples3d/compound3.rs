@@ -0,0 +1,88 @@
+use na::{Isometry3, Point3, Vector3};
+use rapier3d::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
+use rapier3d::geometry::{ColliderBuilder, ColliderSet, Cuboid, Shape};
+use rapier_testbed3d::Testbed;
+
+pub fn init_world(testbed: &mut Testbed) {
+    /*
+     * World
+     */
+    let mut bodies = RigidBodySet::new();
+    let mut colliders = ColliderSet::new();
+    let joints = JointSet::new();
+
+    /*
+     * Ground
+     */
+    let ground_size = 50.0;
+    let ground_height = 0.1;
+
+    let rigid_body = RigidBodyBuilder::new_static()
+        .translation(0.0, -ground_height, 0.0)
+        .build();
+    let handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::cuboid(ground_size, ground_height, ground_size).build();
+    colliders.insert(collider, handle, &mut bodies);
+
+    /*
+     * Create the compound shapes: a table-like shape with a top and four legs.
+     */
+    let num = 6;
+    let rad = 1.0;
+    let leg = rad * 0.2;
+
+    let shift = rad * 2.0 + 1.0;
+    let centerx = shift * (num as f32) / 2.0;
+    let centery = shift / 2.0;
+    let centerz = shift * (num as f32) / 2.0;
+
+    let top = Shape::Cuboid(Cuboid::new(Vector3::new(rad, leg, rad)));
+    let foot = Shape::Cuboid(Cuboid::new(Vector3::new(leg, rad * 0.5, leg)));
+    let foot_shift = rad - leg;
+    let shapes = vec![
+        (Isometry3::identity(), top),
+        (
+            Isometry3::translation(foot_shift, -rad * 0.5, foot_shift),
+            foot.clone(),
+        ),
+        (
+            Isometry3::translation(-foot_shift, -rad * 0.5, foot_shift),
+            foot.clone(),
+        ),
+        (
+            Isometry3::translation(foot_shift, -rad * 0.5, -foot_shift),
+            foot.clone(),
+        ),
+        (
+            Isometry3::translation(-foot_shift, -rad * 0.5, -foot_shift),
+            foot,
+        ),
+    ];
+
+    for j in 0usize..10 {
+        for i in 0..num {
+            for k in 0usize..num {
+                let x = i as f32 * shift - centerx;
+                let y = j as f32 * shift + centery + 3.0;
+                let z = k as f32 * shift - centerz;
+
+                // Build the rigid body.
+                let rigid_body = RigidBodyBuilder::new_dynamic().translation(x, y, z).build();
+                let handle = bodies.insert(rigid_body);
+                let collider = ColliderBuilder::compound(shapes.clone()).build();
+                colliders.insert(collider, handle, &mut bodies);
+            }
+        }
+    }
+
+    /*
+     * Set up the testbed.
+     */
+    testbed.set_world(bodies, colliders, joints);
+    testbed.look_at(Point3::new(100.0, 100.0, 100.0), Point3::origin());
+}
+
+fn main() {
+    let testbed = Testbed::from_builders(0, vec![("Compound", init_world)]);
+    testbed.run()
+}
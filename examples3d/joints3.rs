@@ -1,7 +1,7 @@
 use na::{Isometry3, Point3, Unit, Vector3};
 use rapier3d::dynamics::{
-    BallJoint, BodyStatus, FixedJoint, JointSet, PrismaticJoint, RevoluteJoint, RigidBodyBuilder,
-    RigidBodySet,
+    BallJoint, BodyStatus, FixedJoint, GenericJoint, JointSet, PrismaticJoint, RevoluteJoint,
+    RigidBodyBuilder, RigidBodySet,
 };
 use rapier3d::geometry::{ColliderBuilder, ColliderSet};
 use rapier_testbed3d::Testbed;
@@ -228,6 +228,50 @@ fn create_ball_joints(
     }
 }
 
+fn create_generic_joints(
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    joints: &mut JointSet,
+    origin: Point3<f32>,
+    num: usize,
+) {
+    let rad = 0.4;
+    let shift = 1.0;
+
+    let ground = RigidBodyBuilder::new_static()
+        .translation(origin.x, origin.y, origin.z)
+        .build();
+    let mut curr_parent = bodies.insert(ground);
+    let collider = ColliderBuilder::cuboid(rad, rad, rad).build();
+    colliders.insert(collider, curr_parent, bodies);
+
+    // A chain where each link can slide a little along its parent's `x` axis
+    // and bend a little around its parent's `y` and `z` axes.
+    for i in 0..num {
+        let x = origin.x + (i + 1) as f32 * shift;
+        let rigid_body = RigidBodyBuilder::new_dynamic()
+            .translation(x, origin.y, origin.z)
+            .build();
+        let curr_child = bodies.insert(rigid_body);
+        let collider = ColliderBuilder::cuboid(rad, rad, rad).density(1.0).build();
+        colliders.insert(collider, curr_child, bodies);
+
+        let mut joint = GenericJoint::new(
+            Isometry3::translation(shift, 0.0, 0.0),
+            Isometry3::identity(),
+        );
+        joint.set_limits(0, [-0.1, 0.1]);
+        joint.lock_axis(1);
+        joint.lock_axis(2);
+        joint.lock_axis(3);
+        joint.set_limits(4, [-0.2, 0.2]);
+        joint.set_limits(5, [-0.2, 0.2]);
+        joints.insert(bodies, curr_parent, curr_child, joint);
+
+        curr_parent = curr_child;
+    }
+}
+
 pub fn init_world(testbed: &mut Testbed) {
     /*
      * World
@@ -258,6 +302,13 @@ pub fn init_world(testbed: &mut Testbed) {
         5,
     );
     create_ball_joints(&mut bodies, &mut colliders, &mut joints, 15);
+    create_generic_joints(
+        &mut bodies,
+        &mut colliders,
+        &mut joints,
+        Point3::new(0.0, 20.0, 0.0),
+        5,
+    );
 
     /*
      * Set up the testbed.
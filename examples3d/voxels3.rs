@@ -0,0 +1,123 @@
+use na::{Point3, Vector3};
+use rapier3d::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
+use rapier3d::geometry::{ColliderBuilder, ColliderSet};
+use rapier_testbed3d::Testbed;
+
+pub fn init_world(testbed: &mut Testbed) {
+    /*
+     * World
+     */
+    let mut bodies = RigidBodySet::new();
+    let mut colliders = ColliderSet::new();
+    let joints = JointSet::new();
+
+    /*
+     * Ground made of voxels.
+     */
+    let ground_size = 40;
+    let voxel_size = Vector3::repeat(1.0);
+    let mut keys = Vec::new();
+
+    for i in 0..ground_size {
+        for k in 0..ground_size {
+            let x = i as f32 * 0.3;
+            let z = k as f32 * 0.3;
+            let height = (3.0 + x.sin() + z.cos()).round() as i32;
+
+            for j in 0..height {
+                keys.push(Point3::new(i, j, k));
+            }
+        }
+    }
+
+    // The voxels that will be cleared progressively to dig a crater at the center of the ground.
+    let center = Point3::new(ground_size as f32 / 2.0, 5.0, ground_size as f32 / 2.0);
+    let mut dig_order: Vec<_> = keys
+        .iter()
+        .filter(|key| na::distance(&key.map(|e| e as f32), &center) < 6.0)
+        .cloned()
+        .collect();
+    // Dig from the top layers downward. The next voxel to clear is at the end.
+    dig_order.sort_by_key(|key| key.y);
+
+    let offset = -ground_size as f32 / 2.0;
+    let rigid_body = RigidBodyBuilder::new_static()
+        .translation(offset, 0.0, offset)
+        .build();
+    let ground_handle = bodies.insert(rigid_body);
+    let collider = ColliderBuilder::voxels(voxel_size, &keys).build();
+    let voxels_handle = colliders.insert(collider, ground_handle, &mut bodies);
+
+    /*
+     * Create the cubes and balls.
+     */
+    let num = 6;
+    let rad = 0.5;
+
+    let shift = rad * 2.0 + rad;
+    let centerx = shift * (num / 2) as f32;
+    let centerz = shift * (num / 2) as f32;
+
+    for j in 0usize..10 {
+        for i in 0..num {
+            for k in 0usize..num {
+                let x = i as f32 * shift - centerx;
+                let y = j as f32 * shift + 8.0;
+                let z = k as f32 * shift - centerz;
+
+                // Build the rigid body.
+                let rigid_body = RigidBodyBuilder::new_dynamic().translation(x, y, z).build();
+                let handle = bodies.insert(rigid_body);
+
+                if j % 2 == 0 {
+                    let collider = ColliderBuilder::cuboid(rad, rad, rad).density(1.0).build();
+                    colliders.insert(collider, handle, &mut bodies);
+                } else {
+                    let collider = ColliderBuilder::ball(rad).density(1.0).build();
+                    colliders.insert(collider, handle, &mut bodies);
+                }
+            }
+        }
+    }
+
+    /*
+     * Setup a callback that clears one voxel at each frame, after a few seconds.
+     */
+    testbed.add_callback(move |bodies, colliders, _, graphics, time| {
+        if time < 3.0 {
+            return;
+        }
+
+        if let Some(key) = dig_order.pop() {
+            let removed_id = colliders
+                .modify_voxels(voxels_handle, bodies, |voxels| {
+                    let id = voxels.voxel_id(&key)?;
+                    let _ = voxels.remove(&key);
+                    Some(id)
+                })
+                .flatten();
+
+            // The testbed renders one box per voxel, in the order of the voxel ids.
+            if let Some(id) = removed_id {
+                if let Some(node) = graphics
+                    .body_nodes_mut(ground_handle)
+                    .and_then(|nodes| nodes.get_mut(id as usize))
+                    .and_then(|node| node.scene_node_mut())
+                {
+                    node.set_visible(false);
+                }
+            }
+        }
+    });
+
+    /*
+     * Set up the testbed.
+     */
+    testbed.set_world(bodies, colliders, joints);
+    testbed.look_at(Point3::new(30.0, 30.0, 30.0), Point3::origin());
+}
+
+fn main() {
+    let testbed = Testbed::from_builders(0, vec![("Voxels", init_world)]);
+    testbed.run()
+}
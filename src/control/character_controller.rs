@@ -0,0 +1,475 @@
+use crate::dynamics::RigidBodyHandle;
+use crate::geometry::{
+    Collider, ColliderHandle, ColliderSet, InteractionGroups, Shape, TOIStatus, TOI,
+};
+use crate::math::{Isometry, Point, Translation, Vector};
+use crate::pipeline::QueryPipeline;
+
+/// The maximum number of obstacles the character can hit during a single movement.
+const MAX_ITERATIONS: usize = 20;
+/// Translations smaller than this are ignored.
+const EPSILON: f32 = 1.0e-5;
+
+/// A character controller for kinematic rigid-bodies.
+///
+/// The character controller computes the movement of the collider of a character so that
+/// it slides along the obstacles it hits, climbs stairs, and stays on the ground when going
+/// down slopes. The computed movement should then be applied to the kinematic rigid-body the
+/// collider is attached to with `RigidBody::set_next_kinematic_position`.
+///
+/// The obstacles are found with a `QueryPipeline` which must be up-to-date with the
+/// positions of the colliders. Sensors, as well as the colliders attached to the same
+/// rigid-body as the character, are never considered as obstacles.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct KinematicCharacterController {
+    /// The direction that goes up, used to detect the ground and its slope (default: `+y`).
+    ///
+    /// This must be a unit vector.
+    pub up: Vector<f32>,
+    /// The gap kept between the character and the obstacles it touches (default: `0.01`).
+    ///
+    /// This should be small but non-zero, to prevent the character from getting stuck
+    /// inside of the obstacles because of numerical errors.
+    pub offset: f32,
+    /// If `true`, the character slides along the obstacles it hits. Otherwise, it stops
+    /// at the first obstacle (default: `true`).
+    pub slide: bool,
+    /// The maximum height of the steps the character climbs automatically (default: `0.25`).
+    ///
+    /// Set this to zero to disable stairs climbing.
+    pub max_step_height: f32,
+    /// The minimum free space required on top of a step for the character to climb it
+    /// (default: `0.2`).
+    pub min_step_width: f32,
+    /// The maximum angle, in radians, between `up` and the normal of the slopes the character
+    /// can climb (default: `π / 4`).
+    ///
+    /// Steeper slopes are handled like walls: the character can slide down but not up.
+    pub max_slope_climb_angle: f32,
+    /// The maximum distance the character is moved down to stay on the ground when
+    /// it goes down a slope or a step (default: `0.2`).
+    ///
+    /// Set this to zero to disable snapping to the ground.
+    pub snap_to_ground: f32,
+}
+
+impl Default for KinematicCharacterController {
+    fn default() -> Self {
+        Self {
+            up: Vector::y(),
+            offset: 0.01,
+            slide: true,
+            max_step_height: 0.25,
+            min_step_width: 0.2,
+            max_slope_climb_angle: std::f32::consts::FRAC_PI_4,
+            snap_to_ground: 0.2,
+        }
+    }
+}
+
+/// A collision between the character and an obstacle, detected while moving the character.
+#[derive(Clone, Debug)]
+pub struct CharacterCollision {
+    /// The handle of the collider hit by the character.
+    pub handle: ColliderHandle,
+    /// The translation of the character performed before the collision.
+    pub translation_applied: Vector<f32>,
+    /// The translation the character had yet to perform when the collision occurred.
+    pub translation_remaining: Vector<f32>,
+    /// The time of impact between the character and the obstacle.
+    ///
+    /// The witness points and normals are expressed in the local-space of the character
+    /// (`witness1`, `normal1`) and of the obstacle (`witness2`, `normal2`).
+    pub toi: TOI,
+}
+
+/// The movement of a character computed by the character controller.
+#[derive(Copy, Clone, Debug)]
+pub struct EffectiveCharacterMovement {
+    /// The translation to apply to the character.
+    pub translation: Vector<f32>,
+    /// Is the character standing on the ground after applying `translation`?
+    pub grounded: bool,
+}
+
+impl KinematicCharacterController {
+    /// Computes the movement of a character trying to perform the given translation.
+    ///
+    /// The `events` closure is called for each obstacle hit by the character. This can
+    /// be used, e.g., to apply impulses to the dynamic rigid-bodies pushed by the character.
+    ///
+    /// # Parameters
+    /// - `queries`: the query pipeline used to detect the obstacles.
+    /// - `colliders`: the set of colliders taking part in the query pipeline.
+    /// - `character`: the handle of the collider of the character. Only the colliders with
+    ///   collision groups compatible with the collision groups of this collider are obstacles.
+    /// - `desired_translation`: the translation the character would like to perform.
+    /// - `events`: the closure called for each collision detected during the movement.
+    pub fn move_shape(
+        &self,
+        queries: &QueryPipeline,
+        colliders: &ColliderSet,
+        character: ColliderHandle,
+        desired_translation: &Vector<f32>,
+        mut events: impl FnMut(CharacterCollision),
+    ) -> EffectiveCharacterMovement {
+        let character_collider = &colliders[character];
+        let ctxt = CharacterContext {
+            queries,
+            colliders,
+            shape: character_collider.shape(),
+            parent: character_collider.parent(),
+            groups: character_collider.collision_groups(),
+        };
+        let start_pos = *character_collider.position();
+
+        let was_grounded = self.detect_grounded(&ctxt, &start_pos);
+        let mut touched_ground = false;
+        let mut translation = Vector::zeros();
+        let mut translation_remaining = *desired_translation;
+
+        for _ in 0..MAX_ITERATIONS {
+            let dist = translation_remaining.norm();
+
+            if dist <= EPSILON {
+                break;
+            }
+
+            let dir = translation_remaining / dist;
+            let pos = Translation::from(translation) * start_pos;
+
+            if let Some((handle, collider, toi)) = ctxt.cast(&pos, &dir, dist + self.offset) {
+                // Stop the character right before the obstacle.
+                let allowed_dist = (toi.toi - self.offset).max(0.0).min(dist);
+                translation += dir * allowed_dist;
+                translation_remaining -= dir * allowed_dist;
+
+                events(CharacterCollision {
+                    handle,
+                    translation_applied: translation,
+                    translation_remaining,
+                    toi: toi.clone(),
+                });
+
+                let normal = if toi.status == TOIStatus::Penetrating {
+                    // The character is moving deeper into this obstacle: it can only move
+                    // tangentially to, or away from, it.
+                    penetration_normal(collider, &pos).unwrap_or(-dir)
+                } else {
+                    (collider.position() * toi.normal2).into_inner()
+                };
+
+                if self.is_climbable(&normal) {
+                    touched_ground = true;
+                } else if was_grounded || touched_ground {
+                    let pos = Translation::from(translation) * start_pos;
+
+                    if let Some(step) = self.climb_step(&ctxt, &pos, &translation_remaining) {
+                        translation += step;
+                        translation_remaining = Vector::zeros();
+                        touched_ground = true;
+                        continue;
+                    }
+                }
+
+                if !self.slide {
+                    break;
+                }
+
+                translation_remaining = self.slide(&translation_remaining, &normal);
+            } else {
+                translation += translation_remaining;
+                translation_remaining = Vector::zeros();
+            }
+        }
+
+        // Keep the character on the ground when it goes down a slope or a step,
+        // unless it is jumping.
+        if self.snap_to_ground > 0.0
+            && (was_grounded || touched_ground)
+            && desired_translation.dot(&self.up) <= EPSILON
+        {
+            let pos = Translation::from(translation) * start_pos;
+
+            if let Some((_, collider, toi)) =
+                ctxt.cast(&pos, &-self.up, self.snap_to_ground + self.offset)
+            {
+                if toi.status != TOIStatus::Penetrating
+                    && self.is_climbable(&(collider.position() * toi.normal2))
+                {
+                    translation -= self.up * (toi.toi - self.offset).max(0.0);
+                }
+            }
+        }
+
+        let end_pos = Translation::from(translation) * start_pos;
+
+        EffectiveCharacterMovement {
+            translation,
+            grounded: self.detect_grounded(&ctxt, &end_pos),
+        }
+    }
+
+    /// Can the character stand on a surface with the given normal?
+    fn is_climbable(&self, normal: &Vector<f32>) -> bool {
+        normal.dot(&self.up) >= self.max_slope_climb_angle.cos() - EPSILON
+    }
+
+    /// Is the character at the given position standing on a climbable surface?
+    fn detect_grounded(&self, ctxt: &CharacterContext, pos: &Isometry<f32>) -> bool {
+        match ctxt.cast(pos, &-self.up, self.offset * 2.0) {
+            Some((_, collider, toi)) => {
+                toi.status == TOIStatus::Penetrating
+                    || self.is_climbable(&(collider.position() * toi.normal2))
+            }
+            None => false,
+        }
+    }
+
+    /// The translation remaining after sliding along an obstacle with the given normal.
+    fn slide(&self, translation: &Vector<f32>, normal: &Vector<f32>) -> Vector<f32> {
+        let sliding = translation - normal * translation.dot(normal).min(0.0);
+
+        if !self.is_climbable(normal) && sliding.dot(&self.up) > translation.dot(&self.up).max(0.0)
+        {
+            // Steep slopes must not push the character upward: handle them like vertical walls.
+            let horizontal_normal = normal - self.up * normal.dot(&self.up);
+
+            if let Some(horizontal_normal) = horizontal_normal.try_normalize(EPSILON) {
+                return translation
+                    - horizontal_normal * translation.dot(&horizontal_normal).min(0.0);
+            }
+        }
+
+        sliding
+    }
+
+    /// Attempts to climb the step blocking the horizontal part of the given translation.
+    ///
+    /// Returns the translation moving the character on top of the step.
+    fn climb_step(
+        &self,
+        ctxt: &CharacterContext,
+        pos: &Isometry<f32>,
+        translation: &Vector<f32>,
+    ) -> Option<Vector<f32>> {
+        if self.max_step_height <= 0.0 {
+            return None;
+        }
+
+        let horizontal = translation - self.up * translation.dot(&self.up);
+        let horizontal_dist = horizontal.norm();
+
+        if horizontal_dist <= EPSILON {
+            return None;
+        }
+
+        let horizontal_dir = horizontal / horizontal_dist;
+
+        // Move up, as high as a step can be, unless there is a ceiling.
+        let up_dist = match ctxt.cast(pos, &self.up, self.max_step_height + self.offset) {
+            Some((_, _, toi)) => (toi.toi - self.offset).max(0.0),
+            None => self.max_step_height,
+        };
+
+        if up_dist <= EPSILON {
+            return None;
+        }
+
+        // Move forward, checking that there is enough room on top of the step.
+        let pos_up = Translation::from(self.up * up_dist) * pos;
+        let step_width = horizontal_dist.max(self.min_step_width);
+
+        if ctxt
+            .cast(&pos_up, &horizontal_dir, step_width + self.offset)
+            .is_some()
+        {
+            return None;
+        }
+
+        // Move down onto the step.
+        let pos_forward = Translation::from(horizontal_dir * step_width) * pos_up;
+        let (_, collider, toi) = ctxt.cast(&pos_forward, &-self.up, up_dist)?;
+
+        if toi.status == TOIStatus::Penetrating
+            || !self.is_climbable(&(collider.position() * toi.normal2))
+        {
+            return None;
+        }
+
+        let down_dist = (toi.toi - self.offset).max(0.0);
+        Some(self.up * (up_dist - down_dist) + horizontal_dir * horizontal_dist)
+    }
+}
+
+/// The data needed to find the obstacles of a character.
+struct CharacterContext<'a> {
+    queries: &'a QueryPipeline,
+    colliders: &'a ColliderSet,
+    shape: &'a Shape,
+    parent: RigidBodyHandle,
+    groups: InteractionGroups,
+}
+
+impl<'a> CharacterContext<'a> {
+    /// Finds the first obstacle hit by the character moving from `pos` along the unit
+    /// direction `dir`, up to the distance `max_dist`.
+    ///
+    /// The obstacles the character is already penetrating are ignored if `dir` points
+    /// out of them, so the character can always move out of a penetration.
+    fn cast(
+        &self,
+        pos: &Isometry<f32>,
+        dir: &Vector<f32>,
+        max_dist: f32,
+    ) -> Option<(ColliderHandle, &'a Collider, TOI)> {
+        let mut ignored = Vec::new();
+
+        loop {
+            let (handle, collider, toi) = self.queries.cast_shape_with_filter(
+                self.colliders,
+                pos,
+                dir,
+                self.shape,
+                max_dist,
+                |handle, collider| {
+                    collider.parent() != self.parent
+                        && !collider.is_sensor()
+                        && self.groups.test(collider.collision_groups())
+                        && !ignored.contains(&handle)
+                },
+            )?;
+
+            if toi.status == TOIStatus::Penetrating {
+                if let Some(normal) = penetration_normal(collider, pos) {
+                    if dir.dot(&normal) >= -EPSILON {
+                        ignored.push(handle);
+                        continue;
+                    }
+                }
+            }
+
+            return Some((handle, collider, toi));
+        }
+    }
+}
+
+/// An approximation of the direction the character penetrating an obstacle
+/// should move along to get out of it.
+fn penetration_normal(obstacle: &Collider, character_pos: &Isometry<f32>) -> Option<Vector<f32>> {
+    let center = Point::from(character_pos.translation.vector);
+    let (proj, _) = obstacle
+        .shape()
+        .project_point(obstacle.position(), &center, false)?;
+    let dir = center - proj.point;
+
+    if proj.is_inside {
+        (-dir).try_normalize(EPSILON)
+    } else {
+        dir.try_normalize(EPSILON)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::KinematicCharacterController;
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{ColliderBuilder, ColliderHandle, ColliderSet};
+    use crate::math::Vector;
+    use crate::pipeline::QueryPipeline;
+
+    #[cfg(feature = "dim2")]
+    fn vector(x: f32, y: f32) -> Vector<f32> {
+        Vector::new(x, y)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn vector(x: f32, y: f32) -> Vector<f32> {
+        Vector::new(x, y, 0.0)
+    }
+
+    #[cfg(feature = "dim2")]
+    fn cuboid(hx: f32, hy: f32) -> ColliderBuilder {
+        ColliderBuilder::cuboid(hx, hy)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn cuboid(hx: f32, hy: f32) -> ColliderBuilder {
+        ColliderBuilder::cuboid(hx, hy, 1.0)
+    }
+
+    #[cfg(feature = "dim2")]
+    fn body_at(body: RigidBodyBuilder, x: f32, y: f32) -> RigidBodyBuilder {
+        body.translation(x, y)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn body_at(body: RigidBodyBuilder, x: f32, y: f32) -> RigidBodyBuilder {
+        body.translation(x, y, 0.0)
+    }
+
+    /// A floor with its top at `y = 0`, and a 0.2-high step covering `1 <= x <= 3`.
+    fn floor_with_step(bodies: &mut RigidBodySet, colliders: &mut ColliderSet) {
+        let floor = bodies.insert(body_at(RigidBodyBuilder::new_static(), 0.0, -0.5).build());
+        colliders.insert(cuboid(10.0, 0.5).build(), floor, bodies);
+        let step = bodies.insert(body_at(RigidBodyBuilder::new_static(), 2.0, 0.1).build());
+        colliders.insert(cuboid(1.0, 0.1).build(), step, bodies);
+    }
+
+    fn character_at(
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        x: f32,
+        y: f32,
+    ) -> ColliderHandle {
+        let body = bodies.insert(body_at(RigidBodyBuilder::new_kinematic(), x, y).build());
+        colliders.insert(cuboid(0.25, 0.25).build(), body, bodies)
+    }
+
+    #[test]
+    fn character_slides_climbs_steps_and_snaps_to_the_ground() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        floor_with_step(&mut bodies, &mut colliders);
+        let controller = KinematicCharacterController::default();
+
+        // Standing on the floor, away from the step.
+        let on_floor = character_at(&mut bodies, &mut colliders, -3.0, 0.26);
+        // On the floor, right before the step.
+        let before_step = character_at(&mut bodies, &mut colliders, 0.0, 0.26);
+        // On the step, right before its edge.
+        let on_step = character_at(&mut bodies, &mut colliders, 3.1, 0.46);
+        // Slightly sunk into the floor.
+        let sunk = character_at(&mut bodies, &mut colliders, -6.0, 0.2);
+
+        let mut queries = QueryPipeline::new();
+        queries.update(&colliders);
+
+        // NOTE: the results are only accurate up to the gap kept between the character
+        // and the obstacles.
+
+        // Moving diagonally into the floor slides along it.
+        let movement =
+            controller.move_shape(&queries, &colliders, on_floor, &vector(1.0, -1.0), |_| {});
+        assert!((movement.translation - vector(1.0, 0.0)).norm() < controller.offset);
+        assert!(movement.grounded);
+
+        // Walking into the step climbs it.
+        let movement =
+            controller.move_shape(&queries, &colliders, before_step, &vector(1.0, 0.0), |_| {});
+        assert!((movement.translation - vector(1.0, 0.2)).norm() < controller.offset);
+        assert!(movement.grounded);
+
+        // Walking off the step snaps the character back to the floor.
+        let movement =
+            controller.move_shape(&queries, &colliders, on_step, &vector(0.5, 0.0), |_| {});
+        assert!((movement.translation - vector(0.5, -0.2)).norm() < controller.offset);
+        assert!(movement.grounded);
+
+        // A character penetrating the floor can walk out of it, and still stands on it.
+        let movement = controller.move_shape(&queries, &colliders, sunk, &vector(1.0, 0.0), |_| {});
+        assert!((movement.translation - vector(1.0, 0.0)).norm() < controller.offset);
+        assert!(movement.grounded);
+    }
+}
@@ -0,0 +1,7 @@
+//! Structures for controlling the motion of rigid-bodies, e.g., character controllers.
+
+pub use self::character_controller::{
+    CharacterCollision, EffectiveCharacterMovement, KinematicCharacterController,
+};
+
+mod character_controller;
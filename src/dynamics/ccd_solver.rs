@@ -0,0 +1,353 @@
+use crate::dynamics::{IntegrationParameters, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{ColliderHandle, ColliderSet, NarrowPhase, TOIStatus, AABB};
+use crate::math::{Isometry, Vector};
+use crate::pipeline::{PairFilterContext, PhysicsHooks, QueryPipeline};
+use ncollide::bounding_volume::BoundingVolume;
+
+struct CCDBody {
+    handle: RigidBodyHandle,
+    // The position of the body at the beginning of its remaining motion.
+    start: Isometry<f32>,
+}
+
+struct CCDImpact {
+    // Index of the impacting body on the `CCDSolver::bodies` array.
+    body_id: usize,
+    // The fraction of the remaining motion after which the impact happens.
+    toi: f32,
+    // The world-space normal of the impact, pointing toward the obstacle.
+    normal: Vector<f32>,
+    restitution: f32,
+}
+
+/// Solver responsible for preventing fast-moving bodies with CCD enabled from tunnelling.
+///
+/// The motion of each CCD body during a timestep is approximated by a translation. If this
+/// translation hits an obstacle, the body is moved back to the time of impact, its velocity toward
+/// the obstacle is removed, and it is moved again by its remaining motion. Each of these
+/// resolutions is a CCD substep.
+pub(crate) struct CCDSolver {
+    bodies: Vec<CCDBody>,
+    impacts: Vec<CCDImpact>,
+    // The obstacles that don't move during the CCD substeps.
+    query_pipeline: QueryPipeline,
+    // The non-sensor colliders attached to the CCD bodies, which move during the CCD substeps.
+    moving_obstacles: Vec<ColliderHandle>,
+    obstacles: Vec<ColliderHandle>,
+}
+
+impl CCDSolver {
+    pub fn new() -> Self {
+        Self {
+            bodies: Vec::new(),
+            impacts: Vec::new(),
+            query_pipeline: QueryPipeline::new(),
+            moving_obstacles: Vec::new(),
+            obstacles: Vec::new(),
+        }
+    }
+
+    /// Records the positions of all the active CCD bodies before they get integrated.
+    pub fn init(&mut self, bodies: &RigidBodySet) {
+        self.bodies.clear();
+
+        for handle in &bodies.active_dynamic_set {
+            let rb = &bodies[*handle];
+
+            if rb.is_ccd_enabled() {
+                self.bodies.push(CCDBody {
+                    handle: *handle,
+                    start: rb.position,
+                })
+            }
+        }
+    }
+
+    /// Are there CCD bodies that still need to be processed?
+    pub fn has_ccd_bodies(&self) -> bool {
+        !self.bodies.is_empty()
+    }
+
+    /// Updates the acceleration structure used to find the obstacles of the CCD bodies.
+    ///
+    /// This must be called once per timestep, after all the bodies have been integrated and
+    /// before the first CCD substep.
+    pub fn update_obstacles(&mut self, bodies: &RigidBodySet, colliders: &ColliderSet) {
+        self.query_pipeline.update(colliders);
+        self.moving_obstacles.clear();
+
+        for ccd_body in &self.bodies {
+            for handle in &bodies[ccd_body.handle].colliders {
+                if !colliders[*handle].is_sensor() {
+                    self.moving_obstacles.push(*handle);
+                }
+            }
+        }
+    }
+
+    /// Computes the first impact of each CCD body along its remaining motion.
+    ///
+    /// Returns `false` if no impact was found.
+    pub fn compute_impacts(
+        &mut self,
+        params: &IntegrationParameters,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        hooks: &dyn PhysicsHooks,
+    ) -> bool {
+        self.impacts.clear();
+
+        for (body_id, ccd_body) in self.bodies.iter().enumerate() {
+            let rb = &bodies[ccd_body.handle];
+            let mut best = None;
+            let mut best_toi = 1.0;
+
+            for collider_handle in &rb.colliders {
+                let collider = &colliders[*collider_handle];
+
+                if collider.is_sensor() {
+                    continue;
+                }
+
+                // NOTE: the rotational part of the motion is not taken into account.
+                let start_pos = ccd_body.start * collider.delta();
+                let motion = collider.position().translation.vector - start_pos.translation.vector;
+
+                // A collider moving by less than its own thickness cannot tunnel through anything.
+                let thickness = collider.compute_aabb().half_extents().min();
+                if motion.norm() < thickness {
+                    continue;
+                }
+
+                let start_aabb = collider.shape().compute_aabb(&start_pos);
+                let end_aabb = AABB::new(start_aabb.mins + motion, start_aabb.maxs + motion);
+                let swept_aabb = start_aabb.merged(&end_aabb);
+
+                // The AABBs of the moving obstacles stored by the query pipeline may be
+                // outdated, so these obstacles are tested separately.
+                // NOTE: sensors don't stop the motion of CCD bodies.
+                let moving_obstacles = &self.moving_obstacles;
+                let obstacles = &mut self.obstacles;
+                obstacles.clear();
+                self.query_pipeline.intersections_with_aabb(
+                    colliders,
+                    &swept_aabb,
+                    collider.collision_groups,
+                    |handle, obstacle| {
+                        if !obstacle.is_sensor() && !moving_obstacles.contains(&handle) {
+                            obstacles.push(handle);
+                        }
+                        true
+                    },
+                );
+                obstacles.extend(
+                    moving_obstacles
+                        .iter()
+                        .copied()
+                        .filter(|handle| colliders[*handle].compute_aabb().intersects(&swept_aabb)),
+                );
+
+                for obstacle_handle in obstacles.iter().copied() {
+                    let obstacle = &colliders[obstacle_handle];
+
+                    if obstacle.parent() == ccd_body.handle
+                        || !collider.collision_groups.test(obstacle.collision_groups)
+                        || !collider.solver_groups.test(obstacle.solver_groups)
+                    {
+                        continue;
+                    }
+
+                    let context = PairFilterContext {
+                        collider_handle1: *collider_handle,
+                        collider_handle2: obstacle_handle,
+                        collider1: collider,
+                        collider2: obstacle,
+                        rigid_body1: rb,
+                        rigid_body2: &bodies[obstacle.parent()],
+                    };
+
+                    if !hooks.filter_contact_pair(&context) {
+                        continue;
+                    }
+
+                    // NOTE: obstacles are assumed to be static at their end-of-step position.
+                    let toi = crate::geometry::time_of_impact(
+                        &start_pos,
+                        &motion,
+                        collider.shape(),
+                        obstacle.position(),
+                        &Vector::zeros(),
+                        obstacle.shape(),
+                        best_toi,
+                    );
+
+                    if let Some(toi) = toi {
+                        let normal = if toi.status == TOIStatus::Penetrating {
+                            if !params.ccd_on_penetration_enabled {
+                                continue;
+                            }
+
+                            // The normal is invalid when the shapes are penetrating.
+                            motion.normalize()
+                        } else {
+                            start_pos * toi.normal1.into_inner()
+                        };
+
+                        // Ignore obstacles the collider is moving away from.
+                        if motion.dot(&normal) <= 0.0 {
+                            continue;
+                        }
+
+                        if best.is_none() || toi.toi < best_toi {
+                            best_toi = toi.toi;
+                            best = Some(CCDImpact {
+                                body_id,
+                                toi: toi.toi,
+                                normal,
+                                restitution: (collider.restitution + obstacle.restitution) * 0.5,
+                            });
+                        }
+                    }
+                }
+            }
+
+            if let Some(impact) = best {
+                self.impacts.push(impact);
+            }
+        }
+
+        !self.impacts.is_empty()
+    }
+
+    /// Moves each impacting body back to its time of impact, and integrates its remaining motion.
+    ///
+    /// Only the bodies that were impacting something are kept for the next CCD substep.
+    pub fn resolve_impacts(
+        &mut self,
+        params: &IntegrationParameters,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+    ) {
+        let mut impacting_bodies = Vec::with_capacity(self.impacts.len());
+
+        for impact in &self.impacts {
+            let ccd_body = &self.bodies[impact.body_id];
+            let rb = bodies
+                .get_mut_internal(ccd_body.handle)
+                .expect("Invalid CCD body handle.");
+
+            let start = ccd_body.start;
+            let motion = rb.position.translation.vector - start.translation.vector;
+            let ang_motion = start.rotation.rotation_to(&rb.position.rotation);
+            let impact_pos = Isometry::from_parts(
+                (start.translation.vector + motion * impact.toi).into(),
+                ang_motion.powf(impact.toi) * start.rotation,
+            );
+
+            // Remove the part of the velocity moving toward the obstacle.
+            let normal_vel = rb.linvel.dot(&impact.normal);
+            if normal_vel > 0.0 {
+                rb.linvel -= impact.normal * (normal_vel * (1.0 + impact.restitution));
+            }
+
+            rb.position = impact_pos;
+
+            if !params.return_after_ccd_substep {
+                rb.integrate(params.dt() * (1.0 - impact.toi));
+            }
+
+            rb.update_predicted_position(params.dt());
+            rb.update_world_mass_properties();
+
+            for handle in &rb.colliders {
+                let collider = &mut colliders[*handle];
+                collider.position = rb.position * collider.delta;
+                collider.predicted_position = rb.predicted_position * collider.delta;
+            }
+
+            impacting_bodies.push(CCDBody {
+                handle: ccd_body.handle,
+                start: impact_pos,
+            });
+        }
+
+        self.bodies = impacting_bodies;
+    }
+
+    /// Pushes the bodies processed by the last CCD substep out of the obstacles they penetrate.
+    ///
+    /// This uses the contacts computed by the narrow-phase after the last CCD substep.
+    pub fn correct_penetrations(
+        &self,
+        params: &IntegrationParameters,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) {
+        for _ in 0..params.max_ccd_position_iterations {
+            let mut corrected_any = false;
+
+            for ccd_body in &self.bodies {
+                let mut correction = Vector::zeros();
+                let rb = &bodies[ccd_body.handle];
+
+                for collider_handle in &rb.colliders {
+                    let collider = &colliders[*collider_handle];
+                    let graph = narrow_phase.contact_graph();
+
+                    for (_, _, pair) in graph.interactions_with(collider.contact_graph_index) {
+                        for manifold in &pair.manifolds {
+                            let co1 = &colliders[manifold.pair.collider1];
+                            let co2 = &colliders[manifold.pair.collider2];
+                            let is_first = manifold.pair.collider1 == *collider_handle;
+
+                            if co1.is_sensor() || co2.is_sensor() {
+                                continue;
+                            }
+
+                            let n1 = co1.position * manifold.local_n1;
+                            let shift1 = manifold.local_n1 * -manifold.kinematics.radius1;
+                            let shift2 = manifold.local_n2 * -manifold.kinematics.radius2;
+                            let radius = manifold.kinematics.radius1 + manifold.kinematics.radius2;
+                            // Take into account the correction already computed for this body.
+                            let sign = if is_first { -1.0 } else { 1.0 };
+                            let corrected_dist = correction.dot(&n1) * sign;
+
+                            for contact in manifold.active_contacts() {
+                                let p1 = co1.position * (contact.local_p1 + shift1);
+                                let p2 = co2.position * (contact.local_p2 + shift2);
+                                let dist = (p2 - p1).dot(&n1) - radius + corrected_dist;
+                                let depth = -dist - params.allowed_linear_error;
+
+                                if depth > 0.0 {
+                                    let depth = depth.min(params.max_linear_correction);
+                                    correction += n1 * (depth * sign);
+                                    corrected_any = true;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if correction != Vector::zeros() {
+                    let rb = bodies
+                        .get_mut_internal(ccd_body.handle)
+                        .expect("Invalid CCD body handle.");
+                    rb.position.translation.vector += correction;
+                    rb.update_predicted_position(params.dt());
+                    rb.update_world_mass_properties();
+
+                    for handle in &rb.colliders {
+                        let collider = &mut colliders[*handle];
+                        collider.position = rb.position * collider.delta;
+                        collider.predicted_position = rb.predicted_position * collider.delta;
+                    }
+                }
+            }
+
+            if !corrected_any {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::math::Point;
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A joint that constrains the distance between a pair of points on two bodies.
+///
+/// The distance can be kept within `[min, max]` limits, which makes this joint behave like a
+/// rigid rod if both limits are equal, or like a rope if the min limit is zero. It can also
+/// pull the bodies toward a rest length with a spring, which is solved as a soft constraint.
+pub struct DistanceJoint {
+    /// Where the distance joint is attached on the first body, expressed in the first body local frame.
+    pub local_anchor1: Point<f32>,
+    /// Where the distance joint is attached on the second body, expressed in the second body local frame.
+    pub local_anchor2: Point<f32>,
+    /// Whether or not this joint should enforce the min and max distance between its anchors.
+    pub limits_enabled: bool,
+    /// The min and max distance between the anchors of this joint.
+    ///
+    /// If both are equal, this joint behaves like a rigid rod.
+    pub limits: [f32; 2],
+    /// The impulse applied by this joint to enforce its distance limits.
+    ///
+    /// A positive impulse pushes the second body away from the first body. The impulse applied
+    /// to the first body is given by `-limits_impulse`.
+    pub limits_impulse: f32,
+    /// The distance between the anchors the spring of this joint pulls them toward.
+    pub spring_rest_length: f32,
+    /// The stiffness of the spring, i.e., how strongly it drives the distance toward `spring_rest_length`.
+    pub spring_stiffness: f32,
+    /// The damping of the spring, i.e., how strongly it resists changes of the distance.
+    pub spring_damping: f32,
+    /// The impulse applied by the spring of this joint.
+    ///
+    /// A positive impulse pushes the second body away from the first body. The impulse applied
+    /// to the first body is given by `-spring_impulse`.
+    pub spring_impulse: f32,
+}
+
+impl DistanceJoint {
+    /// Creates a new distance joint from two anchors given on the local spaces of the respective bodies.
+    ///
+    /// The created joint has neither limits nor spring so it doesn't constrain the bodies until
+    /// one of them is configured.
+    pub fn new(local_anchor1: Point<f32>, local_anchor2: Point<f32>) -> Self {
+        Self {
+            local_anchor1,
+            local_anchor2,
+            limits_enabled: false,
+            limits: [0.0, f32::MAX],
+            limits_impulse: 0.0,
+            spring_rest_length: 0.0,
+            spring_stiffness: 0.0,
+            spring_damping: 0.0,
+            spring_impulse: 0.0,
+        }
+    }
+
+    /// Creates a distance joint that keeps its two anchors at a fixed distance, like a rigid rod.
+    pub fn rod(local_anchor1: Point<f32>, local_anchor2: Point<f32>, length: f32) -> Self {
+        let mut result = Self::new(local_anchor1, local_anchor2);
+        result.set_limits([length, length]);
+        result
+    }
+
+    /// Creates a distance joint that prevents its two anchors from being farther than
+    /// `max_length` from each other, like a rope.
+    pub fn rope(local_anchor1: Point<f32>, local_anchor2: Point<f32>, max_length: f32) -> Self {
+        let mut result = Self::new(local_anchor1, local_anchor2);
+        result.set_limits([0.0, max_length]);
+        result
+    }
+
+    /// Sets the min and max distance between the anchors of this joint, and enables these limits.
+    pub fn set_limits(&mut self, limits: [f32; 2]) {
+        self.limits_enabled = true;
+        self.limits = limits;
+    }
+
+    /// Is the spring of this joint enabled?
+    ///
+    /// The spring is enabled as soon as its stiffness or its damping is non-zero.
+    pub fn spring_enabled(&self) -> bool {
+        self.spring_stiffness != 0.0 || self.spring_damping != 0.0
+    }
+
+    /// Configures the spring of this joint so it drives the distance between its anchors
+    /// toward `rest_length`.
+    ///
+    /// Setting both the `stiffness` and the `damping` to zero disables the spring.
+    pub fn configure_spring(&mut self, rest_length: f32, stiffness: f32, damping: f32) {
+        self.spring_rest_length = rest_length;
+        self.spring_stiffness = stiffness;
+        self.spring_damping = damping;
+    }
+
+    /// Configures the spring of this joint from its oscillation frequency (in Hertz) and
+    /// damping ratio, instead of its stiffness and damping.
+    ///
+    /// The `mass` is the mass of the body the spring is tuned for, e.g., the mass of the
+    /// body hanging at the end of the spring. A damping ratio of `1.0` makes the spring
+    /// critically damped.
+    pub fn configure_spring_frequency(
+        &mut self,
+        rest_length: f32,
+        frequency: f32,
+        damping_ratio: f32,
+        mass: f32,
+    ) {
+        let omega = 2.0 * std::f32::consts::PI * frequency;
+        let stiffness = mass * omega * omega;
+        let damping = 2.0 * mass * damping_ratio * omega;
+        self.configure_spring(rest_length, stiffness, damping)
+    }
+
+    /// The impulse bounds used to enforce the limits of this joint given the current distance
+    /// between its anchors, or `None` if the limits are not active.
+    pub(crate) fn limits_impulse_bounds(&self, distance: f32) -> Option<[f32; 2]> {
+        // FIXME: allow predictive constraint activation.
+        if !self.limits_enabled {
+            None
+        } else if self.limits[0] >= self.limits[1] {
+            Some([-f32::MAX, f32::MAX])
+        } else if distance < self.limits[0] {
+            Some([0.0, f32::MAX])
+        } else if distance > self.limits[1] {
+            Some([-f32::MAX, 0.0])
+        } else {
+            None
+        }
+    }
+}
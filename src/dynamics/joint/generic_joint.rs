@@ -0,0 +1,235 @@
+use crate::math::{Isometry, SpacialVector, DIM, SPATIAL_DIM};
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A joint where each relative degree of freedom between two bodies can be configured individually.
+///
+/// The degrees of freedom are expressed along the axes of the frame of reference `local_anchor1`
+/// attached to the first body. Each of them is identified by its index:
+/// - In 2D, `0` and `1` are the linear axes `x` and `y`, and `2` is the angular axis.
+/// - In 3D, `0`, `1` and `2` are the linear axes `x`, `y` and `z`, and `3`, `4` and `5` are the
+///   angular axes `x`, `y` and `z`.
+///
+/// Each axis can be locked, free, limited, and/or motorized. By default, all axes are free.
+/// See [`GenericJoint::coordinates`] for the definition of the relative positions along each axis.
+pub struct GenericJoint {
+    /// The frame of reference for the first body affected by this joint, expressed in the local frame
+    /// of the first body.
+    pub local_anchor1: Isometry<f32>,
+    /// The frame of reference for the second body affected by this joint, expressed in the local frame
+    /// of the second body.
+    pub local_anchor2: Isometry<f32>,
+    /// The axes along which all relative motion is prevented.
+    pub locked_axes: [bool; SPATIAL_DIM],
+    /// The axes along which this joint enforces its limits.
+    pub limits_enabled: [bool; SPATIAL_DIM],
+    /// The min relative position of the attached bodies along each axis.
+    pub min_limits: SpacialVector<f32>,
+    /// The max relative position of the attached bodies along each axis.
+    pub max_limits: SpacialVector<f32>,
+    /// The magnitude of the impulse applied by this joint to enforce its limits along each axis.
+    pub limits_impulse: SpacialVector<f32>,
+    /// The target relative velocity of the attached bodies along each axis.
+    pub motor_target_vel: SpacialVector<f32>,
+    /// The target relative position of the attached bodies along each axis.
+    pub motor_target_pos: SpacialVector<f32>,
+    /// The stiffness of the motor along each axis, i.e., how strongly it drives the joint toward
+    /// `motor_target_pos`.
+    pub motor_stiffness: SpacialVector<f32>,
+    /// The damping of the motor along each axis, i.e., how strongly it drives the joint toward
+    /// `motor_target_vel`.
+    pub motor_damping: SpacialVector<f32>,
+    /// The maximum impulse the motor can apply at each timestep along each axis.
+    pub motor_max_impulse: SpacialVector<f32>,
+    /// The impulse applied by the motor on the second body along each axis.
+    ///
+    /// The impulse applied to the first body is given by `-motor_impulse`.
+    pub motor_impulse: SpacialVector<f32>,
+    /// The impulse applied to the first body to enforce the locked axes, expressed along the axes
+    /// of this joint.
+    ///
+    /// The impulse applied to the second body affected by this joint is given by `-impulse`.
+    pub impulse: SpacialVector<f32>,
+}
+
+impl GenericJoint {
+    /// Creates a new generic joint from the frames of reference of both bodies.
+    ///
+    /// All the axes of the created joint are free.
+    pub fn new(local_anchor1: Isometry<f32>, local_anchor2: Isometry<f32>) -> Self {
+        Self {
+            local_anchor1,
+            local_anchor2,
+            locked_axes: [false; SPATIAL_DIM],
+            limits_enabled: [false; SPATIAL_DIM],
+            min_limits: SpacialVector::repeat(-f32::MAX),
+            max_limits: SpacialVector::repeat(f32::MAX),
+            limits_impulse: SpacialVector::zeros(),
+            motor_target_vel: SpacialVector::zeros(),
+            motor_target_pos: SpacialVector::zeros(),
+            motor_stiffness: SpacialVector::zeros(),
+            motor_damping: SpacialVector::zeros(),
+            motor_max_impulse: SpacialVector::repeat(f32::MAX),
+            motor_impulse: SpacialVector::zeros(),
+            impulse: SpacialVector::zeros(),
+        }
+    }
+
+    /// Prevents any relative motion along the given axis.
+    pub fn lock_axis(&mut self, axis: usize) {
+        self.locked_axes[axis] = true;
+    }
+
+    /// Removes any lock, limit, or motor from the given axis.
+    pub fn free_axis(&mut self, axis: usize) {
+        self.locked_axes[axis] = false;
+        self.limits_enabled[axis] = false;
+        self.configure_motor(axis, 0.0, 0.0, 0.0, 0.0);
+    }
+
+    /// Sets the min and max relative positions allowed along the given axis, and enables these limits.
+    pub fn set_limits(&mut self, axis: usize, limits: [f32; 2]) {
+        self.limits_enabled[axis] = true;
+        self.min_limits[axis] = limits[0];
+        self.max_limits[axis] = limits[1];
+    }
+
+    /// The relative positions of the attached bodies along each axis of this joint.
+    ///
+    /// The linear coordinates are the components of the translation from the frame attached to the
+    /// first body to the frame attached to the second body, expressed in the first frame. The angular
+    /// coordinate is the relative angle of both frames, in `[-pi, pi]`.
+    #[cfg(feature = "dim2")]
+    pub fn coordinates(
+        &self,
+        position1: &Isometry<f32>,
+        position2: &Isometry<f32>,
+    ) -> SpacialVector<f32> {
+        let frame1 = position1 * self.local_anchor1;
+        let frame2 = position2 * self.local_anchor2;
+        let lin = frame1
+            .inverse_transform_vector(&(frame2.translation.vector - frame1.translation.vector));
+        let ang = (frame1.rotation.inverse() * frame2.rotation).angle();
+        SpacialVector::new(lin.x, lin.y, ang)
+    }
+
+    /// The relative positions of the attached bodies along each axis of this joint.
+    ///
+    /// The linear coordinates are the components of the translation from the frame attached to the
+    /// first body to the frame attached to the second body, expressed in the first frame. The angular
+    /// coordinates are the components of the scaled axis of the rotation from the first frame to the
+    /// second frame, expressed in the first frame.
+    #[cfg(feature = "dim3")]
+    pub fn coordinates(
+        &self,
+        position1: &Isometry<f32>,
+        position2: &Isometry<f32>,
+    ) -> SpacialVector<f32> {
+        let frame1 = position1 * self.local_anchor1;
+        let frame2 = position2 * self.local_anchor2;
+        let lin = frame1
+            .inverse_transform_vector(&(frame2.translation.vector - frame1.translation.vector));
+        let ang = (frame1.rotation.inverse() * frame2.rotation).scaled_axis();
+        SpacialVector::new(lin.x, lin.y, lin.z, ang.x, ang.y, ang.z)
+    }
+
+    /// A vector with `1.0` for each locked axis and `0.0` for each other axis.
+    pub(crate) fn locked_axes_mask(&self) -> SpacialVector<f32> {
+        SpacialVector::from_fn(|i, _| if self.locked_axes[i] { 1.0 } else { 0.0 })
+    }
+
+    /// The directions along which the limits of this joint push the second body.
+    ///
+    /// This is `1.0` for each axis where the relative position is smaller than the min limit,
+    /// `-1.0` where it is greater than the max limit, and `0.0` elsewhere.
+    pub(crate) fn limits_directions(&self, coordinates: &SpacialVector<f32>) -> SpacialVector<f32> {
+        // FIXME: we should allow both limits to be active at
+        // the same time, and allow predictive constraint activation.
+        SpacialVector::from_fn(|i, _| {
+            if self.locked_axes[i] || !self.limits_enabled[i] {
+                0.0
+            } else if coordinates[i] < self.min_limits[i] {
+                1.0
+            } else if coordinates[i] > self.max_limits[i] {
+                -1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// The difference between the relative positions of the attached bodies and the motor's target
+    /// positions, the angular ones being wrapped into `[-pi, pi]`.
+    pub(crate) fn motor_errors(&self, coordinates: &SpacialVector<f32>) -> SpacialVector<f32> {
+        use std::f32::consts::PI;
+        SpacialVector::from_fn(|i, _| {
+            let error = coordinates[i] - self.motor_target_pos[i];
+
+            if i < DIM {
+                error
+            } else {
+                error - (error / (2.0 * PI)).round() * 2.0 * PI
+            }
+        })
+    }
+
+    /// The position errors to be corrected along each axis because of the locks and limits
+    /// of this joint.
+    pub(crate) fn position_errors(&self, coordinates: &SpacialVector<f32>) -> SpacialVector<f32> {
+        SpacialVector::from_fn(|i, _| {
+            if self.locked_axes[i] {
+                coordinates[i]
+            } else if self.limits_enabled[i] {
+                coordinates[i] - na::clamp(coordinates[i], self.min_limits[i], self.max_limits[i])
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Is the motor of this joint enabled along the given axis?
+    ///
+    /// The motor is enabled as soon as its stiffness or its damping is non-zero, and the axis
+    /// isn't locked.
+    pub fn motor_enabled(&self, axis: usize) -> bool {
+        !self.locked_axes[axis]
+            && (self.motor_stiffness[axis] != 0.0 || self.motor_damping[axis] != 0.0)
+    }
+
+    /// Configures the motor of this joint so it drives the relative velocity of the
+    /// attached bodies along the given axis toward `target_vel`.
+    ///
+    /// The `factor` controls how strongly the target velocity is enforced.
+    pub fn configure_motor_velocity(&mut self, axis: usize, target_vel: f32, factor: f32) {
+        self.configure_motor(axis, self.motor_target_pos[axis], target_vel, 0.0, factor)
+    }
+
+    /// Configures the motor of this joint as a spring-damper driving the relative position of the
+    /// attached bodies along the given axis toward `target_pos`.
+    pub fn configure_motor_position(
+        &mut self,
+        axis: usize,
+        target_pos: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.configure_motor(axis, target_pos, 0.0, stiffness, damping)
+    }
+
+    /// Configures all the parameters of the motor of this joint along the given axis.
+    ///
+    /// Setting both the `stiffness` and the `damping` to zero disables the motor.
+    pub fn configure_motor(
+        &mut self,
+        axis: usize,
+        target_pos: f32,
+        target_vel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.motor_target_pos[axis] = target_pos;
+        self.motor_target_vel[axis] = target_vel;
+        self.motor_stiffness[axis] = stiffness;
+        self.motor_damping[axis] = damping;
+    }
+}
@@ -1,6 +1,10 @@
+use crate::dynamics::{
+    BallJoint, DistanceJoint, FixedJoint, GenericJoint, JointHandle, PrismaticJoint, RevoluteJoint,
+    RigidBodyHandle,
+};
+use crate::math::{AngDim, Dim, DIM};
 #[cfg(feature = "dim3")]
-use crate::dynamics::RevoluteJoint;
-use crate::dynamics::{BallJoint, FixedJoint, JointHandle, PrismaticJoint, RigidBodyHandle};
+use na::{U2, U3};
 
 #[derive(Copy, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -13,10 +17,15 @@ pub enum JointParams {
     /// A prismatic joint that removes all degrees of degrees of freedom between the affected
     /// bodies except for the translation along one axis.
     PrismaticJoint(PrismaticJoint),
-    #[cfg(feature = "dim3")]
     /// A revolute joint that removes all degrees of degrees of freedom between the affected
-    /// bodies except for the translation along one axis.
+    /// bodies except for the rotation along one axis.
     RevoluteJoint(RevoluteJoint),
+    /// A generic joint where each relative degree of freedom between the affected bodies can
+    /// individually be locked, free, limited, or motorized.
+    GenericJoint(GenericJoint),
+    /// A distance joint that constrains the distance between a pair of points on the affected
+    /// bodies, possibly with a spring.
+    DistanceJoint(DistanceJoint),
 }
 
 impl JointParams {
@@ -26,8 +35,9 @@ impl JointParams {
             JointParams::BallJoint(_) => 0,
             JointParams::FixedJoint(_) => 1,
             JointParams::PrismaticJoint(_) => 2,
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => 3,
+            JointParams::GenericJoint(_) => 4,
+            JointParams::DistanceJoint(_) => 5,
         }
     }
 
@@ -59,7 +69,6 @@ impl JointParams {
     }
 
     /// Gets a reference to the underlying revolute joint, if `self` is one.
-    #[cfg(feature = "dim3")]
     pub fn as_revolute_joint(&self) -> Option<&RevoluteJoint> {
         if let JointParams::RevoluteJoint(j) = self {
             Some(j)
@@ -67,6 +76,68 @@ impl JointParams {
             None
         }
     }
+
+    /// Gets a reference to the underlying generic joint, if `self` is one.
+    pub fn as_generic_joint(&self) -> Option<&GenericJoint> {
+        if let JointParams::GenericJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a reference to the underlying distance joint, if `self` is one.
+    pub fn as_distance_joint(&self) -> Option<&DistanceJoint> {
+        if let JointParams::DistanceJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// The magnitudes of the total linear and angular impulses applied by this joint
+    /// during the last timestep.
+    pub fn impulse_magnitudes(&self) -> (f32, f32) {
+        match self {
+            JointParams::BallJoint(j) => (j.impulse.norm(), 0.0),
+            JointParams::FixedJoint(j) => (
+                j.impulse.fixed_rows::<Dim>(0).norm(),
+                j.impulse.fixed_rows::<AngDim>(DIM).norm(),
+            ),
+            JointParams::PrismaticJoint(j) => {
+                // The limits and motor impulses are orthogonal to the other linear impulses.
+                let axis_impulse = j.limits_impulse.abs() + j.motor_impulse.abs();
+                #[cfg(feature = "dim2")]
+                let (lin, ang) = (j.impulse.x, j.impulse.y.abs());
+                #[cfg(feature = "dim3")]
+                let (lin, ang) = (
+                    j.impulse.fixed_rows::<U2>(0).norm(),
+                    j.impulse.fixed_rows::<U3>(2).norm(),
+                );
+                (lin.hypot(axis_impulse), ang)
+            }
+            JointParams::RevoluteJoint(j) => {
+                // The limits and motor impulses are orthogonal to the other angular impulses.
+                let axis_impulse = j.limits_impulse.abs() + j.motor_impulse.abs();
+                #[cfg(feature = "dim2")]
+                let (lin, ang) = (j.impulse.norm(), 0.0f32);
+                #[cfg(feature = "dim3")]
+                let (lin, ang) = (
+                    j.impulse.fixed_rows::<U3>(0).norm(),
+                    j.impulse.fixed_rows::<U2>(3).norm(),
+                );
+                (lin, ang.hypot(axis_impulse))
+            }
+            JointParams::GenericJoint(j) => {
+                let impulse = j.impulse.abs() + j.limits_impulse.abs() + j.motor_impulse.abs();
+                (
+                    impulse.fixed_rows::<Dim>(0).norm(),
+                    impulse.fixed_rows::<AngDim>(DIM).norm(),
+                )
+            }
+            JointParams::DistanceJoint(j) => ((j.limits_impulse + j.spring_impulse).abs(), 0.0),
+        }
+    }
 }
 
 impl From<BallJoint> for JointParams {
@@ -81,7 +152,6 @@ impl From<FixedJoint> for JointParams {
     }
 }
 
-#[cfg(feature = "dim3")]
 impl From<RevoluteJoint> for JointParams {
     fn from(j: RevoluteJoint) -> Self {
         JointParams::RevoluteJoint(j)
@@ -94,6 +164,18 @@ impl From<PrismaticJoint> for JointParams {
     }
 }
 
+impl From<GenericJoint> for JointParams {
+    fn from(j: GenericJoint) -> Self {
+        JointParams::GenericJoint(j)
+    }
+}
+
+impl From<DistanceJoint> for JointParams {
+    fn from(j: DistanceJoint) -> Self {
+        JointParams::DistanceJoint(j)
+    }
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 /// A joint attached to two bodies.
 pub struct Joint {
@@ -109,4 +191,29 @@ pub struct Joint {
     pub(crate) position_constraint_index: usize,
     /// The joint geometric parameters and impulse.
     pub params: JointParams,
+    /// The maximum force this joint can apply before breaking.
+    ///
+    /// A broken joint is removed from its joint set and a [`JointEvent::Broken`] event is emitted.
+    pub max_force: f32,
+    /// The maximum torque this joint can apply before breaking.
+    ///
+    /// A broken joint is removed from its joint set and a [`JointEvent::Broken`] event is emitted.
+    pub max_torque: f32,
+}
+
+impl Joint {
+    /// Does the force or the torque applied by this joint during the last timestep exceed
+    /// its break thresholds?
+    pub(crate) fn exceeds_break_thresholds(&self, inv_dt: f32) -> bool {
+        let (lin_impulse, ang_impulse) = self.params.impulse_magnitudes();
+        lin_impulse * inv_dt > self.max_force || ang_impulse * inv_dt > self.max_torque
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// Events triggered by joints during a timestep.
+pub enum JointEvent {
+    /// The joint with the given handle applied a force or a torque greater than its break
+    /// thresholds, and has been removed from its joint set.
+    Broken(JointHandle),
 }
@@ -51,6 +51,12 @@ impl JointSet {
         self.joint_graph.graph.edge_weight(*id)
     }
 
+    /// Gets a mutable reference to the joint with the given handle.
+    pub fn get_mut(&mut self, handle: JointHandle) -> Option<&mut Joint> {
+        let id = self.joint_ids.get(handle)?;
+        self.joint_graph.graph.edge_weight_mut(*id)
+    }
+
     /// Gets the joint with the given handle without a known generation.
     ///
     /// This is useful when you know you want the joint at position `i` but
@@ -119,6 +125,8 @@ impl JointSet {
             #[cfg(feature = "parallel")]
             position_constraint_index: 0,
             params: joint_params.into(),
+            max_force: f32::MAX,
+            max_torque: f32::MAX,
         };
 
         let (rb1, rb2) = bodies.get2_mut_internal(joint.body1, joint.body2);
@@ -145,6 +153,42 @@ impl JointSet {
         handle
     }
 
+    /// Removes a joint from this set.
+    ///
+    /// The two rigid-bodies attached by this joint are woken up.
+    pub fn remove(&mut self, handle: JointHandle, bodies: &mut RigidBodySet) -> Option<Joint> {
+        let id = self.joint_ids.remove(handle)?;
+        let joint = self.joint_graph.graph.remove_edge(id)?;
+
+        // Update the id of the edge which took the place of the deleted one.
+        if let Some(j) = self.joint_graph.graph.edge_weight_mut(id) {
+            self.joint_ids[j.handle] = id;
+        }
+
+        // Wake up the attached bodies.
+        bodies.wake_up(joint.body1);
+        bodies.wake_up(joint.body2);
+
+        Some(joint)
+    }
+
+    /// Collects the handles of the given joints that exceeded their break thresholds during
+    /// the last timestep.
+    pub(crate) fn find_broken_joints(
+        &self,
+        joint_ids: &[JointIndex],
+        inv_dt: f32,
+        out: &mut Vec<JointHandle>,
+    ) {
+        for id in joint_ids {
+            let joint = &self.joint_graph.graph.edges[*id].weight;
+
+            if joint.exceeds_break_thresholds(inv_dt) {
+                out.push(joint.handle);
+            }
+        }
+    }
+
     /// Retrieve all the joints happening between two active bodies.
     // NOTE: this is very similar to the code from NarrowPhase::select_active_interactions.
     pub(crate) fn select_active_interactions(
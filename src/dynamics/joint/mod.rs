@@ -1,16 +1,18 @@
 pub use self::ball_joint::BallJoint;
+pub use self::distance_joint::DistanceJoint;
 pub use self::fixed_joint::FixedJoint;
-pub use self::joint::{Joint, JointParams};
+pub use self::generic_joint::GenericJoint;
+pub use self::joint::{Joint, JointEvent, JointParams};
 pub(crate) use self::joint_set::{JointGraphEdge, JointIndex};
 pub use self::joint_set::{JointHandle, JointSet};
 pub use self::prismatic_joint::PrismaticJoint;
-#[cfg(feature = "dim3")]
 pub use self::revolute_joint::RevoluteJoint;
 
 mod ball_joint;
+mod distance_joint;
 mod fixed_joint;
+mod generic_joint;
 mod joint;
 mod joint_set;
 mod prismatic_joint;
-#[cfg(feature = "dim3")]
 mod revolute_joint;
@@ -36,10 +36,20 @@ pub struct PrismaticJoint {
     ///
     /// The impulse applied to the second body is given by `-impulse`.
     pub limits_impulse: f32,
-    // pub motor_enabled: bool,
-    // pub target_motor_vel: f32,
-    // pub max_motor_impulse: f32,
-    // pub motor_impulse: f32,
+    /// The target relative velocity of the attached bodies along this joint's axis.
+    pub motor_target_vel: f32,
+    /// The target relative position of the attached bodies along this joint's axis.
+    pub motor_target_pos: f32,
+    /// The stiffness of the motor, i.e., how strongly it drives the joint toward `motor_target_pos`.
+    pub motor_stiffness: f32,
+    /// The damping of the motor, i.e., how strongly it drives the joint toward `motor_target_vel`.
+    pub motor_damping: f32,
+    /// The maximum impulse the motor can apply at each timestep.
+    pub motor_max_impulse: f32,
+    /// The impulse applied by the motor on the second body along this joint's axis.
+    ///
+    /// The impulse applied to the first body is given by `-motor_impulse`.
+    pub motor_impulse: f32,
 }
 
 impl PrismaticJoint {
@@ -63,10 +73,12 @@ impl PrismaticJoint {
             limits_enabled: false,
             limits: [-f32::MAX, f32::MAX],
             limits_impulse: 0.0,
-            // motor_enabled: false,
-            // target_motor_vel: 0.0,
-            // max_motor_impulse: f32::MAX,
-            // motor_impulse: 0.0,
+            motor_target_vel: 0.0,
+            motor_target_pos: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
         }
     }
 
@@ -118,10 +130,12 @@ impl PrismaticJoint {
             limits_enabled: false,
             limits: [-f32::MAX, f32::MAX],
             limits_impulse: 0.0,
-            // motor_enabled: false,
-            // target_motor_vel: 0.0,
-            // max_motor_impulse: f32::MAX,
-            // motor_impulse: 0.0,
+            motor_target_vel: 0.0,
+            motor_target_pos: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
         }
     }
 
@@ -135,6 +149,43 @@ impl PrismaticJoint {
         self.local_axis2
     }
 
+    /// Is the motor of this joint enabled?
+    ///
+    /// The motor is enabled as soon as its stiffness or its damping is non-zero.
+    pub fn motor_enabled(&self) -> bool {
+        self.motor_stiffness != 0.0 || self.motor_damping != 0.0
+    }
+
+    /// Configures the motor of this joint so it drives the relative velocity of the attached
+    /// bodies along this joint's axis toward `target_vel`.
+    ///
+    /// The `factor` controls how strongly the target velocity is enforced.
+    pub fn configure_motor_velocity(&mut self, target_vel: f32, factor: f32) {
+        self.configure_motor(self.motor_target_pos, target_vel, 0.0, factor)
+    }
+
+    /// Configures the motor of this joint as a spring-damper driving the relative position of the
+    /// attached bodies along this joint's axis toward `target_pos`.
+    pub fn configure_motor_position(&mut self, target_pos: f32, stiffness: f32, damping: f32) {
+        self.configure_motor(target_pos, 0.0, stiffness, damping)
+    }
+
+    /// Configures all the parameters of the motor of this joint.
+    ///
+    /// Setting both the `stiffness` and the `damping` to zero disables the motor.
+    pub fn configure_motor(
+        &mut self,
+        target_pos: f32,
+        target_vel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.motor_target_pos = target_pos;
+        self.motor_target_vel = target_vel;
+        self.motor_stiffness = stiffness;
+        self.motor_damping = damping;
+    }
+
     // FIXME: precompute this?
     #[cfg(feature = "dim2")]
     pub(crate) fn local_frame1(&self) -> Isometry<f32> {
@@ -1,32 +1,96 @@
-use crate::math::{Point, Vector};
-use crate::utils::WBasis;
-use na::{Unit, Vector5};
+use crate::math::{Isometry, Point};
+#[cfg(feature = "dim2")]
+use na::Vector2;
+#[cfg(feature = "dim3")]
+use {
+    crate::math::Vector,
+    crate::utils::WBasis,
+    na::{Unit, Vector5},
+};
 
 #[derive(Copy, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 /// A joint that removes all relative motion between two bodies, except for the rotations along one axis.
+///
+/// In 2D, this axis is always orthogonal to the simulation plane so the attached bodies are only
+/// allowed to rotate around the joint's anchor.
 pub struct RevoluteJoint {
     /// Where the revolute joint is attached on the first body, expressed in the local space of the first attached body.
     pub local_anchor1: Point<f32>,
     /// Where the revolute joint is attached on the second body, expressed in the local space of the second attached body.
     pub local_anchor2: Point<f32>,
     /// The rotation axis of this revolute joint expressed in the local space of the first attached body.
+    #[cfg(feature = "dim3")]
     pub local_axis1: Unit<Vector<f32>>,
     /// The rotation axis of this revolute joint expressed in the local space of the second attached body.
+    #[cfg(feature = "dim3")]
     pub local_axis2: Unit<Vector<f32>>,
     /// The basis orthonormal to `local_axis1`, expressed in the local space of the first attached body.
+    #[cfg(feature = "dim3")]
     pub basis1: [Vector<f32>; 2],
     /// The basis orthonormal to `local_axis2`, expressed in the local space of the second attached body.
+    #[cfg(feature = "dim3")]
     pub basis2: [Vector<f32>; 2],
     /// The impulse applied by this joint on the first body.
     ///
     /// The impulse applied to the second body is given by `-impulse`.
+    #[cfg(feature = "dim3")]
     pub impulse: Vector5<f32>,
+    /// The impulse applied by this joint on the first body.
+    ///
+    /// The impulse applied to the second body is given by `-impulse`.
+    #[cfg(feature = "dim2")]
+    pub impulse: Vector2<f32>,
+    /// Whether or not this joint should enforce angular limits along its axis.
+    pub limits_enabled: bool,
+    /// The min and max relative angle of the attached bodies along this joint's axis.
+    ///
+    /// See [`RevoluteJoint::angle`] for the definition of the relative angle.
+    pub limits: [f32; 2],
+    /// The magnitude of the angular impulse applied by this joint to enforce its angular limits.
+    pub limits_impulse: f32,
+    /// The target relative angular velocity of the attached bodies along this joint's axis.
+    pub motor_target_vel: f32,
+    /// The target relative angle of the attached bodies along this joint's axis.
+    ///
+    /// See [`RevoluteJoint::angle`] for the definition of the relative angle.
+    pub motor_target_pos: f32,
+    /// The stiffness of the motor, i.e., how strongly it drives the joint toward `motor_target_pos`.
+    pub motor_stiffness: f32,
+    /// The damping of the motor, i.e., how strongly it drives the joint toward `motor_target_vel`.
+    pub motor_damping: f32,
+    /// The maximum angular impulse the motor can apply at each timestep.
+    pub motor_max_impulse: f32,
+    /// The angular impulse applied by the motor on the second body along this joint's axis.
+    ///
+    /// The angular impulse applied to the first body is given by `-motor_impulse`.
+    pub motor_impulse: f32,
 }
 
 impl RevoluteJoint {
+    /// Creates a new revolute joint with the given point of applications, expressed in the
+    /// local-space of the affected bodies.
+    #[cfg(feature = "dim2")]
+    pub fn new(local_anchor1: Point<f32>, local_anchor2: Point<f32>) -> Self {
+        Self {
+            local_anchor1,
+            local_anchor2,
+            impulse: na::zero(),
+            limits_enabled: false,
+            limits: [-f32::MAX, f32::MAX],
+            limits_impulse: 0.0,
+            motor_target_vel: 0.0,
+            motor_target_pos: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
+        }
+    }
+
     /// Creates a new revolute joint with the given point of applications and axis, all expressed
     /// in the local-space of the affected bodies.
+    #[cfg(feature = "dim3")]
     pub fn new(
         local_anchor1: Point<f32>,
         local_axis1: Unit<Vector<f32>>,
@@ -41,6 +105,85 @@ impl RevoluteJoint {
             basis1: local_axis1.orthonormal_basis(),
             basis2: local_axis2.orthonormal_basis(),
             impulse: na::zero(),
+            limits_enabled: false,
+            limits: [-f32::MAX, f32::MAX],
+            limits_impulse: 0.0,
+            motor_target_vel: 0.0,
+            motor_target_pos: 0.0,
+            motor_stiffness: 0.0,
+            motor_damping: 0.0,
+            motor_max_impulse: f32::MAX,
+            motor_impulse: 0.0,
         }
     }
+
+    /// The relative angle of the attached bodies, in `[-pi, pi]`.
+    ///
+    /// This is the rotation angle of the second body relative to the first one, measured
+    /// counterclockwise.
+    #[cfg(feature = "dim2")]
+    pub fn angle(&self, position1: &Isometry<f32>, position2: &Isometry<f32>) -> f32 {
+        (position1.rotation.inverse() * position2.rotation).angle()
+    }
+
+    /// The relative angle of the attached bodies along this joint's axis, in `[-pi, pi]`.
+    ///
+    /// This is the angle between `basis1[0]` and `basis2[0]`, once transformed by `position1` and
+    /// `position2` respectively, measured counterclockwise around the first body's axis.
+    #[cfg(feature = "dim3")]
+    pub fn angle(&self, position1: &Isometry<f32>, position2: &Isometry<f32>) -> f32 {
+        let axis1 = position1 * self.local_axis1;
+        let ref1 = position1 * self.basis1[0];
+        let ref2 = position2 * self.basis2[0];
+        ref2.dot(&axis1.cross(&ref1)).atan2(ref2.dot(&ref1))
+    }
+
+    /// The difference between the relative angle of the attached bodies and the motor's target
+    /// angle, wrapped into `[-pi, pi]`.
+    pub(crate) fn motor_angle_error(
+        &self,
+        position1: &Isometry<f32>,
+        position2: &Isometry<f32>,
+    ) -> f32 {
+        use std::f32::consts::PI;
+        let error = self.angle(position1, position2) - self.motor_target_pos;
+        error - (error / (2.0 * PI)).round() * 2.0 * PI
+    }
+
+    /// Is the motor of this joint enabled?
+    ///
+    /// The motor is enabled as soon as its stiffness or its damping is non-zero.
+    pub fn motor_enabled(&self) -> bool {
+        self.motor_stiffness != 0.0 || self.motor_damping != 0.0
+    }
+
+    /// Configures the motor of this joint so it drives the relative angular velocity of the
+    /// attached bodies along this joint's axis toward `target_vel`.
+    ///
+    /// The `factor` controls how strongly the target velocity is enforced.
+    pub fn configure_motor_velocity(&mut self, target_vel: f32, factor: f32) {
+        self.configure_motor(self.motor_target_pos, target_vel, 0.0, factor)
+    }
+
+    /// Configures the motor of this joint as a spring-damper driving the relative angle of the
+    /// attached bodies along this joint's axis toward `target_pos`.
+    pub fn configure_motor_position(&mut self, target_pos: f32, stiffness: f32, damping: f32) {
+        self.configure_motor(target_pos, 0.0, stiffness, damping)
+    }
+
+    /// Configures all the parameters of the motor of this joint.
+    ///
+    /// Setting both the `stiffness` and the `damping` to zero disables the motor.
+    pub fn configure_motor(
+        &mut self,
+        target_pos: f32,
+        target_vel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.motor_target_pos = target_pos;
+        self.motor_target_vel = target_vel;
+        self.motor_stiffness = stiffness;
+        self.motor_damping = damping;
+    }
 }
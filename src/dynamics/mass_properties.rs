@@ -1,7 +1,7 @@
 use crate::math::{AngVector, AngularInertia, Isometry, Point, Rotation, Vector};
 use crate::utils;
 use num::Zero;
-use std::ops::{Add, AddAssign};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 #[cfg(feature = "dim3")]
 use {na::Matrix3, std::ops::MulAssign};
 
@@ -58,6 +58,46 @@ impl MassProperties {
         }
     }
 
+    /// Builds mass properties from an angular inertia tensor expressed in local-space.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn with_inertia_matrix(
+        local_com: Point<f32>,
+        mass: f32,
+        inertia: Matrix3<f32>,
+    ) -> Self {
+        let mut eigen = inertia.symmetric_eigen();
+
+        // The eigenvectors may form a reflection, which `Rotation::from_matrix` would never
+        // converge to. Flipping one of them yields a proper rotation with the same eigenvalues.
+        if eigen.eigenvectors.determinant() < 0.0 {
+            let mut last = eigen.eigenvectors.column_mut(2);
+            last *= -1.0;
+        }
+
+        let principal_inertia_local_frame = Rotation::from_matrix(&eigen.eigenvectors);
+        // Negative eigenvalues can only result from rounding errors.
+        let principal_inertia = eigen.eigenvalues.map(|e| e.max(0.0));
+        Self::with_principal_inertia_frame(
+            local_com,
+            mass,
+            principal_inertia,
+            principal_inertia_local_frame,
+        )
+    }
+
+    /// Transforms these mass properties by the given isometry.
+    ///
+    /// This is useful to express the mass properties of a shape in the local frame of
+    /// the compound shape or rigid-body it is part of.
+    pub fn transform_by(&self, m: &Isometry<f32>) -> Self {
+        Self {
+            local_com: m * self.local_com,
+            #[cfg(feature = "dim3")]
+            principal_inertia_local_frame: m.rotation * self.principal_inertia_local_frame,
+            ..*self
+        }
+    }
+
     /// The world-space center of mass of the rigid-body.
     pub fn world_com(&self, pos: &Isometry<f32>) -> Point<f32> {
         pos * self.local_com
@@ -120,7 +160,7 @@ impl MassProperties {
             let matrix = self.reconstruct_inertia_matrix();
             let diag = shift.norm_squared();
             let diagm = Matrix3::from_diagonal_element(diag);
-            matrix + (diagm + shift * shift.transpose()) * mass
+            matrix + (diagm - shift * shift.transpose()) * mass
         } else {
             Matrix3::zeros()
         }
@@ -185,22 +225,115 @@ impl Add<MassProperties> for MassProperties {
         let i1 = self.construct_shifted_inertia_matrix(local_com - self.local_com);
         let i2 = other.construct_shifted_inertia_matrix(local_com - other.local_com);
         let inertia = i1 + i2;
-        let eigen = inertia.symmetric_eigen();
-        let principal_inertia_local_frame = Rotation::from_matrix(&eigen.eigenvectors);
-        let principal_inertia = eigen.eigenvalues;
-        let inv_principal_inertia_sqrt = principal_inertia.map(|e| utils::inv(e.sqrt()));
+        Self::with_inertia_matrix(local_com, m1 + m2, inertia)
+    }
+}
+
+impl AddAssign<MassProperties> for MassProperties {
+    fn add_assign(&mut self, rhs: MassProperties) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub<MassProperties> for MassProperties {
+    type Output = Self;
+
+    #[cfg(feature = "dim2")]
+    fn sub(self, other: MassProperties) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return self;
+        }
+
+        let m1 = utils::inv(self.inv_mass);
+        let m2 = utils::inv(other.inv_mass);
+        let inv_mass = utils::inv(m1 - m2);
+
+        if inv_mass <= 0.0 {
+            // The mass became zero or negative because of rounding errors.
+            return Self::zero();
+        }
+
+        let local_com = (self.local_com * m1 - other.local_com.coords * m2) * inv_mass;
+        let i1 = self.construct_shifted_inertia_matrix(local_com - self.local_com);
+        let i2 = other.construct_shifted_inertia_matrix(local_com - other.local_com);
+        let inertia = (i1 - i2).max(0.0);
+        let inv_principal_inertia_sqrt = utils::inv(inertia.sqrt());
 
         Self {
             local_com,
             inv_mass,
             inv_principal_inertia_sqrt,
-            principal_inertia_local_frame,
         }
     }
+
+    #[cfg(feature = "dim3")]
+    fn sub(self, other: MassProperties) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return self;
+        }
+
+        let m1 = utils::inv(self.inv_mass);
+        let m2 = utils::inv(other.inv_mass);
+        let inv_mass = utils::inv(m1 - m2);
+
+        if inv_mass <= 0.0 {
+            // The mass became zero or negative because of rounding errors.
+            return Self::zero();
+        }
+
+        let local_com = (self.local_com * m1 - other.local_com.coords * m2) * inv_mass;
+        let i1 = self.construct_shifted_inertia_matrix(local_com - self.local_com);
+        let i2 = other.construct_shifted_inertia_matrix(local_com - other.local_com);
+        let inertia = i1 - i2;
+        Self::with_inertia_matrix(local_com, m1 - m2, inertia)
+    }
 }
 
-impl AddAssign<MassProperties> for MassProperties {
-    fn add_assign(&mut self, rhs: MassProperties) {
-        *self = *self + rhs
+impl SubAssign<MassProperties> for MassProperties {
+    fn sub_assign(&mut self, rhs: MassProperties) {
+        *self = *self - rhs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MassProperties;
+    use crate::math::Point;
+
+    #[cfg(feature = "dim2")]
+    fn mprops(x: f32, y: f32, mass: f32) -> MassProperties {
+        MassProperties::new(Point::new(x, y), mass, mass * 0.5)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn mprops(x: f32, y: f32, mass: f32) -> MassProperties {
+        use crate::math::Vector;
+        MassProperties::new(
+            Point::new(x, y, 0.0),
+            mass,
+            Vector::new(1.0, 2.0, 3.0) * mass,
+        )
+    }
+
+    fn assert_relative_eq(a: MassProperties, b: MassProperties) {
+        assert!((a.inv_mass - b.inv_mass).abs() < 1.0e-5);
+        assert!((a.local_com - b.local_com).norm() < 1.0e-5);
+        #[cfg(feature = "dim2")]
+        assert!((a.inv_principal_inertia_sqrt - b.inv_principal_inertia_sqrt).abs() < 1.0e-5);
+        #[cfg(feature = "dim3")]
+        assert!((a.reconstruct_inertia_matrix() - b.reconstruct_inertia_matrix()).norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn subtracting_mass_properties_undoes_their_addition() {
+        let a = mprops(1.0, 0.0, 2.0);
+        let b = mprops(-1.0, 2.0, 3.0);
+        let sum = a + b;
+        let expected_com = (a.local_com.coords * 2.0 + b.local_com.coords * 3.0) / 5.0;
+
+        assert!((sum.inv_mass - 1.0 / 5.0).abs() < 1.0e-6);
+        assert!((sum.local_com.coords - expected_com).norm() < 1.0e-6);
+        assert_relative_eq(sum - b, a);
+        assert_relative_eq(sum - a, b);
     }
 }
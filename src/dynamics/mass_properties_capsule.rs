@@ -4,7 +4,7 @@ use crate::geometry::Capsule;
 use crate::math::{Point, PrincipalAngularInertia, Vector};
 
 impl MassProperties {
-    fn cylinder_y_volume_unit_inertia(
+    pub(crate) fn cylinder_y_volume_unit_inertia(
         half_height: f32,
         radius: f32,
     ) -> (f32, PrincipalAngularInertia<f32>) {
@@ -0,0 +1,56 @@
+use crate::dynamics::MassProperties;
+use crate::math::{Point, PrincipalAngularInertia, Vector};
+
+impl MassProperties {
+    pub(crate) fn cone_y_volume_unit_inertia(
+        half_height: f32,
+        radius: f32,
+    ) -> (f32, PrincipalAngularInertia<f32>) {
+        let volume = radius * radius * std::f32::consts::PI * half_height * 2.0 / 3.0;
+        let sq_radius = radius * radius;
+        let sq_half_height = half_height * half_height;
+        let off_principal = (sq_radius + sq_half_height) * 3.0 / 20.0;
+        let principal = sq_radius * 3.0 / 10.0;
+
+        let inertia = Vector::new(off_principal, principal, off_principal);
+        (volume, inertia)
+    }
+
+    pub(crate) fn from_cone(density: f32, half_height: f32, radius: f32) -> Self {
+        let (cone_vol, cone_unit_i) = Self::cone_y_volume_unit_inertia(half_height, radius);
+        let cone_mass = cone_vol * density;
+        // The center of mass is located at a quarter of the height, starting from the base.
+        let local_com = Point::new(0.0, -half_height / 2.0, 0.0);
+
+        Self::new(local_com, cone_mass, cone_unit_i * cone_mass)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{Cone, Shape};
+    use crate::math::Point;
+    use na::Matrix3;
+
+    #[test]
+    fn cone_mass_properties() {
+        let (half_height, radius, density) = (1.5, 0.5, 2.0);
+        let mprops = Shape::Cone(Cone::new(half_height, radius)).mass_properties(density);
+
+        let height = half_height * 2.0;
+        let mass = std::f32::consts::PI * radius * radius * height / 3.0 * density;
+        // The inertia of a cone relative to its center of mass.
+        let axial_inertia = mass * radius * radius * 3.0 / 10.0;
+        let transverse_inertia =
+            mass * (radius * radius * 3.0 / 20.0 + height * height * 3.0 / 80.0);
+        let inertia = Matrix3::from_diagonal(&na::Vector3::new(
+            transverse_inertia,
+            axial_inertia,
+            transverse_inertia,
+        ));
+
+        assert!((mprops.inv_mass * mass - 1.0).abs() < 1.0e-5);
+        assert!((mprops.local_com - Point::new(0.0, -height / 4.0, 0.0)).norm() < 1.0e-6);
+        assert!((mprops.reconstruct_inertia_matrix() - inertia).norm() < 1.0e-4 * inertia.norm());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::dynamics::MassProperties;
+use crate::math::{Point, Vector};
+use na::{Matrix3, Point3};
+
+impl MassProperties {
+    pub(crate) fn from_convex_polyhedron(
+        density: f32,
+        vertices: &[Point<f32>],
+        indices: &[Point3<u32>],
+    ) -> MassProperties {
+        let (volume, com, covariance) = convex_mesh_volume_com_and_covariance(vertices, indices);
+
+        if volume == 0.0 {
+            return MassProperties::new(com, 0.0, Vector::zeros());
+        }
+
+        // The inertia tensor is derived from the covariance matrix relative to the center of mass.
+        let inertia = Matrix3::from_diagonal_element(covariance.trace()) - covariance;
+        Self::with_inertia_matrix(com, volume * density, inertia * density)
+    }
+}
+
+/// Computes the volume, center of mass, and covariance matrix (relative to the center of mass) of
+/// the convex polyhedron bounded by the given triangles.
+///
+/// The triangles must be oriented counter-clockwise when seen from the outside of the polyhedron.
+fn convex_mesh_volume_com_and_covariance(
+    vertices: &[Point<f32>],
+    indices: &[Point3<u32>],
+) -> (f32, Point<f32>, Matrix3<f32>) {
+    // The polyhedron is decomposed into tetrahedra with one vertex at its geometric center.
+    let geometric_center = vertices
+        .iter()
+        .fold(Point::origin(), |e1, e2| e1 + e2.coords)
+        / vertices.len() as f32;
+
+    let mut volume = 0.0;
+    let mut com = Vector::zeros();
+    let mut covariance = Matrix3::zeros();
+
+    for idx in indices {
+        let a = vertices[idx.x as usize] - geometric_center;
+        let b = vertices[idx.y as usize] - geometric_center;
+        let c = vertices[idx.z as usize] - geometric_center;
+        let sum = a + b + c;
+
+        let tetra_volume = a.dot(&b.cross(&c)) / 6.0;
+        volume += tetra_volume;
+        com += sum * (tetra_volume / 4.0);
+        covariance +=
+            (a * a.transpose() + b * b.transpose() + c * c.transpose() + sum * sum.transpose())
+                * (tetra_volume / 20.0);
+    }
+
+    if volume == 0.0 {
+        return (0.0, geometric_center, Matrix3::zeros());
+    }
+
+    // Move the covariance matrix to the center of mass.
+    let shift = com / volume;
+    let covariance = covariance - shift * shift.transpose() * volume;
+
+    (volume, geometric_center + shift, covariance)
+}
@@ -1,5 +1,6 @@
 use crate::dynamics::MassProperties;
-use crate::math::{Point, PrincipalAngularInertia, Vector};
+use crate::math::{Point, PrincipalAngularInertia, Vector, DIM};
+use std::f32::consts::PI;
 
 impl MassProperties {
     pub(crate) fn cuboid_volume_unit_inertia(
@@ -30,4 +31,70 @@ impl MassProperties {
         let mass = vol * density;
         Self::new(Point::origin(), mass, unit_i * mass)
     }
+
+    /// The mass properties of a cuboid with its edges and vertices rounded by `border_radius`.
+    pub(crate) fn from_round_cuboid(
+        density: f32,
+        half_extents: Vector<f32>,
+        border_radius: f32,
+    ) -> Self {
+        let r = border_radius;
+        // The volume, first moment, and second moment along one axis, of the part of a ball of
+        // radius `r` that lies in the positive orthant of a `k`-dimensional space.
+        let orthant_moments = |k: u32| match k {
+            0 => (1.0, 0.0, 0.0),
+            1 => (r, r * r / 2.0, r * r * r / 3.0),
+            2 => (PI * r * r / 4.0, r * r * r / 3.0, PI * r * r * r * r / 16.0),
+            _ => (
+                PI * r * r * r / 6.0,
+                PI * r * r * r * r / 16.0,
+                PI * r * r * r * r * r / 30.0,
+            ),
+        };
+
+        // The round cuboid is split into one piece per subset of the axes: the cuboid itself,
+        // the slabs on its faces, and, in 3D, the quarter-cylinders on its edges, and the
+        // spherical corners. Each piece is rounded along the axes of its subset, and
+        // symmetric wrt. the center of the cuboid so the products of inertia cancel out.
+        let mut volume = 0.0;
+        let mut second_moments = Vector::<f32>::zeros();
+
+        for axes in 0..1u32 << DIM {
+            let is_rounded = |i: usize| axes & (1 << i) != 0;
+            let (orthant_volume, orthant_moment1, orthant_moment2) =
+                orthant_moments(axes.count_ones());
+            let mut extent = (1 << axes.count_ones()) as f32;
+
+            for i in (0..DIM).filter(|i| !is_rounded(*i)) {
+                extent *= half_extents[i] * 2.0;
+            }
+
+            let piece_volume = extent * orthant_volume;
+            volume += piece_volume;
+
+            for i in 0..DIM {
+                let h = half_extents[i];
+                second_moments[i] += if is_rounded(i) {
+                    extent * (h * h * orthant_volume + 2.0 * h * orthant_moment1 + orthant_moment2)
+                } else {
+                    piece_volume * h * h / 3.0
+                };
+            }
+        }
+
+        #[cfg(feature = "dim2")]
+        let principal_inertia = second_moments.x + second_moments.y;
+        #[cfg(feature = "dim3")]
+        let principal_inertia = Vector::new(
+            second_moments.y + second_moments.z,
+            second_moments.x + second_moments.z,
+            second_moments.x + second_moments.y,
+        );
+
+        Self::new(
+            Point::origin(),
+            volume * density,
+            principal_inertia * density,
+        )
+    }
 }
@@ -0,0 +1,38 @@
+use crate::dynamics::MassProperties;
+use crate::math::Point;
+
+impl MassProperties {
+    pub(crate) fn from_cylinder(density: f32, half_height: f32, radius: f32) -> Self {
+        let (cyl_vol, cyl_unit_i) = Self::cylinder_y_volume_unit_inertia(half_height, radius);
+        let cyl_mass = cyl_vol * density;
+
+        Self::new(Point::origin(), cyl_mass, cyl_unit_i * cyl_mass)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{Cylinder, Shape};
+    use crate::math::Point;
+    use na::Matrix3;
+
+    #[test]
+    fn cylinder_mass_properties() {
+        let (half_height, radius, density) = (1.5, 0.5, 2.0);
+        let mprops = Shape::Cylinder(Cylinder::new(half_height, radius)).mass_properties(density);
+
+        let height = half_height * 2.0;
+        let mass = std::f32::consts::PI * radius * radius * height * density;
+        let axial_inertia = mass * radius * radius / 2.0;
+        let transverse_inertia = mass * (3.0 * radius * radius + height * height) / 12.0;
+        let inertia = Matrix3::from_diagonal(&na::Vector3::new(
+            transverse_inertia,
+            axial_inertia,
+            transverse_inertia,
+        ));
+
+        assert!((mprops.inv_mass * mass - 1.0).abs() < 1.0e-5);
+        assert_eq!(mprops.local_com, Point::origin());
+        assert!((mprops.reconstruct_inertia_matrix() - inertia).norm() < 1.0e-4 * inertia.norm());
+    }
+}
@@ -1,11 +1,11 @@
 //! Structures related to dynamics: bodies, joints, etc.
 
+pub(crate) use self::ccd_solver::CCDSolver;
 pub use self::integration_parameters::IntegrationParameters;
 pub(crate) use self::joint::JointIndex;
-#[cfg(feature = "dim3")]
-pub use self::joint::RevoluteJoint;
 pub use self::joint::{
-    BallJoint, FixedJoint, Joint, JointHandle, JointParams, JointSet, PrismaticJoint,
+    BallJoint, DistanceJoint, FixedJoint, GenericJoint, Joint, JointEvent, JointHandle,
+    JointParams, JointSet, PrismaticJoint, RevoluteJoint,
 };
 pub use self::mass_properties::MassProperties;
 pub use self::rigid_body::{ActivationStatus, BodyStatus, RigidBody, RigidBodyBuilder};
@@ -17,12 +17,19 @@ pub(crate) use self::solver::IslandSolver;
 #[cfg(feature = "parallel")]
 pub(crate) use self::solver::ParallelIslandSolver;
 
+mod ccd_solver;
 mod integration_parameters;
 mod joint;
 mod mass_properties;
 mod mass_properties_ball;
 mod mass_properties_capsule;
+#[cfg(feature = "dim3")]
+mod mass_properties_cone;
+#[cfg(feature = "dim3")]
+mod mass_properties_convex_polyhedron;
 mod mass_properties_cuboid;
+#[cfg(feature = "dim3")]
+mod mass_properties_cylinder;
 #[cfg(feature = "dim2")]
 mod mass_properties_polygon;
 mod rigid_body;
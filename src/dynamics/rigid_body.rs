@@ -42,6 +42,10 @@ pub struct RigidBody {
     pub linvel: Vector<f32>,
     /// The angular velocity of the rigid-body.
     pub angvel: AngVector<f32>,
+    /// Damping factor for gradually slowing down the translational motion of the rigid-body.
+    pub linear_damping: f32,
+    /// Damping factor for gradually slowing down the angular motion of the rigid-body.
+    pub angular_damping: f32,
     pub(crate) linacc: Vector<f32>,
     pub(crate) angacc: AngVector<f32>,
     pub(crate) colliders: Vec<ColliderHandle>,
@@ -54,6 +58,7 @@ pub struct RigidBody {
     pub(crate) active_set_timestamp: u32,
     /// The status of the body, governing how it is affected by external forces.
     pub body_status: BodyStatus,
+    pub(crate) ccd_enabled: bool,
 }
 
 impl Clone for RigidBody {
@@ -80,6 +85,8 @@ impl RigidBody {
             world_inv_inertia_sqrt: AngularInertia::zero(),
             linvel: Vector::zeros(),
             angvel: na::zero(),
+            linear_damping: 0.0,
+            angular_damping: 0.0,
             linacc: Vector::zeros(),
             angacc: na::zero(),
             colliders: Vec::new(),
@@ -90,6 +97,7 @@ impl RigidBody {
             active_set_offset: 0,
             active_set_timestamp: 0,
             body_status: BodyStatus::Dynamic,
+            ccd_enabled: false,
         }
     }
 
@@ -102,6 +110,11 @@ impl RigidBody {
             self.linacc = na::zero();
             self.angacc = na::zero();
         }
+
+        // NOTE: this is the implicit integration of dv/dt = -damping * v, which
+        // is unconditionally stable for any timestep length.
+        self.linvel *= 1.0 / (1.0 + dt * self.linear_damping);
+        self.angvel *= 1.0 / (1.0 + dt * self.angular_damping);
     }
 
     /// The handles of colliders attached to this rigid body.
@@ -109,6 +122,16 @@ impl RigidBody {
         &self.colliders[..]
     }
 
+    /// Is continuous collision-detection enabled for this rigid-body?
+    pub fn is_ccd_enabled(&self) -> bool {
+        self.ccd_enabled
+    }
+
+    /// Enables or disables continuous collision-detection for this rigid-body.
+    pub fn enable_ccd(&mut self, enabled: bool) {
+        self.ccd_enabled = enabled;
+    }
+
     /// Is this rigid body dynamic?
     ///
     /// A dynamic body can move freely and is affected by forces.
@@ -291,8 +314,11 @@ pub struct RigidBodyBuilder {
     position: Isometry<f32>,
     linvel: Vector<f32>,
     angvel: AngVector<f32>,
+    linear_damping: f32,
+    angular_damping: f32,
     body_status: BodyStatus,
     can_sleep: bool,
+    ccd_enabled: bool,
 }
 
 impl RigidBodyBuilder {
@@ -302,8 +328,11 @@ impl RigidBodyBuilder {
             position: Isometry::identity(),
             linvel: Vector::zeros(),
             angvel: na::zero(),
+            linear_damping: 0.0,
+            angular_damping: 0.0,
             body_status,
             can_sleep: true,
+            ccd_enabled: false,
         }
     }
 
@@ -371,12 +400,39 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets the damping factor for the linear part of the rigid-body motion.
+    ///
+    /// The higher the linear damping factor is, the more quickly the rigid-body
+    /// will slow-down its translational movement.
+    pub fn linear_damping(mut self, factor: f32) -> Self {
+        self.linear_damping = factor;
+        self
+    }
+
+    /// Sets the damping factor for the angular part of the rigid-body motion.
+    ///
+    /// The higher the angular damping factor is, the more quickly the rigid-body
+    /// will slow-down its rotational movement.
+    pub fn angular_damping(mut self, factor: f32) -> Self {
+        self.angular_damping = factor;
+        self
+    }
+
     /// Sets whether or not the rigid-body to be created can sleep if it reaches a dynamic equilibrium.
     pub fn can_sleep(mut self, can_sleep: bool) -> Self {
         self.can_sleep = can_sleep;
         self
     }
 
+    /// Enables continuous collision-detection for the rigid-body to be created.
+    ///
+    /// This prevents fast-moving bodies from tunnelling through thin obstacles, at the cost of
+    /// additional computations after the resolution of each timestep.
+    pub fn ccd_enabled(mut self, enabled: bool) -> Self {
+        self.ccd_enabled = enabled;
+        self
+    }
+
     /// Build a new rigid-body with the parameters configured with this builder.
     pub fn build(&self) -> RigidBody {
         let mut rb = RigidBody::new();
@@ -384,7 +440,10 @@ impl RigidBodyBuilder {
         rb.set_position(self.position);
         rb.linvel = self.linvel;
         rb.angvel = self.angvel;
+        rb.linear_damping = self.linear_damping;
+        rb.angular_damping = self.angular_damping;
         rb.body_status = self.body_status;
+        rb.ccd_enabled = self.ccd_enabled;
 
         if !self.can_sleep {
             rb.activation.threshold = -1.0;
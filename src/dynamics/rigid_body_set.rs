@@ -466,12 +466,17 @@ impl RigidBodySet {
                 let collider = &colliders[*collider_handle];
 
                 for inter in contact_graph.interactions_with(collider.contact_graph_index) {
+                    let other = crate::utils::other_handle((inter.0, inter.1), *collider_handle);
+                    let other_collider = &colliders[other];
+
+                    if !collider.solver_groups.test(other_collider.solver_groups) {
+                        // These contacts are ignored by the solver so they don't link islands.
+                        continue;
+                    }
+
                     for manifold in &inter.2.manifolds {
                         if manifold.num_active_contacts() > 0 {
-                            let other =
-                                crate::utils::other_handle((inter.0, inter.1), *collider_handle);
-                            let other_body = colliders[other].parent;
-                            self.stack.push(other_body);
+                            self.stack.push(other_collider.parent);
                             break;
                         }
                     }
@@ -181,11 +181,11 @@ impl InteractionGroups {
         interaction_indices: &[JointIndex],
     ) {
         // NOTE: in 3D we have up to 10 different joint types.
-        // In 2D we only have 5 joint types.
+        // In 2D we only have 6 joint types.
         #[cfg(feature = "dim3")]
         const NUM_JOINT_TYPES: usize = 10;
         #[cfg(feature = "dim2")]
-        const NUM_JOINT_TYPES: usize = 5;
+        const NUM_JOINT_TYPES: usize = 6;
 
         // The j-th bit of joint_type_conflicts[i] indicates that the
         // j-th bucket contains a joint with a type different than `i`.
@@ -0,0 +1,153 @@
+use crate::dynamics::{DistanceJoint, IntegrationParameters, RigidBody};
+use crate::math::{AngularInertia, Isometry, Point, Rotation};
+use crate::utils::{self, WAngularInertia, WCross, WDot};
+
+#[derive(Debug)]
+pub(crate) struct DistancePositionConstraint {
+    position1: usize,
+    position2: usize,
+
+    local_com1: Point<f32>,
+    local_com2: Point<f32>,
+
+    im1: f32,
+    im2: f32,
+
+    ii1: AngularInertia<f32>,
+    ii2: AngularInertia<f32>,
+
+    local_anchor1: Point<f32>,
+    local_anchor2: Point<f32>,
+
+    limits_enabled: bool,
+    limits: [f32; 2],
+}
+
+impl DistancePositionConstraint {
+    pub fn from_params(rb1: &RigidBody, rb2: &RigidBody, cparams: &DistanceJoint) -> Self {
+        Self {
+            local_com1: rb1.mass_properties.local_com,
+            local_com2: rb2.mass_properties.local_com,
+            im1: rb1.mass_properties.inv_mass,
+            im2: rb2.mass_properties.inv_mass,
+            ii1: rb1.world_inv_inertia_sqrt.squared(),
+            ii2: rb2.world_inv_inertia_sqrt.squared(),
+            local_anchor1: cparams.local_anchor1,
+            local_anchor2: cparams.local_anchor2,
+            limits_enabled: cparams.limits_enabled,
+            limits: cparams.limits,
+            position1: rb1.active_set_offset,
+            position2: rb2.active_set_offset,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        if !self.limits_enabled {
+            return;
+        }
+
+        let mut position1 = positions[self.position1 as usize];
+        let mut position2 = positions[self.position2 as usize];
+
+        let anchor1 = position1 * self.local_anchor1;
+        let anchor2 = position2 * self.local_anchor2;
+        let dir = anchor2 - anchor1;
+        let dist = dir.norm();
+        let err = dist - na::clamp(dist, self.limits[0], self.limits[1]);
+
+        if err != 0.0 {
+            let axis = dir * utils::inv(dist);
+            let centered_anchor1 = anchor1 - position1 * self.local_com1;
+            let centered_anchor2 = anchor2 - position2 * self.local_com2;
+            let gcross1 = centered_anchor1.gcross(axis);
+            let gcross2 = centered_anchor2.gcross(axis);
+
+            let lhs = self.im1
+                + self.im2
+                + gcross1.gdot(self.ii1.transform_vector(gcross1))
+                + gcross2.gdot(self.ii2.transform_vector(gcross2));
+            let impulse = -err * params.joint_erp * utils::inv(lhs);
+
+            position1.translation.vector -= axis * (self.im1 * impulse);
+            position2.translation.vector += axis * (self.im2 * impulse);
+
+            let angle1 = self.ii1.transform_vector(gcross1 * -impulse);
+            let angle2 = self.ii2.transform_vector(gcross2 * impulse);
+
+            position1.rotation = Rotation::new(angle1) * position1.rotation;
+            position2.rotation = Rotation::new(angle2) * position2.rotation;
+
+            positions[self.position1 as usize] = position1;
+            positions[self.position2 as usize] = position2;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DistancePositionGroundConstraint {
+    position2: usize,
+    anchor1: Point<f32>,
+    im2: f32,
+    ii2: AngularInertia<f32>,
+    local_anchor2: Point<f32>,
+    local_com2: Point<f32>,
+    limits_enabled: bool,
+    limits: [f32; 2],
+}
+
+impl DistancePositionGroundConstraint {
+    pub fn from_params(
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &DistanceJoint,
+        flipped: bool,
+    ) -> Self {
+        // NOTE: the distance constraint is symmetric so we only
+        // need to swap the anchors if the bodies were flipped.
+        let (local_anchor1, local_anchor2) = if flipped {
+            (cparams.local_anchor2, cparams.local_anchor1)
+        } else {
+            (cparams.local_anchor1, cparams.local_anchor2)
+        };
+
+        Self {
+            anchor1: rb1.predicted_position * local_anchor1,
+            im2: rb2.mass_properties.inv_mass,
+            ii2: rb2.world_inv_inertia_sqrt.squared(),
+            local_anchor2,
+            position2: rb2.active_set_offset,
+            local_com2: rb2.mass_properties.local_com,
+            limits_enabled: cparams.limits_enabled,
+            limits: cparams.limits,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        if !self.limits_enabled {
+            return;
+        }
+
+        let mut position2 = positions[self.position2 as usize];
+
+        let anchor2 = position2 * self.local_anchor2;
+        let dir = anchor2 - self.anchor1;
+        let dist = dir.norm();
+        let err = dist - na::clamp(dist, self.limits[0], self.limits[1]);
+
+        if err != 0.0 {
+            let axis = dir * utils::inv(dist);
+            let centered_anchor2 = anchor2 - position2 * self.local_com2;
+            let gcross2 = centered_anchor2.gcross(axis);
+
+            let lhs = self.im2 + gcross2.gdot(self.ii2.transform_vector(gcross2));
+            let impulse = -err * params.joint_erp * utils::inv(lhs);
+
+            position2.translation.vector += axis * (self.im2 * impulse);
+
+            let angle2 = self.ii2.transform_vector(gcross2 * impulse);
+            position2.rotation = Rotation::new(angle2) * position2.rotation;
+
+            positions[self.position2 as usize] = position2;
+        }
+    }
+}
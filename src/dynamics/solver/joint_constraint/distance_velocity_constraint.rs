@@ -0,0 +1,355 @@
+use super::motor_coefficients;
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    DistanceJoint, IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RigidBody,
+};
+use crate::math::{AngVector, Point, Vector};
+use crate::utils::{self, WAngularInertia, WCross, WDot};
+
+/// The direction from the first anchor to the second anchor, and the distance between them.
+///
+/// The direction is zero if both anchors coincide.
+fn axis_and_distance(anchor1: &Point<f32>, anchor2: &Point<f32>) -> (Vector<f32>, f32) {
+    let dir = anchor2 - anchor1;
+    let dist = dir.norm();
+    (dir * utils::inv(dist), dist)
+}
+
+#[derive(Debug)]
+pub(crate) struct DistanceVelocityConstraint {
+    mj_lambda1: usize,
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    axis: Vector<f32>,
+    rhs: f32,
+    inv_lhs: f32,
+
+    limits_impulse: f32,
+    limits_impulse_bounds: Option<[f32; 2]>,
+
+    spring_rhs: f32,
+    spring_inv_lhs: f32,
+    spring_gamma: f32,
+    spring_impulse: f32,
+
+    ang_dir1: AngVector<f32>,
+    ang_dir2: AngVector<f32>,
+
+    im1: f32,
+    im2: f32,
+}
+
+impl DistanceVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &DistanceJoint,
+    ) -> Self {
+        let anchor1 = rb1.position * cparams.local_anchor1;
+        let anchor2 = rb2.position * cparams.local_anchor2;
+        let r1 = anchor1 - rb1.world_com;
+        let r2 = anchor2 - rb2.world_com;
+        let (axis, dist) = axis_and_distance(&anchor1, &anchor2);
+
+        let im1 = rb1.mass_properties.inv_mass;
+        let im2 = rb2.mass_properties.inv_mass;
+        let ang_dir1 = rb1.world_inv_inertia_sqrt.transform_vector(r1.gcross(axis));
+        let ang_dir2 = rb2.world_inv_inertia_sqrt.transform_vector(r2.gcross(axis));
+
+        let vel1 = rb1.linvel + rb1.angvel.gcross(r1);
+        let vel2 = rb2.linvel + rb2.angvel.gcross(r2);
+        let rhs = (vel2 - vel1).dot(&axis);
+        let lhs = im1 + im2 + ang_dir1.gdot(ang_dir1) + ang_dir2.gdot(ang_dir2);
+        let inv_lhs = utils::inv(lhs);
+
+        /*
+         * Setup limits.
+         */
+        let limits_impulse_bounds = cparams.limits_impulse_bounds(dist);
+        let limits_impulse = if limits_impulse_bounds.is_some() {
+            cparams.limits_impulse
+        } else {
+            0.0
+        };
+
+        /*
+         * Setup spring.
+         */
+        let (spring_erp_inv_dt, _, spring_gamma) = motor_coefficients(
+            params.dt(),
+            cparams.spring_stiffness,
+            cparams.spring_damping,
+        );
+        let mut spring_rhs = 0.0;
+        let mut spring_inv_lhs = 0.0;
+        let mut spring_impulse = 0.0;
+
+        if cparams.spring_enabled() {
+            spring_rhs = rhs + spring_erp_inv_dt * (dist - cparams.spring_rest_length);
+            spring_inv_lhs = utils::inv(lhs + spring_gamma);
+            spring_impulse = cparams.spring_impulse;
+        }
+
+        DistanceVelocityConstraint {
+            joint_id,
+            mj_lambda1: rb1.active_set_offset,
+            mj_lambda2: rb2.active_set_offset,
+            im1,
+            im2,
+            axis,
+            rhs,
+            inv_lhs,
+            limits_impulse: limits_impulse * params.warmstart_coeff,
+            limits_impulse_bounds,
+            spring_rhs,
+            spring_inv_lhs,
+            spring_gamma,
+            spring_impulse: spring_impulse * params.warmstart_coeff,
+            ang_dir1,
+            ang_dir2,
+        }
+    }
+
+    fn apply_impulse(
+        &self,
+        impulse: f32,
+        mj_lambda1: &mut DeltaVel<f32>,
+        mj_lambda2: &mut DeltaVel<f32>,
+    ) {
+        mj_lambda1.linear -= self.axis * (self.im1 * impulse);
+        mj_lambda1.angular -= self.ang_dir1 * impulse;
+        mj_lambda2.linear += self.axis * (self.im2 * impulse);
+        mj_lambda2.angular += self.ang_dir2 * impulse;
+    }
+
+    fn dvel(&self, mj_lambda1: &DeltaVel<f32>, mj_lambda2: &DeltaVel<f32>) -> f32 {
+        (mj_lambda2.linear - mj_lambda1.linear).dot(&self.axis)
+            + mj_lambda2.angular.gdot(self.ang_dir2)
+            - mj_lambda1.angular.gdot(self.ang_dir1)
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        self.apply_impulse(
+            self.limits_impulse + self.spring_impulse,
+            &mut mj_lambda1,
+            &mut mj_lambda2,
+        );
+
+        mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        /*
+         * Spring.
+         */
+        if self.spring_inv_lhs != 0.0 {
+            let dvel = self.dvel(&mj_lambda1, &mj_lambda2) + self.spring_rhs;
+            let new_impulse = self.spring_impulse
+                - self.spring_inv_lhs * (dvel + self.spring_gamma * self.spring_impulse);
+            let dimpulse = new_impulse - self.spring_impulse;
+            self.spring_impulse = new_impulse;
+
+            self.apply_impulse(dimpulse, &mut mj_lambda1, &mut mj_lambda2);
+        }
+
+        /*
+         * Limits.
+         */
+        if let Some([min_impulse, max_impulse]) = self.limits_impulse_bounds {
+            let dvel = self.dvel(&mj_lambda1, &mj_lambda2) + self.rhs;
+            let new_impulse = na::clamp(
+                self.limits_impulse - dvel * self.inv_lhs,
+                min_impulse,
+                max_impulse,
+            );
+            let dimpulse = new_impulse - self.limits_impulse;
+            self.limits_impulse = new_impulse;
+
+            self.apply_impulse(dimpulse, &mut mj_lambda1, &mut mj_lambda2);
+        }
+
+        mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::DistanceJoint(distance) = &mut joint.params {
+            distance.limits_impulse = self.limits_impulse;
+            distance.spring_impulse = self.spring_impulse;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DistanceVelocityGroundConstraint {
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    axis: Vector<f32>,
+    rhs: f32,
+    inv_lhs: f32,
+
+    limits_impulse: f32,
+    limits_impulse_bounds: Option<[f32; 2]>,
+
+    spring_rhs: f32,
+    spring_inv_lhs: f32,
+    spring_gamma: f32,
+    spring_impulse: f32,
+
+    ang_dir2: AngVector<f32>,
+    im2: f32,
+}
+
+impl DistanceVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &DistanceJoint,
+        flipped: bool,
+    ) -> Self {
+        // NOTE: the distance constraint is symmetric so we only
+        // need to swap the anchors if the bodies were flipped.
+        let (anchor1, anchor2) = if flipped {
+            (
+                rb1.position * cparams.local_anchor2,
+                rb2.position * cparams.local_anchor1,
+            )
+        } else {
+            (
+                rb1.position * cparams.local_anchor1,
+                rb2.position * cparams.local_anchor2,
+            )
+        };
+        let r1 = anchor1 - rb1.world_com;
+        let r2 = anchor2 - rb2.world_com;
+        let (axis, dist) = axis_and_distance(&anchor1, &anchor2);
+
+        let im2 = rb2.mass_properties.inv_mass;
+        let ang_dir2 = rb2.world_inv_inertia_sqrt.transform_vector(r2.gcross(axis));
+
+        let vel1 = rb1.linvel + rb1.angvel.gcross(r1);
+        let vel2 = rb2.linvel + rb2.angvel.gcross(r2);
+        let rhs = (vel2 - vel1).dot(&axis);
+        let lhs = im2 + ang_dir2.gdot(ang_dir2);
+        let inv_lhs = utils::inv(lhs);
+
+        /*
+         * Setup limits.
+         */
+        let limits_impulse_bounds = cparams.limits_impulse_bounds(dist);
+        let limits_impulse = if limits_impulse_bounds.is_some() {
+            cparams.limits_impulse
+        } else {
+            0.0
+        };
+
+        /*
+         * Setup spring.
+         */
+        let (spring_erp_inv_dt, _, spring_gamma) = motor_coefficients(
+            params.dt(),
+            cparams.spring_stiffness,
+            cparams.spring_damping,
+        );
+        let mut spring_rhs = 0.0;
+        let mut spring_inv_lhs = 0.0;
+        let mut spring_impulse = 0.0;
+
+        if cparams.spring_enabled() {
+            spring_rhs = rhs + spring_erp_inv_dt * (dist - cparams.spring_rest_length);
+            spring_inv_lhs = utils::inv(lhs + spring_gamma);
+            spring_impulse = cparams.spring_impulse;
+        }
+
+        DistanceVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            im2,
+            axis,
+            rhs,
+            inv_lhs,
+            limits_impulse: limits_impulse * params.warmstart_coeff,
+            limits_impulse_bounds,
+            spring_rhs,
+            spring_inv_lhs,
+            spring_gamma,
+            spring_impulse: spring_impulse * params.warmstart_coeff,
+            ang_dir2,
+        }
+    }
+
+    fn apply_impulse(&self, impulse: f32, mj_lambda2: &mut DeltaVel<f32>) {
+        mj_lambda2.linear += self.axis * (self.im2 * impulse);
+        mj_lambda2.angular += self.ang_dir2 * impulse;
+    }
+
+    fn dvel(&self, mj_lambda2: &DeltaVel<f32>) -> f32 {
+        mj_lambda2.linear.dot(&self.axis) + mj_lambda2.angular.gdot(self.ang_dir2)
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+        self.apply_impulse(self.limits_impulse + self.spring_impulse, &mut mj_lambda2);
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        /*
+         * Spring.
+         */
+        if self.spring_inv_lhs != 0.0 {
+            let dvel = self.dvel(&mj_lambda2) + self.spring_rhs;
+            let new_impulse = self.spring_impulse
+                - self.spring_inv_lhs * (dvel + self.spring_gamma * self.spring_impulse);
+            let dimpulse = new_impulse - self.spring_impulse;
+            self.spring_impulse = new_impulse;
+
+            self.apply_impulse(dimpulse, &mut mj_lambda2);
+        }
+
+        /*
+         * Limits.
+         */
+        if let Some([min_impulse, max_impulse]) = self.limits_impulse_bounds {
+            let dvel = self.dvel(&mj_lambda2) + self.rhs;
+            let new_impulse = na::clamp(
+                self.limits_impulse - dvel * self.inv_lhs,
+                min_impulse,
+                max_impulse,
+            );
+            let dimpulse = new_impulse - self.limits_impulse;
+            self.limits_impulse = new_impulse;
+
+            self.apply_impulse(dimpulse, &mut mj_lambda2);
+        }
+
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    // FIXME: duplicated code with the non-ground constraint.
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::DistanceJoint(distance) = &mut joint.params {
+            distance.limits_impulse = self.limits_impulse;
+            distance.spring_impulse = self.spring_impulse;
+        }
+    }
+}
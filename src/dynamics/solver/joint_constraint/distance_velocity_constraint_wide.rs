@@ -0,0 +1,475 @@
+use simba::simd::{SimdPartialOrd, SimdValue};
+
+use super::motor_coefficients;
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    DistanceJoint, IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RigidBody,
+};
+use crate::math::{
+    AngVector, AngularInertia, Isometry, Point, SimdBool, SimdFloat, Vector, SIMD_WIDTH,
+};
+use crate::utils::{WAngularInertia, WCross, WDot};
+
+/// The direction from the first anchor to the second anchor, and the distance between them.
+///
+/// The direction is zero if both anchors coincide.
+fn axis_and_distance(
+    anchor1: &Point<SimdFloat>,
+    anchor2: &Point<SimdFloat>,
+) -> (Vector<SimdFloat>, SimdFloat) {
+    let _0: SimdFloat = na::zero();
+    let _1: SimdFloat = na::one();
+    let dir = anchor2 - anchor1;
+    let dist = dir.norm();
+    let inv_dist = (_1 / dist).select(dist.simd_gt(_0), _0);
+    (dir * inv_dist, dist)
+}
+
+/// The impulse bounds used to enforce the limits of each joint, zero for inactive limits.
+fn limits_impulse_bounds(
+    cparams: [&DistanceJoint; SIMD_WIDTH],
+    dist: SimdFloat,
+) -> (SimdFloat, SimdFloat) {
+    let bounds = array![|ii| cparams[ii].limits_impulse_bounds(dist.extract(ii)).unwrap_or([0.0; 2]); SIMD_WIDTH];
+    (
+        SimdFloat::from(array![|ii| bounds[ii][0]; SIMD_WIDTH]),
+        SimdFloat::from(array![|ii| bounds[ii][1]; SIMD_WIDTH]),
+    )
+}
+
+#[derive(Debug)]
+pub(crate) struct WDistanceVelocityConstraint {
+    mj_lambda1: [usize; SIMD_WIDTH],
+    mj_lambda2: [usize; SIMD_WIDTH],
+
+    joint_id: [JointIndex; SIMD_WIDTH],
+
+    axis: Vector<SimdFloat>,
+    rhs: SimdFloat,
+    inv_lhs: SimdFloat,
+
+    limits_impulse: SimdFloat,
+    limits_min_impulse: SimdFloat,
+    limits_max_impulse: SimdFloat,
+
+    spring_rhs: SimdFloat,
+    spring_inv_lhs: SimdFloat,
+    spring_gamma: SimdFloat,
+    spring_impulse: SimdFloat,
+
+    ang_dir1: AngVector<SimdFloat>,
+    ang_dir2: AngVector<SimdFloat>,
+
+    im1: SimdFloat,
+    im2: SimdFloat,
+}
+
+impl WDistanceVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; SIMD_WIDTH],
+        rbs1: [&RigidBody; SIMD_WIDTH],
+        rbs2: [&RigidBody; SIMD_WIDTH],
+        cparams: [&DistanceJoint; SIMD_WIDTH],
+    ) -> Self {
+        let _0: SimdFloat = na::zero();
+        let _1: SimdFloat = na::one();
+
+        let position1 = Isometry::from(array![|ii| rbs1[ii].position; SIMD_WIDTH]);
+        let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
+        let angvel1 = AngVector::<SimdFloat>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
+        let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
+        let im1 = SimdFloat::from(array![|ii| rbs1[ii].mass_properties.inv_mass; SIMD_WIDTH]);
+        let ii1_sqrt = AngularInertia::<SimdFloat>::from(
+            array![|ii| rbs1[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
+        );
+        let mj_lambda1 = array![|ii| rbs1[ii].active_set_offset; SIMD_WIDTH];
+
+        let position2 = Isometry::from(array![|ii| rbs2[ii].position; SIMD_WIDTH]);
+        let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
+        let angvel2 = AngVector::<SimdFloat>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
+        let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
+        let im2 = SimdFloat::from(array![|ii| rbs2[ii].mass_properties.inv_mass; SIMD_WIDTH]);
+        let ii2_sqrt = AngularInertia::<SimdFloat>::from(
+            array![|ii| rbs2[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
+        );
+        let mj_lambda2 = array![|ii| rbs2[ii].active_set_offset; SIMD_WIDTH];
+
+        let local_anchor1 = Point::from(array![|ii| cparams[ii].local_anchor1; SIMD_WIDTH]);
+        let local_anchor2 = Point::from(array![|ii| cparams[ii].local_anchor2; SIMD_WIDTH]);
+
+        let anchor1 = position1 * local_anchor1;
+        let anchor2 = position2 * local_anchor2;
+        let r1 = anchor1 - world_com1;
+        let r2 = anchor2 - world_com2;
+        let (axis, dist) = axis_and_distance(&anchor1, &anchor2);
+
+        let ang_dir1 = ii1_sqrt.transform_vector(r1.gcross(axis));
+        let ang_dir2 = ii2_sqrt.transform_vector(r2.gcross(axis));
+
+        let vel1: Vector<SimdFloat> = linvel1 + angvel1.gcross(r1);
+        let vel2: Vector<SimdFloat> = linvel2 + angvel2.gcross(r2);
+        let rhs = (vel2 - vel1).dot(&axis);
+        let lhs = im1 + im2 + ang_dir1.gdot(ang_dir1) + ang_dir2.gdot(ang_dir2);
+        let inv_lhs = (_1 / lhs).select(lhs.simd_gt(_0), _0);
+
+        /*
+         * Setup limits.
+         */
+        let (limits_min_impulse, limits_max_impulse) = limits_impulse_bounds(cparams, dist);
+        let limits_enabled = limits_min_impulse.simd_ne(_0) | limits_max_impulse.simd_ne(_0);
+        let limits_impulse = SimdFloat::from(array![|ii| cparams[ii].limits_impulse; SIMD_WIDTH])
+            .select(limits_enabled, _0);
+
+        /*
+         * Setup spring.
+         */
+        let spring_coeffs = array![|ii| motor_coefficients(params.dt(), cparams[ii].spring_stiffness, cparams[ii].spring_damping); SIMD_WIDTH];
+        let spring_erp_inv_dt = SimdFloat::from(array![|ii| spring_coeffs[ii].0; SIMD_WIDTH]);
+        let spring_gamma = SimdFloat::from(array![|ii| spring_coeffs[ii].2; SIMD_WIDTH]);
+        let spring_enabled = SimdBool::from(array![|ii| cparams[ii].spring_enabled(); SIMD_WIDTH]);
+        let spring_rest_length =
+            SimdFloat::from(array![|ii| cparams[ii].spring_rest_length; SIMD_WIDTH]);
+        let spring_rhs =
+            (rhs + spring_erp_inv_dt * (dist - spring_rest_length)).select(spring_enabled, _0);
+        let spring_lhs = lhs + spring_gamma;
+        let spring_inv_lhs = (_1 / spring_lhs).select(spring_enabled & spring_lhs.simd_gt(_0), _0);
+        let spring_impulse = SimdFloat::from(array![|ii| cparams[ii].spring_impulse; SIMD_WIDTH])
+            .select(spring_enabled, _0);
+
+        let warmstart_coeff = SimdFloat::splat(params.warmstart_coeff);
+
+        WDistanceVelocityConstraint {
+            joint_id,
+            mj_lambda1,
+            mj_lambda2,
+            im1,
+            im2,
+            axis,
+            rhs,
+            inv_lhs,
+            limits_impulse: limits_impulse * warmstart_coeff,
+            limits_min_impulse,
+            limits_max_impulse,
+            spring_rhs,
+            spring_inv_lhs,
+            spring_gamma,
+            spring_impulse: spring_impulse * warmstart_coeff,
+            ang_dir1,
+            ang_dir2,
+        }
+    }
+
+    fn apply_impulse(
+        &self,
+        impulse: SimdFloat,
+        mj_lambda1: &mut DeltaVel<SimdFloat>,
+        mj_lambda2: &mut DeltaVel<SimdFloat>,
+    ) {
+        mj_lambda1.linear -= self.axis * (self.im1 * impulse);
+        mj_lambda1.angular -= self.ang_dir1 * impulse;
+        mj_lambda2.linear += self.axis * (self.im2 * impulse);
+        mj_lambda2.angular += self.ang_dir2 * impulse;
+    }
+
+    fn dvel(
+        &self,
+        mj_lambda1: &DeltaVel<SimdFloat>,
+        mj_lambda2: &DeltaVel<SimdFloat>,
+    ) -> SimdFloat {
+        (mj_lambda2.linear - mj_lambda1.linear).dot(&self.axis)
+            + mj_lambda2.angular.gdot(self.ang_dir2)
+            - mj_lambda1.angular.gdot(self.ang_dir1)
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+        let mut mj_lambda2 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        self.apply_impulse(
+            self.limits_impulse + self.spring_impulse,
+            &mut mj_lambda1,
+            &mut mj_lambda2,
+        );
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
+            mj_lambdas[self.mj_lambda1[ii] as usize].angular = mj_lambda1.angular.extract(ii);
+        }
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1: DeltaVel<SimdFloat> = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+        let mut mj_lambda2: DeltaVel<SimdFloat> = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        /*
+         * Spring.
+         */
+        if self.spring_inv_lhs != na::zero() {
+            let dvel = self.dvel(&mj_lambda1, &mj_lambda2) + self.spring_rhs;
+            let new_impulse = self.spring_impulse
+                - self.spring_inv_lhs * (dvel + self.spring_gamma * self.spring_impulse);
+            let dimpulse = new_impulse - self.spring_impulse;
+            self.spring_impulse = new_impulse;
+
+            self.apply_impulse(dimpulse, &mut mj_lambda1, &mut mj_lambda2);
+        }
+
+        /*
+         * Limits.
+         */
+        let dvel = self.dvel(&mj_lambda1, &mj_lambda2) + self.rhs;
+        let new_impulse = (self.limits_impulse - dvel * self.inv_lhs)
+            .simd_clamp(self.limits_min_impulse, self.limits_max_impulse);
+        let dimpulse = new_impulse - self.limits_impulse;
+        self.limits_impulse = new_impulse;
+
+        self.apply_impulse(dimpulse, &mut mj_lambda1, &mut mj_lambda2);
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
+            mj_lambdas[self.mj_lambda1[ii] as usize].angular = mj_lambda1.angular.extract(ii);
+        }
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        for ii in 0..SIMD_WIDTH {
+            let joint = &mut joints_all[self.joint_id[ii]].weight;
+            if let JointParams::DistanceJoint(distance) = &mut joint.params {
+                distance.limits_impulse = self.limits_impulse.extract(ii);
+                distance.spring_impulse = self.spring_impulse.extract(ii);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct WDistanceVelocityGroundConstraint {
+    mj_lambda2: [usize; SIMD_WIDTH],
+
+    joint_id: [JointIndex; SIMD_WIDTH],
+
+    axis: Vector<SimdFloat>,
+    rhs: SimdFloat,
+    inv_lhs: SimdFloat,
+
+    limits_impulse: SimdFloat,
+    limits_min_impulse: SimdFloat,
+    limits_max_impulse: SimdFloat,
+
+    spring_rhs: SimdFloat,
+    spring_inv_lhs: SimdFloat,
+    spring_gamma: SimdFloat,
+    spring_impulse: SimdFloat,
+
+    ang_dir2: AngVector<SimdFloat>,
+    im2: SimdFloat,
+}
+
+impl WDistanceVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; SIMD_WIDTH],
+        rbs1: [&RigidBody; SIMD_WIDTH],
+        rbs2: [&RigidBody; SIMD_WIDTH],
+        cparams: [&DistanceJoint; SIMD_WIDTH],
+        flipped: [bool; SIMD_WIDTH],
+    ) -> Self {
+        let _0: SimdFloat = na::zero();
+        let _1: SimdFloat = na::one();
+
+        let position1 = Isometry::from(array![|ii| rbs1[ii].position; SIMD_WIDTH]);
+        let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
+        let angvel1 = AngVector::<SimdFloat>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
+        let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
+        let local_anchor1 = Point::from(
+            array![|ii| if flipped[ii] { cparams[ii].local_anchor2 } else { cparams[ii].local_anchor1 }; SIMD_WIDTH],
+        );
+
+        let position2 = Isometry::from(array![|ii| rbs2[ii].position; SIMD_WIDTH]);
+        let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
+        let angvel2 = AngVector::<SimdFloat>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
+        let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
+        let im2 = SimdFloat::from(array![|ii| rbs2[ii].mass_properties.inv_mass; SIMD_WIDTH]);
+        let ii2_sqrt = AngularInertia::<SimdFloat>::from(
+            array![|ii| rbs2[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
+        );
+        let mj_lambda2 = array![|ii| rbs2[ii].active_set_offset; SIMD_WIDTH];
+        let local_anchor2 = Point::from(
+            array![|ii| if flipped[ii] { cparams[ii].local_anchor1 } else { cparams[ii].local_anchor2 }; SIMD_WIDTH],
+        );
+
+        // NOTE: the distance constraint is symmetric so we only
+        // need to swap the anchors if the bodies were flipped.
+        let anchor1 = position1 * local_anchor1;
+        let anchor2 = position2 * local_anchor2;
+        let r1 = anchor1 - world_com1;
+        let r2 = anchor2 - world_com2;
+        let (axis, dist) = axis_and_distance(&anchor1, &anchor2);
+
+        let ang_dir2 = ii2_sqrt.transform_vector(r2.gcross(axis));
+
+        let vel1: Vector<SimdFloat> = linvel1 + angvel1.gcross(r1);
+        let vel2: Vector<SimdFloat> = linvel2 + angvel2.gcross(r2);
+        let rhs = (vel2 - vel1).dot(&axis);
+        let lhs = im2 + ang_dir2.gdot(ang_dir2);
+        let inv_lhs = (_1 / lhs).select(lhs.simd_gt(_0), _0);
+
+        /*
+         * Setup limits.
+         */
+        let (limits_min_impulse, limits_max_impulse) = limits_impulse_bounds(cparams, dist);
+        let limits_enabled = limits_min_impulse.simd_ne(_0) | limits_max_impulse.simd_ne(_0);
+        let limits_impulse = SimdFloat::from(array![|ii| cparams[ii].limits_impulse; SIMD_WIDTH])
+            .select(limits_enabled, _0);
+
+        /*
+         * Setup spring.
+         */
+        let spring_coeffs = array![|ii| motor_coefficients(params.dt(), cparams[ii].spring_stiffness, cparams[ii].spring_damping); SIMD_WIDTH];
+        let spring_erp_inv_dt = SimdFloat::from(array![|ii| spring_coeffs[ii].0; SIMD_WIDTH]);
+        let spring_gamma = SimdFloat::from(array![|ii| spring_coeffs[ii].2; SIMD_WIDTH]);
+        let spring_enabled = SimdBool::from(array![|ii| cparams[ii].spring_enabled(); SIMD_WIDTH]);
+        let spring_rest_length =
+            SimdFloat::from(array![|ii| cparams[ii].spring_rest_length; SIMD_WIDTH]);
+        let spring_rhs =
+            (rhs + spring_erp_inv_dt * (dist - spring_rest_length)).select(spring_enabled, _0);
+        let spring_lhs = lhs + spring_gamma;
+        let spring_inv_lhs = (_1 / spring_lhs).select(spring_enabled & spring_lhs.simd_gt(_0), _0);
+        let spring_impulse = SimdFloat::from(array![|ii| cparams[ii].spring_impulse; SIMD_WIDTH])
+            .select(spring_enabled, _0);
+
+        let warmstart_coeff = SimdFloat::splat(params.warmstart_coeff);
+
+        WDistanceVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2,
+            im2,
+            axis,
+            rhs,
+            inv_lhs,
+            limits_impulse: limits_impulse * warmstart_coeff,
+            limits_min_impulse,
+            limits_max_impulse,
+            spring_rhs,
+            spring_inv_lhs,
+            spring_gamma,
+            spring_impulse: spring_impulse * warmstart_coeff,
+            ang_dir2,
+        }
+    }
+
+    fn apply_impulse(&self, impulse: SimdFloat, mj_lambda2: &mut DeltaVel<SimdFloat>) {
+        mj_lambda2.linear += self.axis * (self.im2 * impulse);
+        mj_lambda2.angular += self.ang_dir2 * impulse;
+    }
+
+    fn dvel(&self, mj_lambda2: &DeltaVel<SimdFloat>) -> SimdFloat {
+        mj_lambda2.linear.dot(&self.axis) + mj_lambda2.angular.gdot(self.ang_dir2)
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        self.apply_impulse(self.limits_impulse + self.spring_impulse, &mut mj_lambda2);
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2: DeltaVel<SimdFloat> = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        /*
+         * Spring.
+         */
+        if self.spring_inv_lhs != na::zero() {
+            let dvel = self.dvel(&mj_lambda2) + self.spring_rhs;
+            let new_impulse = self.spring_impulse
+                - self.spring_inv_lhs * (dvel + self.spring_gamma * self.spring_impulse);
+            let dimpulse = new_impulse - self.spring_impulse;
+            self.spring_impulse = new_impulse;
+
+            self.apply_impulse(dimpulse, &mut mj_lambda2);
+        }
+
+        /*
+         * Limits.
+         */
+        let dvel = self.dvel(&mj_lambda2) + self.rhs;
+        let new_impulse = (self.limits_impulse - dvel * self.inv_lhs)
+            .simd_clamp(self.limits_min_impulse, self.limits_max_impulse);
+        let dimpulse = new_impulse - self.limits_impulse;
+        self.limits_impulse = new_impulse;
+
+        self.apply_impulse(dimpulse, &mut mj_lambda2);
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    // FIXME: duplicated code with the non-ground constraint.
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        for ii in 0..SIMD_WIDTH {
+            let joint = &mut joints_all[self.joint_id[ii]].weight;
+            if let JointParams::DistanceJoint(distance) = &mut joint.params {
+                distance.limits_impulse = self.limits_impulse.extract(ii);
+                distance.spring_impulse = self.spring_impulse.extract(ii);
+            }
+        }
+    }
+}
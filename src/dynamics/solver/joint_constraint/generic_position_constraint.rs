@@ -0,0 +1,143 @@
+use crate::dynamics::{GenericJoint, IntegrationParameters, RigidBody};
+use crate::math::{AngVector, AngularInertia, Isometry, Rotation, Vector};
+use crate::utils::WAngularInertia;
+#[cfg(feature = "dim3")]
+use na::U3;
+
+#[derive(Debug)]
+pub(crate) struct GenericPositionConstraint {
+    position1: usize,
+    position2: usize,
+    joint: GenericJoint,
+    im1: f32,
+    im2: f32,
+    ii1: AngularInertia<f32>,
+    ii2: AngularInertia<f32>,
+
+    lin_inv_lhs: f32,
+    ang_inv_lhs: AngularInertia<f32>,
+}
+
+impl GenericPositionConstraint {
+    pub fn from_params(rb1: &RigidBody, rb2: &RigidBody, cparams: &GenericJoint) -> Self {
+        let ii1 = rb1.world_inv_inertia_sqrt.squared();
+        let ii2 = rb2.world_inv_inertia_sqrt.squared();
+        let im1 = rb1.mass_properties.inv_mass;
+        let im2 = rb2.mass_properties.inv_mass;
+        let lin_inv_lhs = 1.0 / (im1 + im2);
+        let ang_inv_lhs = (ii1 + ii2).inverse();
+
+        Self {
+            joint: *cparams,
+            position1: rb1.active_set_offset,
+            position2: rb2.active_set_offset,
+            im1,
+            im2,
+            ii1,
+            ii2,
+            lin_inv_lhs,
+            ang_inv_lhs,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        let mut position1 = positions[self.position1 as usize];
+        let mut position2 = positions[self.position2 as usize];
+
+        // Angular correction.
+        let (_, ang_err) = position_errors(&self.joint, &position1, &position2);
+        let ang_impulse = self
+            .ang_inv_lhs
+            .transform_vector(ang_err * params.joint_erp);
+        position1.rotation =
+            Rotation::new(self.ii1.transform_vector(ang_impulse)) * position1.rotation;
+        position2.rotation =
+            Rotation::new(self.ii2.transform_vector(-ang_impulse)) * position2.rotation;
+
+        // Linear correction.
+        let (lin_err, _) = position_errors(&self.joint, &position1, &position2);
+        let impulse = lin_err * (self.lin_inv_lhs * params.joint_erp);
+        position1.translation.vector += self.im1 * impulse;
+        position2.translation.vector -= self.im2 * impulse;
+
+        positions[self.position1 as usize] = position1;
+        positions[self.position2 as usize] = position2;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GenericPositionGroundConstraint {
+    position1: Isometry<f32>,
+    position2: usize,
+    joint: GenericJoint,
+    flipped: bool,
+}
+
+impl GenericPositionGroundConstraint {
+    pub fn from_params(
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &GenericJoint,
+        flipped: bool,
+    ) -> Self {
+        Self {
+            position1: rb1.predicted_position,
+            position2: rb2.active_set_offset,
+            joint: *cparams,
+            flipped,
+        }
+    }
+
+    pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
+        let mut position2 = positions[self.position2 as usize];
+
+        // The errors are oriented from the joint's first body toward its second body
+        // so the correction of the dynamic body depends on which one it is.
+        let erp = if self.flipped {
+            params.joint_erp
+        } else {
+            -params.joint_erp
+        };
+
+        // Angular correction.
+        let (_, ang_err) = self.position_errors(&position2);
+        position2.rotation = Rotation::new(ang_err * erp) * position2.rotation;
+
+        // Linear correction.
+        let (lin_err, _) = self.position_errors(&position2);
+        // NOTE: no need to divide by im2 just to multiply right after.
+        position2.translation.vector += lin_err * erp;
+
+        positions[self.position2 as usize] = position2;
+    }
+
+    fn position_errors(&self, position2: &Isometry<f32>) -> (Vector<f32>, AngVector<f32>) {
+        if self.flipped {
+            position_errors(&self.joint, position2, &self.position1)
+        } else {
+            position_errors(&self.joint, &self.position1, position2)
+        }
+    }
+}
+
+/// The world-space linear and angular errors to be corrected because of the locks and limits
+/// of the joint, given the positions of the joint's first and second bodies.
+fn position_errors(
+    joint: &GenericJoint,
+    position1: &Isometry<f32>,
+    position2: &Isometry<f32>,
+) -> (Vector<f32>, AngVector<f32>) {
+    let frame1 = position1 * joint.local_anchor1;
+    let coordinates = joint.coordinates(position1, position2);
+    let err = joint.position_errors(&coordinates);
+
+    #[cfg(feature = "dim2")]
+    let (lin_err, ang_err) = (Vector::new(err.x, err.y), err.z);
+    #[cfg(feature = "dim3")]
+    let (lin_err, ang_err) = (
+        Vector::new(err.x, err.y, err.z),
+        frame1 * err.fixed_rows::<U3>(3).into_owned(),
+    );
+
+    (frame1 * lin_err, ang_err)
+}
@@ -0,0 +1,605 @@
+use super::motor_coefficients;
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    GenericJoint, IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RigidBody,
+};
+use crate::math::{AngVector, AngularInertia, Dim, Matrix, SpacialVector, Vector, SPATIAL_DIM};
+use crate::utils::{self, WAngularInertia, WCross, WCrossMatrix};
+use na::SimdRealField;
+#[cfg(feature = "dim3")]
+use na::{Cholesky, Matrix6, U3};
+#[cfg(feature = "dim2")]
+use {crate::utils::SdpMatrix3, na::Matrix3};
+
+/// Expresses a relative linear and angular velocity along the axes of a generic joint.
+#[cfg(feature = "dim2")]
+pub(super) fn to_joint_axes<N: SimdRealField>(
+    basis: &Matrix<N>,
+    lin: Vector<N>,
+    ang: AngVector<N>,
+) -> SpacialVector<N> {
+    let lin = basis.tr_mul(&lin);
+    SpacialVector::new(lin.x, lin.y, ang)
+}
+
+/// Expresses a relative linear and angular velocity along the axes of a generic joint.
+#[cfg(feature = "dim3")]
+pub(super) fn to_joint_axes<N: SimdRealField>(
+    basis: &Matrix<N>,
+    lin: Vector<N>,
+    ang: AngVector<N>,
+) -> SpacialVector<N> {
+    let lin = basis.tr_mul(&lin);
+    let ang = basis.tr_mul(&ang);
+    SpacialVector::new(lin.x, lin.y, lin.z, ang.x, ang.y, ang.z)
+}
+
+/// Splits an impulse expressed along the axes of a generic joint into its world-space
+/// linear and angular parts.
+pub(super) fn from_joint_axes<N: SimdRealField>(
+    basis: &Matrix<N>,
+    impulse: &SpacialVector<N>,
+) -> (Vector<N>, AngVector<N>) {
+    let lin = basis * impulse.fixed_rows::<Dim>(0).into_owned();
+    #[cfg(feature = "dim2")]
+    let ang = impulse.z;
+    #[cfg(feature = "dim3")]
+    let ang = basis * impulse.fixed_rows::<U3>(3).into_owned();
+    (lin, ang)
+}
+
+#[derive(Debug)]
+pub(crate) struct GenericVelocityConstraint {
+    mj_lambda1: usize,
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    basis1: Matrix<f32>,
+
+    impulse: SpacialVector<f32>,
+    locked_axes: SpacialVector<f32>,
+
+    #[cfg(feature = "dim3")]
+    inv_lhs: Matrix6<f32>,
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix3<f32>,
+    rhs: SpacialVector<f32>,
+
+    limits_impulse: SpacialVector<f32>,
+    limits_dirs: SpacialVector<f32>,
+    limits_inv_lhs: SpacialVector<f32>,
+
+    motor_rhs: SpacialVector<f32>,
+    motor_inv_lhs: SpacialVector<f32>,
+    motor_gamma: SpacialVector<f32>,
+    motor_impulse: SpacialVector<f32>,
+    motor_max_impulse: SpacialVector<f32>,
+
+    im1: f32,
+    im2: f32,
+
+    ii1_sqrt: AngularInertia<f32>,
+    ii2_sqrt: AngularInertia<f32>,
+
+    r1: Vector<f32>,
+    r2: Vector<f32>,
+}
+
+impl GenericVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &GenericJoint,
+    ) -> Self {
+        let anchor1 = rb1.position * cparams.local_anchor1;
+        let anchor2 = rb2.position * cparams.local_anchor2;
+        let basis1 = anchor1.rotation.to_rotation_matrix().into_inner();
+        let im1 = rb1.mass_properties.inv_mass;
+        let im2 = rb2.mass_properties.inv_mass;
+        let ii1 = rb1.world_inv_inertia_sqrt.squared();
+        let ii2 = rb2.world_inv_inertia_sqrt.squared();
+        let r1 = anchor1.translation.vector - rb1.world_com.coords;
+        let r2 = anchor2.translation.vector - rb2.world_com.coords;
+        let locked_axes = cparams.locked_axes_mask();
+
+        let axis_lhs;
+        let inv_lhs;
+
+        // This is the same system as for the fixed joint, except that it is expressed
+        // along the axes of the joint. The rows and columns of the free axes are then
+        // replaced by the identity so they don't affect the locked ones.
+        #[cfg(feature = "dim3")]
+        {
+            let rmat1 = r1.gcross_matrix() * basis1;
+            let rmat2 = r2.gcross_matrix() * basis1;
+            let lhs00 =
+                ii1.quadform(&rmat1).add_diagonal(im1) + ii2.quadform(&rmat2).add_diagonal(im2);
+            let lhs10 = basis1.tr_mul(&(ii1 * rmat1 + ii2 * rmat2));
+            let lhs11 = (ii1 + ii2).quadform(&basis1);
+
+            // Note that Cholesky only reads the lower-triangular part of the matrix
+            // so we don't need to fill lhs01.
+            let mut lhs = Matrix6::zeros();
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U3, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U3, U3>(3, 3)
+                .copy_from(&lhs11.into_matrix());
+            axis_lhs = lhs.diagonal();
+
+            for i in 0..SPATIAL_DIM {
+                for j in 0..i {
+                    lhs[(i, j)] *= locked_axes[i] * locked_axes[j];
+                }
+                lhs[(i, i)] = lhs[(i, i)] * locked_axes[i] + (1.0 - locked_axes[i]);
+            }
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        // In 2D we just unroll the computation because
+        // it's just easier that way.
+        #[cfg(feature = "dim2")]
+        {
+            let rmat1 = basis1.tr_mul(&r1.gcross_matrix());
+            let rmat2 = basis1.tr_mul(&r2.gcross_matrix());
+            let m11 = im1 + im2 + rmat1.x * rmat1.x * ii1 + rmat2.x * rmat2.x * ii2;
+            let m12 = rmat1.x * rmat1.y * ii1 + rmat2.x * rmat2.y * ii2;
+            let m22 = im1 + im2 + rmat1.y * rmat1.y * ii1 + rmat2.y * rmat2.y * ii2;
+            let m13 = rmat1.x * ii1 + rmat2.x * ii2;
+            let m23 = rmat1.y * ii1 + rmat2.y * ii2;
+            let m33 = ii1 + ii2;
+            let l = locked_axes;
+            axis_lhs = SpacialVector::new(m11, m22, m33);
+            inv_lhs = SdpMatrix3::new(
+                m11 * l.x + (1.0 - l.x),
+                m12 * l.x * l.y,
+                m13 * l.x * l.z,
+                m22 * l.y + (1.0 - l.y),
+                m23 * l.y * l.z,
+                m33 * l.z + (1.0 - l.z),
+            )
+            .inverse_unchecked()
+            .into_matrix();
+        }
+
+        let lin_dvel = rb2.linvel + rb2.angvel.gcross(r2) - rb1.linvel - rb1.angvel.gcross(r1);
+        let ang_dvel = rb2.angvel - rb1.angvel;
+        let rhs = to_joint_axes(&basis1, lin_dvel, ang_dvel);
+
+        // Setup limit constraints.
+        let coordinates = cparams.coordinates(&rb1.position, &rb2.position);
+        let limits_dirs = cparams.limits_directions(&coordinates);
+        let limits_impulse = cparams.limits_impulse.component_mul(&limits_dirs.abs());
+
+        // Setup motors.
+        let motor_errors = cparams.motor_errors(&coordinates);
+        let mut motor_rhs = SpacialVector::zeros();
+        let mut motor_inv_lhs = SpacialVector::zeros();
+        let mut motor_gamma = SpacialVector::zeros();
+        let mut motor_impulse = SpacialVector::zeros();
+
+        for i in 0..SPATIAL_DIM {
+            if cparams.motor_enabled(i) {
+                let (motor_erp_inv_dt, motor_damping, gamma) = motor_coefficients(
+                    params.dt(),
+                    cparams.motor_stiffness[i],
+                    cparams.motor_damping[i],
+                );
+                motor_rhs[i] = rhs[i] - motor_damping * cparams.motor_target_vel[i]
+                    + motor_erp_inv_dt * motor_errors[i];
+                motor_inv_lhs[i] = 1.0 / (axis_lhs[i] + gamma);
+                motor_gamma[i] = gamma;
+                motor_impulse[i] = cparams.motor_impulse[i];
+            }
+        }
+
+        GenericVelocityConstraint {
+            joint_id,
+            mj_lambda1: rb1.active_set_offset,
+            mj_lambda2: rb2.active_set_offset,
+            im1,
+            im2,
+            ii1_sqrt: rb1.world_inv_inertia_sqrt,
+            ii2_sqrt: rb2.world_inv_inertia_sqrt,
+            basis1,
+            impulse: cparams.impulse.component_mul(&locked_axes) * params.warmstart_coeff,
+            locked_axes,
+            inv_lhs,
+            rhs,
+            limits_impulse: limits_impulse * params.warmstart_coeff,
+            limits_dirs,
+            limits_inv_lhs: axis_lhs.map(utils::inv),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * params.warmstart_coeff,
+            motor_max_impulse: cparams.motor_max_impulse,
+            r1,
+            r2,
+        }
+    }
+
+    /// The relative velocity of the bodies along each axis of the joint.
+    fn dvel(&self, mj_lambda1: &DeltaVel<f32>, mj_lambda2: &DeltaVel<f32>) -> SpacialVector<f32> {
+        let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+        let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+        let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2)
+            - mj_lambda1.linear
+            - ang_vel1.gcross(self.r1);
+        to_joint_axes(&self.basis1, lin_dvel, ang_vel2 - ang_vel1)
+    }
+
+    /// Applies `impulse`, expressed along the axes of the joint, to the first body and its opposite
+    /// to the second body.
+    fn apply_impulse(
+        &self,
+        impulse: &SpacialVector<f32>,
+        mj_lambda1: &mut DeltaVel<f32>,
+        mj_lambda2: &mut DeltaVel<f32>,
+    ) {
+        let (lin_impulse, ang_impulse) = from_joint_axes(&self.basis1, impulse);
+
+        mj_lambda1.linear += self.im1 * lin_impulse;
+        mj_lambda1.angular += self
+            .ii1_sqrt
+            .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+
+        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.angular -= self
+            .ii2_sqrt
+            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        // The limits and motor impulses are applied to the second body.
+        let impulse = self.impulse
+            - self.limits_impulse.component_mul(&self.limits_dirs)
+            - self.motor_impulse;
+        self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+
+        mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        /*
+         * Locked axes.
+         */
+        let dvel = self.dvel(&mj_lambda1, &mj_lambda2);
+        let impulse = (self.inv_lhs * (dvel + self.rhs)).component_mul(&self.locked_axes);
+        self.impulse += impulse;
+        self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+
+        /*
+         * Joint motors.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.motor_inv_lhs[i] != 0.0 {
+                let dvel = self.dvel(&mj_lambda1, &mj_lambda2)[i] + self.motor_rhs[i];
+                let new_impulse = na::clamp(
+                    self.motor_impulse[i]
+                        - self.motor_inv_lhs[i]
+                            * (dvel + self.motor_gamma[i] * self.motor_impulse[i]),
+                    -self.motor_max_impulse[i],
+                    self.motor_max_impulse[i],
+                );
+                let dimpulse = new_impulse - self.motor_impulse[i];
+                self.motor_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse);
+                self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+            }
+        }
+
+        /*
+         * Joint limits.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.limits_dirs[i] != 0.0 {
+                let dvel =
+                    (self.dvel(&mj_lambda1, &mj_lambda2)[i] + self.rhs[i]) * self.limits_dirs[i];
+                let new_impulse = (self.limits_impulse[i] - dvel * self.limits_inv_lhs[i]).max(0.0);
+                let dimpulse = new_impulse - self.limits_impulse[i];
+                self.limits_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse * self.limits_dirs[i]);
+                self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+            }
+        }
+
+        mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::GenericJoint(generic) = &mut joint.params {
+            generic.impulse = self.impulse;
+            generic.limits_impulse = self.limits_impulse;
+            generic.motor_impulse = self.motor_impulse;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GenericVelocityGroundConstraint {
+    mj_lambda2: usize,
+
+    joint_id: JointIndex,
+
+    basis1: Matrix<f32>,
+    // -1.0 if the joint's first body is the dynamic one, 1.0 otherwise.
+    flip: f32,
+
+    impulse: SpacialVector<f32>,
+    locked_axes: SpacialVector<f32>,
+
+    #[cfg(feature = "dim3")]
+    inv_lhs: Matrix6<f32>,
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix3<f32>,
+    rhs: SpacialVector<f32>,
+
+    limits_impulse: SpacialVector<f32>,
+    limits_dirs: SpacialVector<f32>,
+    limits_inv_lhs: SpacialVector<f32>,
+
+    motor_rhs: SpacialVector<f32>,
+    motor_inv_lhs: SpacialVector<f32>,
+    motor_gamma: SpacialVector<f32>,
+    motor_impulse: SpacialVector<f32>,
+    motor_max_impulse: SpacialVector<f32>,
+
+    im2: f32,
+    ii2_sqrt: AngularInertia<f32>,
+    r2: Vector<f32>,
+}
+
+impl GenericVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb1: &RigidBody,
+        rb2: &RigidBody,
+        cparams: &GenericJoint,
+        flipped: bool,
+    ) -> Self {
+        let (anchor1, anchor2) = if flipped {
+            (
+                rb1.position * cparams.local_anchor2,
+                rb2.position * cparams.local_anchor1,
+            )
+        } else {
+            (
+                rb1.position * cparams.local_anchor1,
+                rb2.position * cparams.local_anchor2,
+            )
+        };
+        let (position1, position2) = if flipped {
+            (&rb2.position, &rb1.position)
+        } else {
+            (&rb1.position, &rb2.position)
+        };
+
+        // The axes of the joint are attached to the joint's first body.
+        let basis1 = if flipped {
+            anchor2.rotation.to_rotation_matrix().into_inner()
+        } else {
+            anchor1.rotation.to_rotation_matrix().into_inner()
+        };
+        let flip = if flipped { -1.0 } else { 1.0 };
+
+        let r1 = anchor1.translation.vector - rb1.world_com.coords;
+
+        let im2 = rb2.mass_properties.inv_mass;
+        let ii2 = rb2.world_inv_inertia_sqrt.squared();
+        let r2 = anchor2.translation.vector - rb2.world_com.coords;
+        let locked_axes = cparams.locked_axes_mask();
+
+        let axis_lhs;
+        let inv_lhs;
+
+        #[cfg(feature = "dim3")]
+        {
+            let rmat2 = r2.gcross_matrix() * basis1;
+            let lhs00 = ii2.quadform(&rmat2).add_diagonal(im2);
+            let lhs10 = basis1.tr_mul(&(ii2 * rmat2));
+            let lhs11 = ii2.quadform(&basis1);
+
+            // Note that Cholesky only reads the lower-triangular part of the matrix
+            // so we don't need to fill lhs01.
+            let mut lhs = Matrix6::zeros();
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U3, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U3, U3>(3, 3)
+                .copy_from(&lhs11.into_matrix());
+            axis_lhs = lhs.diagonal();
+
+            for i in 0..SPATIAL_DIM {
+                for j in 0..i {
+                    lhs[(i, j)] *= locked_axes[i] * locked_axes[j];
+                }
+                lhs[(i, i)] = lhs[(i, i)] * locked_axes[i] + (1.0 - locked_axes[i]);
+            }
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let rmat2 = basis1.tr_mul(&r2.gcross_matrix());
+            let m11 = im2 + rmat2.x * rmat2.x * ii2;
+            let m12 = rmat2.x * rmat2.y * ii2;
+            let m22 = im2 + rmat2.y * rmat2.y * ii2;
+            let m13 = rmat2.x * ii2;
+            let m23 = rmat2.y * ii2;
+            let m33 = ii2;
+            let l = locked_axes;
+            axis_lhs = SpacialVector::new(m11, m22, m33);
+            inv_lhs = SdpMatrix3::new(
+                m11 * l.x + (1.0 - l.x),
+                m12 * l.x * l.y,
+                m13 * l.x * l.z,
+                m22 * l.y + (1.0 - l.y),
+                m23 * l.y * l.z,
+                m33 * l.z + (1.0 - l.z),
+            )
+            .inverse_unchecked()
+            .into_matrix();
+        }
+
+        let lin_dvel = rb2.linvel + rb2.angvel.gcross(r2) - rb1.linvel - rb1.angvel.gcross(r1);
+        let ang_dvel = rb2.angvel - rb1.angvel;
+        let rhs = to_joint_axes(&basis1, lin_dvel, ang_dvel);
+
+        // Setup limit constraints.
+        // The limits and the motors are oriented from the joint's first body toward its second
+        // body, so they must be negated if the bodies are flipped.
+        let coordinates = cparams.coordinates(position1, position2);
+        let limits_dirs = cparams.limits_directions(&coordinates) * flip;
+        let limits_impulse = cparams.limits_impulse.component_mul(&limits_dirs.abs());
+
+        // Setup motors.
+        let motor_errors = cparams.motor_errors(&coordinates);
+        let mut motor_rhs = SpacialVector::zeros();
+        let mut motor_inv_lhs = SpacialVector::zeros();
+        let mut motor_gamma = SpacialVector::zeros();
+        let mut motor_impulse = SpacialVector::zeros();
+
+        for i in 0..SPATIAL_DIM {
+            if cparams.motor_enabled(i) {
+                let (motor_erp_inv_dt, motor_damping, gamma) = motor_coefficients(
+                    params.dt(),
+                    cparams.motor_stiffness[i],
+                    cparams.motor_damping[i],
+                );
+                motor_rhs[i] = rhs[i] * flip - motor_damping * cparams.motor_target_vel[i]
+                    + motor_erp_inv_dt * motor_errors[i];
+                motor_inv_lhs[i] = 1.0 / (axis_lhs[i] + gamma);
+                motor_gamma[i] = gamma;
+                motor_impulse[i] = cparams.motor_impulse[i];
+            }
+        }
+
+        GenericVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            im2,
+            ii2_sqrt: rb2.world_inv_inertia_sqrt,
+            basis1,
+            flip,
+            impulse: cparams.impulse.component_mul(&locked_axes) * params.warmstart_coeff,
+            locked_axes,
+            inv_lhs,
+            rhs,
+            limits_impulse: limits_impulse * params.warmstart_coeff,
+            limits_dirs,
+            limits_inv_lhs: axis_lhs.map(utils::inv),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * params.warmstart_coeff,
+            motor_max_impulse: cparams.motor_max_impulse,
+            r2,
+        }
+    }
+
+    /// The velocity of the dynamic body along each axis of the joint.
+    fn dvel(&self, mj_lambda2: &DeltaVel<f32>) -> SpacialVector<f32> {
+        let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+        let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2);
+        to_joint_axes(&self.basis1, lin_dvel, ang_vel2)
+    }
+
+    /// Applies the opposite of `impulse`, expressed along the axes of the joint, to the dynamic body.
+    fn apply_impulse(&self, impulse: &SpacialVector<f32>, mj_lambda2: &mut DeltaVel<f32>) {
+        let (lin_impulse, ang_impulse) = from_joint_axes(&self.basis1, impulse);
+
+        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.angular -= self
+            .ii2_sqrt
+            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        let impulse = self.impulse
+            - self.limits_impulse.component_mul(&self.limits_dirs)
+            - self.motor_impulse * self.flip;
+        self.apply_impulse(&impulse, &mut mj_lambda2);
+
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        /*
+         * Locked axes.
+         */
+        let dvel = self.dvel(&mj_lambda2);
+        let impulse = (self.inv_lhs * (dvel + self.rhs)).component_mul(&self.locked_axes);
+        self.impulse += impulse;
+        self.apply_impulse(&impulse, &mut mj_lambda2);
+
+        /*
+         * Joint motors.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.motor_inv_lhs[i] != 0.0 {
+                let dvel = self.dvel(&mj_lambda2)[i] * self.flip + self.motor_rhs[i];
+                let new_impulse = na::clamp(
+                    self.motor_impulse[i]
+                        - self.motor_inv_lhs[i]
+                            * (dvel + self.motor_gamma[i] * self.motor_impulse[i]),
+                    -self.motor_max_impulse[i],
+                    self.motor_max_impulse[i],
+                );
+                let dimpulse = new_impulse - self.motor_impulse[i];
+                self.motor_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse * self.flip);
+                self.apply_impulse(&impulse, &mut mj_lambda2);
+            }
+        }
+
+        /*
+         * Joint limits.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.limits_dirs[i] != 0.0 {
+                let dvel = (self.dvel(&mj_lambda2)[i] + self.rhs[i]) * self.limits_dirs[i];
+                let new_impulse = (self.limits_impulse[i] - dvel * self.limits_inv_lhs[i]).max(0.0);
+                let dimpulse = new_impulse - self.limits_impulse[i];
+                self.limits_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse * self.limits_dirs[i]);
+                self.apply_impulse(&impulse, &mut mj_lambda2);
+            }
+        }
+
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    // FIXME: duplicated code with the non-ground constraint.
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::GenericJoint(generic) = &mut joint.params {
+            generic.impulse = self.impulse;
+            generic.limits_impulse = self.limits_impulse;
+            generic.motor_impulse = self.motor_impulse;
+        }
+    }
+}
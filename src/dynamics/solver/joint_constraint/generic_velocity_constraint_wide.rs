@@ -0,0 +1,707 @@
+use simba::simd::{SimdBool as _, SimdPartialOrd, SimdValue};
+
+use super::generic_velocity_constraint::{from_joint_axes, to_joint_axes};
+use super::motor_coefficients;
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    GenericJoint, IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RigidBody,
+};
+use crate::math::{
+    AngVector, AngularInertia, Isometry, Matrix, Point, SimdBool, SimdFloat, SpacialVector, Vector,
+    SIMD_WIDTH, SPATIAL_DIM,
+};
+use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
+#[cfg(feature = "dim3")]
+use na::{Cholesky, Matrix6, U3};
+#[cfg(feature = "dim2")]
+use {crate::utils::SdpMatrix3, na::Matrix3};
+
+#[derive(Debug)]
+pub(crate) struct WGenericVelocityConstraint {
+    mj_lambda1: [usize; SIMD_WIDTH],
+    mj_lambda2: [usize; SIMD_WIDTH],
+
+    joint_id: [JointIndex; SIMD_WIDTH],
+
+    basis1: Matrix<SimdFloat>,
+
+    impulse: SpacialVector<SimdFloat>,
+    locked_axes: SpacialVector<SimdFloat>,
+
+    #[cfg(feature = "dim3")]
+    inv_lhs: Matrix6<SimdFloat>,
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix3<SimdFloat>,
+    rhs: SpacialVector<SimdFloat>,
+
+    limits_impulse: SpacialVector<SimdFloat>,
+    limits_dirs: SpacialVector<SimdFloat>,
+    limits_inv_lhs: SpacialVector<SimdFloat>,
+
+    motor_rhs: SpacialVector<SimdFloat>,
+    motor_inv_lhs: SpacialVector<SimdFloat>,
+    motor_gamma: SpacialVector<SimdFloat>,
+    motor_impulse: SpacialVector<SimdFloat>,
+    motor_max_impulse: SpacialVector<SimdFloat>,
+
+    im1: SimdFloat,
+    im2: SimdFloat,
+
+    ii1_sqrt: AngularInertia<SimdFloat>,
+    ii2_sqrt: AngularInertia<SimdFloat>,
+
+    r1: Vector<SimdFloat>,
+    r2: Vector<SimdFloat>,
+}
+
+impl WGenericVelocityConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; SIMD_WIDTH],
+        rbs1: [&RigidBody; SIMD_WIDTH],
+        rbs2: [&RigidBody; SIMD_WIDTH],
+        cparams: [&GenericJoint; SIMD_WIDTH],
+    ) -> Self {
+        let position1 = Isometry::from(array![|ii| rbs1[ii].position; SIMD_WIDTH]);
+        let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
+        let angvel1 = AngVector::<SimdFloat>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
+        let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
+        let im1 = SimdFloat::from(array![|ii| rbs1[ii].mass_properties.inv_mass; SIMD_WIDTH]);
+        let ii1_sqrt = AngularInertia::<SimdFloat>::from(
+            array![|ii| rbs1[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
+        );
+        let mj_lambda1 = array![|ii| rbs1[ii].active_set_offset; SIMD_WIDTH];
+
+        let position2 = Isometry::from(array![|ii| rbs2[ii].position; SIMD_WIDTH]);
+        let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
+        let angvel2 = AngVector::<SimdFloat>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
+        let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
+        let im2 = SimdFloat::from(array![|ii| rbs2[ii].mass_properties.inv_mass; SIMD_WIDTH]);
+        let ii2_sqrt = AngularInertia::<SimdFloat>::from(
+            array![|ii| rbs2[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
+        );
+        let mj_lambda2 = array![|ii| rbs2[ii].active_set_offset; SIMD_WIDTH];
+
+        let local_anchor1 = Isometry::from(array![|ii| cparams[ii].local_anchor1; SIMD_WIDTH]);
+        let local_anchor2 = Isometry::from(array![|ii| cparams[ii].local_anchor2; SIMD_WIDTH]);
+        let impulse = SpacialVector::from(array![|ii| cparams[ii].impulse; SIMD_WIDTH]);
+        let locked_axes =
+            SpacialVector::from(array![|ii| cparams[ii].locked_axes_mask(); SIMD_WIDTH]);
+
+        let anchor1 = position1 * local_anchor1;
+        let anchor2 = position2 * local_anchor2;
+        let basis1 = anchor1.rotation.to_rotation_matrix().into_inner();
+        let ii1 = ii1_sqrt.squared();
+        let ii2 = ii2_sqrt.squared();
+        let r1 = anchor1.translation.vector - world_com1.coords;
+        let r2 = anchor2.translation.vector - world_com2.coords;
+
+        let _0: SimdFloat = na::zero();
+        let _1: SimdFloat = na::one();
+
+        let axis_lhs;
+        let inv_lhs;
+
+        // This is the same system as for the fixed joint, except that it is expressed
+        // along the axes of the joint. The rows and columns of the free axes are then
+        // replaced by the identity so they don't affect the locked ones.
+        #[cfg(feature = "dim3")]
+        {
+            let rmat1 = r1.gcross_matrix() * basis1;
+            let rmat2 = r2.gcross_matrix() * basis1;
+            let lhs00 =
+                ii1.quadform(&rmat1).add_diagonal(im1) + ii2.quadform(&rmat2).add_diagonal(im2);
+            let lhs10 = basis1.tr_mul(&(ii1 * rmat1 + ii2 * rmat2));
+            let lhs11 = (ii1 + ii2).quadform(&basis1);
+
+            // Note that Cholesky only reads the lower-triangular part of the matrix
+            // so we don't need to fill lhs01.
+            let mut lhs = Matrix6::zeros();
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U3, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U3, U3>(3, 3)
+                .copy_from(&lhs11.into_matrix());
+            axis_lhs = lhs.diagonal();
+
+            for i in 0..SPATIAL_DIM {
+                for j in 0..i {
+                    lhs[(i, j)] *= locked_axes[i] * locked_axes[j];
+                }
+                lhs[(i, i)] = lhs[(i, i)] * locked_axes[i] + (_1 - locked_axes[i]);
+            }
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        // In 2D we just unroll the computation because
+        // it's just easier that way.
+        #[cfg(feature = "dim2")]
+        {
+            let rmat1: Vector<SimdFloat> = basis1.tr_mul(&r1.gcross_matrix());
+            let rmat2: Vector<SimdFloat> = basis1.tr_mul(&r2.gcross_matrix());
+            let m11 = im1 + im2 + rmat1.x * rmat1.x * ii1 + rmat2.x * rmat2.x * ii2;
+            let m12 = rmat1.x * rmat1.y * ii1 + rmat2.x * rmat2.y * ii2;
+            let m22 = im1 + im2 + rmat1.y * rmat1.y * ii1 + rmat2.y * rmat2.y * ii2;
+            let m13 = rmat1.x * ii1 + rmat2.x * ii2;
+            let m23 = rmat1.y * ii1 + rmat2.y * ii2;
+            let m33 = ii1 + ii2;
+            let l = locked_axes;
+            axis_lhs = SpacialVector::new(m11, m22, m33);
+            inv_lhs = SdpMatrix3::new(
+                m11 * l.x + (_1 - l.x),
+                m12 * l.x * l.y,
+                m13 * l.x * l.z,
+                m22 * l.y + (_1 - l.y),
+                m23 * l.y * l.z,
+                m33 * l.z + (_1 - l.z),
+            )
+            .inverse_unchecked()
+            .into_matrix();
+        }
+
+        let lin_dvel = linvel2 + angvel2.gcross(r2) - linvel1 - angvel1.gcross(r1);
+        let ang_dvel = angvel2 - angvel1;
+        let rhs = to_joint_axes(&basis1, lin_dvel, ang_dvel);
+
+        // Setup limit constraints.
+        let coordinates = array![|ii| cparams[ii].coordinates(&rbs1[ii].position, &rbs2[ii].position); SIMD_WIDTH];
+        let limits_dirs = array![|ii| cparams[ii].limits_directions(&coordinates[ii]); SIMD_WIDTH];
+        let limits_impulse = SpacialVector::from(
+            array![|ii| cparams[ii].limits_impulse.component_mul(&limits_dirs[ii].abs()); SIMD_WIDTH],
+        );
+        let limits_dirs = SpacialVector::from(limits_dirs);
+
+        // Setup motors.
+        let motor_errors = array![|ii| cparams[ii].motor_errors(&coordinates[ii]); SIMD_WIDTH];
+        let motor_max_impulse =
+            SpacialVector::from(array![|ii| cparams[ii].motor_max_impulse; SIMD_WIDTH]);
+        let mut motor_rhs = SpacialVector::zeros();
+        let mut motor_inv_lhs = SpacialVector::zeros();
+        let mut motor_gamma = SpacialVector::zeros();
+        let mut motor_impulse = SpacialVector::zeros();
+
+        for i in 0..SPATIAL_DIM {
+            let motor_enabled =
+                SimdBool::from(array![|ii| cparams[ii].motor_enabled(i); SIMD_WIDTH]);
+
+            if motor_enabled.any() {
+                let coeffs = array![|ii| motor_coefficients(
+                    params.dt(),
+                    cparams[ii].motor_stiffness[i],
+                    cparams[ii].motor_damping[i]
+                ); SIMD_WIDTH];
+                let motor_erp_inv_dt = SimdFloat::from(array![|ii| coeffs[ii].0; SIMD_WIDTH]);
+                let motor_damping = SimdFloat::from(array![|ii| coeffs[ii].1; SIMD_WIDTH]);
+                let target_vel =
+                    SimdFloat::from(array![|ii| cparams[ii].motor_target_vel[i]; SIMD_WIDTH]);
+                let error = SimdFloat::from(array![|ii| motor_errors[ii][i]; SIMD_WIDTH]);
+
+                motor_gamma[i] = SimdFloat::from(array![|ii| coeffs[ii].2; SIMD_WIDTH]);
+                motor_rhs[i] = rhs[i] - motor_damping * target_vel + motor_erp_inv_dt * error;
+                motor_inv_lhs[i] = (_1 / (axis_lhs[i] + motor_gamma[i])).select(motor_enabled, _0);
+                motor_impulse[i] =
+                    SimdFloat::from(array![|ii| cparams[ii].motor_impulse[i]; SIMD_WIDTH])
+                        .select(motor_enabled, _0);
+            }
+        }
+
+        WGenericVelocityConstraint {
+            joint_id,
+            mj_lambda1,
+            mj_lambda2,
+            im1,
+            im2,
+            ii1_sqrt,
+            ii2_sqrt,
+            basis1,
+            impulse: impulse.component_mul(&locked_axes) * SimdFloat::splat(params.warmstart_coeff),
+            locked_axes,
+            inv_lhs,
+            rhs,
+            limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
+            limits_dirs,
+            limits_inv_lhs: axis_lhs.map(|lhs| (_1 / lhs).select(lhs.simd_gt(_0), _0)),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * SimdFloat::splat(params.warmstart_coeff),
+            motor_max_impulse,
+            r1,
+            r2,
+        }
+    }
+
+    /// The relative velocity of the bodies along each axis of the joint.
+    fn dvel(
+        &self,
+        mj_lambda1: &DeltaVel<SimdFloat>,
+        mj_lambda2: &DeltaVel<SimdFloat>,
+    ) -> SpacialVector<SimdFloat> {
+        let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+        let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+        let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2)
+            - mj_lambda1.linear
+            - ang_vel1.gcross(self.r1);
+        to_joint_axes(&self.basis1, lin_dvel, ang_vel2 - ang_vel1)
+    }
+
+    /// Applies `impulse`, expressed along the axes of the joint, to the first body and its opposite
+    /// to the second body.
+    fn apply_impulse(
+        &self,
+        impulse: &SpacialVector<SimdFloat>,
+        mj_lambda1: &mut DeltaVel<SimdFloat>,
+        mj_lambda2: &mut DeltaVel<SimdFloat>,
+    ) {
+        let (lin_impulse, ang_impulse) = from_joint_axes(&self.basis1, impulse);
+
+        mj_lambda1.linear += lin_impulse * self.im1;
+        mj_lambda1.angular += self
+            .ii1_sqrt
+            .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+
+        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.angular -= self
+            .ii2_sqrt
+            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+        let mut mj_lambda2 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        // The limits and motor impulses are applied to the second body.
+        let impulse = self.impulse
+            - self.limits_impulse.component_mul(&self.limits_dirs)
+            - self.motor_impulse;
+        self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
+            mj_lambdas[self.mj_lambda1[ii] as usize].angular = mj_lambda1.angular.extract(ii);
+        }
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda1 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda1[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+        let mut mj_lambda2 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        /*
+         * Locked axes.
+         */
+        let dvel = self.dvel(&mj_lambda1, &mj_lambda2);
+        let impulse = (self.inv_lhs * (dvel + self.rhs)).component_mul(&self.locked_axes);
+        self.impulse += impulse;
+        self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+
+        /*
+         * Joint motors.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.motor_inv_lhs[i] != na::zero() {
+                let dvel = self.dvel(&mj_lambda1, &mj_lambda2)[i] + self.motor_rhs[i];
+                let new_impulse = (self.motor_impulse[i]
+                    - self.motor_inv_lhs[i] * (dvel + self.motor_gamma[i] * self.motor_impulse[i]))
+                    .simd_clamp(-self.motor_max_impulse[i], self.motor_max_impulse[i]);
+                let dimpulse = new_impulse - self.motor_impulse[i];
+                self.motor_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse);
+                self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+            }
+        }
+
+        /*
+         * Joint limits.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.limits_dirs[i] != na::zero() {
+                let dvel =
+                    (self.dvel(&mj_lambda1, &mj_lambda2)[i] + self.rhs[i]) * self.limits_dirs[i];
+                let new_impulse =
+                    (self.limits_impulse[i] - dvel * self.limits_inv_lhs[i]).simd_max(na::zero());
+                let dimpulse = new_impulse - self.limits_impulse[i];
+                self.limits_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse * self.limits_dirs[i]);
+                self.apply_impulse(&impulse, &mut mj_lambda1, &mut mj_lambda2);
+            }
+        }
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
+            mj_lambdas[self.mj_lambda1[ii] as usize].angular = mj_lambda1.angular.extract(ii);
+        }
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        for ii in 0..SIMD_WIDTH {
+            let joint = &mut joints_all[self.joint_id[ii]].weight;
+            if let JointParams::GenericJoint(generic) = &mut joint.params {
+                generic.impulse = self.impulse.extract(ii);
+                generic.limits_impulse = self.limits_impulse.extract(ii);
+                generic.motor_impulse = self.motor_impulse.extract(ii);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct WGenericVelocityGroundConstraint {
+    mj_lambda2: [usize; SIMD_WIDTH],
+
+    joint_id: [JointIndex; SIMD_WIDTH],
+
+    basis1: Matrix<SimdFloat>,
+    // -1.0 if the joint's first body is the dynamic one, 1.0 otherwise.
+    flip: SimdFloat,
+
+    impulse: SpacialVector<SimdFloat>,
+    locked_axes: SpacialVector<SimdFloat>,
+
+    #[cfg(feature = "dim3")]
+    inv_lhs: Matrix6<SimdFloat>,
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix3<SimdFloat>,
+    rhs: SpacialVector<SimdFloat>,
+
+    limits_impulse: SpacialVector<SimdFloat>,
+    limits_dirs: SpacialVector<SimdFloat>,
+    limits_inv_lhs: SpacialVector<SimdFloat>,
+
+    motor_rhs: SpacialVector<SimdFloat>,
+    motor_inv_lhs: SpacialVector<SimdFloat>,
+    motor_gamma: SpacialVector<SimdFloat>,
+    motor_impulse: SpacialVector<SimdFloat>,
+    motor_max_impulse: SpacialVector<SimdFloat>,
+
+    im2: SimdFloat,
+    ii2_sqrt: AngularInertia<SimdFloat>,
+    r2: Vector<SimdFloat>,
+}
+
+impl WGenericVelocityGroundConstraint {
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: [JointIndex; SIMD_WIDTH],
+        rbs1: [&RigidBody; SIMD_WIDTH],
+        rbs2: [&RigidBody; SIMD_WIDTH],
+        cparams: [&GenericJoint; SIMD_WIDTH],
+        flipped: [bool; SIMD_WIDTH],
+    ) -> Self {
+        let position1 = Isometry::from(array![|ii| rbs1[ii].position; SIMD_WIDTH]);
+        let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
+        let angvel1 = AngVector::<SimdFloat>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
+        let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
+
+        let position2 = Isometry::from(array![|ii| rbs2[ii].position; SIMD_WIDTH]);
+        let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
+        let angvel2 = AngVector::<SimdFloat>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
+        let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
+        let im2 = SimdFloat::from(array![|ii| rbs2[ii].mass_properties.inv_mass; SIMD_WIDTH]);
+        let ii2_sqrt = AngularInertia::<SimdFloat>::from(
+            array![|ii| rbs2[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
+        );
+        let mj_lambda2 = array![|ii| rbs2[ii].active_set_offset; SIMD_WIDTH];
+
+        let local_anchor1 = Isometry::from(
+            array![|ii| if flipped[ii] { cparams[ii].local_anchor2 } else { cparams[ii].local_anchor1 }; SIMD_WIDTH],
+        );
+        let local_anchor2 = Isometry::from(
+            array![|ii| if flipped[ii] { cparams[ii].local_anchor1 } else { cparams[ii].local_anchor2 }; SIMD_WIDTH],
+        );
+        let impulse = SpacialVector::from(array![|ii| cparams[ii].impulse; SIMD_WIDTH]);
+        let locked_axes =
+            SpacialVector::from(array![|ii| cparams[ii].locked_axes_mask(); SIMD_WIDTH]);
+
+        let anchor1 = position1 * local_anchor1;
+        let anchor2 = position2 * local_anchor2;
+        let ii2 = ii2_sqrt.squared();
+        let r1 = anchor1.translation.vector - world_com1.coords;
+        let r2 = anchor2.translation.vector - world_com2.coords;
+
+        let _0: SimdFloat = na::zero();
+        let _1: SimdFloat = na::one();
+
+        // The axes of the joint are attached to the joint's first body.
+        let flip = (-_1).select(SimdBool::from(flipped), _1);
+        let basis1 = Isometry::from(
+            array![|ii| if flipped[ii] { rbs2[ii].position * cparams[ii].local_anchor1 } else { rbs1[ii].position * cparams[ii].local_anchor1 }; SIMD_WIDTH],
+        )
+        .rotation
+        .to_rotation_matrix()
+        .into_inner();
+
+        let axis_lhs;
+        let inv_lhs;
+
+        #[cfg(feature = "dim3")]
+        {
+            let rmat2 = r2.gcross_matrix() * basis1;
+            let lhs00 = ii2.quadform(&rmat2).add_diagonal(im2);
+            let lhs10 = basis1.tr_mul(&(ii2 * rmat2));
+            let lhs11 = ii2.quadform(&basis1);
+
+            // Note that Cholesky only reads the lower-triangular part of the matrix
+            // so we don't need to fill lhs01.
+            let mut lhs = Matrix6::zeros();
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U3, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U3, U3>(3, 3)
+                .copy_from(&lhs11.into_matrix());
+            axis_lhs = lhs.diagonal();
+
+            for i in 0..SPATIAL_DIM {
+                for j in 0..i {
+                    lhs[(i, j)] *= locked_axes[i] * locked_axes[j];
+                }
+                lhs[(i, i)] = lhs[(i, i)] * locked_axes[i] + (_1 - locked_axes[i]);
+            }
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let rmat2: Vector<SimdFloat> = basis1.tr_mul(&r2.gcross_matrix());
+            let m11 = im2 + rmat2.x * rmat2.x * ii2;
+            let m12 = rmat2.x * rmat2.y * ii2;
+            let m22 = im2 + rmat2.y * rmat2.y * ii2;
+            let m13 = rmat2.x * ii2;
+            let m23 = rmat2.y * ii2;
+            let m33 = ii2;
+            let l = locked_axes;
+            axis_lhs = SpacialVector::new(m11, m22, m33);
+            inv_lhs = SdpMatrix3::new(
+                m11 * l.x + (_1 - l.x),
+                m12 * l.x * l.y,
+                m13 * l.x * l.z,
+                m22 * l.y + (_1 - l.y),
+                m23 * l.y * l.z,
+                m33 * l.z + (_1 - l.z),
+            )
+            .inverse_unchecked()
+            .into_matrix();
+        }
+
+        let lin_dvel = linvel2 + angvel2.gcross(r2) - linvel1 - angvel1.gcross(r1);
+        let ang_dvel = angvel2 - angvel1;
+        let rhs = to_joint_axes(&basis1, lin_dvel, ang_dvel);
+
+        // Setup limit constraints.
+        // The limits and the motors are oriented from the joint's first body toward its second
+        // body, so they must be negated if the bodies are flipped.
+        let coordinates = array![|ii| if flipped[ii] { cparams[ii].coordinates(&rbs2[ii].position, &rbs1[ii].position) } else { cparams[ii].coordinates(&rbs1[ii].position, &rbs2[ii].position) }; SIMD_WIDTH];
+        let limits_dirs = array![|ii| cparams[ii].limits_directions(&coordinates[ii]); SIMD_WIDTH];
+        let limits_impulse = SpacialVector::from(
+            array![|ii| cparams[ii].limits_impulse.component_mul(&limits_dirs[ii].abs()); SIMD_WIDTH],
+        );
+        let limits_dirs = SpacialVector::from(limits_dirs) * flip;
+
+        // Setup motors.
+        let motor_errors = array![|ii| cparams[ii].motor_errors(&coordinates[ii]); SIMD_WIDTH];
+        let motor_max_impulse =
+            SpacialVector::from(array![|ii| cparams[ii].motor_max_impulse; SIMD_WIDTH]);
+        let mut motor_rhs = SpacialVector::zeros();
+        let mut motor_inv_lhs = SpacialVector::zeros();
+        let mut motor_gamma = SpacialVector::zeros();
+        let mut motor_impulse = SpacialVector::zeros();
+
+        for i in 0..SPATIAL_DIM {
+            let motor_enabled =
+                SimdBool::from(array![|ii| cparams[ii].motor_enabled(i); SIMD_WIDTH]);
+
+            if motor_enabled.any() {
+                let coeffs = array![|ii| motor_coefficients(
+                    params.dt(),
+                    cparams[ii].motor_stiffness[i],
+                    cparams[ii].motor_damping[i]
+                ); SIMD_WIDTH];
+                let motor_erp_inv_dt = SimdFloat::from(array![|ii| coeffs[ii].0; SIMD_WIDTH]);
+                let motor_damping = SimdFloat::from(array![|ii| coeffs[ii].1; SIMD_WIDTH]);
+                let target_vel =
+                    SimdFloat::from(array![|ii| cparams[ii].motor_target_vel[i]; SIMD_WIDTH]);
+                let error = SimdFloat::from(array![|ii| motor_errors[ii][i]; SIMD_WIDTH]);
+
+                motor_gamma[i] = SimdFloat::from(array![|ii| coeffs[ii].2; SIMD_WIDTH]);
+                motor_rhs[i] =
+                    rhs[i] * flip - motor_damping * target_vel + motor_erp_inv_dt * error;
+                motor_inv_lhs[i] = (_1 / (axis_lhs[i] + motor_gamma[i])).select(motor_enabled, _0);
+                motor_impulse[i] =
+                    SimdFloat::from(array![|ii| cparams[ii].motor_impulse[i]; SIMD_WIDTH])
+                        .select(motor_enabled, _0);
+            }
+        }
+
+        WGenericVelocityGroundConstraint {
+            joint_id,
+            mj_lambda2,
+            im2,
+            ii2_sqrt,
+            basis1,
+            flip,
+            impulse: impulse.component_mul(&locked_axes) * SimdFloat::splat(params.warmstart_coeff),
+            locked_axes,
+            inv_lhs,
+            rhs,
+            limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
+            limits_dirs,
+            limits_inv_lhs: axis_lhs.map(|lhs| (_1 / lhs).select(lhs.simd_gt(_0), _0)),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * SimdFloat::splat(params.warmstart_coeff),
+            motor_max_impulse,
+            r2,
+        }
+    }
+
+    /// The velocity of the dynamic body along each axis of the joint.
+    fn dvel(&self, mj_lambda2: &DeltaVel<SimdFloat>) -> SpacialVector<SimdFloat> {
+        let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+        let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2);
+        to_joint_axes(&self.basis1, lin_dvel, ang_vel2)
+    }
+
+    /// Applies the opposite of `impulse`, expressed along the axes of the joint, to the dynamic body.
+    fn apply_impulse(
+        &self,
+        impulse: &SpacialVector<SimdFloat>,
+        mj_lambda2: &mut DeltaVel<SimdFloat>,
+    ) {
+        let (lin_impulse, ang_impulse) = from_joint_axes(&self.basis1, impulse);
+
+        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.angular -= self
+            .ii2_sqrt
+            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        let impulse = self.impulse
+            - self.limits_impulse.component_mul(&self.limits_dirs)
+            - self.motor_impulse * self.flip;
+        self.apply_impulse(&impulse, &mut mj_lambda2);
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<f32>]) {
+        let mut mj_lambda2 = DeltaVel {
+            linear: Vector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].linear; SIMD_WIDTH],
+            ),
+            angular: AngVector::from(
+                array![|ii| mj_lambdas[self.mj_lambda2[ii] as usize].angular; SIMD_WIDTH],
+            ),
+        };
+
+        /*
+         * Locked axes.
+         */
+        let dvel = self.dvel(&mj_lambda2);
+        let impulse = (self.inv_lhs * (dvel + self.rhs)).component_mul(&self.locked_axes);
+        self.impulse += impulse;
+        self.apply_impulse(&impulse, &mut mj_lambda2);
+
+        /*
+         * Joint motors.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.motor_inv_lhs[i] != na::zero() {
+                let dvel = self.dvel(&mj_lambda2)[i] * self.flip + self.motor_rhs[i];
+                let new_impulse = (self.motor_impulse[i]
+                    - self.motor_inv_lhs[i] * (dvel + self.motor_gamma[i] * self.motor_impulse[i]))
+                    .simd_clamp(-self.motor_max_impulse[i], self.motor_max_impulse[i]);
+                let dimpulse = new_impulse - self.motor_impulse[i];
+                self.motor_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse * self.flip);
+                self.apply_impulse(&impulse, &mut mj_lambda2);
+            }
+        }
+
+        /*
+         * Joint limits.
+         */
+        for i in 0..SPATIAL_DIM {
+            if self.limits_dirs[i] != na::zero() {
+                let dvel = (self.dvel(&mj_lambda2)[i] + self.rhs[i]) * self.limits_dirs[i];
+                let new_impulse =
+                    (self.limits_impulse[i] - dvel * self.limits_inv_lhs[i]).simd_max(na::zero());
+                let dimpulse = new_impulse - self.limits_impulse[i];
+                self.limits_impulse[i] = new_impulse;
+
+                let impulse = SpacialVector::ith(i, -dimpulse * self.limits_dirs[i]);
+                self.apply_impulse(&impulse, &mut mj_lambda2);
+            }
+        }
+
+        for ii in 0..SIMD_WIDTH {
+            mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
+            mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
+        }
+    }
+
+    // FIXME: duplicated code with the non-ground constraint.
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        for ii in 0..SIMD_WIDTH {
+            let joint = &mut joints_all[self.joint_id[ii]].weight;
+            if let JointParams::GenericJoint(generic) = &mut joint.params {
+                generic.impulse = self.impulse.extract(ii);
+                generic.limits_impulse = self.limits_impulse.extract(ii);
+                generic.motor_impulse = self.motor_impulse.extract(ii);
+            }
+        }
+    }
+}
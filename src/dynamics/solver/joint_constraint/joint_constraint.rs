@@ -1,18 +1,18 @@
 use super::{
-    BallVelocityConstraint, BallVelocityGroundConstraint, FixedVelocityConstraint,
-    FixedVelocityGroundConstraint, PrismaticVelocityConstraint, PrismaticVelocityGroundConstraint,
+    BallVelocityConstraint, BallVelocityGroundConstraint, DistanceVelocityConstraint,
+    DistanceVelocityGroundConstraint, FixedVelocityConstraint, FixedVelocityGroundConstraint,
+    GenericVelocityConstraint, GenericVelocityGroundConstraint, PrismaticVelocityConstraint,
+    PrismaticVelocityGroundConstraint, RevoluteVelocityConstraint,
+    RevoluteVelocityGroundConstraint,
 };
-#[cfg(feature = "dim3")]
-use super::{RevoluteVelocityConstraint, RevoluteVelocityGroundConstraint};
 #[cfg(feature = "simd-is-enabled")]
 use super::{
-    WBallVelocityConstraint, WBallVelocityGroundConstraint, WFixedVelocityConstraint,
-    WFixedVelocityGroundConstraint, WPrismaticVelocityConstraint,
-    WPrismaticVelocityGroundConstraint,
+    WBallVelocityConstraint, WBallVelocityGroundConstraint, WDistanceVelocityConstraint,
+    WDistanceVelocityGroundConstraint, WFixedVelocityConstraint, WFixedVelocityGroundConstraint,
+    WGenericVelocityConstraint, WGenericVelocityGroundConstraint, WPrismaticVelocityConstraint,
+    WPrismaticVelocityGroundConstraint, WRevoluteVelocityConstraint,
+    WRevoluteVelocityGroundConstraint,
 };
-#[cfg(feature = "dim3")]
-#[cfg(feature = "simd-is-enabled")]
-use super::{WRevoluteVelocityConstraint, WRevoluteVelocityGroundConstraint};
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, Joint, JointGraphEdge, JointIndex, JointParams, RigidBodySet,
@@ -33,22 +33,30 @@ pub(crate) enum AnyJointVelocityConstraint {
     WFixedConstraint(WFixedVelocityConstraint),
     #[cfg(feature = "simd-is-enabled")]
     WFixedGroundConstraint(WFixedVelocityGroundConstraint),
+    GenericConstraint(GenericVelocityConstraint),
+    GenericGroundConstraint(GenericVelocityGroundConstraint),
+    #[cfg(feature = "simd-is-enabled")]
+    WGenericConstraint(WGenericVelocityConstraint),
+    #[cfg(feature = "simd-is-enabled")]
+    WGenericGroundConstraint(WGenericVelocityGroundConstraint),
     PrismaticConstraint(PrismaticVelocityConstraint),
     PrismaticGroundConstraint(PrismaticVelocityGroundConstraint),
     #[cfg(feature = "simd-is-enabled")]
     WPrismaticConstraint(WPrismaticVelocityConstraint),
     #[cfg(feature = "simd-is-enabled")]
     WPrismaticGroundConstraint(WPrismaticVelocityGroundConstraint),
-    #[cfg(feature = "dim3")]
     RevoluteConstraint(RevoluteVelocityConstraint),
-    #[cfg(feature = "dim3")]
     RevoluteGroundConstraint(RevoluteVelocityGroundConstraint),
-    #[cfg(feature = "dim3")]
     #[cfg(feature = "simd-is-enabled")]
     WRevoluteConstraint(WRevoluteVelocityConstraint),
-    #[cfg(feature = "dim3")]
     #[cfg(feature = "simd-is-enabled")]
     WRevoluteGroundConstraint(WRevoluteVelocityGroundConstraint),
+    DistanceConstraint(DistanceVelocityConstraint),
+    DistanceGroundConstraint(DistanceVelocityGroundConstraint),
+    #[cfg(feature = "simd-is-enabled")]
+    WDistanceConstraint(WDistanceVelocityConstraint),
+    #[cfg(feature = "simd-is-enabled")]
+    WDistanceGroundConstraint(WDistanceVelocityGroundConstraint),
     #[allow(dead_code)] // The Empty variant is only used with parallel code.
     Empty,
 }
@@ -75,13 +83,18 @@ impl AnyJointVelocityConstraint {
             JointParams::FixedJoint(p) => AnyJointVelocityConstraint::FixedConstraint(
                 FixedVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
             ),
+            JointParams::GenericJoint(p) => AnyJointVelocityConstraint::GenericConstraint(
+                GenericVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
             JointParams::PrismaticJoint(p) => AnyJointVelocityConstraint::PrismaticConstraint(
                 PrismaticVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
             ),
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(p) => AnyJointVelocityConstraint::RevoluteConstraint(
                 RevoluteVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
             ),
+            JointParams::DistanceJoint(p) => AnyJointVelocityConstraint::DistanceConstraint(
+                DistanceVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
+            ),
         }
     }
 
@@ -108,6 +121,12 @@ impl AnyJointVelocityConstraint {
                     params, joint_id, rbs1, rbs2, joints,
                 ))
             }
+            JointParams::GenericJoint(_) => {
+                let joints = array![|ii| joints[ii].params.as_generic_joint().unwrap(); SIMD_WIDTH];
+                AnyJointVelocityConstraint::WGenericConstraint(
+                    WGenericVelocityConstraint::from_params(params, joint_id, rbs1, rbs2, joints),
+                )
+            }
             JointParams::PrismaticJoint(_) => {
                 let joints =
                     array![|ii| joints[ii].params.as_prismatic_joint().unwrap(); SIMD_WIDTH];
@@ -115,7 +134,6 @@ impl AnyJointVelocityConstraint {
                     WPrismaticVelocityConstraint::from_params(params, joint_id, rbs1, rbs2, joints),
                 )
             }
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => {
                 let joints =
                     array![|ii| joints[ii].params.as_revolute_joint().unwrap(); SIMD_WIDTH];
@@ -123,6 +141,13 @@ impl AnyJointVelocityConstraint {
                     WRevoluteVelocityConstraint::from_params(params, joint_id, rbs1, rbs2, joints),
                 )
             }
+            JointParams::DistanceJoint(_) => {
+                let joints =
+                    array![|ii| joints[ii].params.as_distance_joint().unwrap(); SIMD_WIDTH];
+                AnyJointVelocityConstraint::WDistanceConstraint(
+                    WDistanceVelocityConstraint::from_params(params, joint_id, rbs1, rbs2, joints),
+                )
+            }
         }
     }
 
@@ -147,6 +172,11 @@ impl AnyJointVelocityConstraint {
             JointParams::FixedJoint(p) => AnyJointVelocityConstraint::FixedGroundConstraint(
                 FixedVelocityGroundConstraint::from_params(params, joint_id, rb1, rb2, p, flipped),
             ),
+            JointParams::GenericJoint(p) => AnyJointVelocityConstraint::GenericGroundConstraint(
+                GenericVelocityGroundConstraint::from_params(
+                    params, joint_id, rb1, rb2, p, flipped,
+                ),
+            ),
             JointParams::PrismaticJoint(p) => {
                 AnyJointVelocityConstraint::PrismaticGroundConstraint(
                     PrismaticVelocityGroundConstraint::from_params(
@@ -154,12 +184,16 @@ impl AnyJointVelocityConstraint {
                     ),
                 )
             }
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(p) => AnyJointVelocityConstraint::RevoluteGroundConstraint(
                 RevoluteVelocityGroundConstraint::from_params(
                     params, joint_id, rb1, rb2, p, flipped,
                 ),
             ),
+            JointParams::DistanceJoint(p) => AnyJointVelocityConstraint::DistanceGroundConstraint(
+                DistanceVelocityGroundConstraint::from_params(
+                    params, joint_id, rb1, rb2, p, flipped,
+                ),
+            ),
         }
     }
 
@@ -198,6 +232,14 @@ impl AnyJointVelocityConstraint {
                     ),
                 )
             }
+            JointParams::GenericJoint(_) => {
+                let joints = array![|ii| joints[ii].params.as_generic_joint().unwrap(); SIMD_WIDTH];
+                AnyJointVelocityConstraint::WGenericGroundConstraint(
+                    WGenericVelocityGroundConstraint::from_params(
+                        params, joint_id, rbs1, rbs2, joints, flipped,
+                    ),
+                )
+            }
             JointParams::PrismaticJoint(_) => {
                 let joints =
                     array![|ii| joints[ii].params.as_prismatic_joint().unwrap(); SIMD_WIDTH];
@@ -207,7 +249,6 @@ impl AnyJointVelocityConstraint {
                     ),
                 )
             }
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => {
                 let joints =
                     array![|ii| joints[ii].params.as_revolute_joint().unwrap(); SIMD_WIDTH];
@@ -217,6 +258,15 @@ impl AnyJointVelocityConstraint {
                     ),
                 )
             }
+            JointParams::DistanceJoint(_) => {
+                let joints =
+                    array![|ii| joints[ii].params.as_distance_joint().unwrap(); SIMD_WIDTH];
+                AnyJointVelocityConstraint::WDistanceGroundConstraint(
+                    WDistanceVelocityGroundConstraint::from_params(
+                        params, joint_id, rbs1, rbs2, joints, flipped,
+                    ),
+                )
+            }
         }
     }
 
@@ -234,22 +284,30 @@ impl AnyJointVelocityConstraint {
             AnyJointVelocityConstraint::WFixedConstraint(c) => c.warmstart(mj_lambdas),
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WFixedGroundConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::GenericConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::GenericGroundConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WGenericConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WGenericGroundConstraint(c) => c.warmstart(mj_lambdas),
             AnyJointVelocityConstraint::PrismaticConstraint(c) => c.warmstart(mj_lambdas),
             AnyJointVelocityConstraint::PrismaticGroundConstraint(c) => c.warmstart(mj_lambdas),
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WPrismaticConstraint(c) => c.warmstart(mj_lambdas),
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WPrismaticGroundConstraint(c) => c.warmstart(mj_lambdas),
-            #[cfg(feature = "dim3")]
             AnyJointVelocityConstraint::RevoluteConstraint(c) => c.warmstart(mj_lambdas),
-            #[cfg(feature = "dim3")]
             AnyJointVelocityConstraint::RevoluteGroundConstraint(c) => c.warmstart(mj_lambdas),
-            #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteConstraint(c) => c.warmstart(mj_lambdas),
-            #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::DistanceConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::DistanceGroundConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WDistanceConstraint(c) => c.warmstart(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WDistanceGroundConstraint(c) => c.warmstart(mj_lambdas),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -268,22 +326,30 @@ impl AnyJointVelocityConstraint {
             AnyJointVelocityConstraint::WFixedConstraint(c) => c.solve(mj_lambdas),
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WFixedGroundConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::GenericConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::GenericGroundConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WGenericConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WGenericGroundConstraint(c) => c.solve(mj_lambdas),
             AnyJointVelocityConstraint::PrismaticConstraint(c) => c.solve(mj_lambdas),
             AnyJointVelocityConstraint::PrismaticGroundConstraint(c) => c.solve(mj_lambdas),
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WPrismaticConstraint(c) => c.solve(mj_lambdas),
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WPrismaticGroundConstraint(c) => c.solve(mj_lambdas),
-            #[cfg(feature = "dim3")]
             AnyJointVelocityConstraint::RevoluteConstraint(c) => c.solve(mj_lambdas),
-            #[cfg(feature = "dim3")]
             AnyJointVelocityConstraint::RevoluteGroundConstraint(c) => c.solve(mj_lambdas),
-            #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteConstraint(c) => c.solve(mj_lambdas),
-            #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::DistanceConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::DistanceGroundConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WDistanceConstraint(c) => c.solve(mj_lambdas),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WDistanceGroundConstraint(c) => c.solve(mj_lambdas),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -310,6 +376,16 @@ impl AnyJointVelocityConstraint {
             AnyJointVelocityConstraint::WFixedGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
             }
+            AnyJointVelocityConstraint::GenericConstraint(c) => c.writeback_impulses(joints_all),
+            AnyJointVelocityConstraint::GenericGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WGenericConstraint(c) => c.writeback_impulses(joints_all),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WGenericGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
             AnyJointVelocityConstraint::PrismaticConstraint(c) => c.writeback_impulses(joints_all),
             AnyJointVelocityConstraint::PrismaticGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
@@ -320,20 +396,26 @@ impl AnyJointVelocityConstraint {
             AnyJointVelocityConstraint::WPrismaticGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
             }
-            #[cfg(feature = "dim3")]
             AnyJointVelocityConstraint::RevoluteConstraint(c) => c.writeback_impulses(joints_all),
-            #[cfg(feature = "dim3")]
             AnyJointVelocityConstraint::RevoluteGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
             }
-            #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteConstraint(c) => c.writeback_impulses(joints_all),
-            #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
             }
+            AnyJointVelocityConstraint::DistanceConstraint(c) => c.writeback_impulses(joints_all),
+            AnyJointVelocityConstraint::DistanceGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WDistanceConstraint(c) => c.writeback_impulses(joints_all),
+            #[cfg(feature = "simd-is-enabled")]
+            AnyJointVelocityConstraint::WDistanceGroundConstraint(c) => {
+                c.writeback_impulses(joints_all)
+            }
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
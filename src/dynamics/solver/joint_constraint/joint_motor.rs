@@ -0,0 +1,17 @@
+/// Computes the coefficients of the implicit spring-damper model used by joint motors.
+///
+/// The velocity constraint of a motor reads:
+/// `dvel + erp_inv_dt * (pos - target_pos) - damping * target_vel + gamma * impulse = 0`
+/// where `dvel` is the relative velocity along the motor axis and `impulse` the accumulated
+/// motor impulse. Returns `(erp_inv_dt, damping, gamma)`, which are all zero if the motor
+/// is disabled.
+pub(crate) fn motor_coefficients(dt: f32, stiffness: f32, damping: f32) -> (f32, f32, f32) {
+    let denom = dt * stiffness + damping;
+
+    if denom > 0.0 {
+        let inv_denom = 1.0 / denom;
+        (stiffness * inv_denom, damping * inv_denom, inv_denom / dt)
+    } else {
+        (0.0, 0.0, 0.0)
+    }
+}
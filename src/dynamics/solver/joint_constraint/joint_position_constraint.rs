@@ -1,9 +1,10 @@
 use super::{
-    BallPositionConstraint, BallPositionGroundConstraint, FixedPositionConstraint,
-    FixedPositionGroundConstraint, PrismaticPositionConstraint, PrismaticPositionGroundConstraint,
+    BallPositionConstraint, BallPositionGroundConstraint, DistancePositionConstraint,
+    DistancePositionGroundConstraint, FixedPositionConstraint, FixedPositionGroundConstraint,
+    GenericPositionConstraint, GenericPositionGroundConstraint, PrismaticPositionConstraint,
+    PrismaticPositionGroundConstraint, RevolutePositionConstraint,
+    RevolutePositionGroundConstraint,
 };
-#[cfg(feature = "dim3")]
-use super::{RevolutePositionConstraint, RevolutePositionGroundConstraint};
 #[cfg(feature = "simd-is-enabled")]
 use super::{WBallPositionConstraint, WBallPositionGroundConstraint};
 use crate::dynamics::{IntegrationParameters, Joint, JointParams, RigidBodySet};
@@ -20,12 +21,14 @@ pub(crate) enum AnyJointPositionConstraint {
     WBallGroundConstraint(WBallPositionGroundConstraint),
     FixedJoint(FixedPositionConstraint),
     FixedGroundConstraint(FixedPositionGroundConstraint),
+    GenericJoint(GenericPositionConstraint),
+    GenericGroundConstraint(GenericPositionGroundConstraint),
     PrismaticJoint(PrismaticPositionConstraint),
     PrismaticGroundConstraint(PrismaticPositionGroundConstraint),
-    #[cfg(feature = "dim3")]
     RevoluteJoint(RevolutePositionConstraint),
-    #[cfg(feature = "dim3")]
     RevoluteGroundConstraint(RevolutePositionGroundConstraint),
+    DistanceJoint(DistancePositionConstraint),
+    DistanceGroundConstraint(DistancePositionGroundConstraint),
     #[allow(dead_code)] // The Empty variant is only used with parallel code.
     Empty,
 }
@@ -60,13 +63,18 @@ impl AnyJointPositionConstraint {
             JointParams::FixedJoint(p) => AnyJointPositionConstraint::FixedJoint(
                 FixedPositionConstraint::from_params(rb1, rb2, p),
             ),
+            JointParams::GenericJoint(p) => AnyJointPositionConstraint::GenericJoint(
+                GenericPositionConstraint::from_params(rb1, rb2, p),
+            ),
             JointParams::PrismaticJoint(p) => AnyJointPositionConstraint::PrismaticJoint(
                 PrismaticPositionConstraint::from_params(rb1, rb2, p),
             ),
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(p) => AnyJointPositionConstraint::RevoluteJoint(
                 RevolutePositionConstraint::from_params(rb1, rb2, p),
             ),
+            JointParams::DistanceJoint(p) => AnyJointPositionConstraint::DistanceJoint(
+                DistancePositionConstraint::from_params(rb1, rb2, p),
+            ),
         }
     }
 
@@ -83,9 +91,10 @@ impl AnyJointPositionConstraint {
                 ))
             }
             JointParams::FixedJoint(_) => None,
+            JointParams::GenericJoint(_) => None,
             JointParams::PrismaticJoint(_) => None,
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => None,
+            JointParams::DistanceJoint(_) => None,
         }
     }
 
@@ -105,15 +114,20 @@ impl AnyJointPositionConstraint {
             JointParams::FixedJoint(p) => AnyJointPositionConstraint::FixedGroundConstraint(
                 FixedPositionGroundConstraint::from_params(rb1, rb2, p, flipped),
             ),
+            JointParams::GenericJoint(p) => AnyJointPositionConstraint::GenericGroundConstraint(
+                GenericPositionGroundConstraint::from_params(rb1, rb2, p, flipped),
+            ),
             JointParams::PrismaticJoint(p) => {
                 AnyJointPositionConstraint::PrismaticGroundConstraint(
                     PrismaticPositionGroundConstraint::from_params(rb1, rb2, p, flipped),
                 )
             }
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(p) => AnyJointPositionConstraint::RevoluteGroundConstraint(
                 RevolutePositionGroundConstraint::from_params(rb1, rb2, p, flipped),
             ),
+            JointParams::DistanceJoint(p) => AnyJointPositionConstraint::DistanceGroundConstraint(
+                DistancePositionGroundConstraint::from_params(rb1, rb2, p, flipped),
+            ),
         }
     }
 
@@ -141,9 +155,10 @@ impl AnyJointPositionConstraint {
                 ))
             }
             JointParams::FixedJoint(_) => None,
+            JointParams::GenericJoint(_) => None,
             JointParams::PrismaticJoint(_) => None,
-            #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => None,
+            JointParams::DistanceJoint(_) => None,
         }
     }
 
@@ -157,12 +172,14 @@ impl AnyJointPositionConstraint {
             AnyJointPositionConstraint::WBallGroundConstraint(c) => c.solve(params, positions),
             AnyJointPositionConstraint::FixedJoint(c) => c.solve(params, positions),
             AnyJointPositionConstraint::FixedGroundConstraint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::GenericJoint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::GenericGroundConstraint(c) => c.solve(params, positions),
             AnyJointPositionConstraint::PrismaticJoint(c) => c.solve(params, positions),
             AnyJointPositionConstraint::PrismaticGroundConstraint(c) => c.solve(params, positions),
-            #[cfg(feature = "dim3")]
             AnyJointPositionConstraint::RevoluteJoint(c) => c.solve(params, positions),
-            #[cfg(feature = "dim3")]
             AnyJointPositionConstraint::RevoluteGroundConstraint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::DistanceJoint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::DistanceGroundConstraint(c) => c.solve(params, positions),
             AnyJointPositionConstraint::Empty => unreachable!(),
         }
     }
@@ -8,13 +8,34 @@ pub(self) use ball_velocity_constraint::{BallVelocityConstraint, BallVelocityGro
 pub(self) use ball_velocity_constraint_wide::{
     WBallVelocityConstraint, WBallVelocityGroundConstraint,
 };
+pub(self) use distance_position_constraint::{
+    DistancePositionConstraint, DistancePositionGroundConstraint,
+};
+pub(self) use distance_velocity_constraint::{
+    DistanceVelocityConstraint, DistanceVelocityGroundConstraint,
+};
+#[cfg(feature = "simd-is-enabled")]
+pub(self) use distance_velocity_constraint_wide::{
+    WDistanceVelocityConstraint, WDistanceVelocityGroundConstraint,
+};
 pub(self) use fixed_position_constraint::{FixedPositionConstraint, FixedPositionGroundConstraint};
 pub(self) use fixed_velocity_constraint::{FixedVelocityConstraint, FixedVelocityGroundConstraint};
 #[cfg(feature = "simd-is-enabled")]
 pub(self) use fixed_velocity_constraint_wide::{
     WFixedVelocityConstraint, WFixedVelocityGroundConstraint,
 };
+pub(self) use generic_position_constraint::{
+    GenericPositionConstraint, GenericPositionGroundConstraint,
+};
+pub(self) use generic_velocity_constraint::{
+    GenericVelocityConstraint, GenericVelocityGroundConstraint,
+};
+#[cfg(feature = "simd-is-enabled")]
+pub(self) use generic_velocity_constraint_wide::{
+    WGenericVelocityConstraint, WGenericVelocityGroundConstraint,
+};
 pub(crate) use joint_constraint::AnyJointVelocityConstraint;
+pub(self) use joint_motor::motor_coefficients;
 pub(crate) use joint_position_constraint::AnyJointPositionConstraint;
 pub(self) use prismatic_position_constraint::{
     PrismaticPositionConstraint, PrismaticPositionGroundConstraint,
@@ -26,15 +47,12 @@ pub(self) use prismatic_velocity_constraint::{
 pub(self) use prismatic_velocity_constraint_wide::{
     WPrismaticVelocityConstraint, WPrismaticVelocityGroundConstraint,
 };
-#[cfg(feature = "dim3")]
 pub(self) use revolute_position_constraint::{
     RevolutePositionConstraint, RevolutePositionGroundConstraint,
 };
-#[cfg(feature = "dim3")]
 pub(self) use revolute_velocity_constraint::{
     RevoluteVelocityConstraint, RevoluteVelocityGroundConstraint,
 };
-#[cfg(feature = "dim3")]
 #[cfg(feature = "simd-is-enabled")]
 pub(self) use revolute_velocity_constraint_wide::{
     WRevoluteVelocityConstraint, WRevoluteVelocityGroundConstraint,
@@ -46,20 +64,26 @@ mod ball_position_constraint_wide;
 mod ball_velocity_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod ball_velocity_constraint_wide;
+mod distance_position_constraint;
+mod distance_velocity_constraint;
+#[cfg(feature = "simd-is-enabled")]
+mod distance_velocity_constraint_wide;
 mod fixed_position_constraint;
 mod fixed_velocity_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod fixed_velocity_constraint_wide;
+mod generic_position_constraint;
+mod generic_velocity_constraint;
+#[cfg(feature = "simd-is-enabled")]
+mod generic_velocity_constraint_wide;
 mod joint_constraint;
+mod joint_motor;
 mod joint_position_constraint;
 mod prismatic_position_constraint;
 mod prismatic_velocity_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod prismatic_velocity_constraint_wide;
-#[cfg(feature = "dim3")]
 mod revolute_position_constraint;
-#[cfg(feature = "dim3")]
 mod revolute_velocity_constraint;
-#[cfg(feature = "dim3")]
 #[cfg(feature = "simd-is-enabled")]
 mod revolute_velocity_constraint_wide;
@@ -1,3 +1,4 @@
+use super::motor_coefficients;
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, JointGraphEdge, JointIndex, JointParams, PrismaticJoint, RigidBody,
@@ -45,6 +46,13 @@ pub(crate) struct PrismaticVelocityConstraint {
     limits_forcedirs: Option<(Vector<f32>, Vector<f32>)>,
     limits_rhs: f32,
 
+    motor_axis1: Vector<f32>,
+    motor_rhs: f32,
+    motor_inv_lhs: f32,
+    motor_gamma: f32,
+    motor_impulse: f32,
+    motor_max_impulse: f32,
+
     #[cfg(feature = "dim2")]
     basis1: Vector2<f32>,
     #[cfg(feature = "dim3")]
@@ -171,6 +179,22 @@ impl PrismaticVelocityConstraint {
             }
         }
 
+        // Setup motor.
+        let (motor_erp_inv_dt, motor_damping, motor_gamma) =
+            motor_coefficients(params.dt(), cparams.motor_stiffness, cparams.motor_damping);
+        let mut motor_rhs = 0.0;
+        let mut motor_inv_lhs = 0.0;
+        let mut motor_impulse = 0.0;
+
+        if cparams.motor_enabled() {
+            let dist = (anchor2 - anchor1).dot(&axis1);
+            motor_rhs = (anchor_linvel2 - anchor_linvel1).dot(&axis1)
+                - motor_damping * cparams.motor_target_vel
+                + motor_erp_inv_dt * (dist - cparams.motor_target_pos);
+            motor_inv_lhs = 1.0 / (im1 + im2 + motor_gamma);
+            motor_impulse = cparams.motor_impulse;
+        }
+
         PrismaticVelocityConstraint {
             joint_id,
             mj_lambda1: rb1.active_set_offset,
@@ -183,6 +207,12 @@ impl PrismaticVelocityConstraint {
             limits_impulse: limits_impulse * params.warmstart_coeff,
             limits_forcedirs,
             limits_rhs,
+            motor_axis1: axis1.into_inner(),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * params.warmstart_coeff,
+            motor_max_impulse: cparams.motor_max_impulse,
             basis1,
             inv_lhs,
             rhs,
@@ -216,6 +246,9 @@ impl PrismaticVelocityConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * self.limits_impulse);
         }
 
+        mj_lambda1.linear -= self.motor_axis1 * (self.im1 * self.motor_impulse);
+        mj_lambda2.linear += self.motor_axis1 * (self.im2 * self.motor_impulse);
+
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -256,6 +289,31 @@ impl PrismaticVelocityConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != 0.0 {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let lin_dvel = self.motor_axis1.dot(
+                &(mj_lambda2.linear + ang_vel2.gcross(self.r2)
+                    - mj_lambda1.linear
+                    - ang_vel1.gcross(self.r1)),
+            ) + self.motor_rhs;
+            let new_impulse = na::clamp(
+                self.motor_impulse
+                    - self.motor_inv_lhs * (lin_dvel + self.motor_gamma * self.motor_impulse),
+                -self.motor_max_impulse,
+                self.motor_max_impulse,
+            );
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            mj_lambda1.linear -= self.motor_axis1 * (self.im1 * dimpulse);
+            mj_lambda2.linear += self.motor_axis1 * (self.im2 * dimpulse);
+        }
+
         /*
          * Joint limits.
          */
@@ -285,6 +343,7 @@ impl PrismaticVelocityConstraint {
         if let JointParams::PrismaticJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
             revolute.limits_impulse = self.limits_impulse;
+            revolute.motor_impulse = self.motor_impulse;
         }
     }
 }
@@ -314,6 +373,13 @@ pub(crate) struct PrismaticVelocityGroundConstraint {
     limits_impulse: f32,
     limits_rhs: f32,
 
+    motor_axis2: Vector<f32>,
+    motor_rhs: f32,
+    motor_inv_lhs: f32,
+    motor_gamma: f32,
+    motor_impulse: f32,
+    motor_max_impulse: f32,
+
     axis2: Vector<f32>,
     #[cfg(feature = "dim2")]
     basis1: Vector2<f32>,
@@ -461,6 +527,29 @@ impl PrismaticVelocityGroundConstraint {
             }
         }
 
+        // Setup motor.
+        // The motor acts along the first axis of the joint, oriented from the joint's first
+        // body toward its second body, so it must be negated if the bodies are flipped.
+        let motor_axis2 = if flipped {
+            -axis2.into_inner()
+        } else {
+            axis1.into_inner()
+        };
+        let (motor_erp_inv_dt, motor_damping, motor_gamma) =
+            motor_coefficients(params.dt(), cparams.motor_stiffness, cparams.motor_damping);
+        let mut motor_rhs = 0.0;
+        let mut motor_inv_lhs = 0.0;
+        let mut motor_impulse = 0.0;
+
+        if cparams.motor_enabled() {
+            let dist = (anchor2 - anchor1).dot(&motor_axis2);
+            motor_rhs = (anchor_linvel2 - anchor_linvel1).dot(&motor_axis2)
+                - motor_damping * cparams.motor_target_vel
+                + motor_erp_inv_dt * (dist - cparams.motor_target_pos);
+            motor_inv_lhs = 1.0 / (im2 + motor_gamma);
+            motor_impulse = cparams.motor_impulse;
+        }
+
         PrismaticVelocityGroundConstraint {
             joint_id,
             mj_lambda2: rb2.active_set_offset,
@@ -475,6 +564,12 @@ impl PrismaticVelocityGroundConstraint {
             axis2: axis2.into_inner(),
             limits_forcedir2,
             limits_rhs,
+            motor_axis2,
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * params.warmstart_coeff,
+            motor_max_impulse: cparams.motor_max_impulse,
         }
     }
 
@@ -496,6 +591,8 @@ impl PrismaticVelocityGroundConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * self.limits_impulse);
         }
 
+        mj_lambda2.linear += self.motor_axis2 * (self.im2 * self.motor_impulse);
+
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
 
@@ -527,6 +624,28 @@ impl PrismaticVelocityGroundConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != 0.0 {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let lin_dvel = self
+                .motor_axis2
+                .dot(&(mj_lambda2.linear + ang_vel2.gcross(self.r2)))
+                + self.motor_rhs;
+            let new_impulse = na::clamp(
+                self.motor_impulse
+                    - self.motor_inv_lhs * (lin_dvel + self.motor_gamma * self.motor_impulse),
+                -self.motor_max_impulse,
+                self.motor_max_impulse,
+            );
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            mj_lambda2.linear += self.motor_axis2 * (self.im2 * dimpulse);
+        }
+
         /*
          * Joint limits.
          */
@@ -553,6 +672,7 @@ impl PrismaticVelocityGroundConstraint {
         if let JointParams::PrismaticJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
             revolute.limits_impulse = self.limits_impulse;
+            revolute.motor_impulse = self.motor_impulse;
         }
     }
 }
@@ -1,5 +1,6 @@
 use simba::simd::{SimdBool as _, SimdPartialOrd, SimdValue};
 
+use super::motor_coefficients;
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, JointGraphEdge, JointIndex, JointParams, PrismaticJoint, RigidBody,
@@ -49,6 +50,13 @@ pub(crate) struct WPrismaticVelocityConstraint {
     limits_forcedirs: Option<(Vector<SimdFloat>, Vector<SimdFloat>)>,
     limits_rhs: SimdFloat,
 
+    motor_axis1: Vector<SimdFloat>,
+    motor_rhs: SimdFloat,
+    motor_inv_lhs: SimdFloat,
+    motor_gamma: SimdFloat,
+    motor_impulse: SimdFloat,
+    motor_max_impulse: SimdFloat,
+
     #[cfg(feature = "dim2")]
     basis1: Vector2<SimdFloat>,
     #[cfg(feature = "dim3")]
@@ -216,6 +224,40 @@ impl WPrismaticVelocityConstraint {
             }
         }
 
+        // Setup motor.
+        let motor_enabled = SimdBool::from(array![|ii| cparams[ii].motor_enabled(); SIMD_WIDTH]);
+        let motor_max_impulse =
+            SimdFloat::from(array![|ii| cparams[ii].motor_max_impulse; SIMD_WIDTH]);
+        let mut motor_rhs = na::zero();
+        let mut motor_inv_lhs = na::zero();
+        let mut motor_gamma = na::zero();
+        let mut motor_impulse = na::zero();
+
+        if motor_enabled.any() {
+            let coeffs = array![|ii| motor_coefficients(
+                params.dt(),
+                cparams[ii].motor_stiffness,
+                cparams[ii].motor_damping
+            ); SIMD_WIDTH];
+            let motor_erp_inv_dt = SimdFloat::from(array![|ii| coeffs[ii].0; SIMD_WIDTH]);
+            let motor_damping = SimdFloat::from(array![|ii| coeffs[ii].1; SIMD_WIDTH]);
+            let target_vel =
+                SimdFloat::from(array![|ii| cparams[ii].motor_target_vel; SIMD_WIDTH]);
+            let target_pos =
+                SimdFloat::from(array![|ii| cparams[ii].motor_target_pos; SIMD_WIDTH]);
+            let _0: SimdFloat = na::zero();
+            let _1: SimdFloat = na::one();
+
+            let dist = (anchor2 - anchor1).dot(&axis1);
+            motor_gamma = SimdFloat::from(array![|ii| coeffs[ii].2; SIMD_WIDTH]);
+            motor_rhs = (anchor_linvel2 - anchor_linvel1).dot(&axis1)
+                - motor_damping * target_vel
+                + motor_erp_inv_dt * (dist - target_pos);
+            motor_inv_lhs = (_1 / (im1 + im2 + motor_gamma)).select(motor_enabled, _0);
+            motor_impulse = SimdFloat::from(array![|ii| cparams[ii].motor_impulse; SIMD_WIDTH])
+                .select(motor_enabled, _0);
+        }
+
         WPrismaticVelocityConstraint {
             joint_id,
             mj_lambda1,
@@ -228,6 +270,12 @@ impl WPrismaticVelocityConstraint {
             limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
             limits_forcedirs,
             limits_rhs,
+            motor_axis1: axis1,
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * SimdFloat::splat(params.warmstart_coeff),
+            motor_max_impulse,
             basis1,
             inv_lhs,
             rhs,
@@ -275,6 +323,9 @@ impl WPrismaticVelocityConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * self.limits_impulse);
         }
 
+        mj_lambda1.linear -= self.motor_axis1 * (self.im1 * self.motor_impulse);
+        mj_lambda2.linear += self.motor_axis1 * (self.im2 * self.motor_impulse);
+
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
             mj_lambdas[self.mj_lambda1[ii] as usize].angular = mj_lambda1.angular.extract(ii);
@@ -335,6 +386,28 @@ impl WPrismaticVelocityConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != na::zero() {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let lin_dvel = self.motor_axis1.dot(
+                &(mj_lambda2.linear + ang_vel2.gcross(self.r2)
+                    - mj_lambda1.linear
+                    - ang_vel1.gcross(self.r1)),
+            ) + self.motor_rhs;
+            let new_impulse = (self.motor_impulse
+                - self.motor_inv_lhs * (lin_dvel + self.motor_gamma * self.motor_impulse))
+                .simd_clamp(-self.motor_max_impulse, self.motor_max_impulse);
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            mj_lambda1.linear -= self.motor_axis1 * (self.im1 * dimpulse);
+            mj_lambda2.linear += self.motor_axis1 * (self.im2 * dimpulse);
+        }
+
         /*
          * Joint limits.
          */
@@ -372,6 +445,7 @@ impl WPrismaticVelocityConstraint {
             if let JointParams::PrismaticJoint(rev) = &mut joint.params {
                 rev.impulse = self.impulse.extract(ii);
                 rev.limits_impulse = self.limits_impulse.extract(ii);
+                rev.motor_impulse = self.motor_impulse.extract(ii);
             }
         }
     }
@@ -402,6 +476,13 @@ pub(crate) struct WPrismaticVelocityGroundConstraint {
     limits_impulse: SimdFloat,
     limits_rhs: SimdFloat,
 
+    motor_axis2: Vector<SimdFloat>,
+    motor_rhs: SimdFloat,
+    motor_inv_lhs: SimdFloat,
+    motor_gamma: SimdFloat,
+    motor_impulse: SimdFloat,
+    motor_max_impulse: SimdFloat,
+
     axis2: Vector<SimdFloat>,
     #[cfg(feature = "dim2")]
     basis1: Vector2<SimdFloat>,
@@ -568,6 +649,46 @@ impl WPrismaticVelocityGroundConstraint {
             }
         }
 
+        // The motor acts along the first axis of the joint, oriented from the joint's first
+        // body toward its second body, so it must be negated if the bodies are flipped.
+        let motor_axis2 = Vector::from(
+            array![|ii| if flipped[ii] { -(rbs2[ii].position * *cparams[ii].local_axis1) } else { rbs1[ii].position * *cparams[ii].local_axis1 }; SIMD_WIDTH],
+        );
+
+        // Setup motor.
+        let motor_enabled = SimdBool::from(array![|ii| cparams[ii].motor_enabled(); SIMD_WIDTH]);
+        let motor_max_impulse =
+            SimdFloat::from(array![|ii| cparams[ii].motor_max_impulse; SIMD_WIDTH]);
+        let mut motor_rhs = na::zero();
+        let mut motor_inv_lhs = na::zero();
+        let mut motor_gamma = na::zero();
+        let mut motor_impulse = na::zero();
+
+        if motor_enabled.any() {
+            let coeffs = array![|ii| motor_coefficients(
+                params.dt(),
+                cparams[ii].motor_stiffness,
+                cparams[ii].motor_damping
+            ); SIMD_WIDTH];
+            let motor_erp_inv_dt = SimdFloat::from(array![|ii| coeffs[ii].0; SIMD_WIDTH]);
+            let motor_damping = SimdFloat::from(array![|ii| coeffs[ii].1; SIMD_WIDTH]);
+            let target_vel =
+                SimdFloat::from(array![|ii| cparams[ii].motor_target_vel; SIMD_WIDTH]);
+            let target_pos =
+                SimdFloat::from(array![|ii| cparams[ii].motor_target_pos; SIMD_WIDTH]);
+            let _0: SimdFloat = na::zero();
+            let _1: SimdFloat = na::one();
+
+            let dist = (anchor2 - anchor1).dot(&motor_axis2);
+            motor_gamma = SimdFloat::from(array![|ii| coeffs[ii].2; SIMD_WIDTH]);
+            motor_rhs = (anchor_linvel2 - anchor_linvel1).dot(&motor_axis2)
+                - motor_damping * target_vel
+                + motor_erp_inv_dt * (dist - target_pos);
+            motor_inv_lhs = (_1 / (im2 + motor_gamma)).select(motor_enabled, _0);
+            motor_impulse = SimdFloat::from(array![|ii| cparams[ii].motor_impulse; SIMD_WIDTH])
+                .select(motor_enabled, _0);
+        }
+
         WPrismaticVelocityGroundConstraint {
             joint_id,
             mj_lambda2,
@@ -582,6 +703,12 @@ impl WPrismaticVelocityGroundConstraint {
             axis2,
             limits_forcedir2,
             limits_rhs,
+            motor_axis2,
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * SimdFloat::splat(params.warmstart_coeff),
+            motor_max_impulse,
         }
     }
 
@@ -610,6 +737,8 @@ impl WPrismaticVelocityGroundConstraint {
             mj_lambda2.linear += limits_forcedir2 * (self.im2 * self.limits_impulse);
         }
 
+        mj_lambda2.linear += self.motor_axis2 * (self.im2 * self.motor_impulse);
+
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
             mj_lambdas[self.mj_lambda2[ii] as usize].angular = mj_lambda2.angular.extract(ii);
@@ -651,6 +780,25 @@ impl WPrismaticVelocityGroundConstraint {
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != na::zero() {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let lin_dvel = self
+                .motor_axis2
+                .dot(&(mj_lambda2.linear + ang_vel2.gcross(self.r2)))
+                + self.motor_rhs;
+            let new_impulse = (self.motor_impulse
+                - self.motor_inv_lhs * (lin_dvel + self.motor_gamma * self.motor_impulse))
+                .simd_clamp(-self.motor_max_impulse, self.motor_max_impulse);
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            mj_lambda2.linear += self.motor_axis2 * (self.im2 * dimpulse);
+        }
+
         /*
          * Joint limits.
          */
@@ -681,6 +829,7 @@ impl WPrismaticVelocityGroundConstraint {
             if let JointParams::PrismaticJoint(rev) = &mut joint.params {
                 rev.impulse = self.impulse.extract(ii);
                 rev.limits_impulse = self.limits_impulse.extract(ii);
+                rev.motor_impulse = self.motor_impulse.extract(ii);
             }
         }
     }
@@ -1,7 +1,8 @@
 use crate::dynamics::{IntegrationParameters, RevoluteJoint, RigidBody};
-use crate::math::{AngularInertia, Isometry, Point, Rotation, Vector};
-use crate::utils::WAngularInertia;
-use na::Unit;
+use crate::math::{AngularInertia, Isometry, Point, Rotation};
+use crate::utils::{self, WAngularInertia};
+#[cfg(feature = "dim3")]
+use {crate::math::Vector, na::Unit};
 
 #[derive(Debug)]
 pub(crate) struct RevolutePositionConstraint {
@@ -15,13 +16,24 @@ pub(crate) struct RevolutePositionConstraint {
     ii2: AngularInertia<f32>,
 
     lin_inv_lhs: f32,
+    #[cfg(feature = "dim3")]
     ang_inv_lhs: AngularInertia<f32>,
 
     local_anchor1: Point<f32>,
     local_anchor2: Point<f32>,
 
+    #[cfg(feature = "dim3")]
     local_axis1: Unit<Vector<f32>>,
+    #[cfg(feature = "dim3")]
     local_axis2: Unit<Vector<f32>>,
+
+    #[cfg(feature = "dim3")]
+    local_basis1: Vector<f32>,
+    #[cfg(feature = "dim3")]
+    local_basis2: Vector<f32>,
+
+    limits_enabled: bool,
+    limits: [f32; 2],
 }
 
 impl RevolutePositionConstraint {
@@ -31,7 +43,6 @@ impl RevolutePositionConstraint {
         let im1 = rb1.mass_properties.inv_mass;
         let im2 = rb2.mass_properties.inv_mass;
         let lin_inv_lhs = 1.0 / (im1 + im2);
-        let ang_inv_lhs = (ii1 + ii2).inverse();
 
         Self {
             im1,
@@ -39,11 +50,20 @@ impl RevolutePositionConstraint {
             ii1,
             ii2,
             lin_inv_lhs,
-            ang_inv_lhs,
+            #[cfg(feature = "dim3")]
+            ang_inv_lhs: (ii1 + ii2).inverse(),
             local_anchor1: cparams.local_anchor1,
             local_anchor2: cparams.local_anchor2,
+            #[cfg(feature = "dim3")]
             local_axis1: cparams.local_axis1,
+            #[cfg(feature = "dim3")]
             local_axis2: cparams.local_axis2,
+            #[cfg(feature = "dim3")]
+            local_basis1: cparams.basis1[0],
+            #[cfg(feature = "dim3")]
+            local_basis2: cparams.basis2[0],
+            limits_enabled: cparams.limits_enabled,
+            limits: cparams.limits,
             position1: rb1.active_set_offset,
             position2: rb2.active_set_offset,
         }
@@ -53,17 +73,69 @@ impl RevolutePositionConstraint {
         let mut position1 = positions[self.position1 as usize];
         let mut position2 = positions[self.position2 as usize];
 
-        let axis1 = position1 * self.local_axis1;
-        let axis2 = position2 * self.local_axis2;
-        let delta_rot =
-            Rotation::rotation_between_axis(&axis1, &axis2).unwrap_or(Rotation::identity());
-        let ang_error = delta_rot.scaled_axis() * params.joint_erp;
-        let ang_impulse = self.ang_inv_lhs.transform_vector(ang_error);
+        // In 2D, the rotation axis is always aligned so only the limits need to be corrected.
+        #[cfg(feature = "dim3")]
+        {
+            let axis1 = position1 * self.local_axis1;
+            let axis2 = position2 * self.local_axis2;
+            let delta_rot =
+                Rotation::rotation_between_axis(&axis1, &axis2).unwrap_or(Rotation::identity());
+            let ang_error = delta_rot.scaled_axis() * params.joint_erp;
+            let ang_impulse = self.ang_inv_lhs.transform_vector(ang_error);
+
+            position1.rotation =
+                Rotation::new(self.ii1.transform_vector(ang_impulse)) * position1.rotation;
+            position2.rotation =
+                Rotation::new(self.ii2.transform_vector(-ang_impulse)) * position2.rotation;
+        }
+
+        #[cfg(feature = "dim2")]
+        if self.limits_enabled {
+            let angle = (position1.rotation.inverse() * position2.rotation).angle();
+
+            let ang_error = if angle < self.limits[0] {
+                angle - self.limits[0]
+            } else if angle > self.limits[1] {
+                angle - self.limits[1]
+            } else {
+                0.0
+            };
+
+            if ang_error != 0.0 {
+                let ang_impulse = ang_error * params.joint_erp * utils::inv(self.ii1 + self.ii2);
+
+                position1.rotation =
+                    Rotation::new(self.ii1.transform_vector(ang_impulse)) * position1.rotation;
+                position2.rotation =
+                    Rotation::new(self.ii2.transform_vector(-ang_impulse)) * position2.rotation;
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        if self.limits_enabled {
+            let axis1 = position1 * self.local_axis1;
+            let basis1 = position1 * self.local_basis1;
+            let basis2 = position2 * self.local_basis2;
+            let angle = basis2.dot(&axis1.cross(&basis1)).atan2(basis2.dot(&basis1));
+
+            let ang_error = if angle < self.limits[0] {
+                angle - self.limits[0]
+            } else if angle > self.limits[1] {
+                angle - self.limits[1]
+            } else {
+                0.0
+            };
 
-        position1.rotation =
-            Rotation::new(self.ii1.transform_vector(ang_impulse)) * position1.rotation;
-        position2.rotation =
-            Rotation::new(self.ii2.transform_vector(-ang_impulse)) * position2.rotation;
+            if ang_error != 0.0 {
+                let ang_lhs = axis1.dot(&(self.ii1 + self.ii2).transform_vector(*axis1));
+                let ang_impulse = *axis1 * (ang_error * params.joint_erp * utils::inv(ang_lhs));
+
+                position1.rotation =
+                    Rotation::new(self.ii1.transform_vector(ang_impulse)) * position1.rotation;
+                position2.rotation =
+                    Rotation::new(self.ii2.transform_vector(-ang_impulse)) * position2.rotation;
+            }
+        }
 
         let anchor1 = position1 * self.local_anchor1;
         let anchor2 = position2 * self.local_anchor2;
@@ -85,8 +157,18 @@ pub(crate) struct RevolutePositionGroundConstraint {
     position2: usize,
     anchor1: Point<f32>,
     local_anchor2: Point<f32>,
+    #[cfg(feature = "dim2")]
+    rotation1: Rotation<f32>,
+    #[cfg(feature = "dim3")]
     axis1: Unit<Vector<f32>>,
+    #[cfg(feature = "dim3")]
     local_axis2: Unit<Vector<f32>>,
+    #[cfg(feature = "dim3")]
+    basis1: Vector<f32>,
+    #[cfg(feature = "dim3")]
+    local_basis2: Vector<f32>,
+    limits_enabled: bool,
+    limits: [f32; 2],
 }
 
 impl RevolutePositionGroundConstraint {
@@ -98,26 +180,50 @@ impl RevolutePositionGroundConstraint {
     ) -> Self {
         let anchor1;
         let local_anchor2;
-        let axis1;
-        let local_axis2;
+        #[cfg(feature = "dim3")]
+        let (axis1, local_axis2, basis1, local_basis2);
+        let limits;
 
         if flipped {
             anchor1 = rb1.predicted_position * cparams.local_anchor2;
             local_anchor2 = cparams.local_anchor1;
-            axis1 = rb1.predicted_position * cparams.local_axis2;
-            local_axis2 = cparams.local_axis1;
+            #[cfg(feature = "dim3")]
+            {
+                axis1 = rb1.predicted_position * cparams.local_axis2;
+                local_axis2 = cparams.local_axis1;
+                basis1 = rb1.predicted_position * cparams.basis2[0];
+                local_basis2 = cparams.basis1[0];
+            }
+            // The relative angle is measured the other way around when the bodies are flipped.
+            limits = [-cparams.limits[1], -cparams.limits[0]];
         } else {
             anchor1 = rb1.predicted_position * cparams.local_anchor1;
             local_anchor2 = cparams.local_anchor2;
-            axis1 = rb1.predicted_position * cparams.local_axis1;
-            local_axis2 = cparams.local_axis2;
+            #[cfg(feature = "dim3")]
+            {
+                axis1 = rb1.predicted_position * cparams.local_axis1;
+                local_axis2 = cparams.local_axis2;
+                basis1 = rb1.predicted_position * cparams.basis1[0];
+                local_basis2 = cparams.basis2[0];
+            }
+            limits = cparams.limits;
         };
 
         Self {
             anchor1,
             local_anchor2,
+            #[cfg(feature = "dim2")]
+            rotation1: rb1.predicted_position.rotation,
+            #[cfg(feature = "dim3")]
             axis1,
+            #[cfg(feature = "dim3")]
             local_axis2,
+            #[cfg(feature = "dim3")]
+            basis1,
+            #[cfg(feature = "dim3")]
+            local_basis2,
+            limits_enabled: cparams.limits_enabled,
+            limits,
             position2: rb2.active_set_offset,
         }
     }
@@ -125,12 +231,54 @@ impl RevolutePositionGroundConstraint {
     pub fn solve(&self, params: &IntegrationParameters, positions: &mut [Isometry<f32>]) {
         let mut position2 = positions[self.position2 as usize];
 
-        let axis2 = position2 * self.local_axis2;
+        #[cfg(feature = "dim3")]
+        {
+            let axis2 = position2 * self.local_axis2;
+
+            let delta_rot =
+                Rotation::scaled_rotation_between_axis(&axis2, &self.axis1, params.joint_erp)
+                    .unwrap_or(Rotation::identity());
+            position2.rotation = delta_rot * position2.rotation;
+        }
+
+        #[cfg(feature = "dim2")]
+        if self.limits_enabled {
+            let angle = (self.rotation1.inverse() * position2.rotation).angle();
+
+            let ang_error = if angle < self.limits[0] {
+                angle - self.limits[0]
+            } else if angle > self.limits[1] {
+                angle - self.limits[1]
+            } else {
+                0.0
+            };
 
-        let delta_rot =
-            Rotation::scaled_rotation_between_axis(&axis2, &self.axis1, params.joint_erp)
-                .unwrap_or(Rotation::identity());
-        position2.rotation = delta_rot * position2.rotation;
+            if ang_error != 0.0 {
+                let delta_rot = Rotation::new(-ang_error * params.joint_erp);
+                position2.rotation = delta_rot * position2.rotation;
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        if self.limits_enabled {
+            let basis2 = position2 * self.local_basis2;
+            let angle = basis2
+                .dot(&self.axis1.cross(&self.basis1))
+                .atan2(basis2.dot(&self.basis1));
+
+            let ang_error = if angle < self.limits[0] {
+                angle - self.limits[0]
+            } else if angle > self.limits[1] {
+                angle - self.limits[1]
+            } else {
+                0.0
+            };
+
+            if ang_error != 0.0 {
+                let delta_rot = Rotation::new(*self.axis1 * (-ang_error * params.joint_erp));
+                position2.rotation = delta_rot * position2.rotation;
+            }
+        }
 
         let anchor2 = position2 * self.local_anchor2;
         let delta_tra = anchor2 - self.anchor1;
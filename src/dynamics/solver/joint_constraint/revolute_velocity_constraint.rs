@@ -1,10 +1,17 @@
+use super::motor_coefficients;
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RevoluteJoint, RigidBody,
 };
-use crate::math::{AngularInertia, Vector};
-use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
+use crate::math::{AngVector, AngularInertia, Vector};
+use crate::utils::{self, WAngularInertia, WCross, WCrossMatrix, WDot};
+#[cfg(feature = "dim3")]
 use na::{Cholesky, Matrix3x2, Matrix5, Vector5, U2, U3};
+#[cfg(feature = "dim2")]
+use {
+    crate::utils::SdpMatrix2,
+    na::{Matrix2, Vector2},
+};
 
 #[derive(Debug)]
 pub(crate) struct RevoluteVelocityConstraint {
@@ -16,12 +23,35 @@ pub(crate) struct RevoluteVelocityConstraint {
     r1: Vector<f32>,
     r2: Vector<f32>,
 
+    #[cfg(feature = "dim3")]
     inv_lhs: Matrix5<f32>,
+    #[cfg(feature = "dim3")]
     rhs: Vector5<f32>,
+    #[cfg(feature = "dim3")]
     impulse: Vector5<f32>,
-
+    #[cfg(feature = "dim3")]
     basis1: Matrix3x2<f32>,
 
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix2<f32>,
+    #[cfg(feature = "dim2")]
+    rhs: Vector2<f32>,
+    #[cfg(feature = "dim2")]
+    impulse: Vector2<f32>,
+
+    axis1: AngVector<f32>,
+
+    limits_impulse: f32,
+    limits_forcedir2: Option<AngVector<f32>>,
+    limits_rhs: f32,
+    limits_inv_lhs: f32,
+
+    motor_rhs: f32,
+    motor_inv_lhs: f32,
+    motor_gamma: f32,
+    motor_impulse: f32,
+    motor_max_impulse: f32,
+
     im1: f32,
     im2: f32,
 
@@ -40,6 +70,7 @@ impl RevoluteVelocityConstraint {
         // Linear part.
         let anchor1 = rb1.position * cparams.local_anchor1;
         let anchor2 = rb2.position * cparams.local_anchor2;
+        #[cfg(feature = "dim3")]
         let basis1 = Matrix3x2::from_columns(&[
             rb1.position * cparams.basis1[0],
             rb1.position * cparams.basis1[1],
@@ -63,24 +94,91 @@ impl RevoluteVelocityConstraint {
         let r2 = anchor2 - rb2.world_com;
         let r2_mat = r2.gcross_matrix();
 
-        let mut lhs = Matrix5::zeros();
-        let lhs00 =
-            ii2.quadform(&r2_mat).add_diagonal(im2) + ii1.quadform(&r1_mat).add_diagonal(im1);
-        let lhs10 = basis1.tr_mul(&(ii2 * r2_mat + ii1 * r1_mat));
-        let lhs11 = (ii1 + ii2).quadform3x2(&basis1).into_matrix();
+        let inv_lhs;
+
+        #[cfg(feature = "dim3")]
+        {
+            let mut lhs = Matrix5::zeros();
+            let lhs00 =
+                ii2.quadform(&r2_mat).add_diagonal(im2) + ii1.quadform(&r1_mat).add_diagonal(im1);
+            let lhs10 = basis1.tr_mul(&(ii2 * r2_mat + ii1 * r1_mat));
+            let lhs11 = (ii1 + ii2).quadform3x2(&basis1).into_matrix();
 
-        // Note that cholesky won't read the upper-right part
-        // of lhs so we don't have to fill it.
-        lhs.fixed_slice_mut::<U3, U3>(0, 0)
-            .copy_from(&lhs00.into_matrix());
-        lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
-        lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+            // Note that cholesky won't read the upper-right part
+            // of lhs so we don't have to fill it.
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
 
-        let inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        // In 2D, only the relative linear motion is locked so we just
+        // unroll the computation of the 2x2 matrix, like for ball joints.
+        #[cfg(feature = "dim2")]
+        {
+            let m11 = im1 + im2 + r1_mat.x * r1_mat.x * ii1 + r2_mat.x * r2_mat.x * ii2;
+            let m12 = r1_mat.x * r1_mat.y * ii1 + r2_mat.x * r2_mat.y * ii2;
+            let m22 = im1 + im2 + r1_mat.y * r1_mat.y * ii1 + r2_mat.y * r2_mat.y * ii2;
+            inv_lhs = SdpMatrix2::new(m11, m12, m22)
+                .inverse_unchecked()
+                .into_matrix();
+        }
 
         let lin_rhs = rb2.linvel + rb2.angvel.gcross(r2) - rb1.linvel - rb1.angvel.gcross(r1);
-        let ang_rhs = basis1.tr_mul(&(rb2.angvel - rb1.angvel));
-        let rhs = Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y);
+        #[cfg(feature = "dim2")]
+        let rhs = lin_rhs;
+        #[cfg(feature = "dim3")]
+        let rhs = {
+            let ang_rhs = basis1.tr_mul(&(rb2.angvel - rb1.angvel));
+            Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y)
+        };
+
+        // The limits and the motor act along the joint's axis.
+        #[cfg(feature = "dim2")]
+        let axis1: f32 = 1.0;
+        #[cfg(feature = "dim3")]
+        let axis1 = (rb1.position * cparams.local_axis1).into_inner();
+        let axis_lhs = axis1.gdot((ii1 + ii2).transform_vector(axis1));
+        let axis_rhs = (rb2.angvel - rb1.angvel).gdot(axis1);
+
+        // Setup limit constraint.
+        let mut limits_forcedir2 = None;
+        let mut limits_rhs = 0.0;
+        let mut limits_impulse = 0.0;
+
+        if cparams.limits_enabled {
+            let angle = cparams.angle(&rb1.position, &rb2.position);
+
+            // FIXME: we should allow both limits to be active at
+            // the same time, and allow predictive constraint activation.
+            if angle < cparams.limits[0] {
+                limits_forcedir2 = Some(axis1);
+                limits_rhs = axis_rhs;
+                limits_impulse = cparams.limits_impulse;
+            } else if angle > cparams.limits[1] {
+                limits_forcedir2 = Some(-axis1);
+                limits_rhs = -axis_rhs;
+                limits_impulse = cparams.limits_impulse;
+            }
+        }
+
+        // Setup motor.
+        let (motor_erp_inv_dt, motor_damping, motor_gamma) =
+            motor_coefficients(params.dt(), cparams.motor_stiffness, cparams.motor_damping);
+        let mut motor_rhs = 0.0;
+        let mut motor_inv_lhs = 0.0;
+        let mut motor_impulse = 0.0;
+
+        if cparams.motor_enabled() {
+            let angle_error = cparams.motor_angle_error(&rb1.position, &rb2.position);
+            motor_rhs = axis_rhs - motor_damping * cparams.motor_target_vel
+                + motor_erp_inv_dt * angle_error;
+            motor_inv_lhs = 1.0 / (axis_lhs + motor_gamma);
+            motor_impulse = cparams.motor_impulse;
+        }
+        let motor_max_impulse = cparams.motor_max_impulse;
 
         RevoluteVelocityConstraint {
             joint_id,
@@ -88,7 +186,18 @@ impl RevoluteVelocityConstraint {
             mj_lambda2: rb2.active_set_offset,
             im1,
             ii1_sqrt: rb1.world_inv_inertia_sqrt,
+            #[cfg(feature = "dim3")]
             basis1,
+            axis1,
+            limits_impulse: limits_impulse * params.warmstart_coeff,
+            limits_forcedir2,
+            limits_rhs,
+            limits_inv_lhs: utils::inv(axis_lhs),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * params.warmstart_coeff,
+            motor_max_impulse,
             im2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
             impulse: cparams.impulse * params.warmstart_coeff,
@@ -103,18 +212,42 @@ impl RevoluteVelocityConstraint {
         let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
         let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
 
-        let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+        #[cfg(feature = "dim2")]
+        {
+            let lin_impulse = self.impulse;
 
-        mj_lambda1.linear += self.im1 * lin_impulse;
-        mj_lambda1.angular += self
-            .ii1_sqrt
-            .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+            mj_lambda1.linear += self.im1 * lin_impulse;
+            mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
-        mj_lambda2.angular -= self
-            .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+            mj_lambda2.linear -= self.im2 * lin_impulse;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(lin_impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda1.linear += self.im1 * lin_impulse;
+            mj_lambda1.angular += self
+                .ii1_sqrt
+                .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+
+            mj_lambda2.linear -= self.im2 * lin_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            let limits_impulse = limits_forcedir2 * self.limits_impulse;
+            mj_lambda1.angular -= self.ii1_sqrt.transform_vector(limits_impulse);
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(limits_impulse);
+        }
+
+        let motor_impulse = self.axis1 * self.motor_impulse;
+        mj_lambda1.angular -= self.ii1_sqrt.transform_vector(motor_impulse);
+        mj_lambda2.angular += self.ii2_sqrt.transform_vector(motor_impulse);
 
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
@@ -129,23 +262,78 @@ impl RevoluteVelocityConstraint {
         let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2)
             - mj_lambda1.linear
             - ang_vel1.gcross(self.r1);
-        let ang_dvel = self.basis1.tr_mul(&(ang_vel2 - ang_vel1));
-        let rhs =
-            Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
-        let impulse = self.inv_lhs * rhs;
-        self.impulse += impulse;
-        let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
-
-        mj_lambda1.linear += self.im1 * lin_impulse;
-        mj_lambda1.angular += self
-            .ii1_sqrt
-            .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
-
-        mj_lambda2.linear -= self.im2 * lin_impulse;
-        mj_lambda2.angular -= self
-            .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+
+        #[cfg(feature = "dim2")]
+        {
+            let impulse = self.inv_lhs * (lin_dvel + self.rhs);
+            self.impulse += impulse;
+
+            mj_lambda1.linear += self.im1 * impulse;
+            mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(impulse));
+
+            mj_lambda2.linear -= self.im2 * impulse;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let ang_dvel = self.basis1.tr_mul(&(ang_vel2 - ang_vel1));
+            let rhs =
+                Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
+            let impulse = self.inv_lhs * rhs;
+            self.impulse += impulse;
+            let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda1.linear += self.im1 * lin_impulse;
+            mj_lambda1.angular += self
+                .ii1_sqrt
+                .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+
+            mj_lambda2.linear -= self.im2 * lin_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != 0.0 {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = self.axis1.gdot(ang_vel2 - ang_vel1) + self.motor_rhs;
+            let new_impulse = na::clamp(
+                self.motor_impulse
+                    - self.motor_inv_lhs * (ang_dvel + self.motor_gamma * self.motor_impulse),
+                -self.motor_max_impulse,
+                self.motor_max_impulse,
+            );
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            let motor_impulse = self.axis1 * dimpulse;
+            mj_lambda1.angular -= self.ii1_sqrt.transform_vector(motor_impulse);
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(motor_impulse);
+        }
+
+        /*
+         * Joint limits.
+         */
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = limits_forcedir2.gdot(ang_vel2 - ang_vel1) + self.limits_rhs;
+            let new_impulse = (self.limits_impulse - ang_dvel * self.limits_inv_lhs).max(0.0);
+            let dimpulse = new_impulse - self.limits_impulse;
+            self.limits_impulse = new_impulse;
+
+            let limits_impulse = limits_forcedir2 * dimpulse;
+            mj_lambda1.angular -= self.ii1_sqrt.transform_vector(limits_impulse);
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(limits_impulse);
+        }
 
         mj_lambdas[self.mj_lambda1 as usize] = mj_lambda1;
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
@@ -155,6 +343,8 @@ impl RevoluteVelocityConstraint {
         let joint = &mut joints_all[self.joint_id].weight;
         if let JointParams::RevoluteJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
+            revolute.limits_impulse = self.limits_impulse;
+            revolute.motor_impulse = self.motor_impulse;
         }
     }
 }
@@ -167,12 +357,35 @@ pub(crate) struct RevoluteVelocityGroundConstraint {
 
     r2: Vector<f32>,
 
+    #[cfg(feature = "dim3")]
     inv_lhs: Matrix5<f32>,
+    #[cfg(feature = "dim3")]
     rhs: Vector5<f32>,
+    #[cfg(feature = "dim3")]
     impulse: Vector5<f32>,
-
+    #[cfg(feature = "dim3")]
     basis1: Matrix3x2<f32>,
 
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix2<f32>,
+    #[cfg(feature = "dim2")]
+    rhs: Vector2<f32>,
+    #[cfg(feature = "dim2")]
+    impulse: Vector2<f32>,
+
+    axis2: AngVector<f32>,
+
+    limits_impulse: f32,
+    limits_forcedir2: Option<AngVector<f32>>,
+    limits_rhs: f32,
+    limits_inv_lhs: f32,
+
+    motor_rhs: f32,
+    motor_inv_lhs: f32,
+    motor_gamma: f32,
+    motor_impulse: f32,
+    motor_max_impulse: f32,
+
     im2: f32,
 
     ii2_sqrt: AngularInertia<f32>,
@@ -189,22 +402,29 @@ impl RevoluteVelocityGroundConstraint {
     ) -> Self {
         let anchor2;
         let anchor1;
+        #[cfg(feature = "dim3")]
         let basis1;
 
         if flipped {
             anchor1 = rb1.position * cparams.local_anchor2;
             anchor2 = rb2.position * cparams.local_anchor1;
-            basis1 = Matrix3x2::from_columns(&[
-                rb1.position * cparams.basis2[0],
-                rb1.position * cparams.basis2[1],
-            ]);
+            #[cfg(feature = "dim3")]
+            {
+                basis1 = Matrix3x2::from_columns(&[
+                    rb1.position * cparams.basis2[0],
+                    rb1.position * cparams.basis2[1],
+                ]);
+            }
         } else {
             anchor1 = rb1.position * cparams.local_anchor1;
             anchor2 = rb2.position * cparams.local_anchor2;
-            basis1 = Matrix3x2::from_columns(&[
-                rb1.position * cparams.basis1[0],
-                rb1.position * cparams.basis1[1],
-            ]);
+            #[cfg(feature = "dim3")]
+            {
+                basis1 = Matrix3x2::from_columns(&[
+                    rb1.position * cparams.basis1[0],
+                    rb1.position * cparams.basis1[1],
+                ]);
+            }
         };
 
         //        let r21 = Rotation::rotation_between_axis(&axis1, &axis2)
@@ -218,23 +438,98 @@ impl RevoluteVelocityGroundConstraint {
         let r2 = anchor2 - rb2.world_com;
         let r2_mat = r2.gcross_matrix();
 
-        let mut lhs = Matrix5::zeros();
-        let lhs00 = ii2.quadform(&r2_mat).add_diagonal(im2);
-        let lhs10 = basis1.tr_mul(&(ii2 * r2_mat));
-        let lhs11 = ii2.quadform3x2(&basis1).into_matrix();
+        let inv_lhs;
 
-        // Note that cholesky won't read the upper-right part
-        // of lhs so we don't have to fill it.
-        lhs.fixed_slice_mut::<U3, U3>(0, 0)
-            .copy_from(&lhs00.into_matrix());
-        lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
-        lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+        #[cfg(feature = "dim3")]
+        {
+            let mut lhs = Matrix5::zeros();
+            let lhs00 = ii2.quadform(&r2_mat).add_diagonal(im2);
+            let lhs10 = basis1.tr_mul(&(ii2 * r2_mat));
+            let lhs11 = ii2.quadform3x2(&basis1).into_matrix();
 
-        let inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+            // Note that cholesky won't read the upper-right part
+            // of lhs so we don't have to fill it.
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let m11 = im2 + r2_mat.x * r2_mat.x * ii2;
+            let m12 = r2_mat.x * r2_mat.y * ii2;
+            let m22 = im2 + r2_mat.y * r2_mat.y * ii2;
+            inv_lhs = SdpMatrix2::new(m11, m12, m22)
+                .inverse_unchecked()
+                .into_matrix();
+        }
 
         let lin_rhs = rb2.linvel + rb2.angvel.gcross(r2) - rb1.linvel - rb1.angvel.gcross(r1);
-        let ang_rhs = basis1.tr_mul(&(rb2.angvel - rb1.angvel));
-        let rhs = Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y);
+        #[cfg(feature = "dim2")]
+        let rhs = lin_rhs;
+        #[cfg(feature = "dim3")]
+        let rhs = {
+            let ang_rhs = basis1.tr_mul(&(rb2.angvel - rb1.angvel));
+            Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y)
+        };
+
+        // The limits and the motor act along the joint's axis, oriented from the joint's first
+        // body toward its second body, so it must be negated if the bodies are flipped.
+        #[cfg(feature = "dim2")]
+        let axis2: f32 = if flipped { -1.0 } else { 1.0 };
+        #[cfg(feature = "dim3")]
+        let axis2 = if flipped {
+            -(rb2.position * cparams.local_axis1).into_inner()
+        } else {
+            (rb1.position * cparams.local_axis1).into_inner()
+        };
+        let (position1, position2) = if flipped {
+            (&rb2.position, &rb1.position)
+        } else {
+            (&rb1.position, &rb2.position)
+        };
+        let axis_lhs = axis2.gdot(ii2.transform_vector(axis2));
+        let axis_rhs = (rb2.angvel - rb1.angvel).gdot(axis2);
+
+        // Setup limit constraint.
+        let mut limits_forcedir2 = None;
+        let mut limits_rhs = 0.0;
+        let mut limits_impulse = 0.0;
+
+        if cparams.limits_enabled {
+            let angle = cparams.angle(position1, position2);
+
+            // FIXME: we should allow both limits to be active at
+            // the same time, and allow predictive constraint activation.
+            if angle < cparams.limits[0] {
+                limits_forcedir2 = Some(axis2);
+                limits_rhs = axis_rhs;
+                limits_impulse = cparams.limits_impulse;
+            } else if angle > cparams.limits[1] {
+                limits_forcedir2 = Some(-axis2);
+                limits_rhs = -axis_rhs;
+                limits_impulse = cparams.limits_impulse;
+            }
+        }
+
+        // Setup motor.
+        let (motor_erp_inv_dt, motor_damping, motor_gamma) =
+            motor_coefficients(params.dt(), cparams.motor_stiffness, cparams.motor_damping);
+        let mut motor_rhs = 0.0;
+        let mut motor_inv_lhs = 0.0;
+        let mut motor_impulse = 0.0;
+
+        if cparams.motor_enabled() {
+            let angle_error = cparams.motor_angle_error(position1, position2);
+            motor_rhs = axis_rhs - motor_damping * cparams.motor_target_vel
+                + motor_erp_inv_dt * angle_error;
+            motor_inv_lhs = 1.0 / (axis_lhs + motor_gamma);
+            motor_impulse = cparams.motor_impulse;
+        }
+        let motor_max_impulse = cparams.motor_max_impulse;
 
         RevoluteVelocityGroundConstraint {
             joint_id,
@@ -242,7 +537,18 @@ impl RevoluteVelocityGroundConstraint {
             im2,
             ii2_sqrt: rb2.world_inv_inertia_sqrt,
             impulse: cparams.impulse * params.warmstart_coeff,
+            #[cfg(feature = "dim3")]
             basis1,
+            axis2,
+            limits_impulse: limits_impulse * params.warmstart_coeff,
+            limits_forcedir2,
+            limits_rhs,
+            limits_inv_lhs: utils::inv(axis_lhs),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * params.warmstart_coeff,
+            motor_max_impulse,
             inv_lhs,
             rhs,
             r2,
@@ -252,13 +558,33 @@ impl RevoluteVelocityGroundConstraint {
     pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<f32>]) {
         let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
 
-        let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+        #[cfg(feature = "dim2")]
+        {
+            let lin_impulse = self.impulse;
+            mj_lambda2.linear -= self.im2 * lin_impulse;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(lin_impulse));
+        }
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
-        mj_lambda2.angular -= self
+        #[cfg(feature = "dim3")]
+        {
+            let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda2.linear -= self.im2 * lin_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            mj_lambda2.angular += self
+                .ii2_sqrt
+                .transform_vector(limits_forcedir2 * self.limits_impulse);
+        }
+
+        mj_lambda2.angular += self
             .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+            .transform_vector(self.axis2 * self.motor_impulse);
 
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -268,18 +594,64 @@ impl RevoluteVelocityGroundConstraint {
 
         let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
         let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2);
-        let ang_dvel = self.basis1.tr_mul(&ang_vel2);
-        let rhs =
-            Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
-        let impulse = self.inv_lhs * rhs;
-        self.impulse += impulse;
-        let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
-
-        mj_lambda2.linear -= self.im2 * lin_impulse;
-        mj_lambda2.angular -= self
-            .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+
+        #[cfg(feature = "dim2")]
+        {
+            let impulse = self.inv_lhs * (lin_dvel + self.rhs);
+            self.impulse += impulse;
+
+            mj_lambda2.linear -= self.im2 * impulse;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let ang_dvel = self.basis1.tr_mul(&ang_vel2);
+            let rhs =
+                Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
+            let impulse = self.inv_lhs * rhs;
+            self.impulse += impulse;
+            let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda2.linear -= self.im2 * lin_impulse;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != 0.0 {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = self.axis2.gdot(ang_vel2) + self.motor_rhs;
+            let new_impulse = na::clamp(
+                self.motor_impulse
+                    - self.motor_inv_lhs * (ang_dvel + self.motor_gamma * self.motor_impulse),
+                -self.motor_max_impulse,
+                self.motor_max_impulse,
+            );
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(self.axis2 * dimpulse);
+        }
+
+        /*
+         * Joint limits.
+         */
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = limits_forcedir2.gdot(ang_vel2) + self.limits_rhs;
+            let new_impulse = (self.limits_impulse - ang_dvel * self.limits_inv_lhs).max(0.0);
+            let dimpulse = new_impulse - self.limits_impulse;
+            self.limits_impulse = new_impulse;
+
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(limits_forcedir2 * dimpulse);
+        }
 
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -289,6 +661,8 @@ impl RevoluteVelocityGroundConstraint {
         let joint = &mut joints_all[self.joint_id].weight;
         if let JointParams::RevoluteJoint(revolute) = &mut joint.params {
             revolute.impulse = self.impulse;
+            revolute.limits_impulse = self.limits_impulse;
+            revolute.motor_impulse = self.motor_impulse;
         }
     }
 }
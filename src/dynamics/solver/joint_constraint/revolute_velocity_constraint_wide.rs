@@ -1,12 +1,21 @@
-use simba::simd::SimdValue;
+use simba::simd::{SimdBool as _, SimdPartialOrd, SimdValue};
 
+use super::motor_coefficients;
 use crate::dynamics::solver::DeltaVel;
 use crate::dynamics::{
     IntegrationParameters, JointGraphEdge, JointIndex, JointParams, RevoluteJoint, RigidBody,
 };
-use crate::math::{AngVector, AngularInertia, Isometry, Point, SimdFloat, Vector, SIMD_WIDTH};
-use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
+use crate::math::{
+    AngVector, AngularInertia, Isometry, Point, SimdBool, SimdFloat, Vector, SIMD_WIDTH,
+};
+use crate::utils::{WAngularInertia, WCross, WCrossMatrix, WDot};
+#[cfg(feature = "dim3")]
 use na::{Cholesky, Matrix3x2, Matrix5, Vector5, U2, U3};
+#[cfg(feature = "dim2")]
+use {
+    crate::utils::SdpMatrix2,
+    na::{Matrix2, Vector2},
+};
 
 #[derive(Debug)]
 pub(crate) struct WRevoluteVelocityConstraint {
@@ -18,12 +27,35 @@ pub(crate) struct WRevoluteVelocityConstraint {
     r1: Vector<SimdFloat>,
     r2: Vector<SimdFloat>,
 
+    #[cfg(feature = "dim3")]
     inv_lhs: Matrix5<SimdFloat>,
+    #[cfg(feature = "dim3")]
     rhs: Vector5<SimdFloat>,
+    #[cfg(feature = "dim3")]
     impulse: Vector5<SimdFloat>,
-
+    #[cfg(feature = "dim3")]
     basis1: Matrix3x2<SimdFloat>,
 
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix2<SimdFloat>,
+    #[cfg(feature = "dim2")]
+    rhs: Vector2<SimdFloat>,
+    #[cfg(feature = "dim2")]
+    impulse: Vector2<SimdFloat>,
+
+    axis1: AngVector<SimdFloat>,
+
+    limits_impulse: SimdFloat,
+    limits_forcedir2: Option<AngVector<SimdFloat>>,
+    limits_rhs: SimdFloat,
+    limits_inv_lhs: SimdFloat,
+
+    motor_rhs: SimdFloat,
+    motor_inv_lhs: SimdFloat,
+    motor_gamma: SimdFloat,
+    motor_impulse: SimdFloat,
+    motor_max_impulse: SimdFloat,
+
     im1: SimdFloat,
     im2: SimdFloat,
 
@@ -61,16 +93,21 @@ impl WRevoluteVelocityConstraint {
 
         let local_anchor1 = Point::from(array![|ii| cparams[ii].local_anchor1; SIMD_WIDTH]);
         let local_anchor2 = Point::from(array![|ii| cparams[ii].local_anchor2; SIMD_WIDTH]);
-        let local_basis1 = [
-            Vector::from(array![|ii| cparams[ii].basis1[0]; SIMD_WIDTH]),
-            Vector::from(array![|ii| cparams[ii].basis1[1]; SIMD_WIDTH]),
-        ];
+        #[cfg(feature = "dim2")]
+        let impulse = Vector2::from(array![|ii| cparams[ii].impulse; SIMD_WIDTH]);
+        #[cfg(feature = "dim3")]
         let impulse = Vector5::from(array![|ii| cparams[ii].impulse; SIMD_WIDTH]);
 
         let anchor1 = position1 * local_anchor1;
         let anchor2 = position2 * local_anchor2;
-        let basis1 =
-            Matrix3x2::from_columns(&[position1 * local_basis1[0], position1 * local_basis1[1]]);
+        #[cfg(feature = "dim3")]
+        let basis1 = {
+            let local_basis1 = [
+                Vector::from(array![|ii| cparams[ii].basis1[0]; SIMD_WIDTH]),
+                Vector::from(array![|ii| cparams[ii].basis1[1]; SIMD_WIDTH]),
+            ];
+            Matrix3x2::from_columns(&[position1 * local_basis1[0], position1 * local_basis1[1]])
+        };
 
         //        let r21 = Rotation::rotation_between_axis(&axis1, &axis2)
         //            .unwrap_or(Rotation::identity())
@@ -80,31 +117,121 @@ impl WRevoluteVelocityConstraint {
         // NOTE: to simplify, we use basis2 = basis1.
         // Though we may want to test if that does not introduce any instability.
         let ii1 = ii1_sqrt.squared();
-        let r1 = anchor1 - world_com1;
+        let r1: Vector<SimdFloat> = anchor1 - world_com1;
         let r1_mat = r1.gcross_matrix();
 
         let ii2 = ii2_sqrt.squared();
-        let r2 = anchor2 - world_com2;
+        let r2: Vector<SimdFloat> = anchor2 - world_com2;
         let r2_mat = r2.gcross_matrix();
 
-        let mut lhs = Matrix5::zeros();
-        let lhs00 =
-            ii2.quadform(&r2_mat).add_diagonal(im2) + ii1.quadform(&r1_mat).add_diagonal(im1);
-        let lhs10 = basis1.tr_mul(&(ii2 * r2_mat + ii1 * r1_mat));
-        let lhs11 = (ii1 + ii2).quadform3x2(&basis1).into_matrix();
+        let inv_lhs;
 
-        // Note that cholesky won't read the upper-right part
-        // of lhs so we don't have to fill it.
-        lhs.fixed_slice_mut::<U3, U3>(0, 0)
-            .copy_from(&lhs00.into_matrix());
-        lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
-        lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+        #[cfg(feature = "dim3")]
+        {
+            let mut lhs = Matrix5::zeros();
+            let lhs00 =
+                ii2.quadform(&r2_mat).add_diagonal(im2) + ii1.quadform(&r1_mat).add_diagonal(im1);
+            let lhs10 = basis1.tr_mul(&(ii2 * r2_mat + ii1 * r1_mat));
+            let lhs11 = (ii1 + ii2).quadform3x2(&basis1).into_matrix();
 
-        let inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+            // Note that cholesky won't read the upper-right part
+            // of lhs so we don't have to fill it.
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        // In 2D, only the relative linear motion is locked so we just
+        // unroll the computation of the 2x2 matrix, like for ball joints.
+        #[cfg(feature = "dim2")]
+        {
+            let m11 = im1 + im2 + r1_mat.x * r1_mat.x * ii1 + r2_mat.x * r2_mat.x * ii2;
+            let m12 = r1_mat.x * r1_mat.y * ii1 + r2_mat.x * r2_mat.y * ii2;
+            let m22 = im1 + im2 + r1_mat.y * r1_mat.y * ii1 + r2_mat.y * r2_mat.y * ii2;
+            inv_lhs = SdpMatrix2::new(m11, m12, m22)
+                .inverse_unchecked()
+                .into_matrix();
+        }
 
         let lin_rhs = linvel2 + angvel2.gcross(r2) - linvel1 - angvel1.gcross(r1);
-        let ang_rhs = basis1.tr_mul(&(angvel2 - angvel1));
-        let rhs = Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y);
+        #[cfg(feature = "dim2")]
+        let rhs = lin_rhs;
+        #[cfg(feature = "dim3")]
+        let rhs = {
+            let ang_rhs = basis1.tr_mul(&(angvel2 - angvel1));
+            Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y)
+        };
+
+        let _0: SimdFloat = na::zero();
+        let _1: SimdFloat = na::one();
+
+        // The limits and the motor act along the joint's axis.
+        #[cfg(feature = "dim2")]
+        let axis1 = _1;
+        #[cfg(feature = "dim3")]
+        let axis1 = position1 * Vector::from(array![|ii| *cparams[ii].local_axis1; SIMD_WIDTH]);
+        let axis_lhs = axis1.gdot((ii1 + ii2).transform_vector(axis1));
+        let axis_rhs = (angvel2 - angvel1).gdot(axis1);
+
+        // Setup limit constraint.
+        let mut limits_forcedir2 = None;
+        let mut limits_rhs = na::zero();
+        let mut limits_impulse = na::zero();
+        let limits_enabled = SimdBool::from(array![|ii| cparams[ii].limits_enabled; SIMD_WIDTH]);
+
+        if limits_enabled.any() {
+            let angle = SimdFloat::from(
+                array![|ii| cparams[ii].angle(&rbs1[ii].position, &rbs2[ii].position); SIMD_WIDTH],
+            );
+
+            // FIXME: we should allow both limits to be active at
+            // the same time + allow predictive constraint activation.
+            let min_limit = SimdFloat::from(array![|ii| cparams[ii].limits[0]; SIMD_WIDTH]);
+            let max_limit = SimdFloat::from(array![|ii| cparams[ii].limits[1]; SIMD_WIDTH]);
+            let lim_impulse = SimdFloat::from(array![|ii| cparams[ii].limits_impulse; SIMD_WIDTH]);
+
+            let min_enabled = angle.simd_lt(min_limit) & limits_enabled;
+            let max_enabled = angle.simd_gt(max_limit) & limits_enabled;
+            let sign = _1.select(min_enabled, (-_1).select(max_enabled, _0));
+
+            if sign != _0 {
+                limits_forcedir2 = Some(axis1 * sign);
+                limits_rhs = axis_rhs * sign;
+                limits_impulse = lim_impulse.select(min_enabled | max_enabled, _0);
+            }
+        }
+
+        // Setup motor.
+        let motor_enabled = SimdBool::from(array![|ii| cparams[ii].motor_enabled(); SIMD_WIDTH]);
+        let motor_max_impulse =
+            SimdFloat::from(array![|ii| cparams[ii].motor_max_impulse; SIMD_WIDTH]);
+        let mut motor_rhs = na::zero();
+        let mut motor_inv_lhs = na::zero();
+        let mut motor_gamma = na::zero();
+        let mut motor_impulse = na::zero();
+
+        if motor_enabled.any() {
+            let coeffs = array![|ii| motor_coefficients(
+                params.dt(),
+                cparams[ii].motor_stiffness,
+                cparams[ii].motor_damping
+            ); SIMD_WIDTH];
+            let motor_erp_inv_dt = SimdFloat::from(array![|ii| coeffs[ii].0; SIMD_WIDTH]);
+            let motor_damping = SimdFloat::from(array![|ii| coeffs[ii].1; SIMD_WIDTH]);
+            let target_vel = SimdFloat::from(array![|ii| cparams[ii].motor_target_vel; SIMD_WIDTH]);
+            let angle_error = SimdFloat::from(
+                array![|ii| cparams[ii].motor_angle_error(&rbs1[ii].position, &rbs2[ii].position); SIMD_WIDTH],
+            );
+
+            motor_gamma = SimdFloat::from(array![|ii| coeffs[ii].2; SIMD_WIDTH]);
+            motor_rhs = axis_rhs - motor_damping * target_vel + motor_erp_inv_dt * angle_error;
+            motor_inv_lhs = (_1 / (axis_lhs + motor_gamma)).select(motor_enabled, _0);
+            motor_impulse = SimdFloat::from(array![|ii| cparams[ii].motor_impulse; SIMD_WIDTH])
+                .select(motor_enabled, _0);
+        }
 
         WRevoluteVelocityConstraint {
             joint_id,
@@ -112,7 +239,18 @@ impl WRevoluteVelocityConstraint {
             mj_lambda2,
             im1,
             ii1_sqrt,
+            #[cfg(feature = "dim3")]
             basis1,
+            axis1,
+            limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
+            limits_forcedir2,
+            limits_rhs,
+            limits_inv_lhs: (_1 / axis_lhs).select(axis_lhs.simd_gt(_0), _0),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * SimdFloat::splat(params.warmstart_coeff),
+            motor_max_impulse,
             im2,
             ii2_sqrt,
             impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
@@ -141,18 +279,42 @@ impl WRevoluteVelocityConstraint {
             ),
         };
 
-        let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+        #[cfg(feature = "dim2")]
+        {
+            let lin_impulse = self.impulse;
 
-        mj_lambda1.linear += lin_impulse * self.im1;
-        mj_lambda1.angular += self
-            .ii1_sqrt
-            .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+            mj_lambda1.linear += lin_impulse * self.im1;
+            mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
-        mj_lambda2.angular -= self
-            .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+            mj_lambda2.linear -= lin_impulse * self.im2;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(lin_impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda1.linear += lin_impulse * self.im1;
+            mj_lambda1.angular += self
+                .ii1_sqrt
+                .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+
+            mj_lambda2.linear -= lin_impulse * self.im2;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            let limits_impulse = limits_forcedir2 * self.limits_impulse;
+            mj_lambda1.angular -= self.ii1_sqrt.transform_vector(limits_impulse);
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(limits_impulse);
+        }
+
+        let motor_impulse = self.axis1 * self.motor_impulse;
+        mj_lambda1.angular -= self.ii1_sqrt.transform_vector(motor_impulse);
+        mj_lambda2.angular += self.ii2_sqrt.transform_vector(motor_impulse);
 
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
@@ -187,23 +349,76 @@ impl WRevoluteVelocityConstraint {
         let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2)
             - mj_lambda1.linear
             - ang_vel1.gcross(self.r1);
-        let ang_dvel = self.basis1.tr_mul(&(ang_vel2 - ang_vel1));
-        let rhs =
-            Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
-        let impulse = self.inv_lhs * rhs;
-        self.impulse += impulse;
-        let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
-
-        mj_lambda1.linear += lin_impulse * self.im1;
-        mj_lambda1.angular += self
-            .ii1_sqrt
-            .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
-
-        mj_lambda2.linear -= lin_impulse * self.im2;
-        mj_lambda2.angular -= self
-            .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+
+        #[cfg(feature = "dim2")]
+        {
+            let impulse = self.inv_lhs * (lin_dvel + self.rhs);
+            self.impulse += impulse;
+
+            mj_lambda1.linear += impulse * self.im1;
+            mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(impulse));
+
+            mj_lambda2.linear -= impulse * self.im2;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let ang_dvel = self.basis1.tr_mul(&(ang_vel2 - ang_vel1));
+            let rhs =
+                Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
+            let impulse = self.inv_lhs * rhs;
+            self.impulse += impulse;
+            let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda1.linear += lin_impulse * self.im1;
+            mj_lambda1.angular += self
+                .ii1_sqrt
+                .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
+
+            mj_lambda2.linear -= lin_impulse * self.im2;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != na::zero() {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = self.axis1.gdot(ang_vel2 - ang_vel1) + self.motor_rhs;
+            let new_impulse = (self.motor_impulse
+                - self.motor_inv_lhs * (ang_dvel + self.motor_gamma * self.motor_impulse))
+                .simd_clamp(-self.motor_max_impulse, self.motor_max_impulse);
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            let motor_impulse = self.axis1 * dimpulse;
+            mj_lambda1.angular -= self.ii1_sqrt.transform_vector(motor_impulse);
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(motor_impulse);
+        }
+
+        /*
+         * Joint limits.
+         */
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            let ang_vel1 = self.ii1_sqrt.transform_vector(mj_lambda1.angular);
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = limits_forcedir2.gdot(ang_vel2 - ang_vel1) + self.limits_rhs;
+            let new_impulse =
+                (self.limits_impulse - ang_dvel * self.limits_inv_lhs).simd_max(na::zero());
+            let dimpulse = new_impulse - self.limits_impulse;
+            self.limits_impulse = new_impulse;
+
+            let limits_impulse = limits_forcedir2 * dimpulse;
+            mj_lambda1.angular -= self.ii1_sqrt.transform_vector(limits_impulse);
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(limits_impulse);
+        }
 
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda1[ii] as usize].linear = mj_lambda1.linear.extract(ii);
@@ -219,7 +434,9 @@ impl WRevoluteVelocityConstraint {
         for ii in 0..SIMD_WIDTH {
             let joint = &mut joints_all[self.joint_id[ii]].weight;
             if let JointParams::RevoluteJoint(rev) = &mut joint.params {
-                rev.impulse = self.impulse.extract(ii)
+                rev.impulse = self.impulse.extract(ii);
+                rev.limits_impulse = self.limits_impulse.extract(ii);
+                rev.motor_impulse = self.motor_impulse.extract(ii);
             }
         }
     }
@@ -233,12 +450,35 @@ pub(crate) struct WRevoluteVelocityGroundConstraint {
 
     r2: Vector<SimdFloat>,
 
+    #[cfg(feature = "dim3")]
     inv_lhs: Matrix5<SimdFloat>,
+    #[cfg(feature = "dim3")]
     rhs: Vector5<SimdFloat>,
+    #[cfg(feature = "dim3")]
     impulse: Vector5<SimdFloat>,
-
+    #[cfg(feature = "dim3")]
     basis1: Matrix3x2<SimdFloat>,
 
+    #[cfg(feature = "dim2")]
+    inv_lhs: Matrix2<SimdFloat>,
+    #[cfg(feature = "dim2")]
+    rhs: Vector2<SimdFloat>,
+    #[cfg(feature = "dim2")]
+    impulse: Vector2<SimdFloat>,
+
+    axis2: AngVector<SimdFloat>,
+
+    limits_impulse: SimdFloat,
+    limits_forcedir2: Option<AngVector<SimdFloat>>,
+    limits_rhs: SimdFloat,
+    limits_inv_lhs: SimdFloat,
+
+    motor_rhs: SimdFloat,
+    motor_inv_lhs: SimdFloat,
+    motor_gamma: SimdFloat,
+    motor_impulse: SimdFloat,
+    motor_max_impulse: SimdFloat,
+
     im2: SimdFloat,
 
     ii2_sqrt: AngularInertia<SimdFloat>,
@@ -267,6 +507,9 @@ impl WRevoluteVelocityGroundConstraint {
             array![|ii| rbs2[ii].world_inv_inertia_sqrt; SIMD_WIDTH],
         );
         let mj_lambda2 = array![|ii| rbs2[ii].active_set_offset; SIMD_WIDTH];
+        #[cfg(feature = "dim2")]
+        let impulse = Vector2::from(array![|ii| cparams[ii].impulse; SIMD_WIDTH]);
+        #[cfg(feature = "dim3")]
         let impulse = Vector5::from(array![|ii| cparams[ii].impulse; SIMD_WIDTH]);
 
         let local_anchor1 = Point::from(
@@ -275,6 +518,7 @@ impl WRevoluteVelocityGroundConstraint {
         let local_anchor2 = Point::from(
             array![|ii| if flipped[ii] { cparams[ii].local_anchor1 } else { cparams[ii].local_anchor2 }; SIMD_WIDTH],
         );
+        #[cfg(feature = "dim3")]
         let basis1 = Matrix3x2::from_columns(&[
             position1
                 * Vector::from(
@@ -295,27 +539,119 @@ impl WRevoluteVelocityGroundConstraint {
         //            .into_inner();
         //        let basis2 = /*r21 * */ basis1;
         let ii2 = ii2_sqrt.squared();
-        let r1 = anchor1 - world_com1;
-        let r2 = anchor2 - world_com2;
+        let r1: Vector<SimdFloat> = anchor1 - world_com1;
+        let r2: Vector<SimdFloat> = anchor2 - world_com2;
         let r2_mat = r2.gcross_matrix();
 
-        let mut lhs = Matrix5::zeros();
-        let lhs00 = ii2.quadform(&r2_mat).add_diagonal(im2);
-        let lhs10 = basis1.tr_mul(&(ii2 * r2_mat));
-        let lhs11 = ii2.quadform3x2(&basis1).into_matrix();
+        let inv_lhs;
+
+        #[cfg(feature = "dim3")]
+        {
+            let mut lhs = Matrix5::zeros();
+            let lhs00 = ii2.quadform(&r2_mat).add_diagonal(im2);
+            let lhs10 = basis1.tr_mul(&(ii2 * r2_mat));
+            let lhs11 = ii2.quadform3x2(&basis1).into_matrix();
 
-        // Note that cholesky won't read the upper-right part
-        // of lhs so we don't have to fill it.
-        lhs.fixed_slice_mut::<U3, U3>(0, 0)
-            .copy_from(&lhs00.into_matrix());
-        lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
-        lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
+            // Note that cholesky won't read the upper-right part
+            // of lhs so we don't have to fill it.
+            lhs.fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&lhs00.into_matrix());
+            lhs.fixed_slice_mut::<U2, U3>(3, 0).copy_from(&lhs10);
+            lhs.fixed_slice_mut::<U2, U2>(3, 3).copy_from(&lhs11);
 
-        let inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+            inv_lhs = Cholesky::new_unchecked(lhs).inverse();
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            let m11 = im2 + r2_mat.x * r2_mat.x * ii2;
+            let m12 = r2_mat.x * r2_mat.y * ii2;
+            let m22 = im2 + r2_mat.y * r2_mat.y * ii2;
+            inv_lhs = SdpMatrix2::new(m11, m12, m22)
+                .inverse_unchecked()
+                .into_matrix();
+        }
 
         let lin_rhs = linvel2 + angvel2.gcross(r2) - linvel1 - angvel1.gcross(r1);
-        let ang_rhs = basis1.tr_mul(&(angvel2 - angvel1));
-        let rhs = Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y);
+        #[cfg(feature = "dim2")]
+        let rhs = lin_rhs;
+        #[cfg(feature = "dim3")]
+        let rhs = {
+            let ang_rhs = basis1.tr_mul(&(angvel2 - angvel1));
+            Vector5::new(lin_rhs.x, lin_rhs.y, lin_rhs.z, ang_rhs.x, ang_rhs.y)
+        };
+
+        let _0: SimdFloat = na::zero();
+        let _1: SimdFloat = na::one();
+
+        // The limits and the motor act along the joint's axis.
+        // It is oriented from the joint's first body toward its second body, so it must be
+        // negated if the bodies are flipped.
+        #[cfg(feature = "dim2")]
+        let axis2 = (-_1).select(SimdBool::from(flipped), _1);
+        #[cfg(feature = "dim3")]
+        let axis2 = Vector::from(
+            array![|ii| if flipped[ii] { -(rbs2[ii].position * *cparams[ii].local_axis1) } else { rbs1[ii].position * *cparams[ii].local_axis1 }; SIMD_WIDTH],
+        );
+        let axis_lhs = axis2.gdot(ii2.transform_vector(axis2));
+        let axis_rhs = (angvel2 - angvel1).gdot(axis2);
+
+        // Setup limit constraint.
+        let mut limits_forcedir2 = None;
+        let mut limits_rhs = na::zero();
+        let mut limits_impulse = na::zero();
+        let limits_enabled = SimdBool::from(array![|ii| cparams[ii].limits_enabled; SIMD_WIDTH]);
+
+        if limits_enabled.any() {
+            let angle = SimdFloat::from(
+                array![|ii| if flipped[ii] { cparams[ii].angle(&rbs2[ii].position, &rbs1[ii].position) } else { cparams[ii].angle(&rbs1[ii].position, &rbs2[ii].position) }; SIMD_WIDTH],
+            );
+
+            // FIXME: we should allow both limits to be active at
+            // the same time + allow predictive constraint activation.
+            let min_limit = SimdFloat::from(array![|ii| cparams[ii].limits[0]; SIMD_WIDTH]);
+            let max_limit = SimdFloat::from(array![|ii| cparams[ii].limits[1]; SIMD_WIDTH]);
+            let lim_impulse = SimdFloat::from(array![|ii| cparams[ii].limits_impulse; SIMD_WIDTH]);
+
+            let min_enabled = angle.simd_lt(min_limit) & limits_enabled;
+            let max_enabled = angle.simd_gt(max_limit) & limits_enabled;
+            let sign = _1.select(min_enabled, (-_1).select(max_enabled, _0));
+
+            if sign != _0 {
+                limits_forcedir2 = Some(axis2 * sign);
+                limits_rhs = axis_rhs * sign;
+                limits_impulse = lim_impulse.select(min_enabled | max_enabled, _0);
+            }
+        }
+
+        // Setup motor.
+        let motor_enabled = SimdBool::from(array![|ii| cparams[ii].motor_enabled(); SIMD_WIDTH]);
+        let motor_max_impulse =
+            SimdFloat::from(array![|ii| cparams[ii].motor_max_impulse; SIMD_WIDTH]);
+        let mut motor_rhs = na::zero();
+        let mut motor_inv_lhs = na::zero();
+        let mut motor_gamma = na::zero();
+        let mut motor_impulse = na::zero();
+
+        if motor_enabled.any() {
+            let coeffs = array![|ii| motor_coefficients(
+                params.dt(),
+                cparams[ii].motor_stiffness,
+                cparams[ii].motor_damping
+            ); SIMD_WIDTH];
+            let motor_erp_inv_dt = SimdFloat::from(array![|ii| coeffs[ii].0; SIMD_WIDTH]);
+            let motor_damping = SimdFloat::from(array![|ii| coeffs[ii].1; SIMD_WIDTH]);
+            let target_vel = SimdFloat::from(array![|ii| cparams[ii].motor_target_vel; SIMD_WIDTH]);
+            let angle_error = SimdFloat::from(
+                array![|ii| if flipped[ii] { cparams[ii].motor_angle_error(&rbs2[ii].position, &rbs1[ii].position) } else { cparams[ii].motor_angle_error(&rbs1[ii].position, &rbs2[ii].position) }; SIMD_WIDTH],
+            );
+
+            motor_gamma = SimdFloat::from(array![|ii| coeffs[ii].2; SIMD_WIDTH]);
+            motor_rhs = axis_rhs - motor_damping * target_vel + motor_erp_inv_dt * angle_error;
+            motor_inv_lhs = (_1 / (axis_lhs + motor_gamma)).select(motor_enabled, _0);
+            motor_impulse = SimdFloat::from(array![|ii| cparams[ii].motor_impulse; SIMD_WIDTH])
+                .select(motor_enabled, _0);
+        }
 
         WRevoluteVelocityGroundConstraint {
             joint_id,
@@ -323,7 +659,18 @@ impl WRevoluteVelocityGroundConstraint {
             im2,
             ii2_sqrt,
             impulse: impulse * SimdFloat::splat(params.warmstart_coeff),
+            #[cfg(feature = "dim3")]
             basis1,
+            axis2,
+            limits_impulse: limits_impulse * SimdFloat::splat(params.warmstart_coeff),
+            limits_forcedir2,
+            limits_rhs,
+            limits_inv_lhs: (_1 / axis_lhs).select(axis_lhs.simd_gt(_0), _0),
+            motor_rhs,
+            motor_inv_lhs,
+            motor_gamma,
+            motor_impulse: motor_impulse * SimdFloat::splat(params.warmstart_coeff),
+            motor_max_impulse,
             inv_lhs,
             rhs,
             r2,
@@ -340,13 +687,33 @@ impl WRevoluteVelocityGroundConstraint {
             ),
         };
 
-        let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
+        #[cfg(feature = "dim2")]
+        {
+            let lin_impulse = self.impulse;
+            mj_lambda2.linear -= lin_impulse * self.im2;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(lin_impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
-        mj_lambda2.angular -= self
+            mj_lambda2.linear -= lin_impulse * self.im2;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            mj_lambda2.angular += self
+                .ii2_sqrt
+                .transform_vector(limits_forcedir2 * self.limits_impulse);
+        }
+
+        mj_lambda2.angular += self
             .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+            .transform_vector(self.axis2 * self.motor_impulse);
 
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
@@ -366,18 +733,62 @@ impl WRevoluteVelocityGroundConstraint {
 
         let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
         let lin_dvel = mj_lambda2.linear + ang_vel2.gcross(self.r2);
-        let ang_dvel = self.basis1.tr_mul(&ang_vel2);
-        let rhs =
-            Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
-        let impulse = self.inv_lhs * rhs;
-        self.impulse += impulse;
-        let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
-        let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
-
-        mj_lambda2.linear -= lin_impulse * self.im2;
-        mj_lambda2.angular -= self
-            .ii2_sqrt
-            .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+
+        #[cfg(feature = "dim2")]
+        {
+            let impulse = self.inv_lhs * (lin_dvel + self.rhs);
+            self.impulse += impulse;
+
+            mj_lambda2.linear -= impulse * self.im2;
+            mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let ang_dvel = self.basis1.tr_mul(&ang_vel2);
+            let rhs =
+                Vector5::new(lin_dvel.x, lin_dvel.y, lin_dvel.z, ang_dvel.x, ang_dvel.y) + self.rhs;
+            let impulse = self.inv_lhs * rhs;
+            self.impulse += impulse;
+            let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
+            let ang_impulse = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
+
+            mj_lambda2.linear -= lin_impulse * self.im2;
+            mj_lambda2.angular -= self
+                .ii2_sqrt
+                .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
+        }
+
+        /*
+         * Joint motor.
+         */
+        if self.motor_inv_lhs != na::zero() {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = self.axis2.gdot(ang_vel2) + self.motor_rhs;
+            let new_impulse = (self.motor_impulse
+                - self.motor_inv_lhs * (ang_dvel + self.motor_gamma * self.motor_impulse))
+                .simd_clamp(-self.motor_max_impulse, self.motor_max_impulse);
+            let dimpulse = new_impulse - self.motor_impulse;
+            self.motor_impulse = new_impulse;
+
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(self.axis2 * dimpulse);
+        }
+
+        /*
+         * Joint limits.
+         */
+        if let Some(limits_forcedir2) = self.limits_forcedir2 {
+            let ang_vel2 = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+
+            let ang_dvel = limits_forcedir2.gdot(ang_vel2) + self.limits_rhs;
+            let new_impulse =
+                (self.limits_impulse - ang_dvel * self.limits_inv_lhs).simd_max(na::zero());
+            let dimpulse = new_impulse - self.limits_impulse;
+            self.limits_impulse = new_impulse;
+
+            mj_lambda2.angular += self.ii2_sqrt.transform_vector(limits_forcedir2 * dimpulse);
+        }
 
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
@@ -390,7 +801,9 @@ impl WRevoluteVelocityGroundConstraint {
         for ii in 0..SIMD_WIDTH {
             let joint = &mut joints_all[self.joint_id[ii]].weight;
             if let JointParams::RevoluteJoint(rev) = &mut joint.params {
-                rev.impulse = self.impulse.extract(ii)
+                rev.impulse = self.impulse.extract(ii);
+                rev.limits_impulse = self.limits_impulse.extract(ii);
+                rev.motor_impulse = self.motor_impulse.extract(ii);
             }
         }
     }
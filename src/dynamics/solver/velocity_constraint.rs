@@ -268,7 +268,8 @@ impl VelocityConstraint {
                                 + rb2.mass_properties.inv_mass
                                 + gcross1.gdot(gcross1)
                                 + gcross2.gdot(gcross2));
-                        let rhs = (vel1 - vel2).dot(&tangents1[j]);
+                        let rhs =
+                            (vel1 - vel2 + manifold_point.tangent_velocity).dot(&tangents1[j]);
                         #[cfg(feature = "dim2")]
                         let impulse = manifold_points[k].tangent_impulse * warmstart_coeff;
                         #[cfg(feature = "dim3")]
@@ -159,6 +159,11 @@ impl VelocityGroundConstraint {
                 let dp1 = p1.coords - rb1.position.translation.vector;
                 let vel1 = rb1.linvel + rb1.angvel.gcross(dp1);
                 let vel2 = rb2.linvel + rb2.angvel.gcross(dp2);
+                let tangent_velocity = if flipped {
+                    -manifold_point.tangent_velocity
+                } else {
+                    manifold_point.tangent_velocity
+                };
 
                 // Normal part.
                 {
@@ -189,7 +194,7 @@ impl VelocityGroundConstraint {
                             .world_inv_inertia_sqrt
                             .transform_vector(dp2.gcross(-tangents1[j]));
                         let r = 1.0 / (rb2.mass_properties.inv_mass + gcross2.gdot(gcross2));
-                        let rhs = -vel2.dot(&tangents1[j]) + vel1.dot(&tangents1[j]);
+                        let rhs = (vel1 - vel2 + tangent_velocity).dot(&tangents1[j]);
                         #[cfg(feature = "dim2")]
                         let impulse = manifold_points[k].tangent_impulse * warmstart_coeff;
                         #[cfg(feature = "dim3")]
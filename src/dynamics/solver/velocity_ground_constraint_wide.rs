@@ -135,6 +135,9 @@ impl WVelocityGroundConstraint {
 
                 let vel1 = linvel1 + angvel1.gcross(dp1);
                 let vel2 = linvel2 + angvel2.gcross(dp2);
+                let tangent_velocity = Vector::from(
+                    array![|ii| if flipped[ii] { -manifold_points[ii][k].tangent_velocity } else { manifold_points[ii][k].tangent_velocity }; SIMD_WIDTH],
+                );
 
                 // Normal part.
                 {
@@ -168,7 +171,7 @@ impl WVelocityGroundConstraint {
 
                     let gcross2 = ii2.transform_vector(dp2.gcross(-tangents1[j]));
                     let r = SimdFloat::splat(1.0) / (im2 + gcross2.gdot(gcross2));
-                    let rhs = -vel2.dot(&tangents1[j]) + vel1.dot(&tangents1[j]);
+                    let rhs = (vel1 - vel2 + tangent_velocity).dot(&tangents1[j]);
 
                     constraint.elements[k].tangent_parts[j] =
                         WVelocityGroundConstraintElementPart {
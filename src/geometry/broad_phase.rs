@@ -162,6 +162,38 @@ impl WAABBHierarchy {
             workspace.next_level_interferences.clear();
         }
     }
+
+    /// Computes the indices of all the leaves with an AABB satisfying the given predicate.
+    ///
+    /// The predicate must return `true` for an AABB whenever it would return `true` for
+    /// at least one AABB contained by it.
+    pub fn compute_interferences_with_predicate(
+        &self,
+        mut predicate: impl FnMut(&AABB<f32>) -> bool,
+        workspace: &mut WAABBHierarchyIntersections,
+    ) {
+        workspace.next_level_interferences.clear();
+        workspace.curr_level_interferences.clear();
+        workspace.curr_level_interferences.push(0);
+
+        for level in self.levels.iter().rev() {
+            for i in &workspace.curr_level_interferences {
+                if let Some(waabb2) = level.get(*i) {
+                    for j in 0..SIMD_WIDTH {
+                        if predicate(&waabb2.extract(j)) {
+                            workspace.next_level_interferences.push(i * SIMD_WIDTH + j)
+                        }
+                    }
+                }
+            }
+
+            std::mem::swap(
+                &mut workspace.curr_level_interferences,
+                &mut workspace.next_level_interferences,
+            );
+            workspace.next_level_interferences.clear();
+        }
+    }
 }
 
 #[cfg(not(feature = "simd-is-enabled"))]
@@ -252,4 +284,55 @@ impl WAABBHierarchy {
         );
         workspace.next_level_interferences.clear();
     }
+
+    /// Computes the indices of all the leaves with an AABB satisfying the given predicate.
+    ///
+    /// The predicate must return `true` for an AABB whenever it would return `true` for
+    /// at least one AABB contained by it.
+    pub fn compute_interferences_with_predicate(
+        &self,
+        mut predicate: impl FnMut(&AABB<f32>) -> bool,
+        workspace: &mut WAABBHierarchyIntersections,
+    ) {
+        workspace.next_level_interferences.clear();
+        workspace.curr_level_interferences.clear();
+        workspace.curr_level_interferences.push(0);
+
+        for level in self.levels[1..].iter().rev() {
+            for i in &workspace.curr_level_interferences {
+                for j in 0..Self::GROUP_SIZE {
+                    if let Some(aabb2) = level.get(*i + j) {
+                        if predicate(aabb2) {
+                            workspace
+                                .next_level_interferences
+                                .push((i + j) * Self::GROUP_SIZE)
+                        }
+                    }
+                }
+            }
+
+            std::mem::swap(
+                &mut workspace.curr_level_interferences,
+                &mut workspace.next_level_interferences,
+            );
+            workspace.next_level_interferences.clear();
+        }
+
+        // Last level.
+        for i in &workspace.curr_level_interferences {
+            for j in 0..Self::GROUP_SIZE {
+                if let Some(aabb2) = self.levels[0].get(*i + j) {
+                    if predicate(aabb2) {
+                        workspace.next_level_interferences.push(i + j)
+                    }
+                }
+            }
+        }
+
+        std::mem::swap(
+            &mut workspace.curr_level_interferences,
+            &mut workspace.next_level_interferences,
+        );
+        workspace.next_level_interferences.clear();
+    }
 }
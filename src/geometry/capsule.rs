@@ -1,8 +1,8 @@
-use crate::geometry::AABB;
+use crate::geometry::{Ray, RayIntersection, Shape, AABB};
 use crate::math::{Isometry, Point, Rotation, Vector};
 use approx::AbsDiffEq;
 use na::Unit;
-use ncollide::query::{PointProjection, PointQuery};
+use ncollide::query::{PointProjection, PointQuery, RayCast};
 use ncollide::shape::{FeatureId, Segment};
 
 #[derive(Copy, Clone, Debug)]
@@ -42,6 +42,17 @@ impl Capsule {
         Self { a, b, radius }
     }
 
+    /// The capsule equivalent to `shape`, if it is a capsule or a segment.
+    ///
+    /// A segment is equivalent to a capsule with a zero radius.
+    pub(crate) fn from_shape(shape: &Shape) -> Option<Self> {
+        match shape {
+            Shape::Capsule(capsule) => Some(*capsule),
+            Shape::Segment(segment) => Some(Self::new(segment.a, segment.b, 0.0)),
+            _ => None,
+        }
+    }
+
     /// The axis-aligned bounding box of this capsule.
     pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
         let a = pos * self.a;
@@ -94,6 +105,20 @@ impl Capsule {
         let rot = self.rotation_wrt_y();
         Isometry::from_parts(self.center().coords.into(), rot)
     }
+
+    /// Computes the first intersection between this capsule and a ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        // NOTE: the ncollide capsule is aligned with the `y` axis.
+        let pos = position * self.transform_wrt_y();
+        let caps = ncollide::shape::Capsule::new(self.half_height(), self.radius);
+        caps.toi_and_normal_with_ray(&pos, ray, max_toi, solid)
+    }
 }
 
 // impl SupportMap<f32> for Capsule {
@@ -1,11 +1,19 @@
 use crate::dynamics::{MassProperties, RigidBodyHandle, RigidBodySet};
+#[cfg(feature = "dim2")]
+use crate::geometry::Polyline;
 use crate::geometry::{
-    Ball, Capsule, ColliderGraphIndex, Contact, Cuboid, HeightField, InteractionGraph, Polygon,
-    Proximity, Triangle, Trimesh,
+    Ball, Capsule, ColliderGraphIndex, Compound, Contact, Cuboid, FeatureId, HeightField,
+    InteractionGraph, InteractionGroups, PointProjection, Polygon, Proximity, Ray, RayIntersection,
+    RoundShape, Segment, Triangle, Trimesh, Voxels,
 };
+#[cfg(feature = "dim3")]
+use crate::geometry::{Cone, ConvexPolyhedron, Cylinder};
 use crate::math::{Isometry, Point, Vector};
+#[cfg(feature = "dim2")]
+use na::Point2;
 use na::Point3;
 use ncollide::bounding_volume::{HasBoundingVolume, AABB};
+use ncollide::query::RayCast;
 use num::Zero;
 
 #[derive(Clone)]
@@ -20,12 +28,32 @@ pub enum Shape {
     Cuboid(Cuboid),
     /// A capsule shape.
     Capsule(Capsule),
+    /// A segment shape.
+    Segment(Segment),
     /// A triangle shape.
     Triangle(Triangle),
     /// A triangle mesh shape.
     Trimesh(Trimesh),
     /// A heightfield shape.
     HeightField(HeightField),
+    #[cfg(feature = "dim2")]
+    /// A polyline shape.
+    Polyline(Polyline),
+    /// A voxel grid shape.
+    Voxels(Voxels),
+    /// A compound shape made of several shapes.
+    Compound(Compound),
+    /// A convex shape with rounded borders.
+    RoundShape(RoundShape),
+    #[cfg(feature = "dim3")]
+    /// A convex polyhedron shape.
+    ConvexPolyhedron(ConvexPolyhedron),
+    #[cfg(feature = "dim3")]
+    /// A cylindrical shape.
+    Cylinder(Cylinder),
+    #[cfg(feature = "dim3")]
+    /// A conical shape.
+    Cone(Cone),
 }
 
 impl Shape {
@@ -61,6 +89,14 @@ impl Shape {
         }
     }
 
+    /// Gets a reference to the underlying segment shape, if `self` is one.
+    pub fn as_segment(&self) -> Option<&Segment> {
+        match self {
+            Shape::Segment(s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// Gets a reference to the underlying triangle mesh shape, if `self` is one.
     pub fn as_trimesh(&self) -> Option<&Trimesh> {
         match self {
@@ -77,6 +113,23 @@ impl Shape {
         }
     }
 
+    /// Gets a reference to the underlying polyline shape, if `self` is one.
+    #[cfg(feature = "dim2")]
+    pub fn as_polyline(&self) -> Option<&Polyline> {
+        match self {
+            Shape::Polyline(p) => Some(p),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the underlying voxel grid shape, if `self` is one.
+    pub fn as_voxels(&self) -> Option<&Voxels> {
+        match self {
+            Shape::Voxels(v) => Some(v),
+            _ => None,
+        }
+    }
+
     /// Gets a reference to the underlying triangle shape, if `self` is one.
     pub fn as_triangle(&self) -> Option<&Triangle> {
         match self {
@@ -85,16 +138,200 @@ impl Shape {
         }
     }
 
+    /// Gets a reference to the underlying compound shape, if `self` is one.
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self {
+            Shape::Compound(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the underlying round shape, if `self` is one.
+    pub fn as_round_shape(&self) -> Option<&RoundShape> {
+        match self {
+            Shape::RoundShape(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// The base shape of `self` together with its border radius, if `self` is a round shape.
+    ///
+    /// Returns `self` with a zero border radius otherwise.
+    pub(crate) fn without_border(&self) -> (&Shape, f32) {
+        match self {
+            Shape::RoundShape(r) => (&r.base_shape, r.border_radius),
+            _ => (self, 0.0),
+        }
+    }
+
+    /// Gets a reference to the underlying convex polyhedron shape, if `self` is one.
+    #[cfg(feature = "dim3")]
+    pub fn as_convex_polyhedron(&self) -> Option<&ConvexPolyhedron> {
+        match self {
+            Shape::ConvexPolyhedron(p) => Some(p),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the underlying cylindrical shape, if `self` is one.
+    #[cfg(feature = "dim3")]
+    pub fn as_cylinder(&self) -> Option<&Cylinder> {
+        match self {
+            Shape::Cylinder(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the underlying conical shape, if `self` is one.
+    #[cfg(feature = "dim3")]
+    pub fn as_cone(&self) -> Option<&Cone> {
+        match self {
+            Shape::Cone(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Is this a composite shape, i.e., a shape made of several parts like a compound shape,
+    /// a triangle mesh, a polyline, a heightfield, or a voxel grid?
+    pub(crate) fn is_composite(&self) -> bool {
+        match self {
+            Shape::Trimesh(_) | Shape::HeightField(_) | Shape::Compound(_) | Shape::Voxels(_) => {
+                true
+            }
+            #[cfg(feature = "dim2")]
+            Shape::Polyline(_) => true,
+            _ => false,
+        }
+    }
+
     /// Computes the axis-aligned bounding box of this shape.
     pub fn compute_aabb(&self, position: &Isometry<f32>) -> AABB<f32> {
         match self {
             Shape::Ball(ball) => ball.bounding_volume(position),
             Shape::Polygon(poly) => poly.aabb(position),
             Shape::Capsule(caps) => caps.aabb(position),
+            Shape::Segment(segment) => segment.bounding_volume(position),
             Shape::Cuboid(cuboid) => cuboid.bounding_volume(position),
             Shape::Triangle(triangle) => triangle.bounding_volume(position),
             Shape::Trimesh(trimesh) => trimesh.aabb(position),
             Shape::HeightField(heightfield) => heightfield.bounding_volume(position),
+            #[cfg(feature = "dim2")]
+            Shape::Polyline(polyline) => polyline.aabb(position),
+            Shape::Voxels(voxels) => voxels.aabb(position),
+            Shape::Compound(compound) => compound.aabb(position),
+            Shape::RoundShape(round) => round.aabb(position),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly) => poly.aabb(position),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder) => cylinder.aabb(position),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone) => cone.aabb(position),
+        }
+    }
+
+    /// Computes the first intersection point between a ray and this shape.
+    ///
+    /// # Parameters
+    /// - `position`: the position of this shape.
+    /// - `ray`: the ray to cast.
+    /// - `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
+    ///   limits the length of the ray to `ray.dir.norm() * max_toi`. Use `f32::MAX` for an unbounded ray.
+    /// - `solid`: if `true` a ray starting inside of this shape will hit it at a time-of-impact
+    ///   equal to zero. Otherwise, the ray will hit the boundary of this shape from the inside.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        match self {
+            Shape::Ball(ball) => ball.toi_and_normal_with_ray(position, ray, max_toi, solid),
+            Shape::Polygon(poly) => poly.cast_ray(position, ray, max_toi, solid),
+            Shape::Capsule(caps) => caps.cast_ray(position, ray, max_toi, solid),
+            Shape::Segment(segment) => {
+                segment.toi_and_normal_with_ray(position, ray, max_toi, solid)
+            }
+            Shape::Cuboid(cuboid) => cuboid.toi_and_normal_with_ray(position, ray, max_toi, solid),
+            #[cfg(feature = "dim2")]
+            Shape::Triangle(triangle) => {
+                crate::geometry::triangle::cast_ray(triangle, position, ray, max_toi, solid)
+            }
+            #[cfg(feature = "dim3")]
+            Shape::Triangle(triangle) => {
+                triangle.toi_and_normal_with_ray(position, ray, max_toi, solid)
+            }
+            Shape::Trimesh(trimesh) => trimesh.cast_ray(position, ray, max_toi, solid),
+            Shape::HeightField(heightfield) => {
+                heightfield.toi_and_normal_with_ray(position, ray, max_toi, solid)
+            }
+            #[cfg(feature = "dim2")]
+            Shape::Polyline(polyline) => polyline.cast_ray(position, ray, max_toi, solid),
+            Shape::Voxels(voxels) => voxels.cast_ray(position, ray, max_toi, solid),
+            Shape::Compound(compound) => compound.cast_ray(position, ray, max_toi, solid),
+            Shape::RoundShape(round) => round.cast_ray(position, ray, max_toi, solid),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly) => poly.cast_ray(position, ray, max_toi, solid),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder) => cylinder.cast_ray(position, ray, max_toi, solid),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone) => cone.cast_ray(position, ray, max_toi, solid),
+        }
+    }
+
+    /// Projects a point on this shape.
+    ///
+    /// Returns the projected point together with the feature of this shape it was projected on.
+    /// For composite shapes, this feature is a face identifying the part of the shape the point
+    /// was projected on. Returns `None` for empty voxel grids.
+    ///
+    /// # Parameters
+    /// - `position`: the position of this shape.
+    /// - `point`: the point to project.
+    /// - `solid`: if `true` a point inside of this shape is projected on itself. Otherwise, it is
+    ///   projected on the boundary of this shape.
+    pub fn project_point(
+        &self,
+        position: &Isometry<f32>,
+        point: &Point<f32>,
+        solid: bool,
+    ) -> Option<(PointProjection, FeatureId)> {
+        crate::geometry::project_point(position, self, point, solid)
+    }
+
+    /// Computes the mass properties of this shape, assuming it has the given density.
+    ///
+    /// Shapes without volume, e.g., segments, triangles, triangle meshes, polylines, and heightfields,
+    /// have a zero mass.
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        match self {
+            Shape::Ball(ball) => MassProperties::from_ball(density, ball.radius),
+            #[cfg(feature = "dim2")]
+            Shape::Polygon(p) => MassProperties::from_polygon(density, p.vertices()),
+            // A 3D polygon is flat so it has no volume.
+            #[cfg(feature = "dim3")]
+            Shape::Polygon(_) => MassProperties::zero(),
+            Shape::Cuboid(c) => MassProperties::from_cuboid(density, c.half_extents),
+            Shape::Capsule(caps) => {
+                MassProperties::from_capsule(density, caps.a, caps.b, caps.radius)
+            }
+            Shape::Segment(_) => MassProperties::zero(),
+            Shape::Triangle(_) => MassProperties::zero(),
+            Shape::Trimesh(_) => MassProperties::zero(),
+            Shape::HeightField(_) => MassProperties::zero(),
+            #[cfg(feature = "dim2")]
+            Shape::Polyline(_) => MassProperties::zero(),
+            Shape::Voxels(voxels) => voxels.mass_properties(density),
+            Shape::Compound(compound) => compound.mass_properties(density),
+            Shape::RoundShape(round) => round.mass_properties(density),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly) => {
+                MassProperties::from_convex_polyhedron(density, poly.vertices(), poly.indices())
+            }
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(c) => MassProperties::from_cylinder(density, c.half_height, c.radius),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(c) => MassProperties::from_cone(density, c.half_height, c.radius),
         }
     }
 }
@@ -104,13 +341,15 @@ impl Shape {
 ///
 /// To build a new collider, use the `ColliderBuilder` structure.
 pub struct Collider {
-    shape: Shape,
+    pub(crate) shape: Shape,
     density: f32,
     is_sensor: bool,
     pub(crate) parent: RigidBodyHandle,
     pub(crate) delta: Isometry<f32>,
     pub(crate) position: Isometry<f32>,
     pub(crate) predicted_position: Isometry<f32>,
+    pub(crate) collision_groups: InteractionGroups,
+    pub(crate) solver_groups: InteractionGroups,
     /// The friction coefficient of this collider.
     pub friction: f32,
     /// The restitution coefficient of this collider.
@@ -144,6 +383,35 @@ impl Collider {
         self.is_sensor
     }
 
+    /// The collision groups used by this collider.
+    ///
+    /// Two colliders can only generate contacts or proximity information if their collision
+    /// groups are compatible.
+    pub fn collision_groups(&self) -> InteractionGroups {
+        self.collision_groups
+    }
+
+    /// The solver groups used by this collider.
+    ///
+    /// The contacts between two colliders are only taken into account by the constraints solver
+    /// if their solver groups are compatible.
+    pub fn solver_groups(&self) -> InteractionGroups {
+        self.solver_groups
+    }
+
+    /// Sets the collision groups used by this collider.
+    ///
+    /// If the parent rigid-body is sleeping, it won't react to this change until it is
+    /// woken up.
+    pub fn set_collision_groups(&mut self, groups: InteractionGroups) {
+        self.collision_groups = groups;
+    }
+
+    /// Sets the solver groups used by this collider.
+    pub fn set_solver_groups(&mut self, groups: InteractionGroups) {
+        self.solver_groups = groups;
+    }
+
     #[doc(hidden)]
     pub fn set_position_debug(&mut self, position: Isometry<f32>) {
         self.position = position;
@@ -182,20 +450,7 @@ impl Collider {
 
     /// Compute the local-space mass properties of this collider.
     pub fn mass_properties(&self) -> MassProperties {
-        match &self.shape {
-            Shape::Ball(ball) => MassProperties::from_ball(self.density, ball.radius),
-            #[cfg(feature = "dim2")]
-            Shape::Polygon(p) => MassProperties::from_polygon(self.density, p.vertices()),
-            #[cfg(feature = "dim3")]
-            Shape::Polygon(_p) => unimplemented!(),
-            Shape::Cuboid(c) => MassProperties::from_cuboid(self.density, c.half_extents),
-            Shape::Capsule(caps) => {
-                MassProperties::from_capsule(self.density, caps.a, caps.b, caps.radius)
-            }
-            Shape::Triangle(_) => MassProperties::zero(),
-            Shape::Trimesh(_) => MassProperties::zero(),
-            Shape::HeightField(_) => MassProperties::zero(),
-        }
+        self.shape.mass_properties(self.density)
     }
 }
 
@@ -215,6 +470,10 @@ pub struct ColliderBuilder {
     pub delta: Isometry<f32>,
     /// Is this collider a sensor?
     pub is_sensor: bool,
+    /// The collision groups of the collider to be built.
+    pub collision_groups: InteractionGroups,
+    /// The solver groups of the collider to be built.
+    pub solver_groups: InteractionGroups,
 }
 
 impl ColliderBuilder {
@@ -227,6 +486,8 @@ impl ColliderBuilder {
             restitution: 0.0,
             delta: Isometry::identity(),
             is_sensor: false,
+            collision_groups: InteractionGroups::all(),
+            solver_groups: InteractionGroups::all(),
         }
     }
 
@@ -288,12 +549,52 @@ impl ColliderBuilder {
         Self::new(Shape::Cuboid(cuboid))
     }
 
+    /// Initialize a new collider builder with a cuboid shape defined by its half-extents, and
+    /// with its vertices rounded by the given border radius.
+    #[cfg(feature = "dim2")]
+    pub fn round_cuboid(hx: f32, hy: f32, border_radius: f32) -> Self {
+        let cuboid = Cuboid {
+            half_extents: Vector::new(hx, hy),
+        };
+
+        Self::new(Shape::RoundShape(RoundShape::new(
+            Shape::Cuboid(cuboid),
+            border_radius,
+        )))
+    }
+
+    /// Initialize a new collider builder with a cuboid shape defined by its half-extents, and
+    /// with its edges and vertices rounded by the given border radius.
+    #[cfg(feature = "dim3")]
+    pub fn round_cuboid(hx: f32, hy: f32, hz: f32, border_radius: f32) -> Self {
+        let cuboid = Cuboid {
+            half_extents: Vector::new(hx, hy, hz),
+        };
+
+        Self::new(Shape::RoundShape(RoundShape::new(
+            Shape::Cuboid(cuboid),
+            border_radius,
+        )))
+    }
+
+    /// Initialize a new collider builder with a cylindrical shape defined by its half-height
+    /// (along the `y` axis) and its radius.
+    #[cfg(feature = "dim3")]
+    pub fn cylinder(half_height: f32, radius: f32) -> Self {
+        Self::new(Shape::Cylinder(Cylinder::new(half_height, radius)))
+    }
+
+    /// Initialize a new collider builder with a conical shape defined by its half-height
+    /// (along the `y` axis) and the radius of its base.
+    #[cfg(feature = "dim3")]
+    pub fn cone(half_height: f32, radius: f32) -> Self {
+        Self::new(Shape::Cone(Cone::new(half_height, radius)))
+    }
+
     /// Initializes a collider builder with a segment shape.
-    ///
-    /// A segment shape is modeled by a capsule with a 0 radius.
     pub fn segment(a: Point<f32>, b: Point<f32>) -> Self {
-        let capsule = Capsule::new(a, b, 0.0);
-        Self::new(Shape::Capsule(capsule))
+        let segment = Segment::new(a, b);
+        Self::new(Shape::Segment(segment))
     }
 
     /// Initializes a collider builder with a triangle shape.
@@ -308,6 +609,51 @@ impl ColliderBuilder {
         Self::new(Shape::Trimesh(trimesh))
     }
 
+    /// Initializes a collider builder with a polyline shape defined by its vertex and index buffers.
+    ///
+    /// If no index buffer is provided, the vertices are linked to form a single chain of segments.
+    #[cfg(feature = "dim2")]
+    pub fn polyline(vertices: Vec<Point<f32>>, indices: Option<Vec<Point2<u32>>>) -> Self {
+        let polyline = Polyline::new(vertices, indices);
+        Self::new(Shape::Polyline(polyline))
+    }
+
+    /// Initializes a collider builder with a voxel grid shape made of voxels of the given size,
+    /// filled at the given keys.
+    pub fn voxels(voxel_size: Vector<f32>, keys: &[Point<i32>]) -> Self {
+        let voxels = Voxels::new(voxel_size, keys);
+        Self::new(Shape::Voxels(voxels))
+    }
+
+    /// Initializes a collider builder with a compound shape made of the given shapes and their
+    /// positions relative to the local frame of the collider.
+    pub fn compound(shapes: Vec<(Isometry<f32>, Shape)>) -> Self {
+        let compound = Compound::new(shapes);
+        Self::new(Shape::Compound(compound))
+    }
+
+    /// Initializes a collider builder with a convex polyhedron shape equal to the convex hull of the given points.
+    ///
+    /// Returns `None` if the convex hull is degenerate, e.g., if all the points are coplanar.
+    #[cfg(feature = "dim3")]
+    pub fn convex_hull(points: &[Point<f32>]) -> Option<Self> {
+        let poly = ConvexPolyhedron::from_points(points)?;
+        Some(Self::new(Shape::ConvexPolyhedron(poly)))
+    }
+
+    /// Initializes a collider builder with a convex polyhedron shape equal to the convex hull of the given points,
+    /// and with its edges and vertices rounded by the given border radius.
+    ///
+    /// Returns `None` if the convex hull is degenerate, e.g., if all the points are coplanar.
+    #[cfg(feature = "dim3")]
+    pub fn round_convex_hull(points: &[Point<f32>], border_radius: f32) -> Option<Self> {
+        let poly = ConvexPolyhedron::from_points(points)?;
+        Some(Self::new(Shape::RoundShape(RoundShape::new(
+            Shape::ConvexPolyhedron(poly),
+            border_radius,
+        ))))
+    }
+
     /// Initializes a collider builder with a heightfield shape defined by its set of height and a scale
     /// factor along each coordinate axis.
     #[cfg(feature = "dim2")]
@@ -335,6 +681,24 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets the collision groups used by this collider.
+    ///
+    /// Two colliders will interact iff. their collision groups are compatible.
+    /// See `InteractionGroups::test` for details.
+    pub fn collision_groups(mut self, groups: InteractionGroups) -> Self {
+        self.collision_groups = groups;
+        self
+    }
+
+    /// Sets the solver groups used by this collider.
+    ///
+    /// Forces between two colliders in contact will be computed iff. their solver groups are
+    /// compatible. See `InteractionGroups::test` for details.
+    pub fn solver_groups(mut self, groups: InteractionGroups) -> Self {
+        self.solver_groups = groups;
+        self
+    }
+
     /// Sets the friction coefficient of the collider this builder will build.
     pub fn friction(mut self, friction: f32) -> Self {
         self.friction = friction;
@@ -362,6 +726,8 @@ impl ColliderBuilder {
             restitution: self.restitution,
             delta: self.delta,
             is_sensor: self.is_sensor,
+            collision_groups: self.collision_groups,
+            solver_groups: self.solver_groups,
             parent: RigidBodySet::invalid_handle(),
             position: Isometry::identity(),
             predicted_position: Isometry::identity(),
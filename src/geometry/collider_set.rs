@@ -1,6 +1,6 @@
 use crate::data::arena::Arena;
 use crate::dynamics::{RigidBodyHandle, RigidBodySet};
-use crate::geometry::Collider;
+use crate::geometry::{Collider, Shape, Voxels};
 use std::ops::{Index, IndexMut};
 
 /// The unique identifier of a collider added to a collider set.
@@ -10,6 +10,8 @@ pub type ColliderHandle = crate::data::arena::Index;
 /// A set of colliders that can be handled by a physics `World`.
 pub struct ColliderSet {
     pub(crate) colliders: Arena<Collider>,
+    // Colliders whose shape was modified since the last timestep.
+    pub(crate) modified_colliders: Vec<ColliderHandle>,
 }
 
 impl ColliderSet {
@@ -17,6 +19,7 @@ impl ColliderSet {
     pub fn new() -> Self {
         ColliderSet {
             colliders: Arena::new(),
+            modified_colliders: Vec::new(),
         }
     }
 
@@ -102,6 +105,47 @@ impl ColliderSet {
         self.colliders.get_mut(handle)
     }
 
+    /// Modifies the voxels of the collider with the given handle, e.g., to fill or clear some
+    /// of its voxels.
+    ///
+    /// Returns `None` if the collider does not exist or does not have a voxel grid shape.
+    /// The rigid-body this collider is attached to is activated so that the new voxels are
+    /// taken into account at the next timestep. Every rigid-body in contact with this collider
+    /// is woken up at the next timestep too.
+    pub fn modify_voxels<T>(
+        &mut self,
+        handle: ColliderHandle,
+        bodies: &mut RigidBodySet,
+        f: impl FnOnce(&mut Voxels) -> T,
+    ) -> Option<T> {
+        let coll = self.colliders.get_mut(handle)?;
+        let parent_handle = coll.parent;
+        let density = coll.density();
+        let voxels = match &mut coll.shape {
+            Shape::Voxels(voxels) => voxels,
+            _ => return None,
+        };
+
+        let parent = bodies
+            .get_mut_internal(parent_handle)
+            .expect("Parent rigid body not found.");
+
+        let result = if parent.is_dynamic() {
+            let old_mass_properties = voxels.mass_properties(density);
+            let result = f(voxels);
+            parent.mass_properties += voxels.mass_properties(density) - old_mass_properties;
+            parent.update_world_mass_properties();
+            result
+        } else {
+            // The mass properties of non-dynamic bodies are not used.
+            f(voxels)
+        };
+
+        bodies.activate(parent_handle);
+        self.modified_colliders.push(handle);
+        Some(result)
+    }
+
     pub(crate) fn get2_mut_internal(
         &mut self,
         h1: ColliderHandle,
@@ -0,0 +1,202 @@
+use crate::dynamics::MassProperties;
+use crate::geometry::{
+    Ray, RayIntersection, Shape, WAABBHierarchy, WAABBHierarchyIntersections, AABB,
+};
+use crate::math::Isometry;
+use ncollide::bounding_volume::BoundingVolume;
+use ncollide::query::RayCast;
+use ncollide::shape::FeatureId;
+use num::Zero;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A compound shape, i.e., a set of shapes positioned relative to a common local frame.
+///
+/// The sub-shapes are stored into a bounding volume hierarchy so that only the sub-shapes close
+/// to another shape are considered during collision detection.
+pub struct Compound {
+    shapes: Vec<(Isometry<f32>, Shape)>,
+    waabb_tree: WAABBHierarchy,
+    aabbs: Vec<AABB>,
+    aabb: AABB,
+}
+
+impl Compound {
+    /// Builds a new compound shape from a set of shapes and their positions relative to the
+    /// local frame of the compound.
+    ///
+    /// Panics if `shapes` is empty, or if any of the shapes is itself a compound shape, a
+    /// triangle mesh, a polyline, a heightfield, or a voxel grid.
+    pub fn new(shapes: Vec<(Isometry<f32>, Shape)>) -> Self {
+        assert!(
+            !shapes.is_empty(),
+            "A compound shape must contain at least one shape."
+        );
+
+        let aabbs: Vec<_> = shapes
+            .iter()
+            .map(|(delta, shape)| {
+                assert!(
+                    !shape.is_composite(),
+                    "A compound shape cannot contain a compound shape, a triangle mesh, a polyline, a heightfield, or a voxel grid."
+                );
+                shape.compute_aabb(delta)
+            })
+            .collect();
+
+        let aabb = aabbs
+            .iter()
+            .fold(AABB::new_invalid(), |acc, aabb| acc.merged(aabb));
+        let waabb_tree = WAABBHierarchy::new(&aabbs);
+
+        Self {
+            shapes,
+            waabb_tree,
+            aabbs,
+            aabb,
+        }
+    }
+
+    /// The shapes of this compound shape, with their positions relative to its local frame.
+    pub fn shapes(&self) -> &[(Isometry<f32>, Shape)] {
+        &self.shapes
+    }
+
+    /// The number of shapes forming this compound shape.
+    pub fn num_shapes(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// The local-space axis-aligned bounding box of each shape of this compound shape.
+    pub fn aabbs(&self) -> &[AABB] {
+        &self.aabbs
+    }
+
+    /// Compute the axis-aligned bounding box of this compound shape.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        self.aabb.transform_by(pos)
+    }
+
+    pub(crate) fn waabbs(&self) -> &WAABBHierarchy {
+        &self.waabb_tree
+    }
+
+    /// Computes the mass properties of this compound shape, i.e., the sum of the mass
+    /// properties of all its shapes.
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        self.shapes
+            .iter()
+            .fold(MassProperties::zero(), |acc, (delta, shape)| {
+                acc + shape.mass_properties(density).transform_by(delta)
+            })
+    }
+
+    /// Computes the first intersection between this compound shape and a ray.
+    ///
+    /// The feature of the returned intersection identifies the shape hit by the ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let mut workspace = WAABBHierarchyIntersections::new();
+        self.waabb_tree.compute_interferences_with_predicate(
+            |aabb| aabb.intersects_ray(&Isometry::identity(), &ls_ray, max_toi),
+            &mut workspace,
+        );
+
+        let mut best: Option<RayIntersection> = None;
+
+        for shape_id in workspace.computed_interferences() {
+            if *shape_id >= self.shapes.len() {
+                // Because of SIMD padding, the hierarchy may return indices greater than the max.
+                continue;
+            }
+
+            let max_toi = best.as_ref().map(|b| b.toi).unwrap_or(max_toi);
+            let (delta, shape) = &self.shapes[*shape_id];
+
+            if let Some(mut inter) = shape.cast_ray(delta, &ls_ray, max_toi, solid) {
+                inter.feature = FeatureId::Face(*shape_id);
+                best = Some(inter);
+            }
+        }
+
+        best.map(|mut inter| {
+            inter.normal = position * inter.normal;
+            inter
+        })
+    }
+}
+
+/// Calls `f` on each part of `shape` with an AABB intersecting `local_aabb`.
+///
+/// The parts of a compound shape are its sub-shapes, the parts of a triangle mesh or heightfield
+/// are their triangles (or segments in 2D), the parts of a polyline are its segments, the parts of
+/// a voxel grid are its voxels, and any other shape has only one part: itself.
+/// The closure is given the index of the part, its position relative to the local frame of
+/// `shape`, and the part itself.
+pub(crate) fn map_parts_in_local_aabb(
+    shape: &Shape,
+    local_aabb: &AABB,
+    interferences: &mut WAABBHierarchyIntersections,
+    f: &mut impl FnMut(usize, &Isometry<f32>, &Shape),
+) {
+    match shape {
+        Shape::Compound(compound) => {
+            compound
+                .waabbs()
+                .compute_interferences_with(*local_aabb, interferences);
+
+            for id in interferences.computed_interferences() {
+                if let Some((delta, part)) = compound.shapes().get(*id) {
+                    f(*id, delta, part)
+                }
+            }
+        }
+        Shape::Trimesh(trimesh) => {
+            trimesh
+                .waabbs()
+                .compute_interferences_with(*local_aabb, interferences);
+
+            for id in interferences.computed_interferences() {
+                if *id < trimesh.num_triangles() {
+                    let part = Shape::Triangle(trimesh.triangle(*id));
+                    f(*id, &Isometry::identity(), &part)
+                }
+            }
+        }
+        #[cfg(feature = "dim2")]
+        Shape::Polyline(polyline) => {
+            polyline
+                .waabbs()
+                .compute_interferences_with(*local_aabb, interferences);
+
+            for id in interferences.computed_interferences() {
+                if *id < polyline.num_segments() {
+                    let part = Shape::Segment(polyline.segment(*id));
+                    f(*id, &Isometry::identity(), &part)
+                }
+            }
+        }
+        Shape::HeightField(heightfield) => {
+            heightfield.map_elements_in_local_aabb(local_aabb, &mut |id, part, _| {
+                #[cfg(feature = "dim2")]
+                let part = Shape::Segment(*part);
+                #[cfg(feature = "dim3")]
+                let part = Shape::Triangle(*part);
+                f(id, &Isometry::identity(), &part)
+            })
+        }
+        Shape::Voxels(voxels) => {
+            let part = Shape::Cuboid(voxels.voxel_shape());
+            voxels.map_voxels_in_local_aabb(local_aabb, &mut |id, key| {
+                f(id as usize, &voxels.voxel_position(key), &part)
+            })
+        }
+        _ => f(0, &Isometry::identity(), shape),
+    }
+}
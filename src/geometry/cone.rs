@@ -0,0 +1,135 @@
+use crate::geometry::{PolyhedronFace, Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Vector};
+use ncollide::bounding_volume;
+use ncollide::query::{self, algorithms::VoronoiSimplex, PointProjection, PointQuery, RayCast};
+use ncollide::shape::{FeatureId, SupportMap};
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A cone shape with its principal axis aligned with the `y` axis.
+///
+/// The base of the cone lies on the plane `y = -half_height` and its apex is at `y = half_height`.
+///
+/// The contacts between a cone and a polyhedral shape are computed with an approximation of
+/// the cone: its base is approximated by the square inscribed in it, and its curved side by the
+/// segment of the side closest to the other shape. Thus, a cone resting on its base is supported
+/// by at most four contact points, located on the rim of the base.
+pub struct Cone {
+    /// The half-height of the cone along the `y` axis.
+    pub half_height: f32,
+    /// The radius of the base of the cone.
+    pub radius: f32,
+}
+
+impl Cone {
+    /// Creates a new cone aligned with the `y` axis and with the given half-height and base radius.
+    pub fn new(half_height: f32, radius: f32) -> Self {
+        assert!(half_height > 0.0 && radius > 0.0);
+        Self {
+            half_height,
+            radius,
+        }
+    }
+
+    /// The axis-aligned bounding box of this cone.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        bounding_volume::support_map_aabb(pos, self)
+    }
+
+    /// Computes the first intersection between this cone and a ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let cone = ncollide::shape::Cone::new(self.half_height, self.radius);
+        cone.toi_and_normal_with_ray(position, ray, max_toi, solid)
+    }
+
+    /// The face of this cone with the normal most aligned with `local_dir`.
+    ///
+    /// The base is approximated by a square inscribed in it, and the curved
+    /// side by the segment of the side farthest along `local_dir`.
+    pub(crate) fn support_face(&self, local_dir: Vector<f32>) -> PolyhedronFace {
+        let horizontal_dir = Vector::new(local_dir.x, 0.0, local_dir.z);
+        let horizontal_norm = horizontal_dir.norm();
+        let horizontal_dir = if horizontal_norm > 1.0e-6 {
+            horizontal_dir / horizontal_norm
+        } else {
+            Vector::x()
+        };
+
+        // The outward normal of the side is collinear with (2 * half_height, radius)
+        // in the plane spanned by the horizontal direction and the `y` axis.
+        let side_normal = Vector::new(self.half_height * 2.0, self.radius, 0.0).normalize();
+        let side_dot = horizontal_norm * side_normal.x + local_dir.y * side_normal.y;
+        let rim = horizontal_dir * self.radius;
+
+        if -local_dir.y > side_dot {
+            // Support face on the base.
+            let center = Point::from(-Vector::y() * self.half_height);
+            let dir2 = Vector::y().cross(&rim);
+
+            PolyhedronFace {
+                vertices: vec![center + rim, center + dir2, center - rim, center - dir2],
+                vids: vec![0, 2, 4, 6],
+                eids: vec![1, 3, 5, 7],
+                fid: 8,
+            }
+        } else {
+            // Support segment on the curved side.
+            let apex = Point::from(Vector::y() * self.half_height);
+            let base = Point::from(rim - Vector::y() * self.half_height);
+
+            PolyhedronFace {
+                vertices: vec![apex, base],
+                vids: vec![10, 12],
+                eids: vec![11, 11],
+                fid: 13,
+            }
+        }
+    }
+}
+
+impl SupportMap<f32> for Cone {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        let mut res = Vector::new(dir.x, 0.0, dir.z);
+
+        if res.normalize_mut() == 0.0 {
+            res = Vector::zeros();
+            res.y = self.half_height.copysign(dir.y);
+        } else {
+            res *= self.radius;
+            res.y = -self.half_height;
+
+            if dir.dot(&res) < dir.y * self.half_height {
+                res = Vector::y() * self.half_height;
+            }
+        }
+
+        Point::from(res)
+    }
+}
+
+impl PointQuery<f32> for Cone {
+    #[inline]
+    fn project_point(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+        solid: bool,
+    ) -> PointProjection<f32> {
+        query::point_projection_on_support_map(m, self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+    ) -> (PointProjection<f32>, FeatureId) {
+        (self.project_point(m, pt, false), FeatureId::Unknown)
+    }
+}
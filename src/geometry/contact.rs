@@ -1,7 +1,8 @@
-use crate::dynamics::BodyPair;
+use crate::dynamics::{BodyPair, RigidBodySet};
 use crate::geometry::contact_generator::ContactPhase;
 use crate::geometry::{Collider, ColliderPair, ColliderSet};
 use crate::math::{Isometry, Point, Vector};
+use crate::pipeline::{ContactModificationContext, PhysicsHooks};
 use std::any::Any;
 #[cfg(feature = "simd-is-enabled")]
 use {
@@ -49,8 +50,8 @@ pub(crate) struct WContact {
     pub local_n1: Vector<SimdFloat>,
     pub local_n2: Vector<SimdFloat>,
     pub dist: SimdFloat,
-    pub fid1: [u8; SIMD_WIDTH],
-    pub fid2: [u8; SIMD_WIDTH],
+    pub fid1: [u32; SIMD_WIDTH],
+    pub fid2: [u32; SIMD_WIDTH],
 }
 
 #[cfg(feature = "simd-is-enabled")]
@@ -62,6 +63,7 @@ impl WContact {
             dist: self.dist.extract(i),
             impulse: 0.0,
             tangent_impulse: Contact::zero_tangent_impulse(),
+            tangent_velocity: Vector::zeros(),
             fid1: self.fid1[i],
             fid2: self.fid2[i],
         };
@@ -90,18 +92,24 @@ pub struct Contact {
     /// collider's rigid-body.
     #[cfg(feature = "dim3")]
     pub tangent_impulse: [f32; 2],
+    /// The desired relative velocity, along the contact tangents, between the second and the first
+    /// collider at this contact point, expressed in world-space.
+    ///
+    /// This is zero by default and can be set by `PhysicsHooks::modify_solver_contacts` to
+    /// simulate, e.g., conveyor belts. Its component along the contact normal is ignored.
+    pub tangent_velocity: Vector<f32>,
     /// The identifier of the subshape of the first collider involved in this contact.
     ///
     /// For primitive shapes like cuboid, ball, etc., this is 0.
     /// For shapes like trimesh and heightfield this identifies the specific triangle
     /// involved in the contact.
-    pub fid1: u8,
+    pub fid1: u32,
     /// The identifier of the subshape of the second collider involved in this contact.
     ///
     /// For primitive shapes like cuboid, ball, etc., this is 0.
     /// For shapes like trimesh and heightfield this identifies the specific triangle
     /// involved in the contact.
-    pub fid2: u8,
+    pub fid2: u32,
     /// The distance between the two colliders along the contact normal.
     ///
     /// If this is negative, the colliders are penetrating.
@@ -112,8 +120,8 @@ impl Contact {
     pub(crate) fn new(
         local_p1: Point<f32>,
         local_p2: Point<f32>,
-        fid1: u8,
-        fid2: u8,
+        fid1: u32,
+        fid2: u32,
         dist: f32,
     ) -> Self {
         Self {
@@ -124,6 +132,7 @@ impl Contact {
             tangent_impulse: 0.0,
             #[cfg(feature = "dim3")]
             tangent_impulse: [0.0; 2],
+            tangent_velocity: Vector::zeros(),
             fid1,
             fid2,
             dist,
@@ -170,6 +179,11 @@ pub struct ContactPair {
     ///
     /// All contact manifold contain themselves contact points between the colliders.
     pub manifolds: Vec<ContactManifold>,
+    /// Did the narrow-phase find any active contact during its last update?
+    ///
+    /// The contact events are based on this rather than on the contacts modified by the
+    /// physics hooks.
+    pub(crate) has_narrow_phase_contact: bool,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     pub(crate) generator: Option<ContactPhase>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
@@ -185,6 +199,7 @@ impl ContactPair {
         Self {
             pair,
             manifolds: Vec::new(),
+            has_narrow_phase_contact: false,
             generator: Some(generator),
             generator_workspace,
         }
@@ -203,6 +218,42 @@ impl ContactPair {
         false
     }
 
+    /// Resets the solver data of the manifolds with active contacts, and lets the user modify them.
+    pub(crate) fn modify_solver_contacts(
+        &mut self,
+        colliders: &ColliderSet,
+        bodies: &RigidBodySet,
+        hooks: &dyn PhysicsHooks,
+    ) {
+        for manifold in &mut self.manifolds {
+            if manifold.num_active_contacts == 0 {
+                continue;
+            }
+
+            let co1 = &colliders[manifold.pair.collider1];
+            let co2 = &colliders[manifold.pair.collider2];
+
+            manifold.friction = (co1.friction + co2.friction) * 0.5;
+            manifold.restitution = (co1.restitution + co2.restitution) * 0.5;
+
+            for contact in manifold.active_contacts_mut() {
+                contact.tangent_velocity = Vector::zeros();
+            }
+
+            let mut context = ContactModificationContext {
+                collider_handle1: manifold.pair.collider1,
+                collider_handle2: manifold.pair.collider2,
+                collider1: co1,
+                collider2: co2,
+                rigid_body1: &bodies[co1.parent],
+                rigid_body2: &bodies[co2.parent],
+                manifold,
+            };
+
+            hooks.modify_solver_contacts(&mut context);
+        }
+    }
+
     pub(crate) fn single_manifold<'a, 'b>(
         &'a mut self,
         colliders: &'b ColliderSet,
@@ -376,11 +427,26 @@ impl ContactManifold {
         &self.points[..self.num_active_contacts]
     }
 
+    /// The mutable slice of all the active contacts on this contact manifold.
     #[inline]
-    pub(crate) fn active_contacts_mut(&mut self) -> &mut [Contact] {
+    pub fn active_contacts_mut(&mut self) -> &mut [Contact] {
         &mut self.points[..self.num_active_contacts]
     }
 
+    /// Deactivates the `i`-th active contact so that it does not generate any contact force.
+    ///
+    /// The last active contact takes the place of the deactivated one, so this changes the
+    /// order of the active contacts.
+    #[inline]
+    pub fn deactivate_contact(&mut self, i: usize) {
+        assert!(
+            i < self.num_active_contacts,
+            "Invalid active contact index."
+        );
+        self.points.swap(i, self.num_active_contacts - 1);
+        self.num_active_contacts -= 1;
+    }
+
     /// The slice of all the contacts, active or not, on this contact manifold.
     #[inline]
     pub fn all_contacts(&self) -> &[Contact] {
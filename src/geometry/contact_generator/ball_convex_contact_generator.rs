@@ -12,6 +12,13 @@ pub fn generate_contacts_ball_convex(ctxt: &mut PrimitiveContactGenerationContex
             Shape::Triangle(tri2) => do_generate_contacts(tri2, ball1, ctxt, true),
             Shape::Cuboid(cube2) => do_generate_contacts(cube2, ball1, ctxt, true),
             Shape::Capsule(capsule2) => do_generate_contacts(capsule2, ball1, ctxt, true),
+            Shape::Segment(segment2) => do_generate_contacts(segment2, ball1, ctxt, true),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly2) => do_generate_contacts(poly2, ball1, ctxt, true),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder2) => do_generate_contacts(cylinder2, ball1, ctxt, true),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone2) => do_generate_contacts(cone2, ball1, ctxt, true),
             _ => unimplemented!(),
         }
     } else if let Shape::Ball(ball2) = ctxt.shape2 {
@@ -19,6 +26,13 @@ pub fn generate_contacts_ball_convex(ctxt: &mut PrimitiveContactGenerationContex
             Shape::Triangle(tri1) => do_generate_contacts(tri1, ball2, ctxt, false),
             Shape::Cuboid(cube1) => do_generate_contacts(cube1, ball2, ctxt, false),
             Shape::Capsule(capsule1) => do_generate_contacts(capsule1, ball2, ctxt, false),
+            Shape::Segment(segment1) => do_generate_contacts(segment1, ball2, ctxt, false),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly1) => do_generate_contacts(poly1, ball2, ctxt, false),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder1) => do_generate_contacts(cylinder1, ball2, ctxt, false),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone1) => do_generate_contacts(cone1, ball2, ctxt, false),
             _ => unimplemented!(),
         }
     }
@@ -83,3 +97,54 @@ fn do_generate_contacts<P: PointQuery<f32>>(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{IntegrationParameters, JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsPipeline;
+
+    #[test]
+    fn ball_rests_on_segment() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        #[cfg(feature = "dim2")]
+        let segment = ColliderBuilder::segment(Point::new(-5.0, 0.0), Point::new(5.0, 0.0));
+        #[cfg(feature = "dim3")]
+        let segment =
+            ColliderBuilder::segment(Point::new(-5.0, 0.0, 0.0), Point::new(5.0, 0.0, 0.0));
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        colliders.insert(segment.build(), ground, &mut bodies);
+
+        #[cfg(feature = "dim2")]
+        let body = RigidBodyBuilder::new_dynamic().translation(0.0, 0.6);
+        #[cfg(feature = "dim3")]
+        let body = RigidBodyBuilder::new_dynamic().translation(0.0, 0.6, 0.0);
+        let body = body.build();
+        let body = bodies.insert(body);
+        colliders.insert(ColliderBuilder::ball(0.5).build(), body, &mut bodies);
+
+        for _ in 0..120 {
+            pipeline.step(
+                &(Vector::y() * -9.81),
+                &IntegrationParameters::default(),
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &(),
+                &(),
+            );
+        }
+
+        assert!(bodies[body].linvel.norm() < 1.0e-2);
+        assert!((bodies[body].position.translation.vector.y - 0.5).abs() < 1.0e-2);
+    }
+}
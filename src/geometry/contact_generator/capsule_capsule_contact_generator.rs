@@ -1,5 +1,5 @@
 use crate::geometry::contact_generator::PrimitiveContactGenerationContext;
-use crate::geometry::{Capsule, Contact, ContactManifold, KinematicsCategory, Shape};
+use crate::geometry::{Capsule, Contact, ContactManifold, KinematicsCategory};
 use crate::math::Isometry;
 use crate::math::Vector;
 use approx::AbsDiffEq;
@@ -8,12 +8,15 @@ use na::Unit;
 use ncollide::shape::{Segment, SegmentPointLocation};
 
 pub fn generate_contacts_capsule_capsule(ctxt: &mut PrimitiveContactGenerationContext) {
-    if let (Shape::Capsule(capsule1), Shape::Capsule(capsule2)) = (ctxt.shape1, ctxt.shape2) {
+    if let (Some(capsule1), Some(capsule2)) = (
+        Capsule::from_shape(ctxt.shape1),
+        Capsule::from_shape(ctxt.shape2),
+    ) {
         generate_contacts(
             ctxt.prediction_distance,
-            capsule1,
+            &capsule1,
             ctxt.position1,
-            capsule2,
+            &capsule2,
             ctxt.position2,
             ctxt.manifold,
         );
@@ -53,12 +56,12 @@ pub fn generate_contacts<'a>(
     let swapped = false;
 
     let fid1 = if let SegmentPointLocation::OnVertex(v1) = loc1 {
-        v1 as u8 * 2
+        v1 as u32 * 2
     } else {
         1
     };
     let fid2 = if let SegmentPointLocation::OnVertex(v2) = loc2 {
-        v2 as u8 * 2
+        v2 as u32 * 2
     } else {
         1
     };
@@ -107,8 +110,8 @@ pub fn generate_contacts<'a>(
                         Contact::new(
                             clip_a.0,
                             pos21 * clip_a.1,
-                            clip_a.2 as u8,
-                            clip_a.3 as u8,
+                            clip_a.2 as u32,
+                            clip_a.3 as u32,
                             (clip_a.1 - clip_a.0).dot(&local_n1),
                         )
                     } else {
@@ -116,8 +119,8 @@ pub fn generate_contacts<'a>(
                         Contact::new(
                             clip_b.0,
                             pos21 * clip_b.1,
-                            clip_b.2 as u8,
-                            clip_b.3 as u8,
+                            clip_b.2 as u32,
+                            clip_b.3 as u32,
                             (clip_b.1 - clip_b.0).dot(&local_n1),
                         )
                     };
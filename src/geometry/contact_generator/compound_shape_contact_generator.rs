@@ -0,0 +1,205 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::contact_generator::{
+    ContactGenerationContext, PrimitiveContactGenerationContext, PrimitiveContactGenerator,
+};
+use crate::geometry::{
+    Collider, ColliderHandle, Compound, ContactManifold, Shape, WAABBHierarchyIntersections,
+};
+use crate::math::Isometry;
+use crate::ncollide::bounding_volume::BoundingVolume;
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+struct SubDetector {
+    generator: PrimitiveContactGenerator,
+    manifold_id: usize,
+    timestamp: bool,
+    workspace: Option<Box<(dyn Any + Send + Sync)>>,
+}
+
+pub struct CompoundShapeContactGeneratorWorkspace {
+    timestamp: bool,
+    interferences1: WAABBHierarchyIntersections,
+    interferences2: WAABBHierarchyIntersections,
+    old_manifolds: Vec<ContactManifold>,
+    sub_detectors: HashMap<(usize, usize), SubDetector>,
+}
+
+impl CompoundShapeContactGeneratorWorkspace {
+    pub fn new() -> Self {
+        Self {
+            timestamp: false,
+            interferences1: WAABBHierarchyIntersections::new(),
+            interferences2: WAABBHierarchyIntersections::new(),
+            old_manifolds: Vec::new(),
+            sub_detectors: HashMap::default(),
+        }
+    }
+}
+
+pub fn generate_contacts_compound_shape(ctxt: &mut ContactGenerationContext) {
+    let collider1 = &ctxt.colliders[ctxt.pair.pair.collider1];
+    let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
+
+    if let Shape::Compound(compound1) = collider1.shape() {
+        let handle1 = ctxt.pair.pair.collider1;
+        do_generate_contacts(compound1, collider1, handle1, collider2, ctxt, false)
+    } else if let Shape::Compound(compound2) = collider2.shape() {
+        let handle2 = ctxt.pair.pair.collider2;
+        do_generate_contacts(compound2, collider2, handle2, collider1, ctxt, true)
+    }
+}
+
+fn do_generate_contacts(
+    compound1: &Compound,
+    collider1: &Collider,
+    handle1: ColliderHandle,
+    collider2: &Collider,
+    ctxt: &mut ContactGenerationContext,
+    flipped: bool,
+) {
+    let workspace: &mut CompoundShapeContactGeneratorWorkspace = ctxt
+        .pair
+        .generator_workspace
+        .as_mut()
+        .expect("The CompoundShapeContactGeneratorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a CompoundShapeContactGeneratorWorkspace.");
+    let CompoundShapeContactGeneratorWorkspace {
+        timestamp,
+        interferences1,
+        interferences2,
+        old_manifolds,
+        sub_detectors,
+    } = workspace;
+
+    /*
+     * Detect if the detector context has been reset.
+     */
+    if !ctxt.pair.manifolds.is_empty() && sub_detectors.is_empty() {
+        // The manifolds can no longer be matched with their sub-detectors
+        // so we have to start from scratch.
+        ctxt.pair.manifolds.clear();
+    }
+
+    let new_timestamp = !*timestamp;
+    *timestamp = new_timestamp;
+
+    /*
+     * Compute interferences.
+     */
+    let pos12 = collider1.position.inverse() * collider2.position;
+    let pos21 = pos12.inverse();
+    // TODO: somehow precompute the AABB and reuse it?
+    let ls_aabb2 = collider2
+        .shape()
+        .compute_aabb(&pos12)
+        .loosened(ctxt.prediction_distance);
+    compound1
+        .waabbs()
+        .compute_interferences_with(ls_aabb2, interferences1);
+
+    std::mem::swap(old_manifolds, &mut ctxt.pair.manifolds);
+    ctxt.pair.manifolds.clear();
+    let coll_pair = ctxt.pair.pair;
+    let manifolds = &mut ctxt.pair.manifolds;
+    let prediction_distance = ctxt.prediction_distance;
+    let dispatcher = ctxt.dispatcher;
+
+    for id1 in interferences1.computed_interferences() {
+        let (delta1, part1) = match compound1.shapes().get(*id1) {
+            Some(part) => part,
+            // Because of SIMD padding, the hierarchy may return indices greater than the max.
+            None => continue,
+        };
+        let part_pos1 = collider1.position * delta1;
+        let ls_part_aabb1 = part1
+            .compute_aabb(&(pos21 * delta1))
+            .loosened(prediction_distance);
+
+        let mut handle_part_pair = |id2: usize, delta2: &Isometry<f32>, part2: &Shape| {
+            let sub_detector = match sub_detectors.entry((*id1, id2)) {
+                Entry::Occupied(entry) => {
+                    let sub_detector = entry.into_mut();
+                    let manifold = old_manifolds[sub_detector.manifold_id].take();
+                    sub_detector.manifold_id = manifolds.len();
+                    sub_detector.timestamp = new_timestamp;
+                    manifolds.push(manifold);
+                    sub_detector
+                }
+                Entry::Vacant(entry) => {
+                    let (generator, workspace2) = dispatcher.dispatch_primitives(part1, part2);
+                    let sub_detector = SubDetector {
+                        generator,
+                        manifold_id: manifolds.len(),
+                        timestamp: new_timestamp,
+                        workspace: workspace2,
+                    };
+                    let manifold = if flipped {
+                        ContactManifold::with_subshape_indices(
+                            coll_pair, collider2, collider1, id2, *id1,
+                        )
+                    } else {
+                        ContactManifold::with_subshape_indices(
+                            coll_pair, collider1, collider2, *id1, id2,
+                        )
+                    };
+                    manifolds.push(manifold);
+
+                    entry.insert(sub_detector)
+                }
+            };
+
+            let part_pos2 = collider2.position * delta2;
+            let manifold = &mut manifolds[sub_detector.manifold_id];
+
+            // The sub-generators work on contacts expressed in the local frames of the parts,
+            // whereas the solver expects them in the local frames of the colliders.
+            let swapped = manifold.pair.collider1 != handle1;
+            super::transform_manifold(manifold, &delta1.inverse(), &delta2.inverse(), swapped);
+
+            let mut ctxt2 = if swapped {
+                PrimitiveContactGenerationContext {
+                    prediction_distance,
+                    collider1: collider2,
+                    collider2: collider1,
+                    shape1: part2,
+                    shape2: part1,
+                    position1: &part_pos2,
+                    position2: &part_pos1,
+                    manifold,
+                    workspace: sub_detector.workspace.as_deref_mut(),
+                }
+            } else {
+                PrimitiveContactGenerationContext {
+                    prediction_distance,
+                    collider1,
+                    collider2,
+                    shape1: part1,
+                    shape2: part2,
+                    position1: &part_pos1,
+                    position2: &part_pos2,
+                    manifold,
+                    workspace: sub_detector.workspace.as_deref_mut(),
+                }
+            };
+
+            (sub_detector.generator.generate_contacts)(&mut ctxt2);
+
+            // The sub-generator may have swapped the identifiers of the manifold.
+            let manifold = &mut manifolds[sub_detector.manifold_id];
+            let swapped = manifold.pair.collider1 != handle1;
+            super::transform_manifold(manifold, delta1, delta2, swapped);
+        };
+
+        map_parts_in_local_aabb(
+            collider2.shape(),
+            &ls_part_aabb1,
+            interferences2,
+            &mut handle_part_pair,
+        );
+    }
+
+    sub_detectors.retain(|_, detector| detector.timestamp == new_timestamp)
+}
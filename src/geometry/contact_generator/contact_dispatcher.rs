@@ -1,7 +1,13 @@
+#[cfg(feature = "dim2")]
+use crate::geometry::contact_generator::PolylineShapeContactGeneratorWorkspace;
 use crate::geometry::contact_generator::{
-    ContactGenerator, ContactPhase, HeightFieldShapeContactGeneratorWorkspace,
-    PrimitiveContactGenerator, TrimeshShapeContactGeneratorWorkspace,
+    CompoundShapeContactGeneratorWorkspace, ContactGenerator, ContactPhase,
+    HeightFieldShapeContactGeneratorWorkspace, PrimitiveContactGenerator,
+    RoundShapeContactGeneratorWorkspace, TrimeshShapeContactGeneratorWorkspace,
+    VoxelsShapeContactGeneratorWorkspace,
 };
+#[cfg(feature = "dim3")]
+use crate::geometry::PolyhedralShape;
 use crate::geometry::Shape;
 use std::any::Any;
 
@@ -61,7 +67,18 @@ impl ContactDispatcher for DefaultContactDispatcher {
                 },
                 None,
             ),
-            (Shape::Capsule(_), Shape::Capsule(_)) => (
+            #[cfg(feature = "dim2")]
+            (Shape::Segment(_), Shape::Polygon(_)) | (Shape::Polygon(_), Shape::Segment(_)) => (
+                PrimitiveContactGenerator {
+                    generate_contacts: super::generate_contacts_polygon_polygon,
+                    ..PrimitiveContactGenerator::default()
+                },
+                None,
+            ),
+            (Shape::Capsule(_), Shape::Capsule(_))
+            | (Shape::Capsule(_), Shape::Segment(_))
+            | (Shape::Segment(_), Shape::Capsule(_))
+            | (Shape::Segment(_), Shape::Segment(_)) => (
                 PrimitiveContactGenerator {
                     generate_contacts: super::generate_contacts_capsule_capsule,
                     ..PrimitiveContactGenerator::default()
@@ -73,14 +90,32 @@ impl ContactDispatcher for DefaultContactDispatcher {
             | (Shape::Triangle(_), Shape::Ball(_))
             | (Shape::Ball(_), Shape::Triangle(_))
             | (Shape::Capsule(_), Shape::Ball(_))
-            | (Shape::Ball(_), Shape::Capsule(_)) => (
+            | (Shape::Ball(_), Shape::Capsule(_))
+            | (Shape::Segment(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::Segment(_)) => (
+                PrimitiveContactGenerator {
+                    generate_contacts: super::generate_contacts_ball_convex,
+                    ..PrimitiveContactGenerator::default()
+                },
+                None,
+            ),
+            #[cfg(feature = "dim3")]
+            (Shape::ConvexPolyhedron(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::ConvexPolyhedron(_))
+            | (Shape::Cylinder(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::Cylinder(_))
+            | (Shape::Cone(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::Cone(_)) => (
                 PrimitiveContactGenerator {
                     generate_contacts: super::generate_contacts_ball_convex,
                     ..PrimitiveContactGenerator::default()
                 },
                 None,
             ),
-            (Shape::Capsule(_), Shape::Cuboid(_)) | (Shape::Cuboid(_), Shape::Capsule(_)) => (
+            (Shape::Capsule(_), Shape::Cuboid(_))
+            | (Shape::Cuboid(_), Shape::Capsule(_))
+            | (Shape::Segment(_), Shape::Cuboid(_))
+            | (Shape::Cuboid(_), Shape::Segment(_)) => (
                 PrimitiveContactGenerator {
                     generate_contacts: super::generate_contacts_cuboid_capsule,
                     ..PrimitiveContactGenerator::default()
@@ -94,6 +129,37 @@ impl ContactDispatcher for DefaultContactDispatcher {
                 },
                 None,
             ),
+            #[cfg(feature = "dim3")]
+            (Shape::ConvexPolyhedron(_), _)
+            | (_, Shape::ConvexPolyhedron(_))
+            | (Shape::Cylinder(_), _)
+            | (_, Shape::Cylinder(_))
+            | (Shape::Cone(_), _)
+            | (_, Shape::Cone(_))
+                if PolyhedralShape::from_shape(shape1).is_some()
+                    && PolyhedralShape::from_shape(shape2).is_some() =>
+            {
+                (
+                    PrimitiveContactGenerator {
+                        generate_contacts: super::generate_contacts_convex_polyhedron,
+                        ..PrimitiveContactGenerator::default()
+                    },
+                    None,
+                )
+            }
+            (Shape::RoundShape(_), _) | (_, Shape::RoundShape(_)) => {
+                let (generator, workspace) =
+                    self.dispatch_primitives(shape1.without_border().0, shape2.without_border().0);
+                let workspace = RoundShapeContactGeneratorWorkspace::new(generator, workspace);
+
+                (
+                    PrimitiveContactGenerator {
+                        generate_contacts: super::generate_contacts_round_shape,
+                        ..PrimitiveContactGenerator::default()
+                    },
+                    Some(Box::new(workspace)),
+                )
+            }
             _ => (PrimitiveContactGenerator::default(), None),
         }
     }
@@ -104,6 +170,20 @@ impl ContactDispatcher for DefaultContactDispatcher {
         shape2: &Shape,
     ) -> (ContactPhase, Option<Box<dyn Any + Send + Sync>>) {
         match (shape1, shape2) {
+            (Shape::Compound(_), _) | (_, Shape::Compound(_)) => (
+                ContactPhase::NearPhase(ContactGenerator {
+                    generate_contacts: super::generate_contacts_compound_shape,
+                    ..ContactGenerator::default()
+                }),
+                Some(Box::new(CompoundShapeContactGeneratorWorkspace::new())),
+            ),
+            (Shape::Voxels(_), _) | (_, Shape::Voxels(_)) => (
+                ContactPhase::NearPhase(ContactGenerator {
+                    generate_contacts: super::generate_contacts_voxels_shape,
+                    ..ContactGenerator::default()
+                }),
+                Some(Box::new(VoxelsShapeContactGeneratorWorkspace::new())),
+            ),
             (Shape::Trimesh(_), _) | (_, Shape::Trimesh(_)) => (
                 ContactPhase::NearPhase(ContactGenerator {
                     generate_contacts: super::generate_contacts_trimesh_shape,
@@ -111,6 +191,14 @@ impl ContactDispatcher for DefaultContactDispatcher {
                 }),
                 Some(Box::new(TrimeshShapeContactGeneratorWorkspace::new())),
             ),
+            #[cfg(feature = "dim2")]
+            (Shape::Polyline(_), _) | (_, Shape::Polyline(_)) => (
+                ContactPhase::NearPhase(ContactGenerator {
+                    generate_contacts: super::generate_contacts_polyline_shape,
+                    ..ContactGenerator::default()
+                }),
+                Some(Box::new(PolylineShapeContactGeneratorWorkspace::new())),
+            ),
             (Shape::HeightField(_), _) | (_, Shape::HeightField(_)) => (
                 ContactPhase::NearPhase(ContactGenerator {
                     generate_contacts: super::generate_contacts_heightfield_shape,
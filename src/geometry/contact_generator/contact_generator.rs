@@ -1,10 +1,11 @@
+use crate::dynamics::RigidBodySet;
 use crate::geometry::{
     Collider, ColliderSet, ContactDispatcher, ContactEvent, ContactManifold, ContactPair, Shape,
 };
 use crate::math::Isometry;
 #[cfg(feature = "simd-is-enabled")]
 use crate::math::{SimdFloat, SIMD_WIDTH};
-use crate::pipeline::EventHandler;
+use crate::pipeline::{EventHandler, PhysicsHooks};
 use std::any::Any;
 
 #[derive(Copy, Clone)]
@@ -18,9 +19,11 @@ impl ContactPhase {
     pub fn generate_contacts(
         self,
         mut context: ContactGenerationContext,
+        bodies: &RigidBodySet,
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
-        let had_contacts_before = context.pair.has_any_active_contact();
+        let had_contacts_before = context.pair.has_narrow_phase_contact;
 
         match self {
             Self::NearPhase(gen) => (gen.generate_contacts)(&mut context),
@@ -44,7 +47,15 @@ impl ContactPhase {
             }
         }
 
-        if had_contacts_before != context.pair.has_any_active_contact() {
+        // The contact events are based on the contacts computed by the narrow-phase, not on
+        // the ones modified by the physics hooks.
+        context.pair.has_narrow_phase_contact = context.pair.has_any_active_contact();
+
+        context
+            .pair
+            .modify_solver_contacts(context.colliders, bodies, hooks);
+
+        if had_contacts_before != context.pair.has_narrow_phase_contact {
             if had_contacts_before {
                 events.handle_contact_event(ContactEvent::Stopped(
                     context.pair.pair.collider1,
@@ -69,7 +80,7 @@ impl ContactPhase {
         let mut had_contacts_before = [false; SIMD_WIDTH];
 
         for (i, pair) in context.pairs.iter().enumerate() {
-            had_contacts_before[i] = pair.has_any_active_contact()
+            had_contacts_before[i] = pair.has_narrow_phase_contact
         }
 
         match self {
@@ -117,8 +128,10 @@ impl ContactPhase {
             }
         }
 
-        for (i, pair) in context.pairs.iter().enumerate() {
-            if had_contacts_before[i] != pair.has_any_active_contact() {
+        for (i, pair) in context.pairs.iter_mut().enumerate() {
+            pair.has_narrow_phase_contact = pair.has_any_active_contact();
+
+            if had_contacts_before[i] != pair.has_narrow_phase_contact {
                 if had_contacts_before[i] {
                     events.handle_contact_event(ContactEvent::Stopped(
                         pair.pair.collider1,
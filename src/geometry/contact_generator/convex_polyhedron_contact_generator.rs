@@ -0,0 +1,214 @@
+use crate::geometry::contact_generator::PrimitiveContactGenerationContext;
+use crate::geometry::{
+    Contact, ContactManifold, KinematicsCategory, PolyhedralShape, PolyhedronFace,
+};
+use crate::math::Isometry;
+use ncollide::query;
+
+pub fn generate_contacts_convex_polyhedron(ctxt: &mut PrimitiveContactGenerationContext) {
+    let shape1 = PolyhedralShape::from_shape(ctxt.shape1);
+    let shape2 = PolyhedralShape::from_shape(ctxt.shape2);
+
+    if let (Some((shape1, radius1)), Some((shape2, radius2))) = (shape1, shape2) {
+        generate_contacts(
+            ctxt.prediction_distance,
+            &shape1,
+            radius1,
+            &shape2,
+            radius2,
+            &(ctxt.position1.inverse() * ctxt.position2),
+            ctxt.manifold,
+        );
+        ctxt.manifold.update_warmstart_multiplier();
+    }
+
+    ctxt.manifold.sort_contacts(ctxt.prediction_distance);
+}
+
+fn generate_contacts<'a>(
+    prediction_distance: f32,
+    shape1: &PolyhedralShape<'a>,
+    radius1: f32,
+    shape2: &PolyhedralShape<'a>,
+    radius2: f32,
+    pos12: &Isometry<f32>,
+    manifold: &mut ContactManifold,
+) {
+    let pos21 = pos12.inverse();
+
+    if manifold.try_update_contacts(pos12) {
+        return;
+    }
+
+    /*
+     *
+     * Compute the separating axis with GJK/EPA on the polyhedral cores.
+     *
+     */
+    let radius = radius1 + radius2;
+    let contact = query::contact_support_map_support_map(
+        &Isometry::identity(),
+        shape1,
+        pos12,
+        shape2,
+        prediction_distance + radius,
+    );
+
+    let contact = match contact {
+        Some(contact) => contact,
+        None => {
+            manifold.points.clear();
+            return;
+        }
+    };
+
+    let local_n1 = contact.normal.into_inner();
+    let local_n2 = pos21 * -local_n1;
+
+    /*
+     *
+     * Clip the support faces along the separating axis.
+     *
+     */
+    let feature1 = shape1.support_face(local_n1);
+    let mut feature2 = shape2.support_face(local_n2);
+    feature2.transform_by(pos12);
+
+    // We do this clone to perform contact tracking and transfer impulses.
+    // FIXME: find a more efficient way of doing this.
+    let old_manifold_points = manifold.points.clone();
+    manifold.points.clear();
+
+    PolyhedronFace::contacts(
+        prediction_distance + radius,
+        &feature1,
+        &local_n1,
+        &feature2,
+        &pos21,
+        manifold,
+    );
+
+    if manifold.points.is_empty() {
+        // This happens, e.g., with vertex-vertex contacts. Use the closest points instead.
+        manifold.points.push(Contact::new(
+            contact.world1,
+            pos21 * contact.world2,
+            feature1.fid,
+            feature2.fid,
+            -contact.depth,
+        ));
+    }
+
+    // Adjust points to take the radii into account.
+    for point in &mut manifold.points {
+        point.local_p1 += local_n1 * radius1;
+        point.local_p2 += local_n2 * radius2;
+        point.dist -= radius;
+    }
+
+    manifold.local_n1 = local_n1;
+    manifold.local_n2 = local_n2;
+    manifold.kinematics.category = KinematicsCategory::PlanePoint;
+    manifold.kinematics.radius1 = 0.0;
+    manifold.kinematics.radius2 = 0.0;
+
+    // Transfer impulses.
+    super::match_contacts(manifold, &old_manifold_points, false);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{IntegrationParameters, JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, Cone, Cylinder, NarrowPhase};
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::PhysicsPipeline;
+
+    /// Drops a body with the given collider on a static cuboid with its top face at `y = 0`, and
+    /// returns the position of the body once it is at rest.
+    fn rest_on_cuboid(collider: ColliderBuilder, position: Isometry<f32>) -> Isometry<f32> {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        let ground = bodies.insert(
+            RigidBodyBuilder::new_static()
+                .translation(0.0, -0.5, 0.0)
+                .build(),
+        );
+        let ground_collider = ColliderBuilder::cuboid(5.0, 0.5, 5.0).build();
+        colliders.insert(ground_collider, ground, &mut bodies);
+
+        let body = RigidBodyBuilder::new_dynamic().position(position).build();
+        let body = bodies.insert(body);
+        colliders.insert(collider.build(), body, &mut bodies);
+
+        for _ in 0..180 {
+            pipeline.step(
+                &(Vector::y() * -9.81),
+                &IntegrationParameters::default(),
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &(),
+                &(),
+            );
+        }
+
+        assert!(bodies[body].linvel.norm() < 1.0e-2);
+        assert!(bodies[body].angvel.norm() < 1.0e-2);
+        bodies[body].position
+    }
+
+    #[test]
+    fn cylinder_rests_on_cuboid() {
+        let standing = Isometry::translation(0.0, 0.55, 0.0);
+        let pos = rest_on_cuboid(ColliderBuilder::cylinder(0.5, 0.3), standing);
+        assert!((pos.translation.vector.y - 0.5).abs() < 1.0e-2);
+        assert!(pos.rotation.angle() < 1.0e-2);
+
+        let lying = Isometry::new(
+            Vector::new(0.0, 0.35, 0.0),
+            Vector::z() * std::f32::consts::FRAC_PI_2,
+        );
+        let pos = rest_on_cuboid(ColliderBuilder::cylinder(0.5, 0.3), lying);
+        assert!((pos.translation.vector.y - 0.3).abs() < 1.0e-2);
+        assert!((pos.rotation.angle() - std::f32::consts::FRAC_PI_2).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn cone_rests_on_cuboid() {
+        let standing = Isometry::translation(0.0, 0.55, 0.0);
+        let pos = rest_on_cuboid(ColliderBuilder::cone(0.5, 0.3), standing);
+        assert!((pos.translation.vector.y - 0.5).abs() < 1.0e-2);
+        assert!(pos.rotation.angle() < 1.0e-2);
+    }
+
+    #[test]
+    fn cylinder_and_cone_support_faces_have_distinct_feature_ids() {
+        let dirs = [
+            Vector::y(),
+            -Vector::y(),
+            Vector::x(),
+            Vector::new(1.0, 0.2, -0.3),
+            Vector::new(-0.3, -0.2, 1.0),
+        ];
+
+        for dir in &dirs {
+            let faces = [
+                Cylinder::new(0.5, 0.3).support_face(*dir),
+                Cone::new(0.5, 0.3).support_face(*dir),
+            ];
+
+            for face in &faces {
+                assert!(!face.vids.contains(&face.fid));
+                assert!(!face.eids.contains(&face.fid));
+                assert!(face.vids.iter().all(|vid| !face.eids.contains(vid)));
+            }
+        }
+    }
+}
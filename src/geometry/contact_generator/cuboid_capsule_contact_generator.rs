@@ -9,23 +9,26 @@ use crate::math::Vector;
 use ncollide::shape::Segment;
 
 pub fn generate_contacts_cuboid_capsule(ctxt: &mut PrimitiveContactGenerationContext) {
-    if let (Shape::Cuboid(cube1), Shape::Capsule(capsule2)) = (ctxt.shape1, ctxt.shape2) {
+    if let (Shape::Cuboid(cube1), Some(capsule2)) = (ctxt.shape1, Capsule::from_shape(ctxt.shape2))
+    {
         generate_contacts(
             ctxt.prediction_distance,
             cube1,
             ctxt.position1,
-            capsule2,
+            &capsule2,
             ctxt.position2,
             ctxt.manifold,
             false,
         );
         ctxt.manifold.update_warmstart_multiplier();
-    } else if let (Shape::Capsule(capsule1), Shape::Cuboid(cube2)) = (ctxt.shape1, ctxt.shape2) {
+    } else if let (Some(capsule1), Shape::Cuboid(cube2)) =
+        (Capsule::from_shape(ctxt.shape1), ctxt.shape2)
+    {
         generate_contacts(
             ctxt.prediction_distance,
             cube2,
             ctxt.position2,
-            capsule1,
+            &capsule1,
             ctxt.position1,
             ctxt.manifold,
             true,
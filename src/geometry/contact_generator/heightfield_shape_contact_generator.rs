@@ -1,12 +1,15 @@
 use crate::geometry::contact_generator::{
     ContactGenerationContext, PrimitiveContactGenerationContext, PrimitiveContactGenerator,
 };
+#[cfg(feature = "dim3")]
+use crate::geometry::Triangle;
+#[cfg(feature = "dim2")]
+use crate::geometry::{polyline, Segment};
+use crate::geometry::{Collider, ColliderHandle, ContactManifold, HeightField, Shape};
+use crate::math::Point;
 #[cfg(feature = "dim2")]
-use crate::geometry::Capsule;
-use crate::geometry::{Collider, ContactManifold, HeightField, Shape};
+use crate::math::Vector;
 use crate::ncollide::bounding_volume::BoundingVolume;
-#[cfg(feature = "dim3")]
-use crate::{geometry::Triangle, math::Point};
 use std::any::Any;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
@@ -39,18 +42,21 @@ pub fn generate_contacts_heightfield_shape(ctxt: &mut ContactGenerationContext)
     let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
 
     if let Shape::HeightField(heightfield1) = collider1.shape() {
-        do_generate_contacts(heightfield1, collider1, collider2, ctxt, false)
+        let handle1 = ctxt.pair.pair.collider1;
+        do_generate_contacts(heightfield1, collider1, handle1, collider2, ctxt, false)
     } else if let Shape::HeightField(heightfield2) = collider2.shape() {
-        do_generate_contacts(heightfield2, collider2, collider1, ctxt, true)
+        let handle2 = ctxt.pair.pair.collider2;
+        do_generate_contacts(heightfield2, collider2, handle2, collider1, ctxt, true)
     }
 }
 
 fn do_generate_contacts(
     heightfield1: &HeightField,
     collider1: &Collider,
+    handle1: ColliderHandle,
     collider2: &Collider,
     ctxt: &mut ContactGenerationContext,
-    _flipped: bool,
+    flipped: bool,
 ) {
     let workspace: &mut HeightFieldShapeContactGeneratorWorkspace = ctxt
         .pair
@@ -66,20 +72,15 @@ fn do_generate_contacts(
     if !ctxt.pair.manifolds.is_empty() && workspace.sub_detectors.is_empty() {
         // Rebuild the subdetector hashmap.
         for (manifold_id, manifold) in ctxt.pair.manifolds.iter().enumerate() {
-            let subshape_id = if manifold.pair.collider1 == ctxt.pair.pair.collider1 {
+            let subshape_id = if manifold.pair.collider1 == handle1 {
                 manifold.subshape_index_pair.0
             } else {
                 manifold.subshape_index_pair.1
             };
-            println!(
-                "Restoring for {} [chosen with {:?}]",
-                subshape_id, manifold.subshape_index_pair
-            );
 
             // Use dummy shapes for the dispatch.
             #[cfg(feature = "dim2")]
-            let sub_shape1 =
-                Shape::Capsule(Capsule::new(na::Point::origin(), na::Point::origin(), 0.0));
+            let sub_shape1 = Shape::Segment(Segment::new(na::Point::origin(), na::Point::origin()));
             #[cfg(feature = "dim3")]
             let sub_shape1 = Shape::Triangle(Triangle::new(
                 Point::origin(),
@@ -123,7 +124,7 @@ fn do_generate_contacts(
 
     heightfield1.map_elements_in_local_aabb(&ls_aabb2, &mut |i, part1, _| {
         #[cfg(feature = "dim2")]
-        let sub_shape1 = Shape::Capsule(Capsule::new(part1.a, part1.b, 0.0));
+        let sub_shape1 = Shape::Segment(*part1);
         #[cfg(feature = "dim3")]
         let sub_shape1 = Shape::Triangle(*part1);
         let sub_detector = match workspace.sub_detectors.entry(i) {
@@ -144,8 +145,11 @@ fn do_generate_contacts(
                     timestamp: new_timestamp,
                     workspace: workspace2,
                 };
-                let manifold =
-                    ContactManifold::with_subshape_indices(coll_pair, collider1, collider2, i, 0);
+                let manifold = if flipped {
+                    ContactManifold::with_subshape_indices(coll_pair, collider2, collider1, 0, i)
+                } else {
+                    ContactManifold::with_subshape_indices(coll_pair, collider1, collider2, i, 0)
+                };
                 manifolds.push(manifold);
 
                 entry.insert(sub_detector)
@@ -154,7 +158,7 @@ fn do_generate_contacts(
 
         let manifold = &mut manifolds[sub_detector.manifold_id];
 
-        let mut ctxt2 = if coll_pair.collider1 != manifold.pair.collider1 {
+        let mut ctxt2 = if manifold.pair.collider1 != handle1 {
             PrimitiveContactGenerationContext {
                 prediction_distance,
                 collider1: collider2,
@@ -180,10 +184,130 @@ fn do_generate_contacts(
             }
         };
 
-        (sub_detector.generator.generate_contacts)(&mut ctxt2)
+        (sub_detector.generator.generate_contacts)(&mut ctxt2);
+
+        let manifold = &mut manifolds[sub_detector.manifold_id];
+
+        // Remove the contacts at the vertices shared with the neighbor segments
+        // which are already handled by these neighbors. The sub-generator may
+        // have swapped the identifiers of the manifold.
+        #[cfg(feature = "dim2")]
+        {
+            let prev_segment = if i > 0 {
+                heightfield1.segment_at(i - 1)
+            } else {
+                None
+            };
+            let has_next_segment = heightfield1.segment_at(i + 1).is_some();
+            let len_before = manifold.points.len();
+            let is_ghost_contact = |local_point: &Point<f32>, local_normal: &Vector<f32>| {
+                polyline::is_ghost_segment_contact(
+                    part1,
+                    prev_segment.as_ref(),
+                    has_next_segment,
+                    local_point,
+                    local_normal,
+                )
+            };
+
+            if manifold.pair.collider1 == handle1 {
+                let local_n1 = manifold.local_n1;
+                manifold
+                    .points
+                    .retain(|pt| !is_ghost_contact(&pt.local_p1, &local_n1));
+            } else {
+                let local_n2 = manifold.local_n2;
+                manifold
+                    .points
+                    .retain(|pt| !is_ghost_contact(&pt.local_p2, &local_n2));
+            }
+
+            if manifold.points.len() != len_before {
+                manifold.sort_contacts(prediction_distance);
+            }
+        }
+
+        // Replace the normals pointing over the internal edges of the heightfield.
+        #[cfg(feature = "dim3")]
+        super::correct_internal_edge_normal(
+            manifold,
+            handle1,
+            collider1.position(),
+            collider2.position(),
+            part1,
+            &opposite_vertices(heightfield1, i, part1),
+            prediction_distance,
+        );
     });
 
     workspace
         .sub_detectors
         .retain(|_, detector| detector.timestamp == new_timestamp)
 }
+
+/// The vertices of the triangles adjacent to the `triangle_id`-th triangle of the heightfield,
+/// opposite to the edges `ab`, `bc`, and `ca` of that triangle.
+///
+/// The heightfield does not store any adjacency information, so the adjacent triangles are
+/// searched among the triangles of the cell of this triangle and of its four neighbor cells.
+#[cfg(feature = "dim3")]
+fn opposite_vertices(
+    heightfield: &HeightField,
+    triangle_id: usize,
+    triangle: &Triangle,
+) -> [Option<Point<f32>>; 3] {
+    let nrows = heightfield.nrows();
+    let ncols = heightfield.ncols();
+    let num_cells = nrows * ncols;
+    let cell_id = triangle_id % num_cells;
+    let left = triangle_id < num_cells;
+    let (i, j) = (cell_id % nrows, cell_id / nrows);
+
+    let cells = [
+        (i, j),
+        (i.wrapping_sub(1), j),
+        (i + 1, j),
+        (i, j.wrapping_sub(1)),
+        (i, j + 1),
+    ];
+    let mut candidates = Vec::with_capacity(10);
+
+    for (ci, cj) in cells.iter().copied() {
+        if ci < nrows && cj < ncols {
+            let (tri1, tri2) = heightfield.triangles_at(ci, cj);
+
+            if (ci, cj) != (i, j) || !left {
+                candidates.extend(tri1);
+            }
+
+            if (ci, cj) != (i, j) || left {
+                candidates.extend(tri2);
+            }
+        }
+    }
+
+    let eps = heightfield.cell_width().min(heightfield.cell_height()) * 1.0e-3;
+    let same_point = |a: &Point<f32>, b: &Point<f32>| na::distance_squared(a, b) <= eps * eps;
+    let pts = [triangle.a, triangle.b, triangle.c];
+    let mut result = [None; 3];
+
+    for k in 0..3 {
+        let (a, b) = (pts[k], pts[(k + 1) % 3]);
+
+        for candidate in &candidates {
+            let cpts = [candidate.a, candidate.b, candidate.c];
+            let shares_a = cpts.iter().any(|p| same_point(p, &a));
+            let shares_b = cpts.iter().any(|p| same_point(p, &b));
+
+            if shares_a && shares_b {
+                result[k] = cpts
+                    .iter()
+                    .find(|p| !same_point(p, &a) && !same_point(p, &b))
+                    .copied();
+                break;
+            }
+        }
+    }
+
+    result
+}
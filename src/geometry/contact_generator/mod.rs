@@ -3,6 +3,9 @@ pub use self::ball_ball_contact_generator::generate_contacts_ball_ball;
 pub use self::ball_ball_contact_generator::generate_contacts_ball_ball_simd;
 pub use self::ball_convex_contact_generator::generate_contacts_ball_convex;
 pub use self::capsule_capsule_contact_generator::generate_contacts_capsule_capsule;
+pub use self::compound_shape_contact_generator::{
+    generate_contacts_compound_shape, CompoundShapeContactGeneratorWorkspace,
+};
 pub use self::contact_dispatcher::{ContactDispatcher, DefaultContactDispatcher};
 pub use self::contact_generator::{
     ContactGenerationContext, ContactGenerator, ContactPhase, PrimitiveContactGenerationContext,
@@ -12,6 +15,8 @@ pub use self::contact_generator::{
 pub use self::contact_generator::{
     ContactGenerationContextSimd, PrimitiveContactGenerationContextSimd,
 };
+#[cfg(feature = "dim3")]
+pub use self::convex_polyhedron_contact_generator::generate_contacts_convex_polyhedron;
 pub use self::cuboid_capsule_contact_generator::generate_contacts_cuboid_capsule;
 pub use self::cuboid_cuboid_contact_generator::generate_contacts_cuboid_cuboid;
 pub use self::cuboid_triangle_contact_generator::generate_contacts_cuboid_triangle;
@@ -19,9 +24,19 @@ pub use self::heightfield_shape_contact_generator::{
     generate_contacts_heightfield_shape, HeightFieldShapeContactGeneratorWorkspace,
 };
 pub use self::polygon_polygon_contact_generator::generate_contacts_polygon_polygon;
+#[cfg(feature = "dim2")]
+pub use self::polyline_shape_contact_generator::{
+    generate_contacts_polyline_shape, PolylineShapeContactGeneratorWorkspace,
+};
+pub use self::round_shape_contact_generator::{
+    generate_contacts_round_shape, RoundShapeContactGeneratorWorkspace,
+};
 pub use self::trimesh_shape_contact_generator::{
     generate_contacts_trimesh_shape, TrimeshShapeContactGeneratorWorkspace,
 };
+pub use self::voxels_shape_contact_generator::{
+    generate_contacts_voxels_shape, VoxelsShapeContactGeneratorWorkspace,
+};
 
 #[cfg(feature = "dim2")]
 pub(crate) use self::polygon_polygon_contact_generator::{
@@ -32,17 +47,30 @@ mod ball_ball_contact_generator;
 mod ball_convex_contact_generator;
 mod ball_polygon_contact_generator;
 mod capsule_capsule_contact_generator;
+mod compound_shape_contact_generator;
 mod contact_dispatcher;
 mod contact_generator;
+#[cfg(feature = "dim3")]
+mod convex_polyhedron_contact_generator;
 mod cuboid_capsule_contact_generator;
 mod cuboid_cuboid_contact_generator;
 mod cuboid_polygon_contact_generator;
 mod cuboid_triangle_contact_generator;
 mod heightfield_shape_contact_generator;
 mod polygon_polygon_contact_generator;
+#[cfg(feature = "dim2")]
+mod polyline_shape_contact_generator;
+mod round_shape_contact_generator;
 mod trimesh_shape_contact_generator;
+mod voxels_shape_contact_generator;
 
 use crate::geometry::{Contact, ContactManifold};
+use crate::math::Isometry;
+#[cfg(feature = "dim3")]
+use crate::{
+    geometry::{triangle, ColliderHandle, Triangle},
+    math::Point,
+};
 
 pub(crate) fn match_contacts(
     manifold: &mut ContactManifold,
@@ -69,3 +97,83 @@ pub(crate) fn match_contacts(
         }
     }
 }
+
+/// Replaces the normal of `manifold` by the normal of `triangle` if it points over an internal edge
+/// of the triangle mesh (or heightfield) attached to the collider `mesh_handle`.
+///
+/// The vertices of the adjacent triangles opposite to the edges `ab`, `bc`, and `ca` of `triangle`
+/// are given by `opposite_vertices`. Correcting these normals prevents bodies sliding on the mesh
+/// from bumping on its internal edges.
+#[cfg(feature = "dim3")]
+pub(crate) fn correct_internal_edge_normal(
+    manifold: &mut ContactManifold,
+    mesh_handle: ColliderHandle,
+    mesh_pos: &Isometry<f32>,
+    other_pos: &Isometry<f32>,
+    triangle: &Triangle,
+    opposite_vertices: &[Option<Point<f32>>; 3],
+    prediction_distance: f32,
+) {
+    let mesh_is_first = manifold.pair.collider1 == mesh_handle;
+    let local_n = if mesh_is_first {
+        manifold.local_n1
+    } else {
+        manifold.local_n2
+    };
+
+    let points_over_internal_edge = manifold.points.iter().any(|pt| {
+        let local_p = if mesh_is_first {
+            pt.local_p1
+        } else {
+            pt.local_p2
+        };
+        triangle::is_internal_edge_normal(triangle, opposite_vertices, &local_p, &local_n)
+    });
+
+    if !points_over_internal_edge {
+        return;
+    }
+
+    let face_normal = match triangle.normal() {
+        Some(normal) if normal.dot(&local_n) < 0.0 => -*normal,
+        Some(normal) => *normal,
+        None => return,
+    };
+
+    let (pos12, local_n1) = if mesh_is_first {
+        (mesh_pos.inverse() * other_pos, face_normal)
+    } else {
+        let pos12 = other_pos.inverse() * mesh_pos;
+        (pos12, pos12 * -face_normal)
+    };
+
+    manifold.local_n1 = local_n1;
+    manifold.local_n2 = pos12.inverse_transform_vector(&-local_n1);
+
+    for pt in &mut manifold.points {
+        pt.dist = (pos12 * pt.local_p2 - pt.local_p1).dot(&local_n1);
+    }
+
+    manifold.sort_contacts(prediction_distance);
+}
+
+/// Transforms the contact points and normals of `manifold` attached to `collider1` by `m1`,
+/// and those attached to `collider2` by `m2`.
+///
+/// If `swapped` is `true`, the first collider of the manifold is `collider2`.
+pub(crate) fn transform_manifold(
+    manifold: &mut ContactManifold,
+    m1: &Isometry<f32>,
+    m2: &Isometry<f32>,
+    swapped: bool,
+) {
+    let (m1, m2) = if swapped { (m2, m1) } else { (m1, m2) };
+
+    manifold.local_n1 = m1 * manifold.local_n1;
+    manifold.local_n2 = m2 * manifold.local_n2;
+
+    for pt in &mut manifold.points {
+        pt.local_p1 = m1 * pt.local_p1;
+        pt.local_p2 = m2 * pt.local_p2;
+    }
+}
@@ -1,15 +1,17 @@
 use crate::geometry::contact_generator::PrimitiveContactGenerationContext;
-use crate::geometry::{sat, Contact, ContactManifold, KinematicsCategory, Polygon, Shape};
-use crate::math::{Isometry, Point};
-#[cfg(feature = "dim2")]
-use crate::{math::Vector, utils};
+use crate::geometry::{sat, Contact, ContactManifold, KinematicsCategory, Polygon};
+use crate::math::{Isometry, Point, Vector};
+use crate::utils;
 
 pub fn generate_contacts_polygon_polygon(ctxt: &mut PrimitiveContactGenerationContext) {
-    if let (Shape::Polygon(polygon1), Shape::Polygon(polygon2)) = (ctxt.shape1, ctxt.shape2) {
+    if let (Some(polygon1), Some(polygon2)) = (
+        Polygon::from_shape(ctxt.shape1),
+        Polygon::from_shape(ctxt.shape2),
+    ) {
         generate_contacts(
-            polygon1,
+            &polygon1,
             &ctxt.position1,
-            polygon2,
+            &polygon2,
             &ctxt.position2,
             ctxt.manifold,
         );
@@ -80,13 +82,13 @@ fn generate_contacts<'a>(
         let mut impulses_b = (0.0, Contact::zero_tangent_impulse());
 
         let fids_a = (
-            ((support_face1 * 2 + clip_a.2) % (len1 * 2)) as u8,
-            ((support_face2 * 2 + clip_a.3) % (len2 * 2)) as u8,
+            ((support_face1 * 2 + clip_a.2) % (len1 * 2)) as u32,
+            ((support_face2 * 2 + clip_a.3) % (len2 * 2)) as u32,
         );
 
         let fids_b = (
-            ((support_face1 * 2 + clip_b.2) % (len1 * 2)) as u8,
-            ((support_face2 * 2 + clip_b.3) % (len2 * 2)) as u8,
+            ((support_face1 * 2 + clip_b.2) % (len1 * 2)) as u32,
+            ((support_face2 * 2 + clip_b.3) % (len2 * 2)) as u32,
         );
 
         if manifold.points.len() != 0 {
@@ -137,6 +139,7 @@ fn generate_contacts<'a>(
             local_p2: m21 * clip_a.1,
             impulse: impulses_a.0,
             tangent_impulse: impulses_a.1,
+            tangent_velocity: Vector::zeros(),
             fid1: fids_a.0,
             fid2: fids_a.1,
             dist: dist_a,
@@ -147,6 +150,7 @@ fn generate_contacts<'a>(
             local_p2: m21 * clip_b.1,
             impulse: impulses_b.0,
             tangent_impulse: impulses_b.1,
+            tangent_velocity: Vector::zeros(),
             fid1: fids_b.0,
             fid2: fids_b.1,
             dist: dist_b,
@@ -267,13 +271,13 @@ pub(crate) fn clip_segments(
     let length2 = range2[1] - range2[0];
 
     let ca = if range2[0] > range1[0] {
-        let bcoord = (range2[0] - range1[0]) / length1;
+        let bcoord = (range2[0] - range1[0]) * utils::inv(length1);
         let p1 = seg1.0 + tangent1 * bcoord;
         let p2 = seg2.0;
 
         (p1, p2, 1, features2[0])
     } else {
-        let bcoord = (range1[0] - range2[0]) / length2;
+        let bcoord = (range1[0] - range2[0]) * utils::inv(length2);
         let p1 = seg1.0;
         let p2 = seg2.0 + (seg2.1 - seg2.0) * bcoord;
 
@@ -281,13 +285,13 @@ pub(crate) fn clip_segments(
     };
 
     let cb = if range2[1] < range1[1] {
-        let bcoord = (range2[1] - range1[0]) / length1;
+        let bcoord = (range2[1] - range1[0]) * utils::inv(length1);
         let p1 = seg1.0 + tangent1 * bcoord;
         let p2 = seg2.1;
 
         (p1, p2, 1, features2[1])
     } else {
-        let bcoord = (range1[1] - range2[0]) / length2;
+        let bcoord = (range1[1] - range2[0]) * utils::inv(length2);
         let p1 = seg1.1;
         let p2 = seg2.0 + (seg2.1 - seg2.0) * bcoord;
 
@@ -0,0 +1,273 @@
+use crate::geometry::contact_generator::{
+    ContactGenerationContext, PrimitiveContactGenerationContext, PrimitiveContactGenerator,
+};
+use crate::geometry::{
+    Collider, ColliderHandle, ContactManifold, Polyline, Shape, WAABBHierarchyIntersections,
+};
+use crate::ncollide::bounding_volume::BoundingVolume;
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+struct SubDetector {
+    generator: PrimitiveContactGenerator,
+    manifold_id: usize,
+    timestamp: bool,
+    workspace: Option<Box<(dyn Any + Send + Sync)>>,
+}
+
+pub struct PolylineShapeContactGeneratorWorkspace {
+    timestamp: bool,
+    interferences: WAABBHierarchyIntersections,
+    old_manifolds: Vec<ContactManifold>,
+    sub_detectors: HashMap<usize, SubDetector>,
+}
+
+impl PolylineShapeContactGeneratorWorkspace {
+    pub fn new() -> Self {
+        Self {
+            timestamp: false,
+            interferences: WAABBHierarchyIntersections::new(),
+            old_manifolds: Vec::new(),
+            sub_detectors: HashMap::default(),
+        }
+    }
+}
+
+pub fn generate_contacts_polyline_shape(ctxt: &mut ContactGenerationContext) {
+    let collider1 = &ctxt.colliders[ctxt.pair.pair.collider1];
+    let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
+
+    if let Shape::Polyline(polyline1) = collider1.shape() {
+        let handle1 = ctxt.pair.pair.collider1;
+        do_generate_contacts(polyline1, collider1, handle1, collider2, ctxt, false)
+    } else if let Shape::Polyline(polyline2) = collider2.shape() {
+        let handle2 = ctxt.pair.pair.collider2;
+        do_generate_contacts(polyline2, collider2, handle2, collider1, ctxt, true)
+    }
+}
+
+fn do_generate_contacts(
+    polyline1: &Polyline,
+    collider1: &Collider,
+    handle1: ColliderHandle,
+    collider2: &Collider,
+    ctxt: &mut ContactGenerationContext,
+    flipped: bool,
+) {
+    let workspace: &mut PolylineShapeContactGeneratorWorkspace = ctxt
+        .pair
+        .generator_workspace
+        .as_mut()
+        .expect("The PolylineShapeContactGeneratorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a PolylineShapeContactGeneratorWorkspace.");
+    let PolylineShapeContactGeneratorWorkspace {
+        timestamp,
+        interferences,
+        old_manifolds,
+        sub_detectors,
+    } = workspace;
+
+    /*
+     * Detect if the detector context has been reset.
+     */
+    if !ctxt.pair.manifolds.is_empty() && sub_detectors.is_empty() {
+        // The manifolds can no longer be matched with their sub-detectors
+        // so we have to start from scratch.
+        ctxt.pair.manifolds.clear();
+    }
+
+    let new_timestamp = !*timestamp;
+    *timestamp = new_timestamp;
+
+    /*
+     * Compute interferences.
+     */
+    let pos12 = collider1.position.inverse() * collider2.position;
+    // TODO: somehow precompute the AABB and reuse it?
+    let ls_aabb2 = collider2
+        .shape()
+        .compute_aabb(&pos12)
+        .loosened(ctxt.prediction_distance);
+    polyline1
+        .waabbs()
+        .compute_interferences_with(ls_aabb2, interferences);
+
+    std::mem::swap(old_manifolds, &mut ctxt.pair.manifolds);
+    ctxt.pair.manifolds.clear();
+    let coll_pair = ctxt.pair.pair;
+    let manifolds = &mut ctxt.pair.manifolds;
+    let prediction_distance = ctxt.prediction_distance;
+    let dispatcher = ctxt.dispatcher;
+
+    for segment_id in interferences.computed_interferences() {
+        if *segment_id >= polyline1.num_segments() {
+            // Because of SIMD padding, the hierarchy may return segment indices greater
+            // than the max.
+            continue;
+        }
+
+        let segment1 = Shape::Segment(polyline1.segment(*segment_id));
+
+        let sub_detector = match sub_detectors.entry(*segment_id) {
+            Entry::Occupied(entry) => {
+                let sub_detector = entry.into_mut();
+                let manifold = old_manifolds[sub_detector.manifold_id].take();
+                sub_detector.manifold_id = manifolds.len();
+                sub_detector.timestamp = new_timestamp;
+                manifolds.push(manifold);
+                sub_detector
+            }
+            Entry::Vacant(entry) => {
+                let (generator, workspace2) =
+                    dispatcher.dispatch_primitives(&segment1, collider2.shape());
+                let sub_detector = SubDetector {
+                    generator,
+                    manifold_id: manifolds.len(),
+                    timestamp: new_timestamp,
+                    workspace: workspace2,
+                };
+                let manifold = if flipped {
+                    ContactManifold::with_subshape_indices(
+                        coll_pair,
+                        collider2,
+                        collider1,
+                        0,
+                        *segment_id,
+                    )
+                } else {
+                    ContactManifold::with_subshape_indices(
+                        coll_pair,
+                        collider1,
+                        collider2,
+                        *segment_id,
+                        0,
+                    )
+                };
+                manifolds.push(manifold);
+
+                entry.insert(sub_detector)
+            }
+        };
+
+        let manifold = &mut manifolds[sub_detector.manifold_id];
+
+        let mut ctxt2 = if manifold.pair.collider1 != handle1 {
+            PrimitiveContactGenerationContext {
+                prediction_distance,
+                collider1: collider2,
+                collider2: collider1,
+                shape1: collider2.shape(),
+                shape2: &segment1,
+                position1: collider2.position(),
+                position2: collider1.position(),
+                manifold,
+                workspace: sub_detector.workspace.as_deref_mut(),
+            }
+        } else {
+            PrimitiveContactGenerationContext {
+                prediction_distance,
+                collider1,
+                collider2,
+                shape1: &segment1,
+                shape2: collider2.shape(),
+                position1: collider1.position(),
+                position2: collider2.position(),
+                manifold,
+                workspace: sub_detector.workspace.as_deref_mut(),
+            }
+        };
+
+        (sub_detector.generator.generate_contacts)(&mut ctxt2);
+
+        // Remove the contacts at the vertices shared with the neighbor segments
+        // which are already handled by these neighbors. The sub-generator may
+        // have swapped the identifiers of the manifold.
+        let manifold = &mut manifolds[sub_detector.manifold_id];
+        let len_before = manifold.points.len();
+
+        if manifold.pair.collider1 == handle1 {
+            let local_n1 = manifold.local_n1;
+            manifold
+                .points
+                .retain(|pt| !polyline1.is_ghost_contact(*segment_id, &pt.local_p1, &local_n1));
+        } else {
+            let local_n2 = manifold.local_n2;
+            manifold
+                .points
+                .retain(|pt| !polyline1.is_ghost_contact(*segment_id, &pt.local_p2, &local_n2));
+        }
+
+        if manifold.points.len() != len_before {
+            manifold.sort_contacts(prediction_distance);
+        }
+    }
+
+    sub_detectors.retain(|_, detector| detector.timestamp == new_timestamp)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{IntegrationParameters, JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsPipeline;
+
+    #[test]
+    fn box_slides_across_the_internal_vertices_of_a_flat_polyline() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        // A flat floor made of unit segments.
+        let vertices = (-5..=5).map(|i| Point::new(i as f32, 0.0)).collect();
+        let floor = bodies.insert(RigidBodyBuilder::new_static().build());
+        let floor_collider = ColliderBuilder::polyline(vertices, None)
+            .friction(0.0)
+            .build();
+        colliders.insert(floor_collider, floor, &mut bodies);
+
+        let body = RigidBodyBuilder::new_dynamic()
+            .translation(-3.0, 0.5)
+            .linvel(5.0, 0.0)
+            .can_sleep(false)
+            .build();
+        let body = bodies.insert(body);
+        let collider = ColliderBuilder::cuboid(0.5, 0.5).friction(0.0).build();
+        colliders.insert(collider, body, &mut bodies);
+
+        for _ in 0..60 {
+            pipeline.step(
+                &(Vector::y() * -9.81),
+                &IntegrationParameters::default(),
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &(),
+                &(),
+            );
+
+            for (_, _, pair) in nf.contact_graph().interaction_pairs() {
+                for manifold in &pair.manifolds {
+                    if manifold.num_active_contacts() > 0 {
+                        let pos1 = colliders[manifold.pair.collider1].position();
+                        let normal = pos1 * manifold.local_n1;
+                        assert!(normal.y.abs() > 0.999);
+                    }
+                }
+            }
+
+            assert!(bodies[body].linvel.y.abs() < 0.1);
+            assert!((bodies[body].linvel.x - 5.0).abs() < 0.1);
+        }
+
+        assert!(bodies[body].position.translation.vector.x > 1.0);
+        assert!((bodies[body].position.translation.vector.y - 0.5).abs() < 1.0e-2);
+    }
+}
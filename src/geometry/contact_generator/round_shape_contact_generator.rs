@@ -0,0 +1,77 @@
+use crate::geometry::contact_generator::{
+    PrimitiveContactGenerationContext, PrimitiveContactGenerator,
+};
+use crate::geometry::ContactManifold;
+use std::any::Any;
+
+pub struct RoundShapeContactGeneratorWorkspace {
+    generator: PrimitiveContactGenerator,
+    workspace: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl RoundShapeContactGeneratorWorkspace {
+    pub fn new(
+        generator: PrimitiveContactGenerator,
+        workspace: Option<Box<dyn Any + Send + Sync>>,
+    ) -> Self {
+        Self {
+            generator,
+            workspace,
+        }
+    }
+}
+
+pub fn generate_contacts_round_shape(ctxt: &mut PrimitiveContactGenerationContext) {
+    let (shape1, border_radius1) = ctxt.shape1.without_border();
+    let (shape2, border_radius2) = ctxt.shape2.without_border();
+    let workspace: &mut RoundShapeContactGeneratorWorkspace = ctxt
+        .workspace
+        .as_mut()
+        .expect("The RoundShapeContactGeneratorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a RoundShapeContactGeneratorWorkspace.");
+
+    // The sub-generator works on contacts between the base shapes, whereas the
+    // solver expects contacts on the boundaries of the dilated shapes.
+    let pair = ctxt.manifold.pair;
+    dilate_manifold(ctxt.manifold, -border_radius1, -border_radius2);
+
+    let mut ctxt2 = PrimitiveContactGenerationContext {
+        prediction_distance: ctxt.prediction_distance + border_radius1 + border_radius2,
+        collider1: ctxt.collider1,
+        collider2: ctxt.collider2,
+        shape1,
+        shape2,
+        position1: ctxt.position1,
+        position2: ctxt.position2,
+        manifold: &mut *ctxt.manifold,
+        workspace: workspace.workspace.as_deref_mut(),
+    };
+
+    (workspace.generator.generate_contacts)(&mut ctxt2);
+
+    // The sub-generator may have swapped the identifiers of the manifold.
+    if ctxt.manifold.pair == pair {
+        dilate_manifold(ctxt.manifold, border_radius1, border_radius2);
+    } else {
+        dilate_manifold(ctxt.manifold, border_radius2, border_radius1);
+    }
+
+    ctxt.manifold.sort_contacts(ctxt.prediction_distance);
+}
+
+/// Moves the contact points of `manifold` along the contact normals, by `radius1` on the first
+/// shape and by `radius2` on the second shape, and adds the radii to the contact kinematics.
+fn dilate_manifold(manifold: &mut ContactManifold, radius1: f32, radius2: f32) {
+    let shift1 = manifold.local_n1 * radius1;
+    let shift2 = manifold.local_n2 * radius2;
+
+    for pt in &mut manifold.points {
+        pt.local_p1 += shift1;
+        pt.local_p2 += shift2;
+        pt.dist -= radius1 + radius2;
+    }
+
+    manifold.kinematics.radius1 += radius1;
+    manifold.kinematics.radius2 += radius2;
+}
@@ -1,7 +1,9 @@
 use crate::geometry::contact_generator::{
     ContactGenerationContext, PrimitiveContactGenerationContext,
 };
-use crate::geometry::{Collider, ContactManifold, Shape, Trimesh, WAABBHierarchyIntersections};
+use crate::geometry::{
+    Collider, ColliderHandle, ContactManifold, Shape, Trimesh, WAABBHierarchyIntersections,
+};
 use crate::ncollide::bounding_volume::{BoundingVolume, AABB};
 
 pub struct TrimeshShapeContactGeneratorWorkspace {
@@ -27,15 +29,18 @@ pub fn generate_contacts_trimesh_shape(ctxt: &mut ContactGenerationContext) {
     let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
 
     if let Shape::Trimesh(trimesh1) = collider1.shape() {
-        do_generate_contacts(trimesh1, collider1, collider2, ctxt, false)
+        let handle1 = ctxt.pair.pair.collider1;
+        do_generate_contacts(trimesh1, collider1, handle1, collider2, ctxt, false)
     } else if let Shape::Trimesh(trimesh2) = collider2.shape() {
-        do_generate_contacts(trimesh2, collider2, collider1, ctxt, true)
+        let handle2 = ctxt.pair.pair.collider2;
+        do_generate_contacts(trimesh2, collider2, handle2, collider1, ctxt, true)
     }
 }
 
 fn do_generate_contacts(
     trimesh1: &Trimesh,
     collider1: &Collider,
+    handle1: ColliderHandle,
     collider2: &Collider,
     ctxt: &mut ContactGenerationContext,
     flipped: bool,
@@ -158,12 +163,13 @@ fn do_generate_contacts(
         }
 
         let manifold = &mut ctxt.pair.manifolds[i];
-        let triangle1 = Shape::Triangle(trimesh1.triangle(*triangle_id));
+        let triangle = trimesh1.triangle(*triangle_id);
+        let triangle1 = Shape::Triangle(triangle);
         let (generator, mut workspace2) = ctxt
             .dispatcher
             .dispatch_primitives(&triangle1, collider2.shape());
 
-        let mut ctxt2 = if ctxt.pair.pair.collider1 != manifold.pair.collider1 {
+        let mut ctxt2 = if manifold.pair.collider1 != handle1 {
             PrimitiveContactGenerationContext {
                 prediction_distance: ctxt.prediction_distance,
                 collider1: collider2,
@@ -190,5 +196,89 @@ fn do_generate_contacts(
         };
 
         (generator.generate_contacts)(&mut ctxt2);
+
+        #[cfg(feature = "dim3")]
+        super::correct_internal_edge_normal(
+            &mut ctxt.pair.manifolds[i],
+            handle1,
+            collider1.position(),
+            collider2.position(),
+            &triangle,
+            &trimesh1.opposite_vertices(*triangle_id),
+            ctxt.prediction_distance,
+        );
+    }
+}
+
+#[cfg(all(test, feature = "dim3"))]
+mod test {
+    use crate::dynamics::{IntegrationParameters, JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsPipeline;
+    use na::Point3;
+
+    #[test]
+    fn box_slides_across_the_internal_edge_of_coplanar_triangles() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        // A square floor made of two triangles sharing its diagonal.
+        let vertices = vec![
+            Point::new(-5.0, 0.0, -5.0),
+            Point::new(5.0, 0.0, -5.0),
+            Point::new(5.0, 0.0, 5.0),
+            Point::new(-5.0, 0.0, 5.0),
+        ];
+        let indices = vec![Point3::new(0, 3, 2), Point3::new(0, 2, 1)];
+        let floor = bodies.insert(RigidBodyBuilder::new_static().build());
+        let floor_collider = ColliderBuilder::trimesh(vertices, indices)
+            .friction(0.0)
+            .build();
+        colliders.insert(floor_collider, floor, &mut bodies);
+
+        // The box crosses the diagonal at `x = 0.5` while sliding along the `x` axis.
+        let body = RigidBodyBuilder::new_dynamic()
+            .translation(-3.0, 0.5, 0.5)
+            .linvel(5.0, 0.0, 0.0)
+            .can_sleep(false)
+            .build();
+        let body = bodies.insert(body);
+        let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5).friction(0.0).build();
+        colliders.insert(collider, body, &mut bodies);
+
+        for _ in 0..60 {
+            pipeline.step(
+                &(Vector::y() * -9.81),
+                &IntegrationParameters::default(),
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &(),
+                &(),
+            );
+
+            for (_, _, pair) in nf.contact_graph().interaction_pairs() {
+                for manifold in &pair.manifolds {
+                    if manifold.num_active_contacts() > 0 {
+                        let pos1 = colliders[manifold.pair.collider1].position();
+                        let normal = pos1 * manifold.local_n1;
+                        assert!(normal.y.abs() > 0.999);
+                    }
+                }
+            }
+
+            assert!(bodies[body].linvel.y.abs() < 0.1);
+            assert!((bodies[body].linvel.x - 5.0).abs() < 0.1);
+        }
+
+        assert!(bodies[body].position.translation.vector.x > 0.5);
+        assert!((bodies[body].position.translation.vector.y - 0.5).abs() < 1.0e-2);
     }
 }
@@ -0,0 +1,362 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::contact_generator::{
+    ContactGenerationContext, PrimitiveContactGenerationContext, PrimitiveContactGenerator,
+};
+use crate::geometry::{
+    Collider, ColliderHandle, ContactManifold, Shape, Voxels, WAABBHierarchyIntersections,
+};
+use crate::math::{Isometry, Point};
+use crate::ncollide::bounding_volume::BoundingVolume;
+use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+struct SubDetector {
+    generator: PrimitiveContactGenerator,
+    manifold_id: usize,
+    timestamp: bool,
+    workspace: Option<Box<(dyn Any + Send + Sync)>>,
+}
+
+pub struct VoxelsShapeContactGeneratorWorkspace {
+    timestamp: bool,
+    interferences2: WAABBHierarchyIntersections,
+    old_manifolds: Vec<ContactManifold>,
+    // The sub-detectors are identified by the key of the voxel in addition to its id because
+    // the ids of the removed voxels are reused by the voxels inserted afterward.
+    sub_detectors: HashMap<(Point<i32>, usize, usize), SubDetector>,
+}
+
+impl VoxelsShapeContactGeneratorWorkspace {
+    pub fn new() -> Self {
+        Self {
+            timestamp: false,
+            interferences2: WAABBHierarchyIntersections::new(),
+            old_manifolds: Vec::new(),
+            sub_detectors: HashMap::default(),
+        }
+    }
+}
+
+pub fn generate_contacts_voxels_shape(ctxt: &mut ContactGenerationContext) {
+    let collider1 = &ctxt.colliders[ctxt.pair.pair.collider1];
+    let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
+
+    if let Shape::Voxels(voxels1) = collider1.shape() {
+        let handle1 = ctxt.pair.pair.collider1;
+        do_generate_contacts(voxels1, collider1, handle1, collider2, ctxt, false)
+    } else if let Shape::Voxels(voxels2) = collider2.shape() {
+        let handle2 = ctxt.pair.pair.collider2;
+        do_generate_contacts(voxels2, collider2, handle2, collider1, ctxt, true)
+    }
+}
+
+fn do_generate_contacts(
+    voxels1: &Voxels,
+    collider1: &Collider,
+    handle1: ColliderHandle,
+    collider2: &Collider,
+    ctxt: &mut ContactGenerationContext,
+    flipped: bool,
+) {
+    let workspace: &mut VoxelsShapeContactGeneratorWorkspace = ctxt
+        .pair
+        .generator_workspace
+        .as_mut()
+        .expect("The VoxelsShapeContactGeneratorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a VoxelsShapeContactGeneratorWorkspace.");
+    let VoxelsShapeContactGeneratorWorkspace {
+        timestamp,
+        interferences2,
+        old_manifolds,
+        sub_detectors,
+    } = workspace;
+
+    /*
+     * Detect if the detector context has been reset.
+     */
+    if !ctxt.pair.manifolds.is_empty() && sub_detectors.is_empty() {
+        // The manifolds can no longer be matched with their sub-detectors
+        // so we have to start from scratch.
+        ctxt.pair.manifolds.clear();
+    }
+
+    let new_timestamp = !*timestamp;
+    *timestamp = new_timestamp;
+
+    /*
+     * Compute interferences.
+     */
+    let pos12 = collider1.position.inverse() * collider2.position;
+    let pos21 = pos12.inverse();
+    // TODO: somehow precompute the AABB and reuse it?
+    let ls_aabb2 = collider2
+        .shape()
+        .compute_aabb(&pos12)
+        .loosened(ctxt.prediction_distance);
+
+    std::mem::swap(old_manifolds, &mut ctxt.pair.manifolds);
+    ctxt.pair.manifolds.clear();
+    let coll_pair = ctxt.pair.pair;
+    let manifolds = &mut ctxt.pair.manifolds;
+    let prediction_distance = ctxt.prediction_distance;
+    let dispatcher = ctxt.dispatcher;
+    let part1 = Shape::Cuboid(voxels1.voxel_shape());
+
+    voxels1.map_voxels_in_local_aabb(&ls_aabb2, &mut |id1, key1| {
+        let id1 = id1 as usize;
+        let delta1 = voxels1.voxel_position(key1);
+        let part_pos1 = collider1.position * delta1;
+        let ls_part_aabb1 = part1
+            .compute_aabb(&(pos21 * delta1))
+            .loosened(prediction_distance);
+
+        let mut handle_part_pair = |id2: usize, delta2: &Isometry<f32>, part2: &Shape| {
+            let sub_detector = match sub_detectors.entry((*key1, id1, id2)) {
+                Entry::Occupied(entry) => {
+                    let sub_detector = entry.into_mut();
+                    let manifold = old_manifolds[sub_detector.manifold_id].take();
+                    sub_detector.manifold_id = manifolds.len();
+                    sub_detector.timestamp = new_timestamp;
+                    manifolds.push(manifold);
+                    sub_detector
+                }
+                Entry::Vacant(entry) => {
+                    let (generator, workspace2) = dispatcher.dispatch_primitives(&part1, part2);
+                    let sub_detector = SubDetector {
+                        generator,
+                        manifold_id: manifolds.len(),
+                        timestamp: new_timestamp,
+                        workspace: workspace2,
+                    };
+                    let manifold = if flipped {
+                        ContactManifold::with_subshape_indices(
+                            coll_pair, collider2, collider1, id2, id1,
+                        )
+                    } else {
+                        ContactManifold::with_subshape_indices(
+                            coll_pair, collider1, collider2, id1, id2,
+                        )
+                    };
+                    manifolds.push(manifold);
+
+                    entry.insert(sub_detector)
+                }
+            };
+
+            let part_pos2 = collider2.position * delta2;
+            let manifold = &mut manifolds[sub_detector.manifold_id];
+
+            // The sub-generators work on contacts expressed in the local frames of the parts,
+            // whereas the solver expects them in the local frames of the colliders.
+            let swapped = manifold.pair.collider1 != handle1;
+            super::transform_manifold(manifold, &delta1.inverse(), &delta2.inverse(), swapped);
+
+            let mut ctxt2 = if swapped {
+                PrimitiveContactGenerationContext {
+                    prediction_distance,
+                    collider1: collider2,
+                    collider2: collider1,
+                    shape1: part2,
+                    shape2: &part1,
+                    position1: &part_pos2,
+                    position2: &part_pos1,
+                    manifold,
+                    workspace: sub_detector.workspace.as_deref_mut(),
+                }
+            } else {
+                PrimitiveContactGenerationContext {
+                    prediction_distance,
+                    collider1,
+                    collider2,
+                    shape1: &part1,
+                    shape2: part2,
+                    position1: &part_pos1,
+                    position2: &part_pos2,
+                    manifold,
+                    workspace: sub_detector.workspace.as_deref_mut(),
+                }
+            };
+
+            (sub_detector.generator.generate_contacts)(&mut ctxt2);
+
+            // The sub-generator may have swapped the identifiers of the manifold.
+            let manifold = &mut manifolds[sub_detector.manifold_id];
+            let swapped = manifold.pair.collider1 != handle1;
+            super::transform_manifold(manifold, &delta1, delta2, swapped);
+
+            // Remove the contacts on the faces shared with the neighbor voxels
+            // which are already handled by these neighbors.
+            let len_before = manifold.points.len();
+
+            if swapped {
+                let local_n2 = manifold.local_n2;
+                manifold
+                    .points
+                    .retain(|pt| !voxels1.is_internal_face_contact(key1, &pt.local_p2, &local_n2));
+            } else {
+                let local_n1 = manifold.local_n1;
+                manifold
+                    .points
+                    .retain(|pt| !voxels1.is_internal_face_contact(key1, &pt.local_p1, &local_n1));
+            }
+
+            if manifold.points.len() != len_before {
+                manifold.sort_contacts(prediction_distance);
+            }
+        };
+
+        map_parts_in_local_aabb(
+            collider2.shape(),
+            &ls_part_aabb1,
+            interferences2,
+            &mut handle_part_pair,
+        );
+    });
+
+    sub_detectors.retain(|_, detector| detector.timestamp == new_timestamp)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{
+        IntegrationParameters, JointSet, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
+    };
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderHandle, ColliderSet, NarrowPhase};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsPipeline;
+
+    struct World {
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        joints: JointSet,
+        pipeline: PhysicsPipeline,
+        bf: BroadPhase,
+        nf: NarrowPhase,
+        voxels: ColliderHandle,
+        body: RigidBodyHandle,
+    }
+
+    impl World {
+        // A floor of unit voxels with their top faces at `y = 0`, and a body right above it.
+        fn new(collider: ColliderBuilder, x: f32, linvel_x: f32) -> Self {
+            let mut bodies = RigidBodySet::new();
+            let mut colliders = ColliderSet::new();
+
+            let keys: Vec<_> = (-5..5)
+                .map(|i| {
+                    let mut key = Point::from(Vector::repeat(-1));
+                    key.x = i;
+                    key
+                })
+                .collect();
+            let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+            let voxels = ColliderBuilder::voxels(Vector::repeat(1.0), &keys)
+                .friction(0.0)
+                .build();
+            let voxels = colliders.insert(voxels, ground, &mut bodies);
+
+            #[cfg(feature = "dim2")]
+            let body = RigidBodyBuilder::new_dynamic()
+                .translation(x, 0.5)
+                .linvel(linvel_x, 0.0);
+            #[cfg(feature = "dim3")]
+            let body = RigidBodyBuilder::new_dynamic()
+                .translation(x, 0.5, -0.5)
+                .linvel(linvel_x, 0.0, 0.0);
+            let body = bodies.insert(body.build());
+            colliders.insert(collider.friction(0.0).build(), body, &mut bodies);
+
+            Self {
+                bodies,
+                colliders,
+                joints: JointSet::new(),
+                pipeline: PhysicsPipeline::new(),
+                bf: BroadPhase::new(),
+                nf: NarrowPhase::new(),
+                voxels,
+                body,
+            }
+        }
+
+        fn step(&mut self) {
+            self.pipeline.step(
+                &(Vector::y() * -9.81),
+                &IntegrationParameters::default(),
+                &mut self.bf,
+                &mut self.nf,
+                &mut self.bodies,
+                &mut self.colliders,
+                &mut self.joints,
+                &(),
+                &(),
+            );
+        }
+    }
+
+    #[test]
+    fn box_slides_across_the_internal_faces_of_voxels() {
+        #[cfg(feature = "dim2")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 0.5);
+        // Not as wide as the voxels along `z`, so its edges don't lie on the edges of the voxels.
+        #[cfg(feature = "dim3")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 0.5, 0.4);
+        let mut world = World::new(cuboid, -3.0, 5.0);
+
+        for _ in 0..60 {
+            world.step();
+
+            for (_, _, pair) in world.nf.contact_graph().interaction_pairs() {
+                for manifold in &pair.manifolds {
+                    if manifold.num_active_contacts() > 0 {
+                        let pos1 = world.colliders[manifold.pair.collider1].position();
+                        let normal = pos1 * manifold.local_n1;
+                        assert!(normal.y.abs() > 0.999);
+                    }
+                }
+            }
+
+            let body = &world.bodies[world.body];
+            assert!(body.linvel.y.abs() < 0.1);
+            assert!((body.linvel.x - 5.0).abs() < 0.1);
+        }
+
+        let body = &world.bodies[world.body];
+        assert!(body.position.translation.vector.x > 1.0);
+        assert!((body.position.translation.vector.y - 0.5).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn removing_voxels_wakes_up_the_bodies_resting_on_them() {
+        let mut world = World::new(ColliderBuilder::ball(0.5), 0.5, 0.0);
+
+        for _ in 0..600 {
+            world.step();
+
+            if world.bodies[world.body].is_sleeping() {
+                break;
+            }
+        }
+
+        assert!(world.bodies[world.body].is_sleeping());
+
+        // Remove the voxel right below the ball.
+        let mut key = Point::from(Vector::repeat(-1));
+        key.x = 0;
+        let removed = world
+            .colliders
+            .modify_voxels(world.voxels, &mut world.bodies, |voxels| {
+                voxels.remove(&key)
+            });
+        assert_eq!(removed, Some(true));
+
+        for _ in 0..30 {
+            world.step();
+        }
+
+        let body = &world.bodies[world.body];
+        assert!(!body.is_sleeping());
+        assert!(body.position.translation.vector.y < 0.0);
+    }
+}
@@ -0,0 +1,401 @@
+use crate::geometry::polygon::clip_ray_with_half_spaces;
+use crate::geometry::{PolyhedronFace, Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Vector};
+use na::Point3;
+use ncollide::bounding_volume;
+use ncollide::query::{self, algorithms::VoronoiSimplex, PointProjection, PointQuery};
+use ncollide::shape::{FeatureId, SupportMap};
+use std::collections::HashMap;
+
+// Two adjacent triangles of the convex hull are merged into the same face if the dot product
+// of their normals is greater than this threshold.
+const COPLANARITY_THRESHOLD: f32 = 0.9999;
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+struct Face {
+    first_vertex: u32,
+    num_vertices: u32,
+    normal: Vector<f32>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A convex polyhedron.
+///
+/// The faces of a convex polyhedron are planar convex polygons that may have more than three
+/// vertices, e.g., the faces of a convex polyhedron representing a box have four vertices.
+pub struct ConvexPolyhedron {
+    points: Vec<Point<f32>>,
+    faces: Vec<Face>,
+    // The vertices of all the faces, in counter-clockwise order when seen from outside.
+    // The vertices of the i-th face are
+    // `face_vertices[faces[i].first_vertex..faces[i].first_vertex + faces[i].num_vertices]`.
+    face_vertices: Vec<u32>,
+    // The edge linking the vertex `face_vertices[i]` to the next vertex of the same face.
+    face_edges: Vec<u32>,
+    // A triangulation of the boundary of this polyhedron.
+    indices: Vec<Point3<u32>>,
+}
+
+impl ConvexPolyhedron {
+    /// Builds the convex polyhedron equal to the convex hull of the given set of points.
+    ///
+    /// Returns `None` if the convex hull is degenerate, e.g., if all the points are coplanar.
+    pub fn from_points(points: &[Point<f32>]) -> Option<Self> {
+        if points.len() < 4 {
+            return None;
+        }
+
+        let hull = ncollide::transformation::convex_hull(points);
+        let triangles = hull.indices.unwrap_unified();
+        Self::from_convex_mesh(&hull.coords, &triangles)
+    }
+
+    // Builds a convex polyhedron from a closed convex triangle mesh with outward-facing
+    // counter-clockwise triangles. Adjacent coplanar triangles are merged into a single face.
+    fn from_convex_mesh(vertices: &[Point<f32>], triangles: &[Point3<u32>]) -> Option<Self> {
+        let normals: Vec<_> = triangles
+            .iter()
+            .map(|t| {
+                let a = vertices[t.x as usize];
+                let b = vertices[t.y as usize];
+                let c = vertices[t.z as usize];
+                (b - a).cross(&(c - a))
+            })
+            .collect();
+
+        // Degenerate triangles don't belong to any face.
+        let is_valid: Vec<_> = normals
+            .iter()
+            .map(|n| n.norm_squared() > f32::EPSILON * f32::EPSILON)
+            .collect();
+
+        let mut triangle_of_edge = HashMap::new();
+        for (i, t) in triangles.iter().enumerate() {
+            if is_valid[i] {
+                triangle_of_edge.insert((t.x, t.y), i);
+                triangle_of_edge.insert((t.y, t.z), i);
+                triangle_of_edge.insert((t.z, t.x), i);
+            }
+        }
+
+        // Group the adjacent coplanar triangles.
+        let mut groups: Vec<_> = (0..triangles.len()).collect();
+        for (i, t) in triangles.iter().enumerate() {
+            if !is_valid[i] {
+                continue;
+            }
+
+            for (a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+                if let Some(j) = triangle_of_edge.get(&(*b, *a)) {
+                    let n1 = normals[i].normalize();
+                    let n2 = normals[*j].normalize();
+
+                    if n1.dot(&n2) > COPLANARITY_THRESHOLD {
+                        let root1 = find_root(&mut groups, i);
+                        let root2 = find_root(&mut groups, *j);
+                        groups[root1] = root2;
+                    }
+                }
+            }
+        }
+
+        // Collect the boundary edges of each group, i.e., the edges shared with another group.
+        let mut face_of_group = HashMap::new();
+        let mut face_boundaries: Vec<HashMap<u32, u32>> = Vec::new();
+        let mut face_normals: Vec<Vector<f32>> = Vec::new();
+
+        for (i, t) in triangles.iter().enumerate() {
+            if !is_valid[i] {
+                continue;
+            }
+
+            let root = find_root(&mut groups, i);
+            let face_id = *face_of_group.entry(root).or_insert_with(|| {
+                face_boundaries.push(HashMap::new());
+                face_normals.push(Vector::zeros());
+                face_boundaries.len() - 1
+            });
+
+            // The cross product is proportional to the triangle area so this
+            // computes an area-weighted normal.
+            face_normals[face_id] += normals[i];
+
+            for (a, b) in &[(t.x, t.y), (t.y, t.z), (t.z, t.x)] {
+                let is_boundary = match triangle_of_edge.get(&(*b, *a)) {
+                    Some(j) => find_root(&mut groups, *j) != root,
+                    None => true,
+                };
+
+                if is_boundary {
+                    face_boundaries[face_id].insert(*a, *b);
+                }
+            }
+        }
+
+        // A closed polyhedron has at least four faces.
+        if face_boundaries.len() < 4 {
+            return None;
+        }
+
+        // Chain the boundary edges of each face to form its counter-clockwise vertex loop.
+        let mut points = Vec::new();
+        let mut new_vertex_ids = HashMap::new();
+        let mut faces = Vec::with_capacity(face_boundaries.len());
+        let mut face_vertices = Vec::new();
+        let mut face_edges = Vec::new();
+        let mut edges = HashMap::new();
+        let mut indices = Vec::new();
+
+        for (boundary, normal) in face_boundaries.iter().zip(face_normals.iter()) {
+            let first_vertex = face_vertices.len();
+            let start = *boundary.keys().min()?;
+            let mut curr = start;
+
+            loop {
+                let new_id = *new_vertex_ids.entry(curr).or_insert_with(|| {
+                    points.push(vertices[curr as usize]);
+                    points.len() as u32 - 1
+                });
+                face_vertices.push(new_id);
+                curr = *boundary.get(&curr)?;
+
+                if curr == start {
+                    break;
+                }
+
+                if face_vertices.len() - first_vertex > boundary.len() {
+                    // The boundary is not a simple loop.
+                    return None;
+                }
+            }
+
+            let num_vertices = face_vertices.len() - first_vertex;
+
+            if num_vertices < 3 {
+                return None;
+            }
+
+            for k in 0..num_vertices {
+                let a = face_vertices[first_vertex + k];
+                let b = face_vertices[first_vertex + (k + 1) % num_vertices];
+                let key = if a < b { (a, b) } else { (b, a) };
+                let num_edges = edges.len() as u32;
+                face_edges.push(*edges.entry(key).or_insert(num_edges));
+            }
+
+            for k in 1..num_vertices - 1 {
+                indices.push(Point3::new(
+                    face_vertices[first_vertex],
+                    face_vertices[first_vertex + k],
+                    face_vertices[first_vertex + k + 1],
+                ));
+            }
+
+            faces.push(Face {
+                first_vertex: first_vertex as u32,
+                num_vertices: num_vertices as u32,
+                normal: normal.normalize(),
+            });
+        }
+
+        Some(Self {
+            points,
+            faces,
+            face_vertices,
+            face_edges,
+            indices,
+        })
+    }
+
+    /// The vertices of this convex polyhedron.
+    pub fn vertices(&self) -> &[Point<f32>] {
+        &self.points
+    }
+
+    /// The index buffer of a triangulation of the boundary of this convex polyhedron.
+    pub fn indices(&self) -> &[Point3<u32>] {
+        &self.indices
+    }
+
+    /// The number of faces of this convex polyhedron.
+    pub fn num_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// The outward unit normal of the i-th face of this convex polyhedron.
+    pub fn face_normal(&self, i: usize) -> Vector<f32> {
+        self.faces[i].normal
+    }
+
+    /// Compute the axis-aligned bounding box of this convex polyhedron.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        bounding_volume::point_cloud_aabb(pos, &self.points)
+    }
+
+    /// Computes the first intersection between this convex polyhedron and a ray.
+    ///
+    /// If `solid` is `true` and the ray starts inside of the polyhedron, the returned time of impact is zero.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let half_spaces = self.faces.iter().map(|face| {
+            let pt = self.points[self.face_vertices[face.first_vertex as usize] as usize];
+            (pt, face.normal)
+        });
+        let (toi, normal, feature) =
+            clip_ray_with_half_spaces(&ls_ray, max_toi, solid, half_spaces)?;
+        Some(RayIntersection::new(toi, position * normal, feature))
+    }
+
+    pub(crate) fn support_point_id(&self, dir: &Vector<f32>) -> usize {
+        let mut best_dot = -f32::MAX;
+        let mut best_i = 0;
+
+        for (i, pt) in self.points.iter().enumerate() {
+            let dot = pt.coords.dot(dir);
+            if dot > best_dot {
+                best_dot = dot;
+                best_i = i;
+            }
+        }
+
+        best_i
+    }
+
+    /// The face with the normal most aligned with `local_dir`.
+    pub(crate) fn support_face(&self, local_dir: Vector<f32>) -> PolyhedronFace {
+        let mut best_dot = -f32::MAX;
+        let mut best_i = 0;
+
+        for (i, face) in self.faces.iter().enumerate() {
+            let dot = face.normal.dot(&local_dir);
+            if dot > best_dot {
+                best_dot = dot;
+                best_i = i;
+            }
+        }
+
+        let face = &self.faces[best_i];
+        let first_vertex = face.first_vertex as usize;
+        let range = first_vertex..first_vertex + face.num_vertices as usize;
+
+        PolyhedronFace {
+            vertices: self.face_vertices[range.clone()]
+                .iter()
+                .map(|vid| self.points[*vid as usize])
+                .collect(),
+            vids: self.face_vertices[range.clone()]
+                .iter()
+                .map(|vid| vertex_feature_id(*vid as usize))
+                .collect(),
+            eids: self.face_edges[range]
+                .iter()
+                .map(|eid| edge_feature_id(*eid as usize))
+                .collect(),
+            fid: face_feature_id(best_i),
+        }
+    }
+}
+
+// The feature IDs of the vertices, edges, and faces, are interleaved.
+fn vertex_feature_id(i: usize) -> u32 {
+    (i * 3) as u32
+}
+
+fn edge_feature_id(i: usize) -> u32 {
+    (i * 3 + 1) as u32
+}
+
+fn face_feature_id(i: usize) -> u32 {
+    (i * 3 + 2) as u32
+}
+
+fn find_root(groups: &mut [usize], mut i: usize) -> usize {
+    while groups[i] != i {
+        groups[i] = groups[groups[i]];
+        i = groups[i];
+    }
+
+    i
+}
+
+impl SupportMap<f32> for ConvexPolyhedron {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        self.points[self.support_point_id(dir)]
+    }
+}
+
+impl PointQuery<f32> for ConvexPolyhedron {
+    #[inline]
+    fn project_point(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+        solid: bool,
+    ) -> PointProjection<f32> {
+        query::point_projection_on_support_map(m, self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+    ) -> (PointProjection<f32>, FeatureId) {
+        (self.project_point(m, pt, false), FeatureId::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConvexPolyhedron;
+    use crate::geometry::Shape;
+    use crate::math::{Point, Vector};
+    use na::Matrix3;
+
+    fn unit_cube() -> ConvexPolyhedron {
+        let mut points: Vec<_> = (0..8)
+            .map(|i| Point::new((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32))
+            .collect();
+        // Points inside of the cube don't change its convex hull.
+        points.push(Point::new(0.5, 0.5, 0.5));
+        points.push(Point::new(0.2, 0.7, 0.4));
+        ConvexPolyhedron::from_points(&points).unwrap()
+    }
+
+    #[test]
+    fn unit_cube_hull_has_one_face_per_side() {
+        let cube = unit_cube();
+        assert_eq!(cube.vertices().len(), 8);
+        assert_eq!(cube.indices().len(), 12);
+        assert_eq!(cube.num_faces(), 6);
+
+        for i in 0..3 {
+            for sign in &[-1.0, 1.0] {
+                let axis = Vector::ith(i, *sign);
+                let num_faces = (0..6)
+                    .filter(|j| cube.face_normal(*j).dot(&axis) > 0.9999)
+                    .count();
+                assert_eq!(num_faces, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn unit_cube_hull_mass_properties() {
+        let mprops = Shape::ConvexPolyhedron(unit_cube()).mass_properties(2.0);
+        assert!((mprops.inv_mass - 0.5).abs() < 1.0e-5);
+        assert!((mprops.local_com - Point::new(0.5, 0.5, 0.5)).norm() < 1.0e-5);
+
+        // The angular inertia of a cube with mass `m` and side `a` is `m * a^2 / 6` along every axis.
+        let inertia = Matrix3::from_diagonal_element(2.0 / 6.0);
+        assert!((mprops.reconstruct_inertia_matrix() - inertia).norm() < 1.0e-5);
+    }
+}
@@ -12,7 +12,7 @@ pub enum CuboidFeature {
 #[derive(Debug)]
 pub struct CuboidFeatureVertex {
     pub vertex: Point<f32>,
-    pub vid: u8,
+    pub vid: u32,
 }
 
 impl CuboidFeatureVertex {
@@ -24,8 +24,8 @@ impl CuboidFeatureVertex {
 #[derive(Debug)]
 pub struct CuboidFeatureFace {
     pub vertices: [Point<f32>; 2],
-    pub vids: [u8; 2],
-    pub fid: u8,
+    pub vids: [u32; 2],
+    pub fid: u32,
 }
 
 impl From<Segment<f32>> for CuboidFeatureFace {
@@ -75,6 +75,7 @@ impl CuboidFeature {
             local_p2: pos21 * local_p2,
             impulse: 0.0,
             tangent_impulse: Contact::zero_tangent_impulse(),
+            tangent_velocity: Vector::zeros(),
             fid1: face1.fid,
             fid2: vertex2.vid,
             dist,
@@ -104,6 +105,7 @@ impl CuboidFeature {
                     local_p2: pos21 * clip_a.1,
                     impulse: 0.0,
                     tangent_impulse: Contact::zero_tangent_impulse(),
+                    tangent_velocity: Vector::zeros(),
                     fid1: fids1[clip_a.2],
                     fid2: fids2[clip_a.3],
                     dist,
@@ -118,6 +120,7 @@ impl CuboidFeature {
                     local_p2: pos21 * clip_b.1,
                     impulse: 0.0,
                     tangent_impulse: Contact::zero_tangent_impulse(),
+                    tangent_velocity: Vector::zeros(),
                     fid1: fids1[clip_b.2],
                     fid2: fids2[clip_b.3],
                     dist,
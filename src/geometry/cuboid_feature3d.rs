@@ -14,7 +14,7 @@ pub(crate) enum CuboidFeature {
 #[derive(Debug)]
 pub(crate) struct CuboidFeatureVertex {
     pub vertex: Point<f32>,
-    pub vid: u8,
+    pub vid: u32,
 }
 
 impl CuboidFeatureVertex {
@@ -26,8 +26,8 @@ impl CuboidFeatureVertex {
 #[derive(Debug)]
 pub(crate) struct CuboidFeatureEdge {
     pub vertices: [Point<f32>; 2],
-    pub vids: [u8; 2],
-    pub eid: u8,
+    pub vids: [u32; 2],
+    pub eid: u32,
 }
 
 impl CuboidFeatureEdge {
@@ -40,9 +40,9 @@ impl CuboidFeatureEdge {
 #[derive(Debug)]
 pub(crate) struct CuboidFeatureFace {
     pub vertices: [Point<f32>; 4],
-    pub vids: [u8; 4], // Feature ID of the vertices.
-    pub eids: [u8; 4], // Feature ID of the edges.
-    pub fid: u8,       // Feature ID of the face.
+    pub vids: [u32; 4], // Feature ID of the vertices.
+    pub eids: [u32; 4], // Feature ID of the edges.
+    pub fid: u32,       // Feature ID of the face.
 }
 
 impl CuboidFeatureFace {
@@ -85,6 +85,7 @@ impl CuboidFeature {
             local_p2: pos21 * local_p2,
             impulse: 0.0,
             tangent_impulse: Contact::zero_tangent_impulse(),
+            tangent_velocity: Vector::zeros(),
             fid1: face1.fid,
             fid2: vertex2.vid,
             dist,
@@ -161,6 +162,7 @@ impl CuboidFeature {
                                 local_p2: pos21 * local_p1,
                                 impulse: 0.0,
                                 tangent_impulse: Contact::zero_tangent_impulse(),
+                                tangent_velocity: Vector::zeros(),
                                 fid1: edge2.eid,
                                 fid2: face1.eids[i],
                                 dist,
@@ -171,6 +173,7 @@ impl CuboidFeature {
                                 local_p2: pos21 * local_p2,
                                 impulse: 0.0,
                                 tangent_impulse: Contact::zero_tangent_impulse(),
+                                tangent_velocity: Vector::zeros(),
                                 fid1: face1.eids[i],
                                 fid2: edge2.eid,
                                 dist,
@@ -215,6 +218,7 @@ impl CuboidFeature {
                         local_p2: pos21 * local_p1,
                         impulse: 0.0,
                         tangent_impulse: Contact::zero_tangent_impulse(),
+                        tangent_velocity: Vector::zeros(),
                         fid1: edge2.vids[i],
                         fid2: face1.fid,
                         dist,
@@ -225,6 +229,7 @@ impl CuboidFeature {
                         local_p2: pos21 * local_p2,
                         impulse: 0.0,
                         tangent_impulse: Contact::zero_tangent_impulse(),
+                        tangent_velocity: Vector::zeros(),
                         fid1: face1.fid,
                         fid2: edge2.vids[i],
                         dist,
@@ -278,6 +283,7 @@ impl CuboidFeature {
                 local_p2: pos21 * local_p2, // NOTE: local_p2 is expressed in the local space of cube1.
                 impulse: 0.0,
                 tangent_impulse: Contact::zero_tangent_impulse(),
+                tangent_velocity: Vector::zeros(),
                 fid1: edge1.eid,
                 fid2: edge2.eid,
                 dist,
@@ -372,6 +378,7 @@ impl CuboidFeature {
                     local_p2: pos21 * local_p2,
                     impulse: 0.0,
                     tangent_impulse: Contact::zero_tangent_impulse(),
+                    tangent_velocity: Vector::zeros(),
                     fid1: face1.vids[i],
                     fid2: face2.fid,
                     dist,
@@ -415,6 +422,7 @@ impl CuboidFeature {
                         local_p2: pos21 * local_p2,
                         impulse: 0.0,
                         tangent_impulse: Contact::zero_tangent_impulse(),
+                        tangent_velocity: Vector::zeros(),
                         fid1: face1.fid,
                         fid2: face2.vids[i],
                         dist,
@@ -454,6 +462,7 @@ impl CuboidFeature {
                                     local_p2: pos21 * local_p2,
                                     impulse: 0.0,
                                     tangent_impulse: Contact::zero_tangent_impulse(),
+                                    tangent_velocity: Vector::zeros(),
                                     fid1: face1.eids[i],
                                     fid2: face2.eids[j],
                                     dist,
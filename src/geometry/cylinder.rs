@@ -0,0 +1,132 @@
+use crate::geometry::{PolyhedronFace, Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Vector};
+use ncollide::query::{self, algorithms::VoronoiSimplex, PointProjection, PointQuery, RayCast};
+use ncollide::shape::{FeatureId, SupportMap};
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A cylinder shape with its principal axis aligned with the `y` axis.
+///
+/// The contacts between a cylinder and a polyhedral shape are computed with an approximation of
+/// the cylinder: a cap is approximated by the square inscribed in it, and the curved side by the
+/// segment of the side closest to the other shape. Thus, a cylinder resting on one of its caps is
+/// supported by at most four contact points, located on the rim of the cap.
+pub struct Cylinder {
+    /// The half-height of the cylinder along the `y` axis.
+    pub half_height: f32,
+    /// The radius of the cylinder.
+    pub radius: f32,
+}
+
+impl Cylinder {
+    /// Creates a new cylinder aligned with the `y` axis and with the given half-height and radius.
+    pub fn new(half_height: f32, radius: f32) -> Self {
+        assert!(half_height > 0.0 && radius > 0.0);
+        Self {
+            half_height,
+            radius,
+        }
+    }
+
+    /// The axis-aligned bounding box of this cylinder.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        let axis = pos * Vector::y();
+        let half_extents =
+            axis.map(|e| e.abs() * self.half_height + (1.0 - e * e).max(0.0).sqrt() * self.radius);
+        let center = Point::from(pos.translation.vector);
+        AABB::new(center - half_extents, center + half_extents)
+    }
+
+    /// Computes the first intersection between this cylinder and a ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let cylinder = ncollide::shape::Cylinder::new(self.half_height, self.radius);
+        cylinder.toi_and_normal_with_ray(position, ray, max_toi, solid)
+    }
+
+    /// The face of this cylinder with the normal most aligned with `local_dir`.
+    ///
+    /// The caps are approximated by squares inscribed in them, and the curved
+    /// side by the segment of the side farthest along `local_dir`.
+    pub(crate) fn support_face(&self, local_dir: Vector<f32>) -> PolyhedronFace {
+        let horizontal_dir = Vector::new(local_dir.x, 0.0, local_dir.z);
+        let horizontal_norm = horizontal_dir.norm();
+        let horizontal_dir = if horizontal_norm > 1.0e-6 {
+            horizontal_dir / horizontal_norm
+        } else {
+            Vector::x()
+        };
+
+        if local_dir.y.abs() > horizontal_norm {
+            // Support face on one of the caps.
+            let (normal, first_id) = if local_dir.y > 0.0 {
+                (Vector::y(), 0)
+            } else {
+                (-Vector::y(), 10)
+            };
+            let center = Point::from(normal * self.half_height);
+            let dir1 = horizontal_dir * self.radius;
+            let dir2 = normal.cross(&dir1);
+
+            PolyhedronFace {
+                vertices: vec![center + dir1, center + dir2, center - dir1, center - dir2],
+                vids: vec![first_id, first_id + 2, first_id + 4, first_id + 6],
+                eids: vec![first_id + 1, first_id + 3, first_id + 5, first_id + 7],
+                fid: first_id + 8,
+            }
+        } else {
+            // Support segment on the curved side.
+            let rim = horizontal_dir * self.radius;
+            let top = Point::from(rim + Vector::y() * self.half_height);
+            let bottom = Point::from(rim - Vector::y() * self.half_height);
+
+            PolyhedronFace {
+                vertices: vec![top, bottom],
+                vids: vec![20, 22],
+                eids: vec![21, 21],
+                fid: 23,
+            }
+        }
+    }
+}
+
+impl SupportMap<f32> for Cylinder {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        let mut res = Vector::new(dir.x, 0.0, dir.z);
+
+        if res.normalize_mut() == 0.0 {
+            res = Vector::zeros();
+        } else {
+            res *= self.radius;
+        }
+
+        res.y = self.half_height.copysign(dir.y);
+        Point::from(res)
+    }
+}
+
+impl PointQuery<f32> for Cylinder {
+    #[inline]
+    fn project_point(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+        solid: bool,
+    ) -> PointProjection<f32> {
+        query::point_projection_on_support_map(m, self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<f32>,
+        pt: &Point<f32>,
+    ) -> (PointProjection<f32>, FeatureId) {
+        (self.project_point(m, pt, false), FeatureId::Unknown)
+    }
+}
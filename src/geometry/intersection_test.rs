@@ -0,0 +1,84 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::time_of_impact::{to_ncollide_shape, to_support_map};
+use crate::geometry::{Proximity, Shape, WAABBHierarchyIntersections};
+use crate::math::Isometry;
+use ncollide::query;
+
+/// Tests whether two shapes are intersecting.
+///
+/// Shapes that are only touching are considered intersecting.
+pub(crate) fn intersection_test(
+    pos1: &Isometry<f32>,
+    shape1: &Shape,
+    pos2: &Isometry<f32>,
+    shape2: &Shape,
+) -> bool {
+    if shape1.is_composite() {
+        composite_shape_intersection_test(pos1, shape1, pos2, shape2)
+    } else if shape2.is_composite() {
+        composite_shape_intersection_test(pos2, shape2, pos1, shape1)
+    } else {
+        convex_shape_intersection_test(pos1, shape1, pos2, shape2)
+    }
+}
+
+fn convex_shape_intersection_test(
+    pos1: &Isometry<f32>,
+    shape1: &Shape,
+    pos2: &Isometry<f32>,
+    shape2: &Shape,
+) -> bool {
+    // Round shapes intersect if their base shapes are closer than the sum of their border radii.
+    let (shape1, border_radius1) = shape1.without_border();
+    let (shape2, border_radius2) = shape2.without_border();
+    let margin = border_radius1 + border_radius2;
+
+    let proximity = match (to_ncollide_shape(shape1), to_ncollide_shape(shape2)) {
+        (Some((shift1, g1)), Some((shift2, g2))) => {
+            query::proximity(&(pos1 * shift1), &*g1, &(pos2 * shift2), &*g2, margin)
+        }
+        _ => {
+            // Some convex shapes, e.g., polygons, cylinders, or cones, are only supported
+            // through their support maps.
+            let (shift1, g1) = match to_support_map(shape1) {
+                Some(res) => res,
+                None => return false,
+            };
+            let (shift2, g2) = match to_support_map(shape2) {
+                Some(res) => res,
+                None => return false,
+            };
+            query::proximity_support_map_support_map(
+                &(pos1 * shift1),
+                &*g1,
+                &(pos2 * shift2),
+                &*g2,
+                margin,
+            )
+        }
+    };
+
+    proximity != Proximity::Disjoint
+}
+
+fn composite_shape_intersection_test(
+    pos1: &Isometry<f32>,
+    shape1: &Shape,
+    pos2: &Isometry<f32>,
+    shape2: &Shape,
+) -> bool {
+    let pos12 = pos1.inverse() * pos2;
+    let ls_aabb2 = shape2.compute_aabb(&pos12);
+    let mut intersecting = false;
+
+    let mut check_part = |_: usize, delta1: &Isometry<f32>, part1: &Shape| {
+        if !intersecting {
+            intersecting = intersection_test(&(pos1 * delta1), part1, pos2, shape2);
+        }
+    };
+
+    let mut workspace = WAABBHierarchyIntersections::new();
+    map_parts_in_local_aabb(shape1, &ls_aabb2, &mut workspace, &mut check_part);
+
+    intersecting
+}
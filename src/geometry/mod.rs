@@ -4,10 +4,17 @@ pub use self::broad_phase_multi_sap::BroadPhase;
 pub use self::capsule::Capsule;
 pub use self::collider::{Collider, ColliderBuilder, Shape};
 pub use self::collider_set::{ColliderHandle, ColliderSet};
+pub use self::compound::Compound;
 pub use self::contact::{
     Contact, ContactKinematics, ContactManifold, ContactPair, KinematicsCategory,
 };
+#[cfg(feature = "dim3")]
+pub use self::cone::Cone;
 pub use self::contact_generator::{ContactDispatcher, DefaultContactDispatcher};
+#[cfg(feature = "dim3")]
+pub use self::convex_polyhedron::ConvexPolyhedron;
+#[cfg(feature = "dim3")]
+pub use self::cylinder::Cylinder;
 #[cfg(feature = "dim2")]
 pub(crate) use self::cuboid_feature2d::{CuboidFeature, CuboidFeatureFace};
 #[cfg(feature = "dim3")]
@@ -15,15 +22,23 @@ pub(crate) use self::cuboid_feature3d::{CuboidFeature, CuboidFeatureFace};
 pub use self::interaction_graph::{
     ColliderGraphIndex, InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex,
 };
+pub use self::interaction_groups::InteractionGroups;
 pub use self::narrow_phase::NarrowPhase;
 pub use self::polygon::Polygon;
+#[cfg(feature = "dim2")]
+pub use self::polyline::Polyline;
 pub use self::proximity::ProximityPair;
 pub use self::proximity_detector::{DefaultProximityDispatcher, ProximityDispatcher};
+pub use self::round_shape::RoundShape;
 pub use self::trimesh::Trimesh;
-pub use ncollide::query::Proximity;
+pub use self::voxels::Voxels;
+pub use ncollide::query::{Proximity, TOIStatus};
+pub use ncollide::shape::FeatureId;
 
 /// A cuboid shape.
 pub type Cuboid = ncollide::shape::Cuboid<f32>;
+/// A segment shape.
+pub type Segment = ncollide::shape::Segment<f32>;
 /// A triangle shape.
 pub type Triangle = ncollide::shape::Triangle<f32>;
 /// A ball shape.
@@ -32,6 +47,14 @@ pub type Ball = ncollide::shape::Ball<f32>;
 pub type HeightField = ncollide::shape::HeightField<f32>;
 /// An axis-aligned bounding box.
 pub type AABB = ncollide::bounding_volume::AABB<f32>;
+/// A ray that can be cast against colliders.
+pub type Ray = ncollide::query::Ray<f32>;
+/// The intersection between a ray and a collider.
+pub type RayIntersection = ncollide::query::RayIntersection<f32>;
+/// The projection of a point on a collider.
+pub type PointProjection = ncollide::query::PointProjection<f32>;
+/// The time of impact between two shapes under translational movement.
+pub type TOI = ncollide::query::TOI<f32>;
 /// Event triggered when two non-sensor colliders start or stop being in contact.
 pub type ContactEvent = ncollide::pipeline::ContactEvent<ColliderHandle>;
 /// Event triggered when a sensor collider starts or stop being in proximity with another collider (sensor or not).
@@ -45,7 +68,12 @@ pub(crate) use self::broad_phase_multi_sap::BroadPhasePairEvent;
 pub(crate) use self::contact::WContact;
 #[cfg(feature = "dim2")]
 pub(crate) use self::contact_generator::{clip_segments, clip_segments_with_normal};
+pub(crate) use self::intersection_test::intersection_test;
 pub(crate) use self::narrow_phase::ContactManifoldIndex;
+pub(crate) use self::point_projection::project_point;
+pub(crate) use self::time_of_impact::time_of_impact;
+#[cfg(feature = "dim3")]
+pub(crate) use self::polyhedral_shape::PolyhedralShape;
 #[cfg(feature = "dim3")]
 pub(crate) use self::polyhedron_feature3d::PolyhedronFace;
 #[cfg(feature = "simd-is-enabled")]
@@ -58,23 +86,40 @@ mod broad_phase_multi_sap;
 mod capsule;
 mod collider;
 mod collider_set;
+pub(crate) mod compound;
+#[cfg(feature = "dim3")]
+mod cone;
 mod contact;
 mod contact_generator;
+#[cfg(feature = "dim3")]
+mod convex_polyhedron;
 pub(crate) mod cuboid;
 #[cfg(feature = "dim2")]
 mod cuboid_feature2d;
 #[cfg(feature = "dim3")]
 mod cuboid_feature3d;
+#[cfg(feature = "dim3")]
+mod cylinder;
 mod interaction_graph;
+mod interaction_groups;
+mod intersection_test;
 mod narrow_phase;
-mod polygon;
+mod point_projection;
+pub(crate) mod polygon;
+#[cfg(feature = "dim2")]
+pub(crate) mod polyline;
+#[cfg(feature = "dim3")]
+mod polyhedral_shape;
 #[cfg(feature = "dim3")]
 mod polyhedron_feature3d;
 mod proximity;
 mod proximity_detector;
+mod round_shape;
 pub(crate) mod sat;
+mod time_of_impact;
 pub(crate) mod triangle;
 mod trimesh;
+mod voxels;
 #[cfg(feature = "simd-is-enabled")]
 mod waabb;
 //mod z_order;
@@ -20,7 +20,7 @@ use crate::geometry::{ColliderSet, ContactManifold, ContactPair, InteractionGrap
 //#[cfg(feature = "simd-is-enabled")]
 //use crate::math::{SimdFloat, SIMD_WIDTH};
 use crate::ncollide::query::Proximity;
-use crate::pipeline::EventHandler;
+use crate::pipeline::{EventHandler, PairFilterContext, PhysicsHooks};
 //use simba::simd::SimdValue;
 
 /// The narrow-phase responsible for computing precise contact information between colliders.
@@ -78,6 +78,7 @@ impl NarrowPhase {
         handles: &[ColliderHandle],
         colliders: &mut ColliderSet,
         bodies: &mut RigidBodySet,
+        events: &dyn EventHandler,
     ) {
         for handle in handles {
             if let Some(collider) = colliders.get(*handle) {
@@ -85,7 +86,7 @@ impl NarrowPhase {
                 let contact_graph_id = collider.contact_graph_index;
 
                 // Wake up every body in contact with the deleted collider.
-                for (a, b, _) in self.contact_graph.interactions_with(contact_graph_id) {
+                for (a, b, pair) in self.contact_graph.interactions_with(contact_graph_id) {
                     if let Some(parent) = colliders.get(a).map(|c| c.parent) {
                         bodies.wake_up(parent)
                     }
@@ -93,6 +94,20 @@ impl NarrowPhase {
                     if let Some(parent) = colliders.get(b).map(|c| c.parent) {
                         bodies.wake_up(parent)
                     }
+
+                    // Emit a contact stopped event if the colliders were touching.
+                    if pair.has_narrow_phase_contact {
+                        events.handle_contact_event(ContactEvent::Stopped(a, b))
+                    }
+                }
+
+                // Emit a proximity lost event for every pair in proximity with the deleted collider.
+                for (a, b, pair) in self.proximity_graph.interactions_with(proximity_graph_id) {
+                    if pair.proximity != Proximity::Disjoint {
+                        let prox_event =
+                            ProximityEvent::new(a, b, pair.proximity, Proximity::Disjoint);
+                        events.handle_proximity_event(prox_event)
+                    }
                 }
 
                 // We have to manage the fact that one other collider will
@@ -116,10 +131,37 @@ impl NarrowPhase {
         }
     }
 
+    /// Wakes up every body in contact with a collider which shape was modified since the
+    /// last timestep.
+    pub(crate) fn handle_modified_colliders(
+        &self,
+        colliders: &mut ColliderSet,
+        bodies: &mut RigidBodySet,
+    ) {
+        for handle in colliders.modified_colliders.drain(..) {
+            if let Some(collider) = colliders.colliders.get(handle) {
+                for (a, b, _) in self
+                    .contact_graph
+                    .interactions_with(collider.contact_graph_index)
+                {
+                    if let Some(parent) = colliders.colliders.get(a).map(|c| c.parent) {
+                        bodies.wake_up(parent)
+                    }
+
+                    if let Some(parent) = colliders.colliders.get(b).map(|c| c.parent) {
+                        bodies.wake_up(parent)
+                    }
+                }
+            }
+        }
+    }
+
     pub(crate) fn register_pairs(
         &mut self,
         colliders: &mut ColliderSet,
+        bodies: &RigidBodySet,
         broad_phase_events: &[BroadPhasePairEvent],
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
         for event in broad_phase_events {
@@ -129,7 +171,28 @@ impl NarrowPhase {
                     if let (Some(co1), Some(co2)) =
                         colliders.get2_mut_internal(pair.collider1, pair.collider2)
                     {
-                        if co1.is_sensor() || co2.is_sensor() {
+                        let is_proximity_pair = co1.is_sensor() || co2.is_sensor();
+                        let context = PairFilterContext {
+                            collider_handle1: pair.collider1,
+                            collider_handle2: pair.collider2,
+                            collider1: co1,
+                            collider2: co2,
+                            rigid_body1: &bodies[co1.parent],
+                            rigid_body2: &bodies[co2.parent],
+                        };
+
+                        let allowed = if is_proximity_pair {
+                            hooks.filter_proximity_pair(&context)
+                        } else {
+                            hooks.filter_contact_pair(&context)
+                        };
+
+                        if !allowed {
+                            // The collision was rejected by the user-defined filter.
+                            continue;
+                        }
+
+                        if is_proximity_pair {
                             let gid1 = co1.proximity_graph_index;
                             let gid2 = co2.proximity_graph_index;
 
@@ -215,7 +278,7 @@ impl NarrowPhase {
 
                             // Emit a contact stopped event if we had a proximity before removing the edge.
                             if let Some(ctct) = contact_pair {
-                                if ctct.has_any_active_contact() {
+                                if ctct.has_narrow_phase_contact {
                                     events.handle_contact_event(ContactEvent::Stopped(
                                         pair.collider1,
                                         pair.collider2,
@@ -241,6 +304,22 @@ impl NarrowPhase {
             let co1 = &colliders[pair.pair.collider1];
             let co2 = &colliders[pair.pair.collider2];
 
+            if !co1.collision_groups.test(co2.collision_groups) {
+                // The collision groups are checked here rather than when the pair is
+                // created because they can be modified at any time.
+                if pair.proximity != Proximity::Disjoint {
+                    events.handle_proximity_event(ProximityEvent::new(
+                        pair.pair.collider1,
+                        pair.pair.collider2,
+                        pair.proximity,
+                        Proximity::Disjoint,
+                    ));
+                    pair.proximity = Proximity::Disjoint;
+                }
+
+                return;
+            }
+
             // FIXME: avoid lookup into bodies.
             let rb1 = &bodies[co1.parent];
             let rb2 = &bodies[co2.parent];
@@ -343,6 +422,7 @@ impl NarrowPhase {
         prediction_distance: f32,
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
         par_iter_mut!(&mut self.contact_graph.graph.edges).for_each(|edge| {
@@ -350,6 +430,26 @@ impl NarrowPhase {
             let co1 = &colliders[pair.pair.collider1];
             let co2 = &colliders[pair.pair.collider2];
 
+            if !co1.collision_groups.test(co2.collision_groups) {
+                // The collision groups are checked here rather than when the pair is
+                // created because they can be modified at any time.
+                if pair.has_narrow_phase_contact {
+                    events.handle_contact_event(ContactEvent::Stopped(
+                        pair.pair.collider1,
+                        pair.pair.collider2,
+                    ));
+                }
+
+                for manifold in &mut pair.manifolds {
+                    manifold.points.clear();
+                    manifold.num_active_contacts = 0;
+                }
+
+                pair.has_narrow_phase_contact = false;
+
+                return;
+            }
+
             // FIXME: avoid lookup into bodies.
             let rb1 = &bodies[co1.parent];
             let rb2 = &bodies[co2.parent];
@@ -380,7 +480,7 @@ impl NarrowPhase {
                 .pair
                 .generator
                 .unwrap()
-                .generate_contacts(context, events);
+                .generate_contacts(context, bodies, hooks, events);
         });
 
         /*
@@ -452,6 +552,7 @@ impl NarrowPhase {
     pub(crate) fn sort_and_select_active_contacts<'a>(
         &'a mut self,
         bodies: &RigidBodySet,
+        colliders: &ColliderSet,
         out_manifolds: &mut Vec<&'a mut ContactManifold>,
         out: &mut Vec<Vec<ContactManifoldIndex>>,
     ) {
@@ -461,6 +562,14 @@ impl NarrowPhase {
 
         // FIXME: don't iterate through all the interactions.
         for inter in self.contact_graph.graph.edges.iter_mut() {
+            let co1 = &colliders[inter.weight.pair.collider1];
+            let co2 = &colliders[inter.weight.pair.collider2];
+
+            if !co1.solver_groups.test(co2.solver_groups) {
+                // The contacts are still computed, but they must not generate any force.
+                continue;
+            }
+
             for manifold in &mut inter.weight.manifolds {
                 let rb1 = &bodies[manifold.body_pair.body1];
                 let rb2 = &bodies[manifold.body_pair.body2];
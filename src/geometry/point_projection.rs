@@ -0,0 +1,102 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::time_of_impact::{to_ncollide_shape, to_support_map, SupportMapRef};
+use crate::geometry::{PointProjection, Shape, WAABBHierarchyIntersections, AABB};
+use crate::math::{Isometry, Point, Vector};
+use ncollide::bounding_volume::BoundingVolume;
+use ncollide::query::{self, algorithms::VoronoiSimplex, PointQuery};
+use ncollide::shape::FeatureId;
+
+/// Projects a point on a shape.
+///
+/// If `solid` is `true`, a point inside of the shape is projected on itself. Otherwise, it is
+/// projected on the boundary of the shape. The returned feature identifies the part of the
+/// shape the point was projected on; for composite shapes, this is `FeatureId::Face` with
+/// the index of the part.
+///
+/// Returns `None` only if the shape has nothing to project on, i.e., for an empty voxel grid
+/// or a composite shape without any part.
+pub(crate) fn project_point(
+    pos: &Isometry<f32>,
+    shape: &Shape,
+    point: &Point<f32>,
+    solid: bool,
+) -> Option<(PointProjection, FeatureId)> {
+    if shape.is_composite() {
+        composite_shape_projection(pos, shape, point, solid)
+    } else if let Shape::RoundShape(round) = shape {
+        let proj = round.project_point(pos, point, solid)?;
+        Some((proj, FeatureId::Unknown))
+    } else if let Some((shift, g)) = to_ncollide_shape(shape) {
+        let (proj, feature) = g
+            .as_point_query()?
+            .project_point_with_feature(&(pos * shift), point);
+
+        if solid && proj.is_inside {
+            Some((PointProjection::new(true, *point), FeatureId::Unknown))
+        } else {
+            Some((proj, feature))
+        }
+    } else {
+        // Some convex shapes, e.g., polygons, cylinders, or cones, are only supported
+        // through their support maps.
+        let (shift, g) = to_support_map(shape)?;
+        let proj = query::point_projection_on_support_map(
+            &(pos * shift),
+            &SupportMapRef(&*g),
+            &mut VoronoiSimplex::new(),
+            point,
+            solid,
+        );
+        Some((proj, FeatureId::Unknown))
+    }
+}
+
+fn composite_shape_projection(
+    pos: &Isometry<f32>,
+    shape: &Shape,
+    point: &Point<f32>,
+    solid: bool,
+) -> Option<(PointProjection, FeatureId)> {
+    let local_point = pos.inverse_transform_point(point);
+    let local_aabb = shape.compute_aabb(&Isometry::identity());
+    let mut workspace = WAABBHierarchyIntersections::new();
+    let mut best: Option<(f32, PointProjection, FeatureId)> = None;
+
+    // Look for the parts close to the point with search boxes of increasing sizes
+    // until the closest projection found is guaranteed to be within the search box.
+    let mut radius = local_aabb.distance_to_point(&Isometry::identity(), &local_point, true)
+        + local_aabb.half_extents().norm() / 64.0;
+
+    loop {
+        let extents = Vector::repeat(radius);
+        let search_aabb = AABB::new(local_point - extents, local_point + extents);
+
+        map_parts_in_local_aabb(
+            shape,
+            &search_aabb,
+            &mut workspace,
+            &mut |id, delta, part| {
+                if let Some((proj, _)) = project_point(delta, part, &local_point, solid) {
+                    let dist = na::distance(&local_point, &proj.point);
+
+                    if best.as_ref().map(|b| dist < b.0).unwrap_or(true) {
+                        best = Some((dist, proj, FeatureId::Face(id)));
+                    }
+                }
+            },
+        );
+
+        match &best {
+            Some((dist, _, _)) if *dist <= radius => break,
+            // A closer part may still be outside of the search box.
+            Some((dist, _, _)) => radius = *dist,
+            None if search_aabb.contains(&local_aabb) => break,
+            None => radius *= 2.0,
+        }
+    }
+
+    best.map(|(_, proj, feature)| {
+        let proj = PointProjection::new(proj.is_inside, pos * proj.point);
+        (proj, feature)
+    })
+}
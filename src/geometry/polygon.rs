@@ -1,5 +1,10 @@
+#[cfg(feature = "dim2")]
+use crate::geometry::Segment;
+use crate::geometry::{Ray, RayIntersection, Shape};
 use crate::math::{Isometry, Point, Vector};
 use ncollide::bounding_volume::AABB;
+use ncollide::shape::{FeatureId, SupportMap};
+use std::borrow::Cow;
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -24,6 +29,26 @@ impl Polygon {
         Self { vertices, normals }
     }
 
+    /// Builds a degenerate polygon with two vertices, equivalent to the given segment.
+    #[cfg(feature = "dim2")]
+    pub(crate) fn from_segment(segment: &Segment) -> Self {
+        let dir = (segment.b - segment.a)
+            .try_normalize(1.0e-6)
+            .unwrap_or_else(Vector::x);
+        let normal = Vector::new(dir.y, -dir.x);
+        Self::new(vec![segment.a, segment.b], vec![normal, -normal])
+    }
+
+    /// The polygon equivalent to `shape`, if it is a polygon or, in 2D, a segment.
+    pub(crate) fn from_shape(shape: &Shape) -> Option<Cow<Polygon>> {
+        match shape {
+            Shape::Polygon(polygon) => Some(Cow::Borrowed(polygon)),
+            #[cfg(feature = "dim2")]
+            Shape::Segment(segment) => Some(Cow::Owned(Self::from_segment(segment))),
+            _ => None,
+        }
+    }
+
     /// Compute the axis-aligned bounding box of the polygon.
     pub fn aabb(&self, pos: &Isometry<f32>) -> AABB<f32> {
         let p0 = pos * self.vertices[0];
@@ -44,6 +69,91 @@ impl Polygon {
         &self.vertices
     }
 
+    /// Computes the first intersection between this polygon and a ray.
+    ///
+    /// If `solid` is `true` and the ray starts inside of the polygon, the returned time of impact is zero.
+    #[cfg(feature = "dim2")]
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let half_spaces = self
+            .vertices
+            .iter()
+            .zip(self.normals.iter())
+            .map(|(pt, n)| (*pt, *n));
+        let (toi, normal, feature) =
+            clip_ray_with_half_spaces(&ls_ray, max_toi, solid, half_spaces)?;
+        Some(RayIntersection::new(toi, position * normal, feature))
+    }
+
+    /// Computes the first intersection between this polygon and a ray.
+    ///
+    /// The polygon has no thickness so `solid` has no effect: the ray hits the polygon only
+    /// if it crosses its plane inside of its edges.
+    #[cfg(feature = "dim3")]
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        _solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let normal = self.plane_normal()?;
+        let denom = normal.dot(&ls_ray.dir);
+
+        if denom == 0.0 {
+            // The ray is parallel to the plane of the polygon.
+            return None;
+        }
+
+        let toi = normal.dot(&(self.vertices[0] - ls_ray.origin)) / denom;
+
+        if toi < 0.0 || toi > max_toi {
+            return None;
+        }
+
+        let pt = ls_ray.point_at(toi);
+        let inside = self
+            .vertices
+            .iter()
+            .zip(self.normals.iter())
+            .all(|(v, n)| n.dot(&(pt - v)) <= 0.0);
+
+        if !inside {
+            return None;
+        }
+
+        // Make the normal face the ray origin.
+        let normal = if denom < 0.0 { normal } else { -normal };
+        Some(RayIntersection::new(
+            toi,
+            position * normal,
+            FeatureId::Face(0),
+        ))
+    }
+
+    /// The unit normal of the plane containing this polygon, or `None` if the polygon is degenerate.
+    ///
+    /// The vertices of the polygon are in counter-clockwise order when seen from the tip of this normal.
+    #[cfg(feature = "dim3")]
+    fn plane_normal(&self) -> Option<Vector<f32>> {
+        // Use Newell's method so nearly collinear consecutive vertices don't matter.
+        let mut normal = Vector::zeros();
+
+        for (i, a) in self.vertices.iter().enumerate() {
+            let b = self.vertices[(i + 1) % self.vertices.len()];
+            normal += a.coords.cross(&b.coords);
+        }
+
+        normal.try_normalize(1.0e-6)
+    }
+
     pub(crate) fn support_point(&self, dir: &Vector<f32>) -> usize {
         let mut best_dot = -f32::MAX;
         let mut best_i = 0;
@@ -74,3 +184,61 @@ impl Polygon {
         max_dot_i
     }
 }
+
+impl SupportMap<f32> for Polygon {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        self.vertices[self.support_point(dir)]
+    }
+}
+
+/// Clips a ray with the intersection of a set of half-spaces, each given by a point on its
+/// boundary and its outward normal.
+///
+/// Returns the time of impact, the local normal at the impact point and the index of the
+/// boundary plane that was hit.
+pub(crate) fn clip_ray_with_half_spaces(
+    ray: &Ray,
+    max_toi: f32,
+    solid: bool,
+    half_spaces: impl IntoIterator<Item = (Point<f32>, Vector<f32>)>,
+) -> Option<(f32, Vector<f32>, FeatureId)> {
+    let mut tmin = 0.0;
+    let mut tmax = max_toi;
+    let mut entry = None;
+    let mut exit = None;
+
+    for (i, (pt, normal)) in half_spaces.into_iter().enumerate() {
+        let denom = normal.dot(&ray.dir);
+        let dist = normal.dot(&(ray.origin - pt));
+
+        if denom == 0.0 {
+            if dist > 0.0 {
+                // The ray is parallel to this plane and outside of the half-space.
+                return None;
+            }
+        } else {
+            let t = -dist / denom;
+
+            if denom < 0.0 {
+                if t > tmin {
+                    tmin = t;
+                    entry = Some((i, normal));
+                }
+            } else if t < tmax {
+                tmax = t;
+                exit = Some((i, normal));
+            }
+
+            if tmin > tmax {
+                return None;
+            }
+        }
+    }
+
+    match entry {
+        Some((i, normal)) => Some((tmin, normal.normalize(), FeatureId::Face(i))),
+        // The ray origin is inside of the polytope.
+        None if solid => Some((0.0, Vector::zeros(), FeatureId::Unknown)),
+        None => exit.map(|(i, normal)| (tmax, -normal.normalize(), FeatureId::Face(i))),
+    }
+}
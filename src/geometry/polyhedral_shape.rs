@@ -0,0 +1,65 @@
+use crate::geometry::{
+    cuboid, Cone, ConvexPolyhedron, Cuboid, Cylinder, PolyhedronFace, Shape, Triangle,
+};
+use crate::math::{Point, Vector};
+use ncollide::shape::{Segment, SupportMap};
+
+/// A reference to a shape made of a convex polyhedral core, possibly dilated by a radius.
+///
+/// This is used by the collision-detection algorithms that work with any pair of such shapes.
+/// Cylinders and cones are included too: their curved surfaces are approximated by polygonal
+/// faces when computing support faces.
+pub(crate) enum PolyhedralShape<'a> {
+    Cuboid(&'a Cuboid),
+    Triangle(&'a Triangle),
+    Segment(Segment<f32>),
+    ConvexPolyhedron(&'a ConvexPolyhedron),
+    Cylinder(&'a Cylinder),
+    Cone(&'a Cone),
+}
+
+impl<'a> PolyhedralShape<'a> {
+    /// The polyhedral core of `shape` together with the radius it is dilated by.
+    ///
+    /// Returns `None` if `shape` does not have a polyhedral core.
+    pub fn from_shape(shape: &'a Shape) -> Option<(Self, f32)> {
+        match shape {
+            Shape::Cuboid(cuboid) => Some((PolyhedralShape::Cuboid(cuboid), 0.0)),
+            Shape::Triangle(triangle) => Some((PolyhedralShape::Triangle(triangle), 0.0)),
+            Shape::Segment(segment) => Some((PolyhedralShape::Segment(*segment), 0.0)),
+            Shape::Capsule(capsule) => Some((
+                PolyhedralShape::Segment(Segment::new(capsule.a, capsule.b)),
+                capsule.radius,
+            )),
+            Shape::ConvexPolyhedron(poly) => Some((PolyhedralShape::ConvexPolyhedron(poly), 0.0)),
+            Shape::Cylinder(cylinder) => Some((PolyhedralShape::Cylinder(cylinder), 0.0)),
+            Shape::Cone(cone) => Some((PolyhedralShape::Cone(cone), 0.0)),
+            _ => None,
+        }
+    }
+
+    /// The face of this shape with the normal most aligned with `local_dir`.
+    pub fn support_face(&self, local_dir: Vector<f32>) -> PolyhedronFace {
+        match self {
+            PolyhedralShape::Cuboid(cuboid) => cuboid::polyhedron_support_face(cuboid, local_dir),
+            PolyhedralShape::Triangle(triangle) => PolyhedronFace::from(**triangle),
+            PolyhedralShape::Segment(segment) => PolyhedronFace::from(*segment),
+            PolyhedralShape::ConvexPolyhedron(poly) => poly.support_face(local_dir),
+            PolyhedralShape::Cylinder(cylinder) => cylinder.support_face(local_dir),
+            PolyhedralShape::Cone(cone) => cone.support_face(local_dir),
+        }
+    }
+}
+
+impl<'a> SupportMap<f32> for PolyhedralShape<'a> {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        match self {
+            PolyhedralShape::Cuboid(cuboid) => cuboid::local_support_point(cuboid, *dir),
+            PolyhedralShape::Triangle(triangle) => triangle.local_support_point(dir),
+            PolyhedralShape::Segment(segment) => segment.local_support_point(dir),
+            PolyhedralShape::ConvexPolyhedron(poly) => poly.local_support_point(dir),
+            PolyhedralShape::Cylinder(cylinder) => cylinder.local_support_point(dir),
+            PolyhedralShape::Cone(cone) => cone.local_support_point(dir),
+        }
+    }
+}
@@ -6,21 +6,21 @@ use ncollide::shape::Segment;
 
 #[derive(Debug)]
 pub struct PolyhedronFace {
-    pub vertices: [Point<f32>; 4],
-    pub vids: [u8; 4], // Feature ID of the vertices.
-    pub eids: [u8; 4], // Feature ID of the edges.
-    pub fid: u8,       // Feature ID of the face.
-    pub num_vertices: usize,
+    // The vertices of the face, in counter-clockwise order. The edge `i` links the
+    // vertices `i` and `i + 1` (modulo the number of vertices).
+    pub vertices: Vec<Point<f32>>,
+    pub vids: Vec<u32>, // Feature ID of the vertices.
+    pub eids: Vec<u32>, // Feature ID of the edges.
+    pub fid: u32,       // Feature ID of the face.
 }
 
 impl From<CuboidFeatureFace> for PolyhedronFace {
     fn from(face: CuboidFeatureFace) -> Self {
         Self {
-            vertices: face.vertices,
-            vids: face.vids,
-            eids: face.eids,
+            vertices: face.vertices.to_vec(),
+            vids: face.vids.to_vec(),
+            eids: face.eids.to_vec(),
             fid: face.fid,
-            num_vertices: 4,
         }
     }
 }
@@ -28,11 +28,10 @@ impl From<CuboidFeatureFace> for PolyhedronFace {
 impl From<Triangle> for PolyhedronFace {
     fn from(tri: Triangle) -> Self {
         Self {
-            vertices: [tri.a, tri.b, tri.c, tri.c],
-            vids: [0, 2, 4, 4],
-            eids: [1, 3, 5, 5],
+            vertices: vec![tri.a, tri.b, tri.c],
+            vids: vec![0, 2, 4],
+            eids: vec![1, 3, 5],
             fid: 0,
-            num_vertices: 3,
         }
     }
 }
@@ -40,18 +39,17 @@ impl From<Triangle> for PolyhedronFace {
 impl From<Segment<f32>> for PolyhedronFace {
     fn from(seg: Segment<f32>) -> Self {
         Self {
-            vertices: [seg.a, seg.b, seg.b, seg.b],
-            vids: [0, 2, 2, 2],
-            eids: [1, 1, 1, 1],
+            vertices: vec![seg.a, seg.b],
+            vids: vec![0, 2],
+            eids: vec![1, 1],
             fid: 0,
-            num_vertices: 2,
         }
     }
 }
 
 impl PolyhedronFace {
     pub fn transform_by(&mut self, iso: &Isometry<f32>) {
-        for v in &mut self.vertices[0..self.num_vertices] {
+        for v in &mut self.vertices {
             *v = iso * *v;
         }
     }
@@ -69,50 +67,24 @@ impl PolyhedronFace {
         // and contains the origin (this is numerically OK because
         // we are not working in world-space here).
         let basis = sep_axis1.orthonormal_basis();
-        let projected_face1 = [
-            Point2::new(
-                face1.vertices[0].coords.dot(&basis[0]),
-                face1.vertices[0].coords.dot(&basis[1]),
-            ),
-            Point2::new(
-                face1.vertices[1].coords.dot(&basis[0]),
-                face1.vertices[1].coords.dot(&basis[1]),
-            ),
-            Point2::new(
-                face1.vertices[2].coords.dot(&basis[0]),
-                face1.vertices[2].coords.dot(&basis[1]),
-            ),
-            Point2::new(
-                face1.vertices[3].coords.dot(&basis[0]),
-                face1.vertices[3].coords.dot(&basis[1]),
-            ),
-        ];
-        let projected_face2 = [
-            Point2::new(
-                face2.vertices[0].coords.dot(&basis[0]),
-                face2.vertices[0].coords.dot(&basis[1]),
-            ),
-            Point2::new(
-                face2.vertices[1].coords.dot(&basis[0]),
-                face2.vertices[1].coords.dot(&basis[1]),
-            ),
-            Point2::new(
-                face2.vertices[2].coords.dot(&basis[0]),
-                face2.vertices[2].coords.dot(&basis[1]),
-            ),
-            Point2::new(
-                face2.vertices[3].coords.dot(&basis[0]),
-                face2.vertices[3].coords.dot(&basis[1]),
-            ),
-        ];
+        let project = |face: &PolyhedronFace| -> Vec<Point2<f32>> {
+            face.vertices
+                .iter()
+                .map(|v| Point2::new(v.coords.dot(&basis[0]), v.coords.dot(&basis[1])))
+                .collect()
+        };
+        let projected_face1 = project(face1);
+        let projected_face2 = project(face2);
+        let num_vertices1 = face1.vertices.len();
+        let num_vertices2 = face2.vertices.len();
 
         // Also find all the vertices located inside of the other projected face.
-        if face2.num_vertices > 2 {
+        if num_vertices2 > 2 {
             let normal2 = (face2.vertices[2] - face2.vertices[1])
                 .cross(&(face2.vertices[0] - face2.vertices[1]));
 
-            let last_index2 = face2.num_vertices as usize - 1;
-            'point_loop1: for i in 0..face1.num_vertices as usize {
+            let last_index2 = num_vertices2 - 1;
+            'point_loop1: for i in 0..num_vertices1 {
                 let p1 = projected_face1[i];
 
                 let sign = (projected_face2[0] - projected_face2[last_index2])
@@ -139,6 +111,7 @@ impl PolyhedronFace {
                         local_p2: pos21 * local_p2,
                         impulse: 0.0,
                         tangent_impulse: Contact::zero_tangent_impulse(),
+                        tangent_velocity: Vector::zeros(),
                         fid1: face1.vids[i],
                         fid2: face2.fid,
                         dist,
@@ -147,12 +120,12 @@ impl PolyhedronFace {
             }
         }
 
-        if face1.num_vertices > 2 {
+        if num_vertices1 > 2 {
             let normal1 = (face1.vertices[2] - face1.vertices[1])
                 .cross(&(face1.vertices[0] - face1.vertices[1]));
 
-            let last_index1 = face1.num_vertices as usize - 1;
-            'point_loop2: for i in 0..face2.num_vertices as usize {
+            let last_index1 = num_vertices1 - 1;
+            'point_loop2: for i in 0..num_vertices2 {
                 let p2 = projected_face2[i];
 
                 let sign = (projected_face1[0] - projected_face1[last_index1])
@@ -181,6 +154,7 @@ impl PolyhedronFace {
                         local_p2: pos21 * local_p2,
                         impulse: 0.0,
                         tangent_impulse: Contact::zero_tangent_impulse(),
+                        tangent_velocity: Vector::zeros(),
                         fid1: face1.fid,
                         fid2: face2.vids[i],
                         dist,
@@ -191,28 +165,17 @@ impl PolyhedronFace {
 
         // Now we have to compute the intersection between all pairs of
         // edges from the face 1 and from the face2.
-        for j in 0..face2.num_vertices {
-            let projected_edge2 = [
-                projected_face2[j],
-                projected_face2[(j + 1) % face2.num_vertices],
-            ];
+        for j in 0..num_vertices2 {
+            let projected_edge2 = [projected_face2[j], projected_face2[(j + 1) % num_vertices2]];
 
-            for i in 0..face1.num_vertices {
-                let projected_edge1 = [
-                    projected_face1[i],
-                    projected_face1[(i + 1) % face1.num_vertices],
-                ];
+            for i in 0..num_vertices1 {
+                let projected_edge1 =
+                    [projected_face1[i], projected_face1[(i + 1) % num_vertices1]];
                 if let Some(bcoords) = closest_points_line2d(projected_edge1, projected_edge2) {
                     if bcoords.0 > 0.0 && bcoords.0 < 1.0 && bcoords.1 > 0.0 && bcoords.1 < 1.0 {
                         // Found a contact between the two edges.
-                        let edge1 = (
-                            face1.vertices[i],
-                            face1.vertices[(i + 1) % face1.num_vertices],
-                        );
-                        let edge2 = (
-                            face2.vertices[j],
-                            face2.vertices[(j + 1) % face2.num_vertices],
-                        );
+                        let edge1 = (face1.vertices[i], face1.vertices[(i + 1) % num_vertices1]);
+                        let edge2 = (face2.vertices[j], face2.vertices[(j + 1) % num_vertices2]);
                         let local_p1 = edge1.0 * (1.0 - bcoords.0) + edge1.1.coords * bcoords.0;
                         let local_p2 = edge2.0 * (1.0 - bcoords.1) + edge2.1.coords * bcoords.1;
                         let dist = (local_p2 - local_p1).dot(&sep_axis1);
@@ -223,6 +186,7 @@ impl PolyhedronFace {
                                 local_p2: pos21 * local_p2,
                                 impulse: 0.0,
                                 tangent_impulse: Contact::zero_tangent_impulse(),
+                                tangent_velocity: Vector::zeros(),
                                 fid1: face1.eids[i],
                                 fid2: face2.eids[j],
                                 dist,
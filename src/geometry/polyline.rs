@@ -0,0 +1,291 @@
+use crate::geometry::{
+    Ray, RayIntersection, Segment, WAABBHierarchy, WAABBHierarchyIntersections, AABB,
+};
+use crate::math::{Isometry, Point, Vector};
+use na::Point2;
+use ncollide::bounding_volume::HasBoundingVolume;
+use ncollide::query::RayCast;
+use ncollide::shape::FeatureId;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A polyline, i.e., a set of segments, typically used to model static 2D terrains.
+///
+/// The contacts at a vertex shared by two segments of the polyline are filtered so that bodies
+/// sliding on the polyline don't snag on its internal vertices.
+pub struct Polyline {
+    waabb_tree: WAABBHierarchy,
+    aabb: AABB,
+    vertices: Vec<Point<f32>>,
+    indices: Vec<Point2<u32>>,
+    // For each segment, the segments ending at its first vertex and starting at its second vertex.
+    adjacency: Vec<[Option<u32>; 2]>,
+}
+
+impl Polyline {
+    /// Creates a new polyline from a vertex buffer and an index buffer.
+    ///
+    /// If no index buffer is provided, the vertices are linked to form a single chain of segments.
+    pub fn new(vertices: Vec<Point<f32>>, indices: Option<Vec<Point2<u32>>>) -> Self {
+        assert!(
+            vertices.len() > 1,
+            "A polyline must contain at least two points."
+        );
+
+        let indices = indices.unwrap_or_else(|| {
+            (0..vertices.len() as u32 - 1)
+                .map(|i| Point2::new(i, i + 1))
+                .collect()
+        });
+
+        assert!(
+            !indices.is_empty(),
+            "A polyline must contain at least one segment."
+        );
+
+        let aabb = AABB::from_points(&vertices);
+        let aabbs: Vec<_> = indices
+            .iter()
+            .map(|idx| {
+                Segment::new(vertices[idx.x as usize], vertices[idx.y as usize])
+                    .local_bounding_volume()
+            })
+            .collect();
+        let waabb_tree = WAABBHierarchy::new(&aabbs);
+
+        let mut segment_ending_at = vec![None; vertices.len()];
+        let mut segment_starting_at = vec![None; vertices.len()];
+
+        for (i, idx) in indices.iter().enumerate() {
+            segment_starting_at[idx.x as usize] = Some(i as u32);
+            segment_ending_at[idx.y as usize] = Some(i as u32);
+        }
+
+        let adjacency = indices
+            .iter()
+            .map(|idx| {
+                [
+                    segment_ending_at[idx.x as usize],
+                    segment_starting_at[idx.y as usize],
+                ]
+            })
+            .collect();
+
+        Self {
+            waabb_tree,
+            aabb,
+            vertices,
+            indices,
+            adjacency,
+        }
+    }
+
+    /// Compute the axis-aligned bounding box of this polyline.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        self.aabb.transform_by(pos)
+    }
+
+    pub(crate) fn waabbs(&self) -> &WAABBHierarchy {
+        &self.waabb_tree
+    }
+
+    /// Computes the first intersection between this polyline and a ray.
+    ///
+    /// The feature of the returned intersection identifies the segment hit by the ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let mut workspace = WAABBHierarchyIntersections::new();
+        self.waabb_tree.compute_interferences_with_predicate(
+            |aabb| aabb.intersects_ray(&Isometry::identity(), &ls_ray, max_toi),
+            &mut workspace,
+        );
+
+        let mut best: Option<RayIntersection> = None;
+
+        for segment_id in workspace.computed_interferences() {
+            if *segment_id >= self.num_segments() {
+                // Because of SIMD padding, the hierarchy may return segment indices greater
+                // than the max.
+                continue;
+            }
+
+            let max_toi = best.as_ref().map(|b| b.toi).unwrap_or(max_toi);
+            let segment = self.segment(*segment_id);
+
+            if let Some(mut inter) =
+                segment.toi_and_normal_with_ray(&Isometry::identity(), &ls_ray, max_toi, solid)
+            {
+                inter.feature = FeatureId::Face(*segment_id);
+                best = Some(inter);
+            }
+        }
+
+        best.map(|mut inter| {
+            inter.normal = position * inter.normal;
+            inter
+        })
+    }
+
+    /// The number of segments forming this polyline.
+    pub fn num_segments(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// An iterator through all the segments of this polyline.
+    pub fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.indices.iter().map(move |ids| {
+            Segment::new(self.vertices[ids.x as usize], self.vertices[ids.y as usize])
+        })
+    }
+
+    /// Get the `i`-th segment of this polyline.
+    pub fn segment(&self, i: usize) -> Segment {
+        let idx = self.indices[i];
+        Segment::new(self.vertices[idx.x as usize], self.vertices[idx.y as usize])
+    }
+
+    /// The vertex buffer of this polyline.
+    pub fn vertices(&self) -> &[Point<f32>] {
+        &self.vertices[..]
+    }
+
+    /// The index buffer of this polyline.
+    pub fn indices(&self) -> &[Point2<u32>] {
+        &self.indices
+    }
+
+    /// Checks if a contact with the `i`-th segment of this polyline is a ghost contact, i.e.,
+    /// a contact at a vertex shared with another segment that already handles it.
+    ///
+    /// Here, `local_point` is the contact point on the segment and `local_normal` is the contact
+    /// normal pointing away from the segment, both expressed in the local-space of this polyline.
+    pub(crate) fn is_ghost_contact(
+        &self,
+        i: usize,
+        local_point: &Point<f32>,
+        local_normal: &Vector<f32>,
+    ) -> bool {
+        let [prev, next] = self.adjacency[i];
+        let prev_segment = prev.map(|j| self.segment(j as usize));
+
+        is_ghost_segment_contact(
+            &self.segment(i),
+            prev_segment.as_ref(),
+            next.is_some(),
+            local_point,
+            local_normal,
+        )
+    }
+}
+
+/// Checks if a contact with `segment` is a ghost contact, i.e., a contact at a vertex shared
+/// with the previous segment (ending at `segment.a`) or the next segment (starting at
+/// `segment.b`) of a chain of segments, that is already handled by that other segment.
+///
+/// Here, `local_point` is the contact point on the segment and `local_normal` is the contact
+/// normal pointing away from the segment, both expressed in the local-space of the chain.
+pub(crate) fn is_ghost_segment_contact(
+    segment: &Segment,
+    prev_segment: Option<&Segment>,
+    has_next_segment: bool,
+    local_point: &Point<f32>,
+    local_normal: &Vector<f32>,
+) -> bool {
+    const VERTEX_EPS: f32 = 1.0e-3;
+    const NORMAL_EPS: f32 = 1.0e-4;
+
+    let ab = segment.b - segment.a;
+    let length = ab.norm();
+
+    if length <= 1.0e-6 {
+        return false;
+    }
+
+    let dir = ab / length;
+    let t = (local_point - segment.a).dot(&dir) / length;
+    let lean = local_normal.dot(&dir);
+
+    if t <= VERTEX_EPS && lean < -NORMAL_EPS {
+        // The contact normal leans toward the previous segment.
+        if let Some(prev_dir) =
+            prev_segment.and_then(|prev| (prev.b - prev.a).try_normalize(1.0e-6))
+        {
+            // The vertex is convex if, seen from the side the normal points to, the chain
+            // turns away from the other shape at this vertex. In that case, the contact is
+            // handled by this segment as long as its normal lies in the normal cone of the
+            // vertex. In every other case, it is handled by the previous segment.
+            let side = if Vector::new(-dir.y, dir.x).dot(local_normal) >= 0.0 {
+                1.0
+            } else {
+                -1.0
+            };
+            let convex = side * prev_dir.perp(&dir) < 0.0;
+            return !convex || local_normal.dot(&prev_dir) < 0.0;
+        }
+    } else if t >= 1.0 - VERTEX_EPS && lean > NORMAL_EPS {
+        // The contact normal leans toward the next segment, which handles it.
+        return has_next_segment;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::Polyline;
+    use crate::math::{Point, Vector};
+
+    #[test]
+    fn contacts_at_the_vertices_of_a_flat_polyline() {
+        let polyline = Polyline::new(
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(2.0, 0.0),
+            ],
+            None,
+        );
+        let shared = Point::new(1.0, 0.0);
+        let leaning_right = Vector::new(1.0, 1.0).normalize();
+        let leaning_left = Vector::new(-1.0, 1.0).normalize();
+
+        // Normals orthogonal to the segments are handled by both segments.
+        assert!(!polyline.is_ghost_contact(0, &shared, &Vector::y()));
+        assert!(!polyline.is_ghost_contact(1, &shared, &Vector::y()));
+
+        // Normals leaning over the shared vertex are ghost contacts.
+        assert!(polyline.is_ghost_contact(0, &shared, &leaning_right));
+        assert!(polyline.is_ghost_contact(1, &shared, &leaning_left));
+
+        // Normals leaning over the free ends of the polyline are valid.
+        assert!(!polyline.is_ghost_contact(0, &Point::origin(), &leaning_left));
+        assert!(!polyline.is_ghost_contact(1, &Point::new(2.0, 0.0), &leaning_right));
+    }
+
+    #[test]
+    fn contacts_at_a_convex_vertex_of_a_polyline() {
+        // The second segment goes down, so the shared vertex is convex seen from above.
+        let polyline = Polyline::new(
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(2.0, -1.0),
+            ],
+            None,
+        );
+        let shared = Point::new(1.0, 0.0);
+
+        // Normals in the normal cone of the vertex are handled by the second segment.
+        assert!(!polyline.is_ghost_contact(1, &shared, &Vector::y()));
+        assert!(!polyline.is_ghost_contact(1, &shared, &Vector::new(0.5, 1.0).normalize()));
+
+        // Normals leaning over the first segment are handled by the first segment.
+        assert!(polyline.is_ghost_contact(1, &shared, &Vector::new(-1.0, 1.0).normalize()));
+    }
+}
@@ -8,12 +8,26 @@ pub fn detect_proximity_ball_convex(ctxt: &mut PrimitiveProximityDetectionContex
         match ctxt.shape2 {
             Shape::Triangle(tri2) => do_detect_proximity(tri2, ball1, &ctxt),
             Shape::Cuboid(cube2) => do_detect_proximity(cube2, ball1, &ctxt),
+            Shape::Segment(segment2) => do_detect_proximity(segment2, ball1, &ctxt),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly2) => do_detect_proximity(poly2, ball1, &ctxt),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder2) => do_detect_proximity(cylinder2, ball1, &ctxt),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone2) => do_detect_proximity(cone2, ball1, &ctxt),
             _ => unimplemented!(),
         }
     } else if let Shape::Ball(ball2) = ctxt.shape2 {
         match ctxt.shape1 {
             Shape::Triangle(tri1) => do_detect_proximity(tri1, ball2, &ctxt),
             Shape::Cuboid(cube1) => do_detect_proximity(cube1, ball2, &ctxt),
+            Shape::Segment(segment1) => do_detect_proximity(segment1, ball2, &ctxt),
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly1) => do_detect_proximity(poly1, ball2, &ctxt),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder1) => do_detect_proximity(cylinder1, ball2, &ctxt),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone1) => do_detect_proximity(cone1, ball2, &ctxt),
             _ => unimplemented!(),
         }
     } else {
@@ -0,0 +1,120 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::proximity_detector::{
+    PrimitiveProximityDetectionContext, ProximityDetectionContext,
+};
+use crate::geometry::{Collider, Compound, Proximity, Shape, WAABBHierarchyIntersections};
+use crate::math::Isometry;
+use crate::ncollide::bounding_volume::BoundingVolume;
+
+pub struct CompoundShapeProximityDetectorWorkspace {
+    interferences1: WAABBHierarchyIntersections,
+    interferences2: WAABBHierarchyIntersections,
+}
+
+impl CompoundShapeProximityDetectorWorkspace {
+    pub fn new() -> Self {
+        Self {
+            interferences1: WAABBHierarchyIntersections::new(),
+            interferences2: WAABBHierarchyIntersections::new(),
+        }
+    }
+}
+
+pub fn detect_proximity_compound_shape(ctxt: &mut ProximityDetectionContext) -> Proximity {
+    let collider1 = &ctxt.colliders[ctxt.pair.pair.collider1];
+    let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
+
+    if let Shape::Compound(compound1) = collider1.shape() {
+        do_detect_proximity(compound1, collider1, collider2, ctxt)
+    } else if let Shape::Compound(compound2) = collider2.shape() {
+        do_detect_proximity(compound2, collider2, collider1, ctxt)
+    } else {
+        panic!("Invalid shape types provided.")
+    }
+}
+
+fn do_detect_proximity(
+    compound1: &Compound,
+    collider1: &Collider,
+    collider2: &Collider,
+    ctxt: &mut ProximityDetectionContext,
+) -> Proximity {
+    let workspace: &mut CompoundShapeProximityDetectorWorkspace = ctxt
+        .pair
+        .detector_workspace
+        .as_mut()
+        .expect("The CompoundShapeProximityDetectorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a CompoundShapeProximityDetectorWorkspace.");
+
+    /*
+     * Compute interferences.
+     */
+    let pos12 = collider1.position.inverse() * collider2.position;
+    let pos21 = pos12.inverse();
+    let ls_aabb2 = collider2
+        .shape()
+        .compute_aabb(&pos12)
+        .loosened(ctxt.prediction_distance);
+    compound1
+        .waabbs()
+        .compute_interferences_with(ls_aabb2, &mut workspace.interferences1);
+
+    /*
+     * Run the proximity detectors on each pair of parts.
+     */
+    let prediction_distance = ctxt.prediction_distance;
+    let dispatcher = ctxt.dispatcher;
+    let mut best_proximity = Proximity::Disjoint;
+
+    for id1 in workspace.interferences1.computed_interferences() {
+        let (delta1, part1) = match compound1.shapes().get(*id1) {
+            Some(part) => part,
+            // Because of SIMD padding, the hierarchy may return indices greater than the max.
+            None => continue,
+        };
+        let part_pos1 = collider1.position * delta1;
+        let ls_part_aabb1 = part1
+            .compute_aabb(&(pos21 * delta1))
+            .loosened(prediction_distance);
+
+        let mut handle_part_pair = |_: usize, delta2: &Isometry<f32>, part2: &Shape| {
+            if best_proximity == Proximity::Intersecting {
+                return;
+            }
+
+            let part_pos2 = collider2.position * delta2;
+            let (proximity_detector, mut workspace2) = dispatcher.dispatch_primitives(part1, part2);
+
+            let mut ctxt2 = PrimitiveProximityDetectionContext {
+                prediction_distance,
+                collider1,
+                collider2,
+                shape1: part1,
+                shape2: part2,
+                position1: &part_pos1,
+                position2: &part_pos2,
+                workspace: workspace2.as_mut().map(|w| &mut **w),
+            };
+
+            match (proximity_detector.detect_proximity)(&mut ctxt2) {
+                Proximity::Intersecting => best_proximity = Proximity::Intersecting,
+                Proximity::WithinMargin => best_proximity = Proximity::WithinMargin,
+                Proximity::Disjoint => {}
+            }
+        };
+
+        map_parts_in_local_aabb(
+            collider2.shape(),
+            &ls_part_aabb1,
+            &mut workspace.interferences2,
+            &mut handle_part_pair,
+        );
+
+        if best_proximity == Proximity::Intersecting {
+            return Proximity::Intersecting;
+        }
+    }
+
+    best_proximity
+}
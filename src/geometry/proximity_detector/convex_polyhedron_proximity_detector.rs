@@ -0,0 +1,30 @@
+use crate::geometry::proximity_detector::PrimitiveProximityDetectionContext;
+use crate::geometry::{PolyhedralShape, Proximity};
+use ncollide::query;
+
+pub fn detect_proximity_convex_polyhedron(
+    ctxt: &mut PrimitiveProximityDetectionContext,
+) -> Proximity {
+    let shape1 = PolyhedralShape::from_shape(ctxt.shape1);
+    let shape2 = PolyhedralShape::from_shape(ctxt.shape2);
+
+    if let (Some((shape1, radius1)), Some((shape2, radius2))) = (shape1, shape2) {
+        let dist = query::distance_support_map_support_map(
+            ctxt.position1,
+            &shape1,
+            ctxt.position2,
+            &shape2,
+        ) - radius1
+            - radius2;
+
+        if dist <= 0.0 {
+            Proximity::Intersecting
+        } else if dist <= ctxt.prediction_distance {
+            Proximity::WithinMargin
+        } else {
+            Proximity::Disjoint
+        }
+    } else {
+        panic!("Invalid shape types")
+    }
+}
@@ -2,9 +2,18 @@ pub use self::ball_ball_proximity_detector::detect_proximity_ball_ball;
 #[cfg(feature = "simd-is-enabled")]
 pub use self::ball_ball_proximity_detector::detect_proximity_ball_ball_simd;
 pub use self::ball_convex_proximity_detector::detect_proximity_ball_convex;
+pub use self::compound_shape_proximity_detector::{
+    detect_proximity_compound_shape, CompoundShapeProximityDetectorWorkspace,
+};
+#[cfg(feature = "dim3")]
+pub use self::convex_polyhedron_proximity_detector::detect_proximity_convex_polyhedron;
 pub use self::cuboid_cuboid_proximity_detector::detect_proximity_cuboid_cuboid;
 pub use self::cuboid_triangle_proximity_detector::detect_proximity_cuboid_triangle;
 pub use self::polygon_polygon_proximity_detector::detect_proximity_polygon_polygon;
+#[cfg(feature = "dim2")]
+pub use self::polyline_shape_proximity_detector::{
+    detect_proximity_polyline_shape, PolylineShapeProximityDetectorWorkspace,
+};
 pub use self::proximity_detector::{
     PrimitiveProximityDetectionContext, PrimitiveProximityDetector, ProximityDetectionContext,
     ProximityDetector, ProximityPhase,
@@ -14,17 +23,30 @@ pub use self::proximity_detector::{
     PrimitiveProximityDetectionContextSimd, ProximityDetectionContextSimd,
 };
 pub use self::proximity_dispatcher::{DefaultProximityDispatcher, ProximityDispatcher};
+pub use self::round_shape_proximity_detector::{
+    detect_proximity_round_shape, RoundShapeProximityDetectorWorkspace,
+};
 pub use self::trimesh_shape_proximity_detector::{
     detect_proximity_trimesh_shape, TrimeshShapeProximityDetectorWorkspace,
 };
+pub use self::voxels_shape_proximity_detector::{
+    detect_proximity_voxels_shape, VoxelsShapeProximityDetectorWorkspace,
+};
 
 mod ball_ball_proximity_detector;
 mod ball_convex_proximity_detector;
 mod ball_polygon_proximity_detector;
+mod compound_shape_proximity_detector;
+#[cfg(feature = "dim3")]
+mod convex_polyhedron_proximity_detector;
 mod cuboid_cuboid_proximity_detector;
 mod cuboid_polygon_proximity_detector;
 mod cuboid_triangle_proximity_detector;
 mod polygon_polygon_proximity_detector;
+#[cfg(feature = "dim2")]
+mod polyline_shape_proximity_detector;
 mod proximity_detector;
 mod proximity_dispatcher;
+mod round_shape_proximity_detector;
 mod trimesh_shape_proximity_detector;
+mod voxels_shape_proximity_detector;
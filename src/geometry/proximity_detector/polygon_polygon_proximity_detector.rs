@@ -1,16 +1,19 @@
 use crate::geometry::proximity_detector::PrimitiveProximityDetectionContext;
-use crate::geometry::{sat, Polygon, Proximity, Shape};
+use crate::geometry::{sat, Polygon, Proximity};
 use crate::math::Isometry;
 
 pub fn detect_proximity_polygon_polygon(
     ctxt: &mut PrimitiveProximityDetectionContext,
 ) -> Proximity {
-    if let (Shape::Polygon(polygon1), Shape::Polygon(polygon2)) = (ctxt.shape1, ctxt.shape2) {
+    if let (Some(polygon1), Some(polygon2)) = (
+        Polygon::from_shape(ctxt.shape1),
+        Polygon::from_shape(ctxt.shape2),
+    ) {
         detect_proximity(
             ctxt.prediction_distance,
-            polygon1,
+            &polygon1,
             &ctxt.position1,
-            polygon2,
+            &polygon2,
             &ctxt.position2,
         )
     } else {
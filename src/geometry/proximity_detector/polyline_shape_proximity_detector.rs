@@ -0,0 +1,94 @@
+use crate::geometry::proximity_detector::{
+    PrimitiveProximityDetectionContext, ProximityDetectionContext,
+};
+use crate::geometry::{Collider, Polyline, Proximity, Shape, WAABBHierarchyIntersections};
+use crate::ncollide::bounding_volume::BoundingVolume;
+
+pub struct PolylineShapeProximityDetectorWorkspace {
+    interferences: WAABBHierarchyIntersections,
+}
+
+impl PolylineShapeProximityDetectorWorkspace {
+    pub fn new() -> Self {
+        Self {
+            interferences: WAABBHierarchyIntersections::new(),
+        }
+    }
+}
+
+pub fn detect_proximity_polyline_shape(ctxt: &mut ProximityDetectionContext) -> Proximity {
+    let collider1 = &ctxt.colliders[ctxt.pair.pair.collider1];
+    let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
+
+    if let Shape::Polyline(polyline1) = collider1.shape() {
+        do_detect_proximity(polyline1, collider1, collider2, ctxt)
+    } else if let Shape::Polyline(polyline2) = collider2.shape() {
+        do_detect_proximity(polyline2, collider2, collider1, ctxt)
+    } else {
+        panic!("Invalid shape types provided.")
+    }
+}
+
+fn do_detect_proximity(
+    polyline1: &Polyline,
+    collider1: &Collider,
+    collider2: &Collider,
+    ctxt: &mut ProximityDetectionContext,
+) -> Proximity {
+    let workspace: &mut PolylineShapeProximityDetectorWorkspace = ctxt
+        .pair
+        .detector_workspace
+        .as_mut()
+        .expect("The PolylineShapeProximityDetectorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a PolylineShapeProximityDetectorWorkspace.");
+
+    /*
+     * Compute interferences.
+     */
+    let pos12 = collider1.position.inverse() * collider2.position;
+    let ls_aabb2 = collider2
+        .shape()
+        .compute_aabb(&pos12)
+        .loosened(ctxt.prediction_distance);
+    polyline1
+        .waabbs()
+        .compute_interferences_with(ls_aabb2, &mut workspace.interferences);
+
+    /*
+     * Run the proximity detectors on each segment.
+     */
+    let mut best_proximity = Proximity::Disjoint;
+
+    for segment_id in workspace.interferences.computed_interferences() {
+        if *segment_id >= polyline1.num_segments() {
+            // Because of SIMD padding, the hierarchy may return segment indices greater
+            // than the max.
+            continue;
+        }
+
+        let segment1 = Shape::Segment(polyline1.segment(*segment_id));
+        let (proximity_detector, mut workspace2) = ctxt
+            .dispatcher
+            .dispatch_primitives(&segment1, collider2.shape());
+
+        let mut ctxt2 = PrimitiveProximityDetectionContext {
+            prediction_distance: ctxt.prediction_distance,
+            collider1,
+            collider2,
+            shape1: &segment1,
+            shape2: collider2.shape(),
+            position1: collider1.position(),
+            position2: collider2.position(),
+            workspace: workspace2.as_mut().map(|w| &mut **w),
+        };
+
+        match (proximity_detector.detect_proximity)(&mut ctxt2) {
+            Proximity::Intersecting => return Proximity::Intersecting,
+            Proximity::WithinMargin => best_proximity = Proximity::WithinMargin,
+            Proximity::Disjoint => {}
+        }
+    }
+
+    best_proximity
+}
@@ -1,7 +1,12 @@
+#[cfg(feature = "dim2")]
+use crate::geometry::proximity_detector::PolylineShapeProximityDetectorWorkspace;
 use crate::geometry::proximity_detector::{
-    PrimitiveProximityDetector, ProximityDetector, ProximityPhase,
-    TrimeshShapeProximityDetectorWorkspace,
+    CompoundShapeProximityDetectorWorkspace, PrimitiveProximityDetector, ProximityDetector,
+    ProximityPhase, RoundShapeProximityDetectorWorkspace, TrimeshShapeProximityDetectorWorkspace,
+    VoxelsShapeProximityDetectorWorkspace,
 };
+#[cfg(feature = "dim3")]
+use crate::geometry::PolyhedralShape;
 use crate::geometry::Shape;
 use std::any::Any;
 
@@ -61,6 +66,21 @@ impl ProximityDispatcher for DefaultProximityDispatcher {
                 },
                 None,
             ),
+            #[cfg(feature = "dim2")]
+            (Shape::Segment(_), Shape::Polygon(_)) | (Shape::Polygon(_), Shape::Segment(_)) => (
+                PrimitiveProximityDetector {
+                    detect_proximity: super::detect_proximity_polygon_polygon,
+                    ..PrimitiveProximityDetector::default()
+                },
+                None,
+            ),
+            (Shape::Segment(_), Shape::Ball(_)) | (Shape::Ball(_), Shape::Segment(_)) => (
+                PrimitiveProximityDetector {
+                    detect_proximity: super::detect_proximity_ball_convex,
+                    ..PrimitiveProximityDetector::default()
+                },
+                None,
+            ),
             (Shape::Triangle(_), Shape::Ball(_)) => (
                 PrimitiveProximityDetector {
                     detect_proximity: super::detect_proximity_ball_convex,
@@ -103,6 +123,50 @@ impl ProximityDispatcher for DefaultProximityDispatcher {
                 },
                 None,
             ),
+            #[cfg(feature = "dim3")]
+            (Shape::ConvexPolyhedron(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::ConvexPolyhedron(_))
+            | (Shape::Cylinder(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::Cylinder(_))
+            | (Shape::Cone(_), Shape::Ball(_))
+            | (Shape::Ball(_), Shape::Cone(_)) => (
+                PrimitiveProximityDetector {
+                    detect_proximity: super::detect_proximity_ball_convex,
+                    ..PrimitiveProximityDetector::default()
+                },
+                None,
+            ),
+            #[cfg(feature = "dim3")]
+            (Shape::ConvexPolyhedron(_), _)
+            | (_, Shape::ConvexPolyhedron(_))
+            | (Shape::Cylinder(_), _)
+            | (_, Shape::Cylinder(_))
+            | (Shape::Cone(_), _)
+            | (_, Shape::Cone(_))
+                if PolyhedralShape::from_shape(shape1).is_some()
+                    && PolyhedralShape::from_shape(shape2).is_some() =>
+            {
+                (
+                    PrimitiveProximityDetector {
+                        detect_proximity: super::detect_proximity_convex_polyhedron,
+                        ..PrimitiveProximityDetector::default()
+                    },
+                    None,
+                )
+            }
+            (Shape::RoundShape(_), _) | (_, Shape::RoundShape(_)) => {
+                let (detector, workspace) =
+                    self.dispatch_primitives(shape1.without_border().0, shape2.without_border().0);
+                let workspace = RoundShapeProximityDetectorWorkspace::new(detector, workspace);
+
+                (
+                    PrimitiveProximityDetector {
+                        detect_proximity: super::detect_proximity_round_shape,
+                        ..PrimitiveProximityDetector::default()
+                    },
+                    Some(Box::new(workspace)),
+                )
+            }
             _ => (PrimitiveProximityDetector::default(), None),
         }
     }
@@ -113,6 +177,20 @@ impl ProximityDispatcher for DefaultProximityDispatcher {
         shape2: &Shape,
     ) -> (ProximityPhase, Option<Box<dyn Any + Send + Sync>>) {
         match (shape1, shape2) {
+            (Shape::Compound(_), _) | (_, Shape::Compound(_)) => (
+                ProximityPhase::NearPhase(ProximityDetector {
+                    detect_proximity: super::detect_proximity_compound_shape,
+                    ..ProximityDetector::default()
+                }),
+                Some(Box::new(CompoundShapeProximityDetectorWorkspace::new())),
+            ),
+            (Shape::Voxels(_), _) | (_, Shape::Voxels(_)) => (
+                ProximityPhase::NearPhase(ProximityDetector {
+                    detect_proximity: super::detect_proximity_voxels_shape,
+                    ..ProximityDetector::default()
+                }),
+                Some(Box::new(VoxelsShapeProximityDetectorWorkspace::new())),
+            ),
             (Shape::Trimesh(_), _) => (
                 ProximityPhase::NearPhase(ProximityDetector {
                     detect_proximity: super::detect_proximity_trimesh_shape,
@@ -127,6 +205,14 @@ impl ProximityDispatcher for DefaultProximityDispatcher {
                 }),
                 Some(Box::new(TrimeshShapeProximityDetectorWorkspace::new())),
             ),
+            #[cfg(feature = "dim2")]
+            (Shape::Polyline(_), _) | (_, Shape::Polyline(_)) => (
+                ProximityPhase::NearPhase(ProximityDetector {
+                    detect_proximity: super::detect_proximity_polyline_shape,
+                    ..ProximityDetector::default()
+                }),
+                Some(Box::new(PolylineShapeProximityDetectorWorkspace::new())),
+            ),
             _ => {
                 let (gen, workspace) = self.dispatch_primitives(shape1, shape2);
                 (ProximityPhase::ExactPhase(gen), workspace)
@@ -0,0 +1,60 @@
+use crate::geometry::proximity_detector::{
+    PrimitiveProximityDetectionContext, PrimitiveProximityDetector,
+};
+use crate::geometry::Proximity;
+use std::any::Any;
+
+pub struct RoundShapeProximityDetectorWorkspace {
+    detector: PrimitiveProximityDetector,
+    workspace: Option<Box<dyn Any + Send + Sync>>,
+}
+
+impl RoundShapeProximityDetectorWorkspace {
+    pub fn new(
+        detector: PrimitiveProximityDetector,
+        workspace: Option<Box<dyn Any + Send + Sync>>,
+    ) -> Self {
+        Self {
+            detector,
+            workspace,
+        }
+    }
+}
+
+pub fn detect_proximity_round_shape(ctxt: &mut PrimitiveProximityDetectionContext) -> Proximity {
+    let (shape1, border_radius1) = ctxt.shape1.without_border();
+    let (shape2, border_radius2) = ctxt.shape2.without_border();
+    let border_radius = border_radius1 + border_radius2;
+    let workspace: &mut RoundShapeProximityDetectorWorkspace = ctxt
+        .workspace
+        .as_mut()
+        .expect("The RoundShapeProximityDetectorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a RoundShapeProximityDetectorWorkspace.");
+
+    let mut ctxt2 = PrimitiveProximityDetectionContext {
+        prediction_distance: ctxt.prediction_distance + border_radius,
+        collider1: ctxt.collider1,
+        collider2: ctxt.collider2,
+        shape1,
+        shape2,
+        position1: ctxt.position1,
+        position2: ctxt.position2,
+        workspace: workspace.workspace.as_deref_mut(),
+    };
+
+    match (workspace.detector.detect_proximity)(&mut ctxt2) {
+        Proximity::Disjoint => Proximity::Disjoint,
+        Proximity::Intersecting => Proximity::Intersecting,
+        Proximity::WithinMargin => {
+            // The base shapes are within `prediction_distance + border_radius` of each other.
+            // They intersect only if they are within `border_radius` of each other.
+            ctxt2.prediction_distance = border_radius;
+
+            match (workspace.detector.detect_proximity)(&mut ctxt2) {
+                Proximity::Disjoint => Proximity::WithinMargin,
+                _ => Proximity::Intersecting,
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::proximity_detector::{
+    PrimitiveProximityDetectionContext, ProximityDetectionContext,
+};
+use crate::geometry::{Collider, Proximity, Shape, Voxels, WAABBHierarchyIntersections};
+use crate::math::Isometry;
+use crate::ncollide::bounding_volume::BoundingVolume;
+
+pub struct VoxelsShapeProximityDetectorWorkspace {
+    interferences2: WAABBHierarchyIntersections,
+}
+
+impl VoxelsShapeProximityDetectorWorkspace {
+    pub fn new() -> Self {
+        Self {
+            interferences2: WAABBHierarchyIntersections::new(),
+        }
+    }
+}
+
+pub fn detect_proximity_voxels_shape(ctxt: &mut ProximityDetectionContext) -> Proximity {
+    let collider1 = &ctxt.colliders[ctxt.pair.pair.collider1];
+    let collider2 = &ctxt.colliders[ctxt.pair.pair.collider2];
+
+    if let Shape::Voxels(voxels1) = collider1.shape() {
+        do_detect_proximity(voxels1, collider1, collider2, ctxt)
+    } else if let Shape::Voxels(voxels2) = collider2.shape() {
+        do_detect_proximity(voxels2, collider2, collider1, ctxt)
+    } else {
+        panic!("Invalid shape types provided.")
+    }
+}
+
+fn do_detect_proximity(
+    voxels1: &Voxels,
+    collider1: &Collider,
+    collider2: &Collider,
+    ctxt: &mut ProximityDetectionContext,
+) -> Proximity {
+    let workspace: &mut VoxelsShapeProximityDetectorWorkspace = ctxt
+        .pair
+        .detector_workspace
+        .as_mut()
+        .expect("The VoxelsShapeProximityDetectorWorkspace is missing.")
+        .downcast_mut()
+        .expect("Invalid workspace type, expected a VoxelsShapeProximityDetectorWorkspace.");
+
+    /*
+     * Compute interferences.
+     */
+    let pos12 = collider1.position.inverse() * collider2.position;
+    let pos21 = pos12.inverse();
+    let ls_aabb2 = collider2
+        .shape()
+        .compute_aabb(&pos12)
+        .loosened(ctxt.prediction_distance);
+
+    /*
+     * Run the proximity detectors on each pair of parts.
+     */
+    let prediction_distance = ctxt.prediction_distance;
+    let dispatcher = ctxt.dispatcher;
+    let interferences2 = &mut workspace.interferences2;
+    let part1 = Shape::Cuboid(voxels1.voxel_shape());
+    let mut best_proximity = Proximity::Disjoint;
+
+    voxels1.map_voxels_in_local_aabb(&ls_aabb2, &mut |_, key1| {
+        if best_proximity == Proximity::Intersecting {
+            return;
+        }
+
+        let delta1 = voxels1.voxel_position(key1);
+        let part_pos1 = collider1.position * delta1;
+        let ls_part_aabb1 = part1
+            .compute_aabb(&(pos21 * delta1))
+            .loosened(prediction_distance);
+
+        let mut handle_part_pair = |_: usize, delta2: &Isometry<f32>, part2: &Shape| {
+            if best_proximity == Proximity::Intersecting {
+                return;
+            }
+
+            let part_pos2 = collider2.position * delta2;
+            let (proximity_detector, mut workspace2) =
+                dispatcher.dispatch_primitives(&part1, part2);
+
+            let mut ctxt2 = PrimitiveProximityDetectionContext {
+                prediction_distance,
+                collider1,
+                collider2,
+                shape1: &part1,
+                shape2: part2,
+                position1: &part_pos1,
+                position2: &part_pos2,
+                workspace: workspace2.as_mut().map(|w| &mut **w),
+            };
+
+            match (proximity_detector.detect_proximity)(&mut ctxt2) {
+                Proximity::Intersecting => best_proximity = Proximity::Intersecting,
+                Proximity::WithinMargin => best_proximity = Proximity::WithinMargin,
+                Proximity::Disjoint => {}
+            }
+        };
+
+        map_parts_in_local_aabb(
+            collider2.shape(),
+            &ls_part_aabb1,
+            interferences2,
+            &mut handle_part_pair,
+        );
+    });
+
+    best_proximity
+}
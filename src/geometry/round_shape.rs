@@ -0,0 +1,366 @@
+use crate::dynamics::MassProperties;
+use crate::geometry::time_of_impact::to_support_map;
+use crate::geometry::{Ray, RayIntersection, Shape, AABB};
+use crate::math::{Isometry, Point, Vector};
+use approx::AbsDiffEq;
+use ncollide::bounding_volume::BoundingVolume;
+use ncollide::query::{self, algorithms::VoronoiSimplex, PointProjection};
+use ncollide::shape::SupportMap;
+#[cfg(feature = "dim3")]
+use {crate::geometry::ConvexPolyhedron, num::Zero};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A convex shape dilated by a border radius.
+///
+/// This is the Minkowski sum of the base shape and of a ball with a radius equal to the
+/// border radius. Rounding the edges and vertices of polyhedral shapes like cuboids makes
+/// contacts between them smoother, which reduces jitter on stacks of such shapes.
+pub struct RoundShape {
+    /// The convex shape dilated by the border radius.
+    pub base_shape: Box<Shape>,
+    /// The radius of the rounded border of this shape.
+    pub border_radius: f32,
+}
+
+impl RoundShape {
+    /// Creates a new round shape by dilating `base_shape` by `border_radius`.
+    ///
+    /// Panics if `base_shape` is not convex, i.e., if it is a triangle mesh, a polyline,
+    /// a heightfield, or a compound shape.
+    pub fn new(base_shape: Shape, border_radius: f32) -> Self {
+        assert!(border_radius >= 0.0, "The border radius must be positive.");
+
+        match base_shape {
+            // Avoid nesting round shapes: the radii simply add up.
+            Shape::RoundShape(round) => Self {
+                base_shape: round.base_shape,
+                border_radius: round.border_radius + border_radius,
+            },
+            base_shape if base_shape.is_composite() => {
+                panic!("The base shape of a round shape must be convex.")
+            }
+            base_shape => Self {
+                base_shape: Box::new(base_shape),
+                border_radius,
+            },
+        }
+    }
+
+    /// The axis-aligned bounding box of this round shape.
+    pub fn aabb(&self, position: &Isometry<f32>) -> AABB {
+        self.base_shape
+            .compute_aabb(position)
+            .loosened(self.border_radius)
+    }
+
+    /// Computes the first intersection between this round shape and a ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let (shift, base_shape) = to_support_map(&self.base_shape)?;
+        let dilated = DilatedSupportMap {
+            base: &*base_shape,
+            radius: self.border_radius,
+        };
+
+        query::ray_intersection_with_support_map_with_params(
+            &(position * shift),
+            &dilated,
+            &mut VoronoiSimplex::new(),
+            ray,
+            max_toi,
+            solid,
+        )
+    }
+
+    /// Projects a point on this round shape.
+    pub fn project_point(
+        &self,
+        position: &Isometry<f32>,
+        point: &Point<f32>,
+        solid: bool,
+    ) -> Option<PointProjection<f32>> {
+        let (shift, base_shape) = to_support_map(&self.base_shape)?;
+        let dilated = DilatedSupportMap {
+            base: &*base_shape,
+            radius: self.border_radius,
+        };
+
+        Some(query::point_projection_on_support_map(
+            &(position * shift),
+            &dilated,
+            &mut VoronoiSimplex::new(),
+            point,
+            solid,
+        ))
+    }
+
+    /// Computes the mass properties of this round shape, assuming it has the given density.
+    ///
+    /// These are exact for balls, capsules, and cuboids. For any other base shape, they are
+    /// computed from the convex hull of points sampled on the boundary of the round shape, which
+    /// underestimates the mass of its rounded edges and vertices by about one percent.
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        let r = self.border_radius;
+
+        match &*self.base_shape {
+            Shape::Ball(ball) => MassProperties::from_ball(density, ball.radius + r),
+            Shape::Capsule(caps) => {
+                MassProperties::from_capsule(density, caps.a, caps.b, caps.radius + r)
+            }
+            Shape::Cuboid(c) => MassProperties::from_round_cuboid(density, c.half_extents, r),
+            base_shape if r == 0.0 => base_shape.mass_properties(density),
+            base_shape => match to_support_map(base_shape) {
+                Some((shift, base_shape)) => {
+                    let dilated = DilatedSupportMap {
+                        base: &*base_shape,
+                        radius: r,
+                    };
+                    sampled_mass_properties(density, &dilated).transform_by(&shift)
+                }
+                None => base_shape.mass_properties(density),
+            },
+        }
+    }
+}
+
+/// The number of directions along which the boundary of a round shape is sampled to compute
+/// its mass properties.
+#[cfg(feature = "dim2")]
+const NUM_MASS_SAMPLES: u32 = 128;
+#[cfg(feature = "dim3")]
+const NUM_MASS_SAMPLES: u32 = 512;
+
+/// Computes the mass properties of the polygon with vertices sampled on the boundary of `shape`.
+#[cfg(feature = "dim2")]
+fn sampled_mass_properties(density: f32, shape: &dyn SupportMap<f32>) -> MassProperties {
+    // The support points along directions sorted by angle are in counter-clockwise order.
+    let vertices: Vec<_> = (0..NUM_MASS_SAMPLES)
+        .map(|i| {
+            let angle = i as f32 * std::f32::consts::PI * 2.0 / NUM_MASS_SAMPLES as f32;
+            shape.local_support_point(&Vector::new(angle.cos(), angle.sin()))
+        })
+        .collect();
+
+    MassProperties::from_polygon(density, &vertices)
+}
+
+/// Computes the mass properties of the convex hull of points sampled on the boundary of `shape`.
+#[cfg(feature = "dim3")]
+fn sampled_mass_properties(density: f32, shape: &dyn SupportMap<f32>) -> MassProperties {
+    // The directions are distributed evenly on the unit sphere along a Fibonacci spiral.
+    let golden_angle = std::f32::consts::PI * (3.0 - 5.0f32.sqrt());
+    let points: Vec<_> = (0..NUM_MASS_SAMPLES)
+        .map(|i| {
+            let y = 1.0 - (i as f32 + 0.5) * 2.0 / NUM_MASS_SAMPLES as f32;
+            let radius = (1.0 - y * y).sqrt();
+            let angle = golden_angle * i as f32;
+            let dir = Vector::new(angle.cos() * radius, y, angle.sin() * radius);
+            shape.local_support_point(&dir)
+        })
+        .collect();
+
+    match ConvexPolyhedron::from_points(&points) {
+        Some(hull) => {
+            MassProperties::from_convex_polyhedron(density, hull.vertices(), hull.indices())
+        }
+        // This can't happen with a positive border radius since the round shape has a volume.
+        None => MassProperties::zero(),
+    }
+}
+
+/// A support map dilated by a ball.
+struct DilatedSupportMap<'a> {
+    base: &'a dyn SupportMap<f32>,
+    radius: f32,
+}
+
+impl<'a> SupportMap<f32> for DilatedSupportMap<'a> {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        let pt = self.base.local_support_point(dir);
+
+        if let Some(dir) = dir.try_normalize(f32::default_epsilon()) {
+            pt + dir * self.radius
+        } else {
+            pt
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RoundShape;
+    use crate::dynamics::MassProperties;
+    use crate::geometry::{Cuboid, Shape, Triangle};
+    use crate::math::{Point, Vector};
+    use std::f32::consts::PI;
+
+    fn assert_mass_eq(mprops: &MassProperties, mass: f32, tolerance: f32) {
+        assert!(
+            (mprops.inv_mass * mass - 1.0).abs() < tolerance,
+            "mass: {}, expected: {}",
+            1.0 / mprops.inv_mass,
+            mass
+        );
+    }
+
+    #[test]
+    fn round_cuboid_with_zero_radius_has_the_mass_properties_of_a_cuboid() {
+        let cuboid = Shape::Cuboid(Cuboid::new(Vector::repeat(0.5)));
+        let round_cuboid = RoundShape::new(cuboid.clone(), 0.0);
+        assert_eq!(
+            round_cuboid.mass_properties(2.0),
+            cuboid.mass_properties(2.0)
+        );
+    }
+
+    #[test]
+    fn round_cuboid_mass_properties() {
+        let (half_extents, r) = (Vector::repeat(0.5), 0.2);
+        let round_cuboid = RoundShape::new(Shape::Cuboid(Cuboid::new(half_extents)), r);
+        let mprops = round_cuboid.mass_properties(1.0);
+
+        // The volume of a Minkowski sum is given by the Steiner formula.
+        let extents = half_extents * 2.0;
+        #[cfg(feature = "dim2")]
+        let volume = extents.x * extents.y + 2.0 * r * (extents.x + extents.y) + PI * r * r;
+        #[cfg(feature = "dim3")]
+        let volume = extents.x * extents.y * extents.z
+            + 2.0 * r * (extents.x * extents.y + extents.y * extents.z + extents.z * extents.x)
+            + PI * r * r * (extents.x + extents.y + extents.z)
+            + PI * r * r * r * 4.0 / 3.0;
+
+        assert_mass_eq(&mprops, volume, 1.0e-2);
+        assert!(mprops.local_com.coords.norm() < 1.0e-4);
+
+        // The rounded border makes the shape lighter than the cuboid with dilated extents.
+        let dilated = Shape::Cuboid(Cuboid::new(half_extents.add_scalar(r)));
+        assert!(mprops.inv_mass > dilated.mass_properties(1.0).inv_mass);
+    }
+
+    fn triangle_vertices() -> [Point<f32>; 3] {
+        #[cfg(feature = "dim2")]
+        return [
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(0.0, 4.0),
+        ];
+        #[cfg(feature = "dim3")]
+        return [
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(3.0, 0.0, 0.0),
+            Point::new(0.0, 4.0, 0.0),
+        ];
+    }
+
+    #[test]
+    fn round_shapes_with_tiny_bases_have_the_mass_properties_of_a_ball() {
+        let r = 1.0;
+        let ball_mprops = MassProperties::from_ball(1.0, r);
+        let [a, b, c] = triangle_vertices();
+        let tiny_triangle = Triangle::new(a * 1.0e-4, b * 1.0e-4, c * 1.0e-4);
+        let tiny_bases = [
+            Shape::Cuboid(Cuboid::new(Vector::repeat(1.0e-4))),
+            Shape::Triangle(tiny_triangle),
+        ];
+
+        for base in &tiny_bases {
+            // The mass properties of the round triangle are approximate.
+            let mprops = RoundShape::new(base.clone(), r).mass_properties(1.0);
+            assert_mass_eq(&mprops, 1.0 / ball_mprops.inv_mass, 2.0e-2);
+            assert!(mprops.local_com.coords.norm() < 1.0e-3);
+
+            #[cfg(feature = "dim2")]
+            assert!(
+                (mprops.inv_principal_inertia_sqrt / ball_mprops.inv_principal_inertia_sqrt - 1.0)
+                    .abs()
+                    < 1.0e-2
+            );
+            #[cfg(feature = "dim3")]
+            {
+                let inertia = mprops.reconstruct_inertia_matrix();
+                let ball_inertia = ball_mprops.reconstruct_inertia_matrix();
+                assert!((inertia - ball_inertia).norm() < 2.0e-2 * ball_inertia.norm());
+            }
+        }
+    }
+
+    #[test]
+    fn round_triangle_mass_properties() {
+        let vertices = triangle_vertices();
+        let r = 0.1;
+        let triangle = Triangle::new(vertices[0], vertices[1], vertices[2]);
+        let mprops = RoundShape::new(Shape::Triangle(triangle), r).mass_properties(1.0);
+
+        // The round triangle is made of the (thickened, in 3D) triangle, of pieces of cylinders
+        // on its edges, and of pieces of balls on its vertices.
+        let area = 6.0;
+        #[cfg(feature = "dim2")]
+        let (face_volume, edge_section, edge_com_offset, ball_volume) =
+            (area, r, r / 2.0, PI * r * r);
+        #[cfg(feature = "dim3")]
+        let (face_volume, edge_section, edge_com_offset, ball_volume) = (
+            area * 2.0 * r,
+            PI * r * r / 2.0,
+            4.0 * r / (3.0 * PI),
+            PI * r * r * r * 4.0 / 3.0,
+        );
+
+        let centroid = (vertices[0].coords + vertices[1].coords + vertices[2].coords) / 3.0;
+        let mut volume = face_volume;
+        let mut moment = centroid * face_volume;
+
+        for i in 0..3 {
+            let (a, b, c) = (vertices[i], vertices[(i + 1) % 3], vertices[(i + 2) % 3]);
+            let edge = b - a;
+            let mut normal = Vector::zeros();
+            normal[0] = edge[1];
+            normal[1] = -edge[0];
+            let normal = normal.normalize();
+            let edge_volume = edge.norm() * edge_section;
+            volume += edge_volume;
+            moment += (na::center(&a, &b).coords + normal * edge_com_offset) * edge_volume;
+
+            // The piece of ball on the vertex `b` has an angle equal to the exterior angle at `b`.
+            let exterior_angle = edge.normalize().dot(&(c - b).normalize()).acos();
+            let vertex_volume = ball_volume * exterior_angle / (2.0 * PI);
+            volume += vertex_volume;
+            moment += b.coords * vertex_volume;
+        }
+
+        assert_mass_eq(&mprops, volume, 1.0e-2);
+        assert!((mprops.local_com.coords - moment / volume).norm() < 1.0e-2);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn round_cylinder_and_cone_mass_properties() {
+        use crate::geometry::{Cone, Cylinder};
+
+        let (half_height, radius, r) = (0.5, 0.3, 0.1);
+        let height = half_height * 2.0;
+
+        // Steiner formula for a cylinder: its mean width integral involves the rim length.
+        let round_cylinder =
+            RoundShape::new(Shape::Cylinder(Cylinder::new(half_height, radius)), r);
+        let volume = PI * radius * radius * height
+            + r * (2.0 * PI * radius * radius + 2.0 * PI * radius * height)
+            + PI * r * r * (height + PI * radius)
+            + PI * r * r * r * 4.0 / 3.0;
+        let mprops = round_cylinder.mass_properties(1.0);
+        assert_mass_eq(&mprops, volume, 1.0e-2);
+        assert!(mprops.local_com.coords.norm() < 1.0e-3);
+
+        // The round cone is between the cone and the cone with dilated dimensions.
+        let cone = Shape::Cone(Cone::new(half_height, radius));
+        let round_cone = RoundShape::new(cone.clone(), r).mass_properties(1.0);
+        assert!(round_cone.inv_mass < cone.mass_properties(1.0).inv_mass);
+        let dilated = Shape::Cone(Cone::new(half_height + r * 2.0, radius + r * 2.0));
+        assert!(round_cone.inv_mass > dilated.mass_properties(1.0).inv_mass);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::geometry::compound::map_parts_in_local_aabb;
+use crate::geometry::{Shape, WAABBHierarchyIntersections, TOI};
+use crate::math::{Isometry, Point, Vector};
+use ncollide::bounding_volume::{BoundingVolume, AABB};
+use ncollide::query;
+use ncollide::shape::SupportMap;
+
+/// Computes the smallest time at which two shapes under translational movement start touching.
+///
+/// Returns `None` if the shapes don't touch before `max_toi`. The witness points and normals of
+/// the returned `TOI` are expressed in the local-space of their respective shapes.
+pub(crate) fn time_of_impact(
+    pos1: &Isometry<f32>,
+    vel1: &Vector<f32>,
+    shape1: &Shape,
+    pos2: &Isometry<f32>,
+    vel2: &Vector<f32>,
+    shape2: &Shape,
+    max_toi: f32,
+) -> Option<TOI> {
+    if shape1.is_composite() {
+        composite_shape_toi(pos1, vel1, shape1, pos2, vel2, shape2, max_toi)
+    } else if shape2.is_composite() {
+        composite_shape_toi(pos2, vel2, shape2, pos1, vel1, shape1, max_toi)
+            .map(|toi| toi.swapped())
+    } else {
+        convex_shape_toi(pos1, vel1, shape1, pos2, vel2, shape2, max_toi)
+    }
+}
+
+fn convex_shape_toi(
+    pos1: &Isometry<f32>,
+    vel1: &Vector<f32>,
+    shape1: &Shape,
+    pos2: &Isometry<f32>,
+    vel2: &Vector<f32>,
+    shape2: &Shape,
+    max_toi: f32,
+) -> Option<TOI> {
+    // Round shapes are handled by looking for the time at which their base shapes
+    // are separated by the sum of their border radii.
+    let (shape1, border_radius1) = shape1.without_border();
+    let (shape2, border_radius2) = shape2.without_border();
+    let target_distance = border_radius1 + border_radius2;
+
+    let (shift1, shift2, toi) = match (to_ncollide_shape(shape1), to_ncollide_shape(shape2)) {
+        (Some((shift1, g1)), Some((shift2, g2))) => {
+            let toi = query::time_of_impact(
+                &query::DefaultTOIDispatcher,
+                &(pos1 * shift1),
+                vel1,
+                &*g1,
+                &(pos2 * shift2),
+                vel2,
+                &*g2,
+                max_toi,
+                target_distance,
+            )
+            .ok()
+            .flatten()?;
+            (shift1, shift2, toi)
+        }
+        _ => {
+            // Some convex shapes, e.g., polygons, cylinders and cones, are only supported
+            // through their support maps.
+            let (shift1, g1) = to_support_map(shape1)?;
+            let (shift2, g2) = to_support_map(shape2)?;
+            let toi = query::time_of_impact_support_map_support_map(
+                &(pos1 * shift1),
+                vel1,
+                &*g1,
+                &(pos2 * shift2),
+                vel2,
+                &*g2,
+                max_toi,
+                target_distance,
+            )?;
+            (shift1, shift2, toi)
+        }
+    };
+
+    // Express the result in the local-space of the original shapes.
+    Some(TOI {
+        toi: toi.toi,
+        witness1: shift1 * (toi.witness1 + *toi.normal1 * border_radius1),
+        witness2: shift2 * (toi.witness2 + *toi.normal2 * border_radius2),
+        normal1: shift1 * toi.normal1,
+        normal2: shift2 * toi.normal2,
+        status: toi.status,
+    })
+}
+
+fn composite_shape_toi(
+    pos1: &Isometry<f32>,
+    vel1: &Vector<f32>,
+    shape1: &Shape,
+    pos2: &Isometry<f32>,
+    vel2: &Vector<f32>,
+    shape2: &Shape,
+    max_toi: f32,
+) -> Option<TOI> {
+    // Compute the AABB swept by the second shape, in the local-space of the composite shape.
+    let pos12 = pos1.inverse() * pos2;
+    let ls_vel12 = pos1.inverse_transform_vector(&(vel2 - vel1));
+    let aabb2 = shape2.compute_aabb(&pos12);
+    let shift = ls_vel12 * max_toi;
+    let swept_aabb2 = aabb2.merged(&AABB::new(aabb2.mins + shift, aabb2.maxs + shift));
+
+    let mut best: Option<TOI> = None;
+    let mut check_part = |_: usize, delta1: &Isometry<f32>, part1: &Shape| {
+        let max_toi = best.as_ref().map(|b| b.toi).unwrap_or(max_toi);
+        let part_pos1 = pos1 * delta1;
+
+        if let Some(mut toi) = time_of_impact(&part_pos1, vel1, part1, pos2, vel2, shape2, max_toi)
+        {
+            if best.as_ref().map(|b| toi.toi < b.toi).unwrap_or(true) {
+                // Express the result in the local-space of the composite shape.
+                toi.witness1 = delta1 * toi.witness1;
+                toi.normal1 = delta1 * toi.normal1;
+                best = Some(toi);
+            }
+        }
+    };
+
+    let mut workspace = WAABBHierarchyIntersections::new();
+    map_parts_in_local_aabb(shape1, &swept_aabb2, &mut workspace, &mut check_part);
+
+    best
+}
+
+/// Converts a convex shape to an ncollide shape, together with the position of that ncollide
+/// shape relative to the local-space of the original shape.
+///
+/// Returns `None` for round shapes: their base shape should be converted instead. Returns `None`
+/// for cylinders, cones and 3D polygons too because they have no ncollide `Shape` equivalent, and
+/// for 2D polygons and triangles, and convex polyhedra, because their ncollide equivalent would
+/// recompute their convex hull each time: use `to_support_map` for these.
+pub(crate) fn to_ncollide_shape(
+    shape: &Shape,
+) -> Option<(Isometry<f32>, Box<dyn ncollide::shape::Shape<f32>>)> {
+    match shape {
+        Shape::Ball(ball) => Some((Isometry::identity(), Box::new(*ball))),
+        Shape::Cuboid(cuboid) => Some((Isometry::identity(), Box::new(*cuboid))),
+        Shape::Capsule(caps) => {
+            if caps.radius == 0.0 {
+                let segment = ncollide::shape::Segment::new(caps.a, caps.b);
+                Some((Isometry::identity(), Box::new(segment)))
+            } else {
+                // NOTE: the ncollide capsule is aligned with the `y` axis.
+                let ncollide_caps = ncollide::shape::Capsule::new(caps.half_height(), caps.radius);
+                Some((caps.transform_wrt_y(), Box::new(ncollide_caps)))
+            }
+        }
+        Shape::Polygon(_) => None,
+        #[cfg(feature = "dim2")]
+        Shape::Triangle(_) => None,
+        #[cfg(feature = "dim3")]
+        Shape::Triangle(triangle) => Some((Isometry::identity(), Box::new(*triangle))),
+        #[cfg(feature = "dim3")]
+        Shape::ConvexPolyhedron(_) | Shape::Cylinder(_) | Shape::Cone(_) => None,
+        Shape::Segment(segment) => Some((Isometry::identity(), Box::new(*segment))),
+        #[cfg(feature = "dim2")]
+        Shape::Polyline(_) => None,
+        Shape::Trimesh(_)
+        | Shape::HeightField(_)
+        | Shape::Voxels(_)
+        | Shape::Compound(_)
+        | Shape::RoundShape(_) => None,
+    }
+}
+
+/// Converts a convex shape to an ncollide support map, together with the position of that
+/// support map relative to the local-space of the original shape.
+///
+/// Returns `None` for non-convex shapes and for round shapes: the base shape of a round shape
+/// should be converted instead.
+pub(crate) fn to_support_map(shape: &Shape) -> Option<(Isometry<f32>, Box<dyn SupportMap<f32>>)> {
+    match shape {
+        Shape::Ball(ball) => Some((Isometry::identity(), Box::new(*ball))),
+        Shape::Cuboid(cuboid) => Some((Isometry::identity(), Box::new(*cuboid))),
+        Shape::Capsule(caps) => {
+            if caps.radius == 0.0 {
+                let segment = ncollide::shape::Segment::new(caps.a, caps.b);
+                Some((Isometry::identity(), Box::new(segment)))
+            } else {
+                // NOTE: the ncollide capsule is aligned with the `y` axis.
+                let ncollide_caps = ncollide::shape::Capsule::new(caps.half_height(), caps.radius);
+                Some((caps.transform_wrt_y(), Box::new(ncollide_caps)))
+            }
+        }
+        Shape::Polygon(poly) => Some((Isometry::identity(), Box::new(poly.clone()))),
+        Shape::Triangle(triangle) => Some((Isometry::identity(), Box::new(*triangle))),
+        #[cfg(feature = "dim3")]
+        Shape::ConvexPolyhedron(poly) => Some((Isometry::identity(), Box::new(poly.clone()))),
+        #[cfg(feature = "dim3")]
+        Shape::Cylinder(cylinder) => {
+            let cylinder = ncollide::shape::Cylinder::new(cylinder.half_height, cylinder.radius);
+            Some((Isometry::identity(), Box::new(cylinder)))
+        }
+        #[cfg(feature = "dim3")]
+        Shape::Cone(cone) => {
+            let cone = ncollide::shape::Cone::new(cone.half_height, cone.radius);
+            Some((Isometry::identity(), Box::new(cone)))
+        }
+        Shape::Segment(segment) => Some((Isometry::identity(), Box::new(*segment))),
+        #[cfg(feature = "dim2")]
+        Shape::Polyline(_) => None,
+        Shape::Trimesh(_)
+        | Shape::HeightField(_)
+        | Shape::Voxels(_)
+        | Shape::Compound(_)
+        | Shape::RoundShape(_) => None,
+    }
+}
+
+/// A sized support map, for the ncollide queries that don't accept support map trait-objects.
+pub(crate) struct SupportMapRef<'a>(pub &'a dyn SupportMap<f32>);
+
+impl<'a> SupportMap<f32> for SupportMapRef<'a> {
+    fn local_support_point(&self, dir: &Vector<f32>) -> Point<f32> {
+        self.0.local_support_point(dir)
+    }
+}
@@ -1,9 +1,236 @@
+use crate::geometry::Triangle;
 #[cfg(feature = "dim2")]
-use crate::geometry::{CuboidFeatureFace, Triangle};
+use crate::geometry::{CuboidFeatureFace, Ray, RayIntersection};
 #[cfg(feature = "dim2")]
+use crate::math::Isometry;
+#[cfg(feature = "dim3")]
+use crate::math::Point;
 use crate::math::Vector;
 
 #[cfg(feature = "dim2")]
 pub fn support_face(_triangle: &Triangle, _local_dir: Vector<f32>) -> CuboidFeatureFace {
     unimplemented!()
 }
+
+/// Computes the first intersection between a 2D triangle and a ray.
+#[cfg(feature = "dim2")]
+pub fn cast_ray(
+    triangle: &Triangle,
+    position: &Isometry<f32>,
+    ray: &Ray,
+    max_toi: f32,
+    solid: bool,
+) -> Option<RayIntersection> {
+    let pts = [triangle.a, triangle.b, triangle.c];
+    let half_spaces = (0..3).map(|i| {
+        let a = pts[i];
+        let b = pts[(i + 1) % 3];
+        let c = pts[(i + 2) % 3];
+        let ab = b - a;
+        let normal = Vector::new(ab.y, -ab.x);
+
+        // Make sure the normal points outward, independently from the triangle orientation.
+        if normal.dot(&(c - a)) > 0.0 {
+            (a, -normal)
+        } else {
+            (a, normal)
+        }
+    });
+
+    let ls_ray = ray.inverse_transform_by(position);
+    let (toi, normal, feature) =
+        crate::geometry::polygon::clip_ray_with_half_spaces(&ls_ray, max_toi, solid, half_spaces)?;
+    Some(RayIntersection::new(toi, position * normal, feature))
+}
+
+/// Checks if `local_normal` is a contact normal pointing over an internal edge of a triangle mesh,
+/// for a contact at `local_point` on `triangle`.
+///
+/// The vertices of the adjacent triangles opposite to the edges `ab`, `bc`, and `ca` of `triangle`
+/// are given by `opposite_vertices`, with `None` for the edges on the boundary of the mesh. The
+/// contact point and the contact normal, pointing away from the triangle, are both expressed in the
+/// local-space of the mesh. Such a normal should be replaced by the normal of the triangle: the
+/// contact is already handled by the adjacent triangle, and keeping it would make bodies sliding on
+/// the mesh bump on its internal edges.
+#[cfg(feature = "dim3")]
+pub(crate) fn is_internal_edge_normal(
+    triangle: &Triangle,
+    opposite_vertices: &[Option<Point<f32>>; 3],
+    local_point: &Point<f32>,
+    local_normal: &Vector<f32>,
+) -> bool {
+    const NORMAL_EPS: f32 = 1.0e-4;
+
+    let face_normal = match triangle.normal() {
+        Some(normal) => *normal,
+        None => return false,
+    };
+    // The normal of the face on the side of the triangle the contact normal points to.
+    let face_normal = if face_normal.dot(local_normal) < 0.0 {
+        -face_normal
+    } else {
+        face_normal
+    };
+
+    if face_normal.dot(local_normal) >= 1.0 - NORMAL_EPS {
+        return false;
+    }
+
+    let pts = [triangle.a, triangle.b, triangle.c];
+    let size = (0..3)
+        .map(|k| (pts[(k + 1) % 3] - pts[k]).norm())
+        .fold(0.0, f32::max);
+    let dist_eps = size * 1.0e-3;
+    let mut points_over_internal_edge = false;
+
+    for k in 0..3 {
+        let a = pts[k];
+        let b = pts[(k + 1) % 3];
+        let c = pts[(k + 2) % 3];
+        let edge_dir = match (b - a).try_normalize(1.0e-6) {
+            Some(dir) => dir,
+            None => continue,
+        };
+
+        // The direction orthogonal to the edge in the plane of the triangle, pointing outward.
+        let mut tangent = edge_dir.cross(&face_normal);
+        if tangent.dot(&(c - a)) > 0.0 {
+            tangent = -tangent;
+        }
+
+        let on_edge = (local_point - a).dot(&tangent) >= -dist_eps;
+        if !on_edge || local_normal.dot(&tangent) <= NORMAL_EPS {
+            continue;
+        }
+
+        match opposite_vertices[k] {
+            // Any normal pointing over a boundary edge is valid.
+            None => return false,
+            Some(opposite) => {
+                // The edge is convex if the adjacent triangle lies below the plane of the face.
+                // In that case, the normals between the normals of both triangles are valid.
+                let to_opposite = opposite - a;
+
+                if to_opposite.dot(&face_normal) < -dist_eps {
+                    let adj_tangent = edge_dir * to_opposite.dot(&edge_dir) - to_opposite;
+
+                    if local_normal.dot(&adj_tangent) >= -NORMAL_EPS * adj_tangent.norm() {
+                        return false;
+                    }
+                }
+
+                points_over_internal_edge = true;
+            }
+        }
+    }
+
+    points_over_internal_edge
+}
+
+#[cfg(all(test, feature = "dim3"))]
+mod test {
+    use super::is_internal_edge_normal;
+    use crate::geometry::Triangle;
+    use crate::math::{Point, Vector};
+
+    // A triangle of the unit square on the `y = 0` plane. Its edge `ab` is the diagonal of the
+    // square, shared with a second triangle with the vertex `opposite` opposite to that edge.
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 1.0),
+            Point::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    fn opposite_vertices(opposite: Point<f32>) -> [Option<Point<f32>>; 3] {
+        [Some(opposite), None, None]
+    }
+
+    #[test]
+    fn face_normals_are_not_internal_edge_normals() {
+        let opposite = opposite_vertices(Point::new(0.0, 0.0, 1.0));
+        let on_diagonal = Point::new(0.5, 0.0, 0.5);
+        assert!(!is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &Vector::y()
+        ));
+        assert!(!is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &-Vector::y()
+        ));
+    }
+
+    #[test]
+    fn normals_leaning_over_a_flat_internal_edge_are_internal_edge_normals() {
+        let opposite = opposite_vertices(Point::new(0.0, 0.0, 1.0));
+        let on_diagonal = Point::new(0.5, 0.0, 0.5);
+        let leaning = Vector::new(-0.5, 1.0, 0.5).normalize();
+        assert!(is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &leaning
+        ));
+
+        // Leaning toward the interior of the triangle isn't leaning over the edge.
+        assert!(!is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &Vector::new(0.5, 1.0, -0.5).normalize()
+        ));
+    }
+
+    #[test]
+    fn normals_leaning_over_a_boundary_edge_are_valid() {
+        let opposite = opposite_vertices(Point::new(0.0, 0.0, 1.0));
+        let on_boundary = Point::new(0.5, 0.0, 0.0);
+        let leaning = Vector::new(0.0, 1.0, -1.0).normalize();
+        assert!(!is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_boundary,
+            &leaning
+        ));
+    }
+
+    #[test]
+    fn normals_between_the_faces_of_a_convex_edge_are_valid() {
+        // The adjacent triangle goes down, so the diagonal is a convex edge.
+        let opposite = opposite_vertices(Point::new(0.0, -1.0, 1.0));
+        let on_diagonal = Point::new(0.5, 0.0, 0.5);
+        let between = Vector::new(-0.1, 1.0, 0.1).normalize();
+        let beyond = Vector::new(-1.0, 0.2, 1.0).normalize();
+        assert!(!is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &between
+        ));
+        assert!(is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &beyond
+        ));
+    }
+
+    #[test]
+    fn normals_leaning_over_a_concave_edge_are_internal_edge_normals() {
+        // The adjacent triangle goes up, so the diagonal is a concave edge.
+        let opposite = opposite_vertices(Point::new(0.0, 1.0, 1.0));
+        let on_diagonal = Point::new(0.5, 0.0, 0.5);
+        let leaning = Vector::new(-0.1, 1.0, 0.1).normalize();
+        assert!(is_internal_edge_normal(
+            &triangle(),
+            &opposite,
+            &on_diagonal,
+            &leaning
+        ));
+    }
+}
@@ -1,7 +1,13 @@
-use crate::geometry::{Triangle, WAABBHierarchy};
+use crate::geometry::{
+    Ray, RayIntersection, Shape, Triangle, WAABBHierarchy, WAABBHierarchyIntersections,
+};
 use crate::math::{Isometry, Point};
 use na::Point3;
 use ncollide::bounding_volume::{HasBoundingVolume, AABB};
+use ncollide::query::RayCast;
+use ncollide::shape::FeatureId;
+#[cfg(feature = "dim3")]
+use std::collections::HashMap;
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -11,6 +17,9 @@ pub struct Trimesh {
     aabb: AABB<f32>,
     vertices: Vec<Point<f32>>,
     indices: Vec<Point3<u32>>,
+    // For each triangle, the vertices of the adjacent triangles opposite to its edges.
+    #[cfg(feature = "dim3")]
+    opposite_vertices: Vec<[Option<u32>; 3]>,
 }
 
 impl Trimesh {
@@ -57,12 +66,16 @@ impl Trimesh {
             .collect();
 
         let waabb_tree = WAABBHierarchy::new(&aabbs);
+        #[cfg(feature = "dim3")]
+        let opposite_vertices = compute_opposite_vertices(&indices);
 
         Self {
             waabb_tree,
             aabb,
             vertices,
             indices,
+            #[cfg(feature = "dim3")]
+            opposite_vertices,
         }
     }
 
@@ -75,6 +88,49 @@ impl Trimesh {
         &self.waabb_tree
     }
 
+    /// Computes the first intersection between this triangle mesh and a ray.
+    ///
+    /// The feature of the returned intersection identifies the triangle hit by the ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let mut workspace = WAABBHierarchyIntersections::new();
+        self.waabb_tree.compute_interferences_with_predicate(
+            |aabb| aabb.intersects_ray(&Isometry::identity(), &ls_ray, max_toi),
+            &mut workspace,
+        );
+
+        let mut best: Option<RayIntersection> = None;
+
+        for triangle_id in workspace.computed_interferences() {
+            if *triangle_id >= self.num_triangles() {
+                // Because of SIMD padding, the hierarchy may return triangle indices greater
+                // than the max.
+                continue;
+            }
+
+            let max_toi = best.as_ref().map(|b| b.toi).unwrap_or(max_toi);
+            let triangle = Shape::Triangle(self.triangle(*triangle_id));
+
+            if let Some(mut inter) =
+                triangle.cast_ray(&Isometry::identity(), &ls_ray, max_toi, solid)
+            {
+                inter.feature = FeatureId::Face(*triangle_id);
+                best = Some(inter);
+            }
+        }
+
+        best.map(|mut inter| {
+            inter.normal = position * inter.normal;
+            inter
+        })
+    }
+
     /// The number of triangles forming this mesh.
     pub fn num_triangles(&self) -> usize {
         self.indices.len()
@@ -101,6 +157,15 @@ impl Trimesh {
         )
     }
 
+    /// The vertices of the adjacent triangles opposite to the edges `ab`, `bc`, and `ca` of the
+    /// `i`-th triangle of this mesh, or `None` for the edges on the boundary of the mesh.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn opposite_vertices(&self, i: usize) -> [Option<Point<f32>>; 3] {
+        let opposite = self.opposite_vertices[i];
+        let vertex = |k: usize| opposite[k].map(|id| self.vertices[id as usize]);
+        [vertex(0), vertex(1), vertex(2)]
+    }
+
     /// The vertex buffer of this mesh.
     pub fn vertices(&self) -> &[Point<f32>] {
         &self.vertices[..]
@@ -120,3 +185,65 @@ impl Trimesh {
         }
     }
 }
+
+/// Computes, for each edge of each triangle, the vertex of the adjacent triangle opposite to
+/// that edge.
+///
+/// Edges shared by more than two triangles are considered as boundary edges.
+#[cfg(feature = "dim3")]
+fn compute_opposite_vertices(indices: &[Point3<u32>]) -> Vec<[Option<u32>; 3]> {
+    let mut edges = HashMap::new();
+
+    for (i, idx) in indices.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (idx[k], idx[(k + 1) % 3]);
+            let key = (a.min(b), a.max(b));
+            edges.entry(key).or_insert_with(Vec::new).push((i, k));
+        }
+    }
+
+    let mut result = vec![[None; 3]; indices.len()];
+
+    for triangles in edges.values() {
+        if let [(i1, k1), (i2, k2)] = triangles[..] {
+            result[i1][k1] = Some(indices[i2][(k2 + 2) % 3]);
+            result[i2][k2] = Some(indices[i1][(k1 + 2) % 3]);
+        }
+    }
+
+    result
+}
+
+#[cfg(all(test, feature = "dim3"))]
+mod test {
+    use super::compute_opposite_vertices;
+    use na::Point3;
+
+    #[test]
+    fn opposite_vertices_of_a_quad() {
+        // Two triangles sharing the edge 0-2, which is their edge `ca` and `ab` respectively.
+        let indices = [Point3::new(0, 1, 2), Point3::new(0, 2, 3)];
+        let opposite = compute_opposite_vertices(&indices);
+
+        assert_eq!(opposite[0], [None, None, Some(3)]);
+        assert_eq!(opposite[1], [Some(1), None, None]);
+    }
+
+    #[test]
+    fn edges_shared_by_more_than_two_triangles_have_no_opposite_vertex() {
+        // Three triangles sharing the edge 0-1, and a fourth one sharing the edge 1-2 with the
+        // first triangle.
+        let indices = [
+            Point3::new(0, 1, 2),
+            Point3::new(1, 0, 3),
+            Point3::new(0, 1, 4),
+            Point3::new(2, 1, 5),
+        ];
+        let opposite = compute_opposite_vertices(&indices);
+
+        assert_eq!(opposite[0], [None, Some(5), None]);
+        assert_eq!(opposite[1], [None, None, None]);
+        assert_eq!(opposite[2], [None, None, None]);
+        assert_eq!(opposite[3], [Some(0), None, None]);
+    }
+}
@@ -0,0 +1,528 @@
+use crate::dynamics::MassProperties;
+use crate::geometry::{Cuboid, Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Vector, DIM};
+#[cfg(feature = "enhanced-determinism")]
+use crate::utils::FxHashMap32 as HashMap;
+use ncollide::query::RayCast;
+use ncollide::shape::FeatureId;
+use num::Zero;
+#[cfg(not(feature = "enhanced-determinism"))]
+use rustc_hash::FxHashMap as HashMap;
+
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+struct VoxelData {
+    id: u32,
+    // Bit `2 * k` (resp. `2 * k + 1`) is set if the neighbor of this voxel along
+    // the negative (resp. positive) `k`-th coordinate axis is filled.
+    neighbors: u8,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A sparse set of voxels, i.e., cuboids of identical size aligned on a regular grid, typically
+/// used to model static destructible worlds.
+///
+/// Each voxel is identified by its key, i.e., its integer coordinates on the grid: the voxel with
+/// the key `key` covers the local-space region between `key * voxel_size` and
+/// `(key + 1) * voxel_size`. Voxels can be added and removed at any time. The contacts on the
+/// faces shared by two filled voxels are discarded so that bodies sliding on the voxels don't
+/// snag on these internal faces.
+pub struct Voxels {
+    voxel_size: Vector<f32>,
+    voxels: HashMap<Point<i32>, VoxelData>,
+    // The key of each voxel, indexed by voxel id.
+    keys: Vec<Option<Point<i32>>>,
+    free_ids: Vec<u32>,
+    // The smallest and largest keys of the filled voxels.
+    mins: Point<i32>,
+    maxs: Point<i32>,
+}
+
+impl Voxels {
+    /// Creates a new set of voxels with the given size, and filled at the given keys.
+    pub fn new(voxel_size: Vector<f32>, keys: &[Point<i32>]) -> Self {
+        assert!(
+            voxel_size.iter().all(|e| *e > 0.0),
+            "The size of the voxels must be positive."
+        );
+
+        let mut result = Self {
+            voxel_size,
+            voxels: HashMap::default(),
+            keys: Vec::new(),
+            free_ids: Vec::new(),
+            mins: Point::from(Vector::repeat(i32::MAX)),
+            maxs: Point::from(Vector::repeat(i32::MIN)),
+        };
+
+        for key in keys {
+            let _ = result.insert(*key);
+        }
+
+        result
+    }
+
+    /// The size of each voxel.
+    pub fn voxel_size(&self) -> &Vector<f32> {
+        &self.voxel_size
+    }
+
+    /// The number of filled voxels.
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    /// Is this set of voxels empty?
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    /// Is the voxel with the given key filled?
+    pub fn contains(&self, key: &Point<i32>) -> bool {
+        self.voxels.contains_key(key)
+    }
+
+    /// The id of the filled voxel with the given key.
+    ///
+    /// The id of a voxel is the index of the sub-shape reported by the contact manifolds and
+    /// ray intersections involving that voxel. It remains valid until the voxel is removed.
+    pub fn voxel_id(&self, key: &Point<i32>) -> Option<u32> {
+        self.voxels.get(key).map(|data| data.id)
+    }
+
+    /// The key of the filled voxel with the given id.
+    pub fn voxel_key(&self, id: u32) -> Option<Point<i32>> {
+        self.keys.get(id as usize).cloned().flatten()
+    }
+
+    /// An iterator through the ids and keys of all the filled voxels, sorted by id.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, Point<i32>)> + '_ {
+        self.keys
+            .iter()
+            .enumerate()
+            .filter_map(|(id, key)| Some((id as u32, (*key)?)))
+    }
+
+    /// The key of the voxel containing the given local-space point.
+    pub fn voxel_key_at(&self, local_point: &Point<f32>) -> Point<i32> {
+        Point::from(
+            local_point
+                .coords
+                .component_div(&self.voxel_size)
+                .map(|e| e.floor() as i32),
+        )
+    }
+
+    /// The local-space center of the voxel with the given key.
+    pub fn voxel_center(&self, key: &Point<i32>) -> Point<f32> {
+        Point::from(
+            key.coords
+                .map(|e| e as f32 + 0.5)
+                .component_mul(&self.voxel_size),
+        )
+    }
+
+    /// The position of the voxel with the given key, relative to the local frame of this set
+    /// of voxels.
+    pub fn voxel_position(&self, key: &Point<i32>) -> Isometry<f32> {
+        Isometry::new(self.voxel_center(key).coords, na::zero())
+    }
+
+    /// The cuboid shape of a single voxel, centered at the origin.
+    pub fn voxel_shape(&self) -> Cuboid {
+        Cuboid::new(self.voxel_size / 2.0)
+    }
+
+    /// Fills the voxel with the given key.
+    ///
+    /// Returns `false` if this voxel was already filled.
+    pub fn insert(&mut self, key: Point<i32>) -> bool {
+        if self.voxels.contains_key(&key) {
+            return false;
+        }
+
+        let id = match self.free_ids.pop() {
+            Some(id) => {
+                self.keys[id as usize] = Some(key);
+                id
+            }
+            None => {
+                self.keys.push(Some(key));
+                self.keys.len() as u32 - 1
+            }
+        };
+
+        let mut neighbors = 0;
+
+        for bit in 0..DIM * 2 {
+            if let Some(neighbor) = self.voxels.get_mut(&neighbor_key(&key, bit)) {
+                neighbors |= 1 << bit;
+                // The current voxel is the opposite neighbor of this neighbor.
+                neighbor.neighbors |= 1 << (bit ^ 1);
+            }
+        }
+
+        let _ = self.voxels.insert(key, VoxelData { id, neighbors });
+        self.mins = Point::from(self.mins.coords.zip_map(&key.coords, |a, b| a.min(b)));
+        self.maxs = Point::from(self.maxs.coords.zip_map(&key.coords, |a, b| a.max(b)));
+        true
+    }
+
+    /// Clears the voxel with the given key.
+    ///
+    /// Returns `false` if this voxel was not filled. Clearing a voxel at the boundary of the
+    /// grid recomputes the bounds of the grid, which requires going through all the voxels.
+    pub fn remove(&mut self, key: &Point<i32>) -> bool {
+        let data = match self.voxels.remove(key) {
+            Some(data) => data,
+            None => return false,
+        };
+
+        for bit in 0..DIM * 2 {
+            if let Some(neighbor) = self.voxels.get_mut(&neighbor_key(key, bit)) {
+                neighbor.neighbors &= !(1 << (bit ^ 1));
+            }
+        }
+
+        self.keys[data.id as usize] = None;
+        self.free_ids.push(data.id);
+
+        if (0..DIM).any(|k| key[k] == self.mins[k] || key[k] == self.maxs[k]) {
+            self.mins = Point::from(Vector::repeat(i32::MAX));
+            self.maxs = Point::from(Vector::repeat(i32::MIN));
+
+            for key in self.voxels.keys() {
+                self.mins = Point::from(self.mins.coords.zip_map(&key.coords, |a, b| a.min(b)));
+                self.maxs = Point::from(self.maxs.coords.zip_map(&key.coords, |a, b| a.max(b)));
+            }
+        }
+
+        true
+    }
+
+    /// The local-space axis-aligned bounding box of all the filled voxels.
+    ///
+    /// Returns `None` if there is no filled voxel.
+    pub fn local_aabb(&self) -> Option<AABB> {
+        if self.voxels.is_empty() {
+            return None;
+        }
+
+        let mins = self.mins.coords.map(|e| e as f32);
+        let maxs = self.maxs.coords.map(|e| e as f32 + 1.0);
+        Some(AABB::new(
+            Point::from(mins.component_mul(&self.voxel_size)),
+            Point::from(maxs.component_mul(&self.voxel_size)),
+        ))
+    }
+
+    /// Compute the axis-aligned bounding box of this set of voxels.
+    ///
+    /// If there is no filled voxel, this is an empty AABB located at the origin of `pos`.
+    pub fn aabb(&self, pos: &Isometry<f32>) -> AABB {
+        match self.local_aabb() {
+            Some(aabb) => aabb.transform_by(pos),
+            None => {
+                let origin = Point::from(pos.translation.vector);
+                AABB::new(origin, origin)
+            }
+        }
+    }
+
+    /// Computes the mass properties of this set of voxels, i.e., the sum of the mass properties
+    /// of all its voxels.
+    pub fn mass_properties(&self, density: f32) -> MassProperties {
+        let voxel_mprops = MassProperties::from_cuboid(density, self.voxel_size / 2.0);
+
+        self.voxels.keys().fold(MassProperties::zero(), |acc, key| {
+            acc + voxel_mprops.transform_by(&self.voxel_position(key))
+        })
+    }
+
+    /// Computes the first intersection between this set of voxels and a ray.
+    ///
+    /// The feature of the returned intersection identifies the id of the voxel hit by the ray.
+    pub fn cast_ray(
+        &self,
+        position: &Isometry<f32>,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        let ls_ray = ray.inverse_transform_by(position);
+        let local_aabb = self.local_aabb()?;
+        let start_toi = local_aabb.toi_with_ray(&Isometry::identity(), &ls_ray, max_toi, true)?;
+
+        // Traverse the grid cells crossed by the ray, in the order they are crossed.
+        let start = ls_ray.point_at(start_toi);
+        let mut key = self.voxel_key_at(&start);
+
+        for k in 0..DIM {
+            // Because of rounding errors, the starting point may be slightly out of the grid.
+            key[k] = key[k].max(self.mins[k]).min(self.maxs[k]);
+        }
+
+        let mut step = Vector::zeros();
+        let mut next_toi = Vector::repeat(f32::MAX);
+        let mut delta_toi = Vector::repeat(f32::MAX);
+
+        for k in 0..DIM {
+            if ls_ray.dir[k] > 0.0 {
+                step[k] = 1;
+                let boundary = (key[k] + 1) as f32 * self.voxel_size[k];
+                next_toi[k] = (boundary - ls_ray.origin[k]) / ls_ray.dir[k];
+                delta_toi[k] = self.voxel_size[k] / ls_ray.dir[k];
+            } else if ls_ray.dir[k] < 0.0 {
+                step[k] = -1;
+                let boundary = key[k] as f32 * self.voxel_size[k];
+                next_toi[k] = (boundary - ls_ray.origin[k]) / ls_ray.dir[k];
+                delta_toi[k] = -self.voxel_size[k] / ls_ray.dir[k];
+            }
+        }
+
+        let voxel_shape = self.voxel_shape();
+
+        loop {
+            if let Some(data) = self.voxels.get(&key) {
+                let voxel_pos = self.voxel_position(&key);
+
+                if let Some(mut inter) =
+                    voxel_shape.toi_and_normal_with_ray(&voxel_pos, &ls_ray, max_toi, solid)
+                {
+                    inter.feature = FeatureId::Face(data.id as usize);
+                    inter.normal = position * inter.normal;
+                    return Some(inter);
+                }
+            }
+
+            let k = next_toi.imin();
+
+            if next_toi[k] > max_toi {
+                return None;
+            }
+
+            key[k] += step[k];
+
+            if key[k] < self.mins[k] || key[k] > self.maxs[k] {
+                return None;
+            }
+
+            next_toi[k] += delta_toi[k];
+        }
+    }
+
+    /// Calls `f` on the id and the key of each filled voxel intersecting the given local-space AABB.
+    pub(crate) fn map_voxels_in_local_aabb(
+        &self,
+        local_aabb: &AABB,
+        f: &mut impl FnMut(u32, &Point<i32>),
+    ) {
+        if self.voxels.is_empty() {
+            return;
+        }
+
+        let mins = Point::from(
+            self.voxel_key_at(&local_aabb.mins)
+                .coords
+                .zip_map(&self.mins.coords, |a, b| a.max(b)),
+        );
+        let maxs = Point::from(
+            self.voxel_key_at(&local_aabb.maxs)
+                .coords
+                .zip_map(&self.maxs.coords, |a, b| a.min(b)),
+        );
+
+        if (0..DIM).any(|k| mins[k] > maxs[k]) {
+            return;
+        }
+
+        let num_cells = (0..DIM).fold(1i64, |acc, k| acc * (maxs[k] - mins[k] + 1) as i64);
+
+        if num_cells > self.voxels.len() as i64 {
+            // There are fewer voxels than cells in the AABB, so we go through all the voxels.
+            for (key, data) in self.voxels.iter() {
+                if (0..DIM).all(|k| key[k] >= mins[k] && key[k] <= maxs[k]) {
+                    f(data.id, key)
+                }
+            }
+        } else {
+            #[cfg(feature = "dim2")]
+            for i in mins.x..=maxs.x {
+                for j in mins.y..=maxs.y {
+                    let key = Point::new(i, j);
+
+                    if let Some(data) = self.voxels.get(&key) {
+                        f(data.id, &key)
+                    }
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            for i in mins.x..=maxs.x {
+                for j in mins.y..=maxs.y {
+                    for k in mins.z..=maxs.z {
+                        let key = Point::new(i, j, k);
+
+                        if let Some(data) = self.voxels.get(&key) {
+                            f(data.id, &key)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks if a contact with the voxel `key` is located on one of its faces shared with
+    /// another filled voxel, and has a normal pointing toward that other voxel.
+    ///
+    /// Such a contact is already handled by the other voxel. Here, `local_point` is the contact
+    /// point on the voxel and `local_normal` is the contact normal pointing away from the voxel,
+    /// both expressed in the local-space of this set of voxels.
+    pub(crate) fn is_internal_face_contact(
+        &self,
+        key: &Point<i32>,
+        local_point: &Point<f32>,
+        local_normal: &Vector<f32>,
+    ) -> bool {
+        const FACE_EPS: f32 = 1.0e-3;
+        const NORMAL_EPS: f32 = 1.0e-4;
+
+        let neighbors = match self.voxels.get(key) {
+            Some(data) => data.neighbors,
+            None => return false,
+        };
+        let center = self.voxel_center(key);
+
+        for k in 0..DIM {
+            let half_size = self.voxel_size[k] / 2.0;
+            let dist = local_point[k] - center[k];
+            let eps = self.voxel_size[k] * FACE_EPS;
+
+            if dist <= -half_size + eps
+                && local_normal[k] < -NORMAL_EPS
+                && neighbors & (1 << (2 * k)) != 0
+            {
+                return true;
+            }
+
+            if dist >= half_size - eps
+                && local_normal[k] > NORMAL_EPS
+                && neighbors & (1 << (2 * k + 1)) != 0
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// The key of the neighbor of the voxel `key` identified by the given neighbor bit.
+fn neighbor_key(key: &Point<i32>, bit: usize) -> Point<i32> {
+    let mut result = *key;
+    result[bit / 2] += if bit % 2 == 0 { -1 } else { 1 };
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::Voxels;
+    use crate::math::{Point, Vector, DIM};
+
+    fn key(x: i32, y: i32) -> Point<i32> {
+        let mut key = Point::origin();
+        key.x = x;
+        key.y = y;
+        key
+    }
+
+    fn neighbors(voxels: &Voxels, key: &Point<i32>) -> u8 {
+        voxels.voxels[key].neighbors
+    }
+
+    #[test]
+    fn insert_and_remove_update_the_neighbors_of_voxels() {
+        let mut voxels = Voxels::new(Vector::repeat(1.0), &[key(0, 0), key(1, 0)]);
+        // The voxel (0, 0) has a neighbor along `+x`, the voxel (1, 0) along `-x`.
+        assert_eq!(neighbors(&voxels, &key(0, 0)), 0b10);
+        assert_eq!(neighbors(&voxels, &key(1, 0)), 0b01);
+
+        assert!(voxels.insert(key(0, 1)));
+        assert!(!voxels.insert(key(0, 1)));
+        assert_eq!(neighbors(&voxels, &key(0, 0)), 0b1010);
+        assert_eq!(neighbors(&voxels, &key(0, 1)), 0b0100);
+        assert_eq!(neighbors(&voxels, &key(1, 0)), 0b01);
+
+        assert!(voxels.remove(&key(0, 0)));
+        assert!(!voxels.remove(&key(0, 0)));
+        assert_eq!(neighbors(&voxels, &key(0, 1)), 0);
+        assert_eq!(neighbors(&voxels, &key(1, 0)), 0);
+        assert_eq!(voxels.len(), 2);
+    }
+
+    #[test]
+    fn removed_voxel_ids_are_reused() {
+        let mut voxels = Voxels::new(Vector::repeat(1.0), &[key(0, 0), key(1, 0), key(2, 0)]);
+        assert_eq!(voxels.voxel_id(&key(1, 0)), Some(1));
+
+        assert!(voxels.remove(&key(1, 0)));
+        assert_eq!(voxels.voxel_id(&key(1, 0)), None);
+        assert_eq!(voxels.voxel_key(1), None);
+
+        assert!(voxels.insert(key(5, 0)));
+        assert_eq!(voxels.voxel_id(&key(5, 0)), Some(1));
+        assert_eq!(voxels.voxel_key(1), Some(key(5, 0)));
+
+        let ids: Vec<_> = voxels.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn removing_boundary_voxels_shrinks_the_bounds() {
+        let mut voxels = Voxels::new(Vector::repeat(0.5), &[key(0, 0), key(3, 2)]);
+        let aabb = voxels.local_aabb().unwrap();
+        assert_eq!(aabb.mins, Point::origin());
+        assert_eq!(aabb.maxs.x, 2.0);
+        assert_eq!(aabb.maxs.y, 1.5);
+
+        assert!(voxels.remove(&key(3, 2)));
+        let aabb = voxels.local_aabb().unwrap();
+        assert_eq!(aabb.maxs, Point::from(Vector::repeat(0.5)));
+
+        assert!(voxels.remove(&key(0, 0)));
+        assert!(voxels.is_empty());
+        assert!(voxels.local_aabb().is_none());
+    }
+
+    #[test]
+    fn contacts_on_internal_faces_are_detected() {
+        let voxels = Voxels::new(Vector::repeat(1.0), &[key(0, 0), key(1, 0)]);
+        let mut shared_face_point = voxels.voxel_center(&key(0, 0));
+        shared_face_point.x = 1.0;
+        let mut top_face_point = voxels.voxel_center(&key(0, 0));
+        top_face_point.y = 1.0;
+
+        // Normals pointing toward the neighbor voxel, on the face shared with it.
+        assert!(voxels.is_internal_face_contact(&key(0, 0), &shared_face_point, &Vector::x()));
+        assert!(voxels.is_internal_face_contact(&key(1, 0), &shared_face_point, &-Vector::x()));
+
+        // Normals pointing away from the neighbor, or on a free face.
+        assert!(!voxels.is_internal_face_contact(&key(0, 0), &shared_face_point, &Vector::y()));
+        assert!(!voxels.is_internal_face_contact(&key(0, 0), &top_face_point, &Vector::y()));
+        assert!(!voxels.is_internal_face_contact(
+            &key(0, 0),
+            &voxels.voxel_center(&key(0, 0)),
+            &-Vector::x()
+        ));
+
+        // The voxels only have a neighbor along the `x` axis.
+        for k in 1..DIM {
+            let mut normal = Vector::zeros();
+            normal[k] = 1.0;
+            let mut point = voxels.voxel_center(&key(0, 0));
+            point[k] = 1.0;
+            assert!(!voxels.is_internal_face_contact(&key(0, 0), &point, &normal));
+        }
+    }
+}
@@ -84,6 +84,10 @@ impl WAABB {
         }
     }
 
+    pub fn extract(&self, i: usize) -> AABB<f32> {
+        AABB::new(self.mins.extract(i), self.maxs.extract(i))
+    }
+
     #[cfg(feature = "dim2")]
     pub fn intersects_lanewise(&self, other: &WAABB) -> SimdBool {
         self.mins.x.simd_le(other.maxs.x)
@@ -127,6 +127,7 @@ pub(crate) const INVALID_U32: u32 = u32::MAX;
 pub(crate) const INVALID_U64: u64 = u64::MAX;
 pub(crate) const INVALID_USIZE: usize = INVALID_U32 as usize;
 
+pub mod control;
 pub mod counters;
 pub mod data;
 pub mod dynamics;
@@ -143,6 +144,8 @@ pub mod math {
 
     /// The dimension of the physics simulated by this crate.
     pub const DIM: usize = 2;
+    /// The number of degrees of freedom of a rigid body, i.e., the dimension of a `SpacialVector`.
+    pub const SPATIAL_DIM: usize = 3;
     /// The maximum number of point a contact manifold can hold.
     pub const MAX_MANIFOLD_POINTS: usize = 2;
     /// The dimension of the physics simulated by this crate, given as a type-level-integer.
@@ -186,6 +189,8 @@ pub mod math {
 
     /// The dimension of the physics simulated by this crate.
     pub const DIM: usize = 3;
+    /// The number of degrees of freedom of a rigid body, i.e., the dimension of a `SpacialVector`.
+    pub const SPATIAL_DIM: usize = 6;
     /// The maximum number of point a contact manifold can hold.
     pub const MAX_MANIFOLD_POINTS: usize = 4;
     /// The dimension of the physics simulated by this crate, given as a type-level-integer.
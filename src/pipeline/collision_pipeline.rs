@@ -1,8 +1,11 @@
 //! Physics pipeline structures.
 
 use crate::dynamics::{JointSet, RigidBody, RigidBodyHandle, RigidBodySet};
-use crate::geometry::{BroadPhase, BroadPhasePairEvent, ColliderPair, ColliderSet, NarrowPhase};
-use crate::pipeline::EventHandler;
+use crate::geometry::{
+    BroadPhase, BroadPhasePairEvent, Collider, ColliderHandle, ColliderPair, ColliderSet,
+    NarrowPhase,
+};
+use crate::pipeline::{EventHandler, PhysicsHooks};
 
 /// The collision pipeline, responsible for performing collision detection between colliders.
 ///
@@ -40,9 +43,11 @@ impl CollisionPipeline {
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
         bodies.maintain_active_set();
+        narrow_phase.handle_modified_colliders(colliders, bodies);
         self.broadphase_collider_pairs.clear();
 
         broad_phase.update_aabbs(prediction_distance, bodies, colliders);
@@ -50,9 +55,9 @@ impl CollisionPipeline {
         self.broad_phase_events.clear();
         broad_phase.find_pairs(&mut self.broad_phase_events);
 
-        narrow_phase.register_pairs(colliders, &self.broad_phase_events, events);
+        narrow_phase.register_pairs(colliders, bodies, &self.broad_phase_events, hooks, events);
 
-        narrow_phase.compute_contacts(prediction_distance, bodies, colliders, events);
+        narrow_phase.compute_contacts(prediction_distance, bodies, colliders, hooks, events);
         narrow_phase.compute_proximities(prediction_distance, bodies, colliders, events);
 
         bodies.update_active_set_with_contacts(
@@ -99,7 +104,7 @@ impl CollisionPipeline {
 
         // Remove this rigid-body from the broad-phase and narrow-phase.
         broad_phase.remove_colliders(&body.colliders, colliders);
-        narrow_phase.remove_colliders(&body.colliders, colliders, bodies);
+        narrow_phase.remove_colliders(&body.colliders, colliders, bodies, &());
 
         // Remove all colliders attached to this body.
         for collider in &body.colliders {
@@ -108,4 +113,54 @@ impl CollisionPipeline {
 
         Some(body)
     }
+
+    /// Remove a collider and all its associated data.
+    ///
+    /// The collider is detached from its parent rigid-body, which loses the collider's mass
+    /// properties and is woken up. Contact and proximity events are emitted for every
+    /// interaction that is lost because of this removal.
+    pub fn remove_collider(
+        &mut self,
+        handle: ColliderHandle,
+        broad_phase: &mut BroadPhase,
+        narrow_phase: &mut NarrowPhase,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &dyn EventHandler,
+    ) -> Option<Collider> {
+        remove_collider(handle, broad_phase, narrow_phase, bodies, colliders, events)
+    }
+}
+
+/// Removes a collider from the given sets and from the broad-phase and narrow-phase.
+///
+/// The collider is detached from its parent rigid-body, which loses the collider's mass
+/// properties and is woken up.
+pub(crate) fn remove_collider(
+    handle: ColliderHandle,
+    broad_phase: &mut BroadPhase,
+    narrow_phase: &mut NarrowPhase,
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    events: &dyn EventHandler,
+) -> Option<Collider> {
+    let collider = colliders.get(handle)?;
+    let parent_handle = collider.parent;
+
+    // Detach the collider from its parent.
+    if let Some(parent) = bodies.get_mut_internal(parent_handle) {
+        if let Some(i) = parent.colliders.iter().position(|h| *h == handle) {
+            parent.colliders.swap_remove(i);
+        }
+
+        parent.mass_properties -= collider.mass_properties();
+        parent.update_world_mass_properties();
+        bodies.wake_up(parent_handle);
+    }
+
+    // Remove this collider from the broad-phase and narrow-phase.
+    broad_phase.remove_colliders(&[handle], colliders);
+    narrow_phase.remove_colliders(&[handle], colliders, bodies, events);
+
+    colliders.remove_internal(handle)
 }
@@ -1,3 +1,4 @@
+use crate::dynamics::JointEvent;
 use crate::geometry::{ContactEvent, ProximityEvent};
 use crossbeam::channel::Sender;
 
@@ -14,6 +15,11 @@ pub trait EventHandler: Send + Sync {
     /// A contact event is emitted when two collider start or stop touching, independently from the
     /// number of contact points involved.
     fn handle_contact_event(&self, event: ContactEvent);
+    /// Handle a joint event.
+    ///
+    /// A joint event is emitted when a joint breaks because it applied a force or a torque greater
+    /// than its break thresholds. Joint events are ignored by default.
+    fn handle_joint_event(&self, _event: JointEvent) {}
 }
 
 impl EventHandler for () {
@@ -25,10 +31,13 @@ impl EventHandler for () {
 pub struct ChannelEventCollector {
     proximity_event_sender: Sender<ProximityEvent>,
     contact_event_sender: Sender<ContactEvent>,
+    joint_event_sender: Option<Sender<JointEvent>>,
 }
 
 impl ChannelEventCollector {
     /// Initialize a new physics event handler from crossbeam channel senders.
+    ///
+    /// Joint events are ignored unless a sender is set with `with_joint_event_sender`.
     pub fn new(
         proximity_event_sender: Sender<ProximityEvent>,
         contact_event_sender: Sender<ContactEvent>,
@@ -36,8 +45,15 @@ impl ChannelEventCollector {
         Self {
             proximity_event_sender,
             contact_event_sender,
+            joint_event_sender: None,
         }
     }
+
+    /// Sets the crossbeam channel sender the joint events are collected into.
+    pub fn with_joint_event_sender(mut self, joint_event_sender: Sender<JointEvent>) -> Self {
+        self.joint_event_sender = Some(joint_event_sender);
+        self
+    }
 }
 
 impl EventHandler for ChannelEventCollector {
@@ -48,4 +64,10 @@ impl EventHandler for ChannelEventCollector {
     fn handle_contact_event(&self, event: ContactEvent) {
         let _ = self.contact_event_sender.send(event);
     }
+
+    fn handle_joint_event(&self, event: JointEvent) {
+        if let Some(sender) = &self.joint_event_sender {
+            let _ = sender.send(event);
+        }
+    }
 }
@@ -2,8 +2,12 @@
 
 pub use collision_pipeline::CollisionPipeline;
 pub use event_handler::{ChannelEventCollector, EventHandler};
+pub use physics_hooks::{ContactModificationContext, PairFilterContext, PhysicsHooks};
 pub use physics_pipeline::PhysicsPipeline;
+pub use query_pipeline::QueryPipeline;
 
 mod collision_pipeline;
 mod event_handler;
+mod physics_hooks;
 mod physics_pipeline;
+mod query_pipeline;
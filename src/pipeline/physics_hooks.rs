@@ -0,0 +1,79 @@
+use crate::dynamics::RigidBody;
+use crate::geometry::{Collider, ColliderHandle, ContactManifold};
+
+/// Context given to custom collision filters to filter-out collisions.
+pub struct PairFilterContext<'a> {
+    /// The first collider involved in the potential collision.
+    pub collider_handle1: ColliderHandle,
+    /// The second collider involved in the potential collision.
+    pub collider_handle2: ColliderHandle,
+    /// The first collider involved in the potential collision.
+    pub collider1: &'a Collider,
+    /// The second collider involved in the potential collision.
+    pub collider2: &'a Collider,
+    /// The rigid-body attached to the first collider.
+    pub rigid_body1: &'a RigidBody,
+    /// The rigid-body attached to the second collider.
+    pub rigid_body2: &'a RigidBody,
+}
+
+/// Context given to custom contact modifiers to modify the contacts seen by the constraints solver.
+pub struct ContactModificationContext<'a> {
+    /// The first collider involved in the contact manifold.
+    pub collider_handle1: ColliderHandle,
+    /// The second collider involved in the contact manifold.
+    pub collider_handle2: ColliderHandle,
+    /// The first collider involved in the contact manifold.
+    pub collider1: &'a Collider,
+    /// The second collider involved in the contact manifold.
+    pub collider2: &'a Collider,
+    /// The rigid-body attached to the first collider.
+    pub rigid_body1: &'a RigidBody,
+    /// The rigid-body attached to the second collider.
+    pub rigid_body2: &'a RigidBody,
+    /// The contact manifold to modify.
+    ///
+    /// The first and second colliders of this manifold are `collider1` and `collider2`.
+    pub manifold: &'a mut ContactManifold,
+}
+
+/// User-defined functions called by the physics engines during one timestep in order to customize its behavior.
+///
+/// The pair filters are only called when the narrow-phase creates a new pair, i.e., when the
+/// broad-phase detects that the AABBs of two colliders started overlapping. A pair that has been
+/// rejected will not be filtered again until the AABBs of its colliders stop overlapping and start
+/// overlapping again.
+pub trait PhysicsHooks: Send + Sync {
+    /// Applies the contact pair filter.
+    ///
+    /// This is called for pairs of non-sensor colliders. If this returns `false`, no contact will be computed between these two colliders.
+    fn filter_contact_pair(&self, _context: &PairFilterContext) -> bool {
+        true
+    }
+
+    /// Applies the proximity pair filter.
+    ///
+    /// This is called for pairs of colliders involving at least one sensor. If this returns
+    /// `false`, no proximity will be computed between these two colliders.
+    fn filter_proximity_pair(&self, _context: &PairFilterContext) -> bool {
+        true
+    }
+
+    /// Modifies the set of contacts seen by the constraints solver.
+    ///
+    /// This is called for each contact manifold with at least one active contact, right after
+    /// the contacts were computed by the narrow-phase. The contact events are not affected by
+    /// these modifications: they only depend on the contacts computed by the narrow-phase.
+    /// The context gives mutable access to the manifold, allowing, e.g.:
+    /// - To deactivate some contacts with `ContactManifold::deactivate_contact`, e.g., for one-way platforms.
+    /// - To modify the contact normal, `friction`, and `restitution` of the manifold.
+    /// - To set the `tangent_velocity` of the active contacts, e.g., for conveyor belts.
+    ///
+    /// Before this is called, the friction and restitution coefficients of the manifold are
+    /// reset to their default values computed from the colliders, and the tangent velocities are
+    /// reset to zero. Deactivated contacts are reactivated the next time the narrow-phase updates
+    /// the contacts of this manifold.
+    fn modify_solver_contacts(&self, _context: &mut ContactModificationContext) {}
+}
+
+impl PhysicsHooks for () {}
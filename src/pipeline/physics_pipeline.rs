@@ -3,14 +3,18 @@
 use crate::counters::Counters;
 #[cfg(not(feature = "parallel"))]
 use crate::dynamics::IslandSolver;
-use crate::dynamics::{IntegrationParameters, JointSet, RigidBody, RigidBodyHandle, RigidBodySet};
+use crate::dynamics::{
+    CCDSolver, IntegrationParameters, JointEvent, JointHandle, JointSet, RigidBody,
+    RigidBodyHandle, RigidBodySet,
+};
 #[cfg(feature = "parallel")]
 use crate::dynamics::{JointGraphEdge, ParallelIslandSolver as IslandSolver};
 use crate::geometry::{
-    BroadPhase, BroadPhasePairEvent, ColliderPair, ColliderSet, ContactManifoldIndex, NarrowPhase,
+    BroadPhase, BroadPhasePairEvent, Collider, ColliderHandle, ColliderPair, ColliderSet,
+    ContactManifoldIndex, NarrowPhase,
 };
 use crate::math::Vector;
-use crate::pipeline::EventHandler;
+use crate::pipeline::{EventHandler, PhysicsHooks};
 
 /// The physics pipeline, responsible for stepping the whole physics simulation.
 ///
@@ -31,6 +35,8 @@ pub struct PhysicsPipeline {
     broadphase_collider_pairs: Vec<ColliderPair>,
     broad_phase_events: Vec<BroadPhasePairEvent>,
     solvers: Vec<IslandSolver>,
+    ccd_solver: CCDSolver,
+    broken_joints: Vec<JointHandle>,
 }
 
 impl Default for PhysicsPipeline {
@@ -55,6 +61,8 @@ impl PhysicsPipeline {
             joint_constraint_indices: Vec::new(),
             broadphase_collider_pairs: Vec::new(),
             broad_phase_events: Vec::new(),
+            ccd_solver: CCDSolver::new(),
+            broken_joints: Vec::new(),
         }
     }
 
@@ -68,11 +76,13 @@ impl PhysicsPipeline {
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
         joints: &mut JointSet,
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
         // println!("Step");
         self.counters.step_started();
         bodies.maintain_active_set();
+        narrow_phase.handle_modified_colliders(colliders, bodies);
 
         self.counters.stages.collision_detection_time.start();
         self.counters.cd.broad_phase_time.start();
@@ -90,7 +100,7 @@ impl PhysicsPipeline {
         broad_phase.find_pairs(&mut self.broad_phase_events);
         //        println!("Find pairs time: {}", instant::now() - t);
 
-        narrow_phase.register_pairs(colliders, &self.broad_phase_events, events);
+        narrow_phase.register_pairs(colliders, bodies, &self.broad_phase_events, hooks, events);
         self.counters.cd.broad_phase_time.pause();
 
         //        println!("Num contact pairs: {}", pairs.len());
@@ -102,6 +112,7 @@ impl PhysicsPipeline {
             integration_parameters.prediction_distance,
             bodies,
             colliders,
+            hooks,
             events,
         );
         narrow_phase.compute_proximities(
@@ -139,6 +150,7 @@ impl PhysicsPipeline {
         let mut manifolds = Vec::new();
         narrow_phase.sort_and_select_active_contacts(
             bodies,
+            colliders,
             &mut manifolds,
             &mut self.manifold_indices,
         );
@@ -152,6 +164,7 @@ impl PhysicsPipeline {
             b.update_world_mass_properties();
             b.integrate_accelerations(integration_parameters.dt(), *gravity)
         });
+        self.ccd_solver.init(bodies);
         self.counters.stages.update_time.pause();
 
         self.counters.solver.reset();
@@ -241,10 +254,120 @@ impl PhysicsPipeline {
 
         self.counters.stages.solver_time.pause();
 
+        // Remove the joints that applied a force or torque greater than their break thresholds.
+        self.broken_joints.clear();
+        for joint_ids in &self.joint_constraint_indices[..bodies.num_islands()] {
+            joints.find_broken_joints(
+                joint_ids,
+                integration_parameters.inv_dt(),
+                &mut self.broken_joints,
+            );
+        }
+
+        for handle in self.broken_joints.drain(..) {
+            if joints.remove(handle, bodies).is_some() {
+                events.handle_joint_event(JointEvent::Broken(handle));
+            }
+        }
+
+        self.counters.ccd.reset();
+        if self.ccd_solver.has_ccd_bodies() {
+            self.counters.ccd_started();
+            self.solve_ccd(
+                integration_parameters,
+                broad_phase,
+                narrow_phase,
+                bodies,
+                colliders,
+                hooks,
+                events,
+            );
+            self.counters.ccd_completed();
+        }
+
         bodies.modified_inactive_set.clear();
         self.counters.step_completed();
     }
 
+    fn solve_ccd(
+        &mut self,
+        integration_parameters: &IntegrationParameters,
+        broad_phase: &mut BroadPhase,
+        narrow_phase: &mut NarrowPhase,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) {
+        self.counters.ccd.toi_computation_time.resume();
+        self.ccd_solver.update_obstacles(bodies, colliders);
+        self.counters.ccd.toi_computation_time.pause();
+
+        for _ in 0..integration_parameters.max_ccd_substeps {
+            self.counters.ccd.toi_computation_time.resume();
+            let found_impacts =
+                self.ccd_solver
+                    .compute_impacts(integration_parameters, bodies, colliders, hooks);
+            self.counters.ccd.toi_computation_time.pause();
+
+            if !found_impacts {
+                break;
+            }
+
+            self.counters.ccd.num_substeps += 1;
+            self.counters.ccd.solver_time.resume();
+            self.ccd_solver
+                .resolve_impacts(integration_parameters, bodies, colliders);
+            self.counters.ccd.solver_time.pause();
+
+            // Update the contacts so they match the new positions of the bodies.
+            self.counters.ccd.broad_phase_time.resume();
+            broad_phase.update_aabbs(
+                integration_parameters.prediction_distance,
+                bodies,
+                colliders,
+            );
+            self.broad_phase_events.clear();
+            broad_phase.find_pairs(&mut self.broad_phase_events);
+            narrow_phase.register_pairs(colliders, bodies, &self.broad_phase_events, hooks, events);
+            self.counters.ccd.broad_phase_time.pause();
+
+            self.counters.ccd.narrow_phase_time.resume();
+            narrow_phase.compute_contacts(
+                integration_parameters.prediction_distance,
+                bodies,
+                colliders,
+                hooks,
+                events,
+            );
+
+            // NOTE: if multiple sensor events are disabled, the proximities will only
+            // be updated at the next timestep so at most one event is generated.
+            if integration_parameters.multiple_ccd_substep_sensor_events_enabled {
+                narrow_phase.compute_proximities(
+                    integration_parameters.prediction_distance,
+                    bodies,
+                    colliders,
+                    events,
+                );
+            }
+            self.counters.ccd.narrow_phase_time.pause();
+
+            self.counters.ccd.solver_time.resume();
+            self.ccd_solver.correct_penetrations(
+                integration_parameters,
+                bodies,
+                colliders,
+                narrow_phase,
+            );
+            self.counters.ccd.solver_time.pause();
+
+            if integration_parameters.return_after_ccd_substep {
+                break;
+            }
+        }
+    }
+
     /// Remove a rigid-body and all its associated data.
     pub fn remove_rigid_body(
         &mut self,
@@ -260,7 +383,7 @@ impl PhysicsPipeline {
 
         // Remove this rigid-body from the broad-phase and narrow-phase.
         broad_phase.remove_colliders(&body.colliders, colliders);
-        narrow_phase.remove_colliders(&body.colliders, colliders, bodies);
+        narrow_phase.remove_colliders(&body.colliders, colliders, bodies, &());
 
         // Remove all joints attached to this body.
         joints.remove_rigid_body(body.joint_graph_index, bodies);
@@ -272,13 +395,48 @@ impl PhysicsPipeline {
 
         Some(body)
     }
+
+    /// Remove a collider and all its associated data.
+    ///
+    /// The collider is detached from its parent rigid-body, which loses the collider's mass
+    /// properties and is woken up. Contact and proximity events are emitted for every
+    /// interaction that is lost because of this removal.
+    pub fn remove_collider(
+        &mut self,
+        handle: ColliderHandle,
+        broad_phase: &mut BroadPhase,
+        narrow_phase: &mut NarrowPhase,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &dyn EventHandler,
+    ) -> Option<Collider> {
+        super::collision_pipeline::remove_collider(
+            handle,
+            broad_phase,
+            narrow_phase,
+            bodies,
+            colliders,
+            events,
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
-    use crate::geometry::{BroadPhase, ColliderSet, NarrowPhase};
-    use crate::pipeline::PhysicsPipeline;
+    use crate::dynamics::{
+        BallJoint, DistanceJoint, GenericJoint, IntegrationParameters, JointEvent, JointHandle,
+        JointParams, JointSet, PrismaticJoint, RevoluteJoint, RigidBodyBuilder, RigidBodyHandle,
+        RigidBodySet,
+    };
+    use crate::geometry::{
+        BroadPhase, ColliderBuilder, ColliderHandle, ColliderSet, ContactEvent, InteractionGroups,
+        NarrowPhase, Proximity, ProximityEvent,
+    };
+    use crate::math::{Isometry, Point, Vector, SPATIAL_DIM};
+    use crate::pipeline::{
+        ChannelEventCollector, ContactModificationContext, PairFilterContext, PhysicsHooks,
+        PhysicsPipeline,
+    };
 
     #[test]
     fn rigid_body_removal_before_step() {
@@ -329,4 +487,765 @@ mod test {
         assert_eq!(h2a, h2b);
         assert_eq!(h3a, h3b);
     }
+
+    #[test]
+    fn collider_removal_restores_parent_mass_properties() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        let body = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        colliders.insert(ColliderBuilder::ball(1.0).build(), body, &mut bodies);
+        let initial_mprops = bodies[body].mass_properties;
+        let initial_com = bodies[body].world_com;
+
+        #[cfg(feature = "dim2")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 1.0).build();
+        #[cfg(feature = "dim3")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 1.0, 0.25).build();
+        let handle = colliders.insert(cuboid, body, &mut bodies);
+        assert!(bodies[body].mass_properties.inv_mass < initial_mprops.inv_mass);
+
+        let removed =
+            pipeline.remove_collider(handle, &mut bf, &mut nf, &mut bodies, &mut colliders, &());
+        assert!(removed.is_some());
+        assert!(colliders.get(handle).is_none());
+
+        let rb = &bodies[body];
+        assert_eq!(rb.colliders.len(), 1);
+        assert!((rb.mass_properties.inv_mass - initial_mprops.inv_mass).abs() < 1.0e-5);
+        assert!((rb.mass_properties.local_com - initial_mprops.local_com).norm() < 1.0e-5);
+        assert!((rb.world_com - initial_com).norm() < 1.0e-5);
+        #[cfg(feature = "dim2")]
+        assert!(
+            (rb.mass_properties.inv_principal_inertia_sqrt
+                - initial_mprops.inv_principal_inertia_sqrt)
+                .abs()
+                < 1.0e-4
+        );
+        #[cfg(feature = "dim3")]
+        assert!(
+            (rb.mass_properties.reconstruct_inertia_matrix()
+                - initial_mprops.reconstruct_inertia_matrix())
+            .norm()
+                < 1.0e-4
+        );
+    }
+
+    #[test]
+    fn joint_removal_snapshot_handle_determinism() {
+        let mut joints = JointSet::new();
+        let mut set = RigidBodySet::new();
+        let rb = RigidBodyBuilder::new_dynamic().build();
+        let b1 = set.insert(rb.clone());
+        let b2 = set.insert(rb.clone());
+        let b3 = set.insert(rb.clone());
+
+        let joint = BallJoint::new(Point::origin(), Point::origin());
+        let h1 = joints.insert(&mut set, b1, b2, joint);
+        let h2 = joints.insert(&mut set, b2, b3, joint);
+        let h3 = joints.insert(&mut set, b3, b1, joint);
+
+        assert!(joints.remove(h1, &mut set).is_some());
+        assert!(joints.remove(h3, &mut set).is_some());
+        assert!(joints.get(h2).is_some());
+        assert!(joints.remove(h2, &mut set).is_some());
+        assert!(joints.remove(h2, &mut set).is_none());
+        assert_eq!(joints.len(), 0);
+
+        let ser_joints = bincode::serialize(&joints).unwrap();
+        let mut joints2: JointSet = bincode::deserialize(&ser_joints).unwrap();
+
+        let h1a = joints.insert(&mut set, b1, b2, joint);
+        let h2a = joints.insert(&mut set, b2, b3, joint);
+        let h3a = joints.insert(&mut set, b3, b1, joint);
+
+        let h1b = joints2.insert(&mut set, b1, b2, joint);
+        let h2b = joints2.insert(&mut set, b2, b3, joint);
+        let h3b = joints2.insert(&mut set, b3, b1, joint);
+
+        assert_eq!(h1a, h1b);
+        assert_eq!(h2a, h2b);
+        assert_eq!(h3a, h3b);
+    }
+
+    #[test]
+    fn joint_breaks_when_exceeding_max_force() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        let body = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        colliders.insert(ColliderBuilder::ball(0.5).build(), body, &mut bodies);
+
+        let joint = BallJoint::new(Point::origin(), Point::origin());
+        let handle = joints.insert(&mut bodies, ground, body, joint);
+        joints.get_mut(handle).unwrap().max_force = 1.0e-3;
+
+        let proximity_channel = crossbeam::channel::unbounded();
+        let contact_channel = crossbeam::channel::unbounded();
+        let joint_channel = crossbeam::channel::unbounded();
+        let events = ChannelEventCollector::new(proximity_channel.0, contact_channel.0)
+            .with_joint_event_sender(joint_channel.0);
+
+        pipeline.step(
+            &(Vector::y() * -9.81),
+            &IntegrationParameters::default(),
+            &mut bf,
+            &mut nf,
+            &mut bodies,
+            &mut colliders,
+            &mut joints,
+            &(),
+            &events,
+        );
+
+        assert!(joints.get(handle).is_none());
+        assert_eq!(joint_channel.1.try_recv(), Ok(JointEvent::Broken(handle)));
+        assert!(joint_channel.1.try_recv().is_err());
+    }
+
+    #[test]
+    fn ccd_prevents_tunnelling_through_thin_wall() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+
+        let wall = bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let wall_collider = ColliderBuilder::cuboid(0.05, 2.0).build();
+        #[cfg(feature = "dim3")]
+        let wall_collider = ColliderBuilder::cuboid(0.05, 2.0, 2.0).build();
+        colliders.insert(wall_collider, wall, &mut bodies);
+
+        // This ball moves by several times the thickness of the wall at each timestep.
+        #[cfg(feature = "dim2")]
+        let ball = RigidBodyBuilder::new_dynamic()
+            .translation(-1.0, 0.0)
+            .linvel(200.0, 0.0);
+        #[cfg(feature = "dim3")]
+        let ball = RigidBodyBuilder::new_dynamic()
+            .translation(-1.0, 0.0, 0.0)
+            .linvel(200.0, 0.0, 0.0);
+        let ball = bodies.insert(ball.ccd_enabled(true).build());
+        colliders.insert(ColliderBuilder::ball(0.1).build(), ball, &mut bodies);
+
+        for _ in 0..5 {
+            pipeline.step(
+                &Vector::zeros(),
+                &IntegrationParameters::default(),
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &(),
+                &(),
+            );
+        }
+
+        assert!(bodies[ball].position.translation.vector.x < 0.0);
+    }
+
+    #[test]
+    fn damping_scales_velocities_at_each_step() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut bf = BroadPhase::new();
+        let mut nf = NarrowPhase::new();
+        let params = IntegrationParameters::default();
+
+        #[cfg(feature = "dim2")]
+        let body = RigidBodyBuilder::new_dynamic()
+            .linvel(4.0, -2.0)
+            .angvel(3.0);
+        #[cfg(feature = "dim3")]
+        let body = RigidBodyBuilder::new_dynamic()
+            .linvel(4.0, -2.0, 1.0)
+            .angvel(Vector::new(3.0, -1.0, 2.0));
+        let body = body
+            .linear_damping(0.5)
+            .angular_damping(2.0)
+            .can_sleep(false)
+            .build();
+        let mut linvel = body.linvel;
+        let mut angvel = body.angvel;
+        let handle = bodies.insert(body);
+        colliders.insert(ColliderBuilder::ball(0.5).build(), handle, &mut bodies);
+
+        for _ in 0..10 {
+            pipeline.step(
+                &Vector::zeros(),
+                &params,
+                &mut bf,
+                &mut nf,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &(),
+                &(),
+            );
+
+            linvel *= 1.0 / (1.0 + params.dt() * 0.5);
+            angvel *= 1.0 / (1.0 + params.dt() * 2.0);
+            assert!((bodies[handle].linvel - linvel).norm() < 1.0e-5);
+            #[cfg(feature = "dim2")]
+            assert!((bodies[handle].angvel - angvel).abs() < 1.0e-5);
+            #[cfg(feature = "dim3")]
+            assert!((bodies[handle].angvel - angvel).norm() < 1.0e-5);
+        }
+    }
+
+    struct BodyOnFloor {
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        joints: JointSet,
+        pipeline: PhysicsPipeline,
+        bf: BroadPhase,
+        nf: NarrowPhase,
+        floor_collider: ColliderHandle,
+        body: RigidBodyHandle,
+        collider: ColliderHandle,
+        proximity_events: Vec<ProximityEvent>,
+    }
+
+    impl BodyOnFloor {
+        /// A dynamic body dropped from right above a static floor.
+        fn new(floor: ColliderBuilder, collider: ColliderBuilder) -> Self {
+            let mut bodies = RigidBodySet::new();
+            let mut colliders = ColliderSet::new();
+
+            let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+            let floor_collider = colliders.insert(floor.build(), ground, &mut bodies);
+
+            #[cfg(feature = "dim2")]
+            let body = RigidBodyBuilder::new_dynamic().translation(0.0, 0.7);
+            #[cfg(feature = "dim3")]
+            let body = RigidBodyBuilder::new_dynamic().translation(0.0, 0.7, 0.0);
+            let body = bodies.insert(body.build());
+            let collider = colliders.insert(collider.build(), body, &mut bodies);
+
+            Self {
+                bodies,
+                colliders,
+                joints: JointSet::new(),
+                pipeline: PhysicsPipeline::new(),
+                bf: BroadPhase::new(),
+                nf: NarrowPhase::new(),
+                floor_collider,
+                body,
+                collider,
+                proximity_events: Vec::new(),
+            }
+        }
+
+        fn floor() -> ColliderBuilder {
+            #[cfg(feature = "dim2")]
+            return ColliderBuilder::cuboid(5.0, 0.1);
+            #[cfg(feature = "dim3")]
+            return ColliderBuilder::cuboid(5.0, 0.1, 5.0);
+        }
+
+        /// Runs a second of simulation and returns the contact events emitted meanwhile.
+        fn run(&mut self) -> Vec<ContactEvent> {
+            self.run_with_hooks(&())
+        }
+
+        fn run_with_hooks(&mut self, hooks: &dyn PhysicsHooks) -> Vec<ContactEvent> {
+            let proximity_channel = crossbeam::channel::unbounded();
+            let contact_channel = crossbeam::channel::unbounded();
+            let events = ChannelEventCollector::new(proximity_channel.0, contact_channel.0);
+
+            for _ in 0..60 {
+                self.pipeline.step(
+                    &(Vector::y() * -9.81),
+                    &IntegrationParameters::default(),
+                    &mut self.bf,
+                    &mut self.nf,
+                    &mut self.bodies,
+                    &mut self.colliders,
+                    &mut self.joints,
+                    hooks,
+                    &events,
+                );
+            }
+
+            self.proximity_events.extend(proximity_channel.1.try_iter());
+            contact_channel.1.try_iter().collect()
+        }
+
+        fn height(&self) -> f32 {
+            self.bodies[self.body].position.translation.vector.y
+        }
+    }
+
+    const GROUP1: InteractionGroups = InteractionGroups::new(0b01, 0b01);
+    const GROUP2: InteractionGroups = InteractionGroups::new(0b10, 0b10);
+
+    #[test]
+    fn ball_rests_on_floor_with_compatible_groups() {
+        let floor = BodyOnFloor::floor()
+            .collision_groups(GROUP1)
+            .solver_groups(GROUP2);
+        let ball = ColliderBuilder::ball(0.5)
+            .collision_groups(GROUP1)
+            .solver_groups(GROUP2);
+        let mut world = BodyOnFloor::new(floor, ball);
+        let events = world.run();
+
+        assert!((world.height() - 0.6).abs() < 0.05);
+        assert!(matches!(events[..], [ContactEvent::Started(..)]));
+    }
+
+    #[test]
+    fn incompatible_solver_groups_let_colliders_pass_through_each_other() {
+        let floor = BodyOnFloor::floor().solver_groups(GROUP1);
+        let ball = ColliderBuilder::ball(0.5).solver_groups(GROUP2);
+        let mut world = BodyOnFloor::new(floor, ball);
+        let events = world.run();
+
+        // The contacts are still computed even though they don't affect the motion.
+        assert!(world.height() < -1.0);
+        assert!(matches!(
+            events[..],
+            [ContactEvent::Started(..), ContactEvent::Stopped(..)]
+        ));
+    }
+
+    #[test]
+    fn incompatible_collision_groups_let_colliders_pass_through_each_other() {
+        let floor = BodyOnFloor::floor().collision_groups(GROUP1);
+        let ball = ColliderBuilder::ball(0.5).collision_groups(GROUP2);
+        let mut world = BodyOnFloor::new(floor, ball);
+        let events = world.run();
+
+        assert!(world.height() < -1.0);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn modified_groups_apply_to_colliders_already_in_contact() {
+        let floor = BodyOnFloor::floor()
+            .collision_groups(GROUP1)
+            .solver_groups(GROUP1);
+        let mut world = BodyOnFloor::new(floor, ColliderBuilder::ball(0.5));
+        world.run();
+        assert!((world.height() - 0.6).abs() < 0.05);
+
+        let collider = world.colliders.get_mut(world.collider).unwrap();
+        collider.set_solver_groups(GROUP2);
+        world.bodies.wake_up(world.body);
+        let events = world.run();
+        assert!(world.height() < -1.0);
+        assert!(matches!(events[..], [ContactEvent::Stopped(..)]));
+
+        // Move the ball back above the floor, and let it go through the floor again.
+        #[cfg(feature = "dim2")]
+        let position = Isometry::translation(0.0, 0.7);
+        #[cfg(feature = "dim3")]
+        let position = Isometry::translation(0.0, 0.7, 0.0);
+        {
+            let mut ball = world.bodies.get_mut(world.body).unwrap();
+            ball.set_position(position);
+            ball.linvel = Vector::zeros();
+        }
+        let collider = world.colliders.get_mut(world.collider).unwrap();
+        collider.set_solver_groups(InteractionGroups::all());
+        collider.set_collision_groups(GROUP2);
+        let events = world.run();
+        assert!(world.height() < -1.0);
+        assert!(events.is_empty());
+    }
+
+    struct PairFilter(bool);
+
+    impl PhysicsHooks for PairFilter {
+        fn filter_contact_pair(&self, _: &PairFilterContext) -> bool {
+            self.0
+        }
+
+        fn filter_proximity_pair(&self, _: &PairFilterContext) -> bool {
+            self.0
+        }
+    }
+
+    #[test]
+    fn contact_pair_filter_lets_colliders_pass_through_each_other() {
+        let mut world = BodyOnFloor::new(BodyOnFloor::floor(), ColliderBuilder::ball(0.5));
+        let events = world.run_with_hooks(&PairFilter(false));
+        assert!(world.height() < -1.0);
+        assert!(events.is_empty());
+
+        let mut world = BodyOnFloor::new(BodyOnFloor::floor(), ColliderBuilder::ball(0.5));
+        let events = world.run_with_hooks(&PairFilter(true));
+        assert!((world.height() - 0.6).abs() < 0.05);
+        assert!(matches!(events[..], [ContactEvent::Started(..)]));
+    }
+
+    #[test]
+    fn proximity_pair_filter_disables_proximity_events() {
+        let sensor = ColliderBuilder::ball(0.5).sensor(true);
+        let mut world = BodyOnFloor::new(BodyOnFloor::floor(), sensor.clone());
+        world.run_with_hooks(&PairFilter(false));
+        assert!(world.proximity_events.is_empty());
+
+        let mut world = BodyOnFloor::new(BodyOnFloor::floor(), sensor);
+        world.run_with_hooks(&PairFilter(true));
+        assert!(world
+            .proximity_events
+            .iter()
+            .any(|e| e.new_status == Proximity::Intersecting));
+        assert_eq!(
+            world.proximity_events.last().map(|e| e.new_status),
+            Some(Proximity::Disjoint)
+        );
+    }
+
+    struct DeactivateAllContacts;
+
+    impl PhysicsHooks for DeactivateAllContacts {
+        fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+            while context.manifold.num_active_contacts() != 0 {
+                context.manifold.deactivate_contact(0);
+            }
+        }
+    }
+
+    #[test]
+    fn deactivated_contacts_still_generate_contact_events() {
+        let mut world = BodyOnFloor::new(BodyOnFloor::floor(), ColliderBuilder::ball(0.5));
+        let events = world.run_with_hooks(&DeactivateAllContacts);
+
+        assert!(world.height() < -1.0);
+        assert!(matches!(
+            events[..],
+            [ContactEvent::Started(..), ContactEvent::Stopped(..)]
+        ));
+    }
+
+    struct ConveyorBelt {
+        belt: ColliderHandle,
+        velocity: Vector<f32>,
+    }
+
+    impl PhysicsHooks for ConveyorBelt {
+        fn modify_solver_contacts(&self, context: &mut ContactModificationContext) {
+            // The tangent velocity is the velocity of the second collider relative to the first one.
+            let velocity = if context.collider_handle1 == self.belt {
+                self.velocity
+            } else {
+                -self.velocity
+            };
+
+            for contact in context.manifold.active_contacts_mut() {
+                contact.tangent_velocity = velocity;
+            }
+        }
+    }
+
+    #[test]
+    fn contact_tangent_velocity_drags_bodies_along_the_contact_plane() {
+        #[cfg(feature = "dim2")]
+        let crate_collider = ColliderBuilder::cuboid(0.5, 0.1);
+        #[cfg(feature = "dim3")]
+        let crate_collider = ColliderBuilder::cuboid(0.5, 0.1, 0.5);
+        let mut world = BodyOnFloor::new(BodyOnFloor::floor(), crate_collider);
+        let hooks = ConveyorBelt {
+            belt: world.floor_collider,
+            velocity: Vector::x() * 2.0,
+        };
+        world.run_with_hooks(&hooks);
+
+        let linvel = world.bodies[world.body].linvel;
+        assert!((linvel - Vector::x() * 2.0).norm() < 0.05);
+        assert!((world.height() - 0.2).abs() < 0.05);
+    }
+
+    struct JointedBody {
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        joints: JointSet,
+        pipeline: PhysicsPipeline,
+        bf: BroadPhase,
+        nf: NarrowPhase,
+        body: RigidBodyHandle,
+        joint: JointHandle,
+    }
+
+    impl JointedBody {
+        /// A dynamic ball attached by `joint` to a static body at the origin.
+        fn new(body: RigidBodyBuilder, joint: impl Into<JointParams>) -> Self {
+            let mut bodies = RigidBodySet::new();
+            let mut colliders = ColliderSet::new();
+            let mut joints = JointSet::new();
+
+            let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+            let body = bodies.insert(body.build());
+            colliders.insert(ColliderBuilder::ball(0.5).build(), body, &mut bodies);
+            let joint = joints.insert(&mut bodies, ground, body, joint);
+
+            Self {
+                bodies,
+                colliders,
+                joints,
+                pipeline: PhysicsPipeline::new(),
+                bf: BroadPhase::new(),
+                nf: NarrowPhase::new(),
+                body,
+                joint,
+            }
+        }
+
+        fn step(&mut self, gravity: Vector<f32>) {
+            self.pipeline.step(
+                &gravity,
+                &IntegrationParameters::default(),
+                &mut self.bf,
+                &mut self.nf,
+                &mut self.bodies,
+                &mut self.colliders,
+                &mut self.joints,
+                &(),
+                &(),
+            );
+        }
+
+        fn position(&self) -> &Isometry<f32> {
+            &self.bodies[self.body].position
+        }
+
+        fn params(&self) -> &JointParams {
+            &self.joints.get(self.joint).unwrap().params
+        }
+
+        fn params_mut(&mut self) -> &mut JointParams {
+            &mut self.joints.get_mut(self.joint).unwrap().params
+        }
+    }
+
+    #[test]
+    fn revolute_motor_reaches_its_target_velocity() {
+        #[cfg(feature = "dim2")]
+        let mut joint = RevoluteJoint::new(Point::origin(), Point::origin());
+        #[cfg(feature = "dim3")]
+        let mut joint = RevoluteJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Point::origin(),
+            Vector::y_axis(),
+        );
+        joint.configure_motor_velocity(2.0, 1.0);
+        let mut world = JointedBody::new(RigidBodyBuilder::new_dynamic(), joint);
+
+        for _ in 0..120 {
+            world.step(Vector::zeros());
+        }
+
+        #[cfg(feature = "dim2")]
+        let angvel = world.bodies[world.body].angvel;
+        #[cfg(feature = "dim3")]
+        let angvel = world.bodies[world.body].angvel.y;
+        assert!((angvel - 2.0).abs() < 1.0e-2);
+    }
+
+    #[test]
+    fn prismatic_position_motor_converges_to_its_target() {
+        #[cfg(feature = "dim2")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Point::origin(),
+            Vector::x_axis(),
+        );
+        #[cfg(feature = "dim3")]
+        let mut joint = PrismaticJoint::new(
+            Point::origin(),
+            Vector::x_axis(),
+            Vector::y(),
+            Point::origin(),
+            Vector::x_axis(),
+            Vector::y(),
+        );
+        joint.configure_motor_position(1.0, 100.0, 20.0);
+        let mut world = JointedBody::new(RigidBodyBuilder::new_dynamic(), joint);
+
+        for _ in 0..300 {
+            world.step(Vector::zeros());
+        }
+
+        let translation = world.position().translation.vector;
+        assert!((translation.x - 1.0).abs() < 1.0e-2);
+        assert!(translation.y.abs() < 1.0e-3);
+        assert!(world.bodies[world.body].linvel.norm() < 1.0e-2);
+    }
+
+    #[test]
+    fn revolute_limits_bound_the_joint_angle() {
+        #[cfg(feature = "dim2")]
+        let mut joint = RevoluteJoint::new(Point::origin(), Point::origin());
+        #[cfg(feature = "dim3")]
+        let mut joint = RevoluteJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Point::origin(),
+            Vector::y_axis(),
+        );
+        joint.limits_enabled = true;
+        joint.limits = [-0.5, 0.5];
+        let mut world = JointedBody::new(RigidBodyBuilder::new_dynamic(), joint);
+
+        let angle = |world: &JointedBody| {
+            let joint = world.params().as_revolute_joint().unwrap();
+            joint.angle(&Isometry::identity(), world.position())
+        };
+
+        // The motor keeps pushing the body past the max limit, then past the min limit.
+        for &(target_vel, limit) in &[(5.0, 0.5), (-5.0, -0.5)] {
+            if let JointParams::RevoluteJoint(joint) = world.params_mut() {
+                joint.configure_motor_velocity(target_vel, 1.0);
+            }
+
+            for _ in 0..120 {
+                world.step(Vector::zeros());
+                // The limits are only enforced once they are exceeded, so the angle may
+                // overshoot them during the timestep the limit is reached.
+                let angle = angle(&world);
+                assert!(angle.abs() < 0.6);
+            }
+
+            assert!((angle(&world) - limit).abs() < 1.0e-3);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dim2")]
+    fn revolute_pendulum_stays_attached_to_its_anchor() {
+        let joint = RevoluteJoint::new(Point::origin(), Point::new(-1.0, 0.0));
+        let body = RigidBodyBuilder::new_dynamic().translation(1.0, 0.0);
+        let mut world = JointedBody::new(body, joint);
+        let mut lowest_height = 0.0f32;
+
+        for _ in 0..600 {
+            world.step(Vector::y() * -9.81);
+            let anchor2 = world.position() * Point::new(-1.0, 0.0);
+            assert!(anchor2.coords.norm() < 1.0e-2);
+            lowest_height = lowest_height.min(world.position().translation.vector.y);
+        }
+
+        // The pendulum actually swung down instead of staying stuck.
+        assert!(lowest_height < -0.99);
+    }
+
+    /// A generic joint that locks every axis but the first linear one.
+    fn generic_slider() -> GenericJoint {
+        let mut joint = GenericJoint::new(Isometry::identity(), Isometry::identity());
+        for axis in 1..SPATIAL_DIM {
+            joint.lock_axis(axis);
+        }
+        joint
+    }
+
+    #[test]
+    fn generic_joint_locked_axes_prevent_relative_motion() {
+        #[cfg(feature = "dim2")]
+        let body = RigidBodyBuilder::new_dynamic().linvel(1.0, 1.0).angvel(1.0);
+        #[cfg(feature = "dim3")]
+        let body = RigidBodyBuilder::new_dynamic()
+            .linvel(1.0, 1.0, 1.0)
+            .angvel(Vector::repeat(1.0));
+        let mut world = JointedBody::new(body, generic_slider());
+
+        for _ in 0..60 {
+            world.step(Vector::zeros());
+        }
+
+        let joint = world.params().as_generic_joint().unwrap();
+        let coordinates = joint.coordinates(&Isometry::identity(), world.position());
+        assert!((coordinates[0] - 1.0).abs() < 1.0e-2);
+        for axis in 1..SPATIAL_DIM {
+            assert!(coordinates[axis].abs() < 1.0e-3);
+        }
+    }
+
+    #[test]
+    fn generic_joint_limits_bound_the_relative_position() {
+        let mut joint = generic_slider();
+        joint.set_limits(0, [-0.5, 0.5]);
+        #[cfg(feature = "dim2")]
+        let body = RigidBodyBuilder::new_dynamic().linvel(5.0, 0.0);
+        #[cfg(feature = "dim3")]
+        let body = RigidBodyBuilder::new_dynamic().linvel(5.0, 0.0, 0.0);
+        let mut world = JointedBody::new(body, joint);
+
+        let coordinates = |world: &JointedBody| {
+            let joint = world.params().as_generic_joint().unwrap();
+            joint.coordinates(&Isometry::identity(), world.position())
+        };
+
+        for _ in 0..60 {
+            world.step(Vector::zeros());
+            // The limits are only enforced once they are exceeded, so the body may
+            // overshoot them during the timestep the limit is reached.
+            assert!(coordinates(&world)[0].abs() < 0.6);
+        }
+
+        assert!((coordinates(&world)[0] - 0.5).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn distance_joint_limits_bound_the_anchor_distance() {
+        let mut joint = DistanceJoint::new(Point::origin(), Point::origin());
+        joint.set_limits([1.0, 2.0]);
+        #[cfg(feature = "dim2")]
+        let body = RigidBodyBuilder::new_dynamic().translation(1.5, 0.0);
+        #[cfg(feature = "dim3")]
+        let body = RigidBodyBuilder::new_dynamic().translation(1.5, 0.0, 0.0);
+        let mut world = JointedBody::new(body, joint);
+
+        // Throw the body away from the anchor, then toward it.
+        for &(speed, limit) in &[(5.0, 2.0), (-5.0, 1.0)] {
+            world.bodies[world.body].linvel = Vector::x() * speed;
+
+            for _ in 0..60 {
+                world.step(Vector::zeros());
+                // The limits are only enforced once they are exceeded, so the distance may
+                // overshoot them during the timestep the limit is reached.
+                let distance = world.position().translation.vector.norm();
+                assert!(distance > 0.9 && distance < 2.1);
+            }
+
+            let distance = world.position().translation.vector.norm();
+            assert!((distance - limit).abs() < 1.0e-3);
+        }
+    }
+
+    #[test]
+    fn distance_joint_spring_settles_at_its_rest_length() {
+        let mut joint = DistanceJoint::new(Point::origin(), Point::origin());
+        joint.configure_spring(1.0, 50.0, 10.0);
+        #[cfg(feature = "dim2")]
+        let body = RigidBodyBuilder::new_dynamic().translation(2.0, 0.0);
+        #[cfg(feature = "dim3")]
+        let body = RigidBodyBuilder::new_dynamic().translation(2.0, 0.0, 0.0);
+        let mut world = JointedBody::new(body, joint);
+
+        for _ in 0..300 {
+            world.step(Vector::zeros());
+        }
+
+        let distance = world.position().translation.vector.norm();
+        assert!((distance - 1.0).abs() < 1.0e-2);
+        assert!(world.bodies[world.body].linvel.norm() < 1.0e-2);
+    }
 }
@@ -0,0 +1,800 @@
+//! Query pipeline structures.
+
+use crate::geometry::{
+    Collider, ColliderHandle, ColliderSet, FeatureId, InteractionGroups, PointProjection, Ray,
+    RayIntersection, Shape, WAABBHierarchy, WAABBHierarchyIntersections, AABB, TOI,
+};
+use crate::math::{Isometry, Point, Vector};
+use ncollide::bounding_volume::BoundingVolume;
+use ncollide::query::{PointQuery, RayCast};
+
+/// A pipeline for performing queries on all the colliders of a scene.
+///
+/// The acceleration structure used by the queries is not updated automatically. Call
+/// `QueryPipeline::update` whenever the colliders have moved, have been added, or have been removed.
+// NOTE: this contains only workspace data, so there is no point in making this serializable.
+pub struct QueryPipeline {
+    tree: Option<WAABBHierarchy>,
+    handles: Vec<ColliderHandle>,
+    // The AABB of all the colliders at the time of the last update.
+    aabb: AABB,
+}
+
+impl Default for QueryPipeline {
+    fn default() -> Self {
+        QueryPipeline::new()
+    }
+}
+
+impl QueryPipeline {
+    /// Initializes an empty query pipeline.
+    pub fn new() -> Self {
+        Self {
+            tree: None,
+            handles: Vec::new(),
+            aabb: AABB::new_invalid(),
+        }
+    }
+
+    /// Update the acceleration structure on the query pipeline.
+    pub fn update(&mut self, colliders: &ColliderSet) {
+        self.handles.clear();
+        self.aabb = AABB::new_invalid();
+        let mut aabbs = Vec::with_capacity(colliders.len());
+
+        for (handle, collider) in colliders.iter() {
+            let aabb = collider.compute_aabb();
+            self.handles.push(handle);
+            self.aabb.merge(&aabb);
+            aabbs.push(aabb);
+        }
+
+        self.tree = if aabbs.is_empty() {
+            None
+        } else {
+            Some(WAABBHierarchy::new(&aabbs))
+        };
+    }
+
+    /// Find the closest intersection between a ray and a set of collider.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set of colliders taking part in this pipeline.
+    /// - `ray`: the ray to cast.
+    /// - `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
+    ///   limits the length of the ray to `ray.dir.norm() * max_toi`. Use `f32::MAX` for an unbounded ray.
+    /// - `solid`: if `true` a ray starting inside of a shape will hit it at a time-of-impact
+    ///   equal to zero. Otherwise, the ray will hit the boundary of that shape from the inside.
+    pub fn cast_ray(
+        &self,
+        colliders: &ColliderSet,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<(ColliderHandle, f32)> {
+        self.cast_ray_and_get_normal(colliders, ray, max_toi, solid)
+            .map(|(handle, inter)| (handle, inter.toi))
+    }
+
+    /// Find the closest intersection between a ray and a set of collider, as well as the normal
+    /// of the collider's boundary at the hit point.
+    ///
+    /// See `QueryPipeline::cast_ray` for the description of the parameters.
+    pub fn cast_ray_and_get_normal(
+        &self,
+        colliders: &ColliderSet,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+    ) -> Option<(ColliderHandle, RayIntersection)> {
+        let mut best = max_toi;
+        let mut result = None;
+
+        for handle in self.ray_interferences(ray, max_toi) {
+            if let Some(collider) = colliders.get(handle) {
+                if let Some(inter) =
+                    collider
+                        .shape()
+                        .cast_ray(collider.position(), ray, best, solid)
+                {
+                    if inter.toi <= best {
+                        best = inter.toi;
+                        result = Some((handle, inter));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Find all the intersections between a ray and a set of collider.
+    ///
+    /// The `callback` is called once for each collider hit by the ray, with the handle of
+    /// the collider, the collider itself, and the intersection with the ray. The search
+    /// stops as soon as the `callback` returns `false`.
+    ///
+    /// See `QueryPipeline::cast_ray` for the description of the other parameters.
+    pub fn intersections_with_ray<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        ray: &Ray,
+        max_toi: f32,
+        solid: bool,
+        mut callback: impl FnMut(ColliderHandle, &'a Collider, RayIntersection) -> bool,
+    ) {
+        for handle in self.ray_interferences(ray, max_toi) {
+            if let Some(collider) = colliders.get(handle) {
+                if let Some(inter) =
+                    collider
+                        .shape()
+                        .cast_ray(collider.position(), ray, max_toi, solid)
+                {
+                    if !callback(handle, collider, inter) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Find the projection of a point on the closest collider.
+    ///
+    /// Returns the handle of the closest collider, the collider itself, the projection of the
+    /// point on that collider, and the feature of the collider the point was projected on.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set of colliders taking part in this pipeline.
+    /// - `point`: the point to project.
+    /// - `solid`: if `true` a point inside of a collider is projected on itself. Otherwise, it is
+    ///   projected on the boundary of that collider.
+    /// - `groups`: the interaction groups of the point. Only the colliders with collision groups
+    ///   compatible with `groups` are taken into account.
+    ///
+    /// Colliders with nothing to project on, i.e., empty voxel grids, are ignored.
+    pub fn project_point<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        point: &Point<f32>,
+        solid: bool,
+        groups: InteractionGroups,
+    ) -> Option<(ColliderHandle, &'a Collider, PointProjection, FeatureId)> {
+        // Nothing to project on if the pipeline is empty.
+        self.tree.as_ref()?;
+
+        let mut best: Option<(
+            f32,
+            ColliderHandle,
+            &'a Collider,
+            PointProjection,
+            FeatureId,
+        )> = None;
+
+        // Look for the colliders close to the point with search boxes of increasing sizes
+        // until the closest projection found is guaranteed to be within the search box.
+        let mut radius = self
+            .aabb
+            .distance_to_point(&Isometry::identity(), point, true)
+            + self.aabb.half_extents().norm() / 64.0;
+
+        loop {
+            let extents = Vector::repeat(radius);
+            let search_aabb = AABB::new(point - extents, point + extents);
+
+            for handle in self.interferences_with_predicate(|aabb| aabb.intersects(&search_aabb)) {
+                if let Some(collider) = colliders.get(handle) {
+                    if !groups.test(collider.collision_groups) {
+                        continue;
+                    }
+
+                    if let Some((proj, feature)) =
+                        collider
+                            .shape()
+                            .project_point(collider.position(), point, solid)
+                    {
+                        let dist = na::distance(point, &proj.point);
+
+                        if best.as_ref().map(|b| dist < b.0).unwrap_or(true) {
+                            best = Some((dist, handle, collider, proj, feature));
+                        }
+                    }
+                }
+            }
+
+            match &best {
+                Some((dist, ..)) if *dist <= radius => break,
+                // A closer collider may still be outside of the search box.
+                Some((dist, ..)) => radius = *dist,
+                None if search_aabb.contains(&self.aabb) => break,
+                None => radius *= 2.0,
+            }
+        }
+
+        best.map(|(_, handle, collider, proj, feature)| (handle, collider, proj, feature))
+    }
+
+    /// Find all the colliders containing the given point.
+    ///
+    /// The `callback` is called once for each collider containing the point, with the handle
+    /// of the collider and the collider itself. The search stops as soon as the `callback`
+    /// returns `false`. Colliders with nothing to contain the point, i.e., empty voxel grids,
+    /// are never reported.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set of colliders taking part in this pipeline.
+    /// - `point`: the point to test.
+    /// - `groups`: the interaction groups of the point. Only the colliders with collision groups
+    ///   compatible with `groups` are taken into account.
+    /// - `callback`: the closure called for each collider containing the point.
+    pub fn intersections_with_point<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        point: &Point<f32>,
+        groups: InteractionGroups,
+        mut callback: impl FnMut(ColliderHandle, &'a Collider) -> bool,
+    ) {
+        let point_aabb = AABB::new(*point, *point);
+
+        for handle in self.interferences_with_predicate(|aabb| aabb.intersects(&point_aabb)) {
+            if let Some(collider) = colliders.get(handle) {
+                if !groups.test(collider.collision_groups) {
+                    continue;
+                }
+
+                let is_inside = collider
+                    .shape()
+                    .project_point(collider.position(), point, true)
+                    .map(|(proj, _)| proj.is_inside)
+                    .unwrap_or(false);
+
+                if is_inside && !callback(handle, collider) {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Find all the colliders with an AABB intersecting the given AABB.
+    ///
+    /// The AABBs of the colliders are the ones computed during the last call to
+    /// `QueryPipeline::update`.
+    ///
+    /// The `callback` is called once for each collider with an AABB intersecting `aabb`, with
+    /// the handle of the collider and the collider itself. The search stops as soon as the
+    /// `callback` returns `false`.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set of colliders taking part in this pipeline.
+    /// - `aabb`: the AABB to test.
+    /// - `groups`: the interaction groups of the AABB. Only the colliders with collision groups
+    ///   compatible with `groups` are taken into account.
+    /// - `callback`: the closure called for each collider with an AABB intersecting `aabb`.
+    pub fn intersections_with_aabb<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        aabb: &AABB,
+        groups: InteractionGroups,
+        mut callback: impl FnMut(ColliderHandle, &'a Collider) -> bool,
+    ) {
+        for handle in self.interferences_with_predicate(|node_aabb| node_aabb.intersects(aabb)) {
+            if let Some(collider) = colliders.get(handle) {
+                if !groups.test(collider.collision_groups) {
+                    continue;
+                }
+
+                if !callback(handle, collider) {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Find all the colliders intersecting the given shape.
+    ///
+    /// The `callback` is called once for each collider intersecting the shape, with the handle
+    /// of the collider and the collider itself. The search stops as soon as the `callback`
+    /// returns `false`. Colliders that are only touching the shape are considered intersecting.
+    ///
+    /// # Parameters
+    /// - `colliders`: the set of colliders taking part in this pipeline.
+    /// - `shape_pos`: the position of the shape.
+    /// - `shape`: the shape to test.
+    /// - `groups`: the interaction groups of the shape. Only the colliders with collision groups
+    ///   compatible with `groups` are taken into account.
+    /// - `callback`: the closure called for each collider intersecting the shape.
+    pub fn intersections_with_shape<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        shape_pos: &Isometry<f32>,
+        shape: &Shape,
+        groups: InteractionGroups,
+        mut callback: impl FnMut(ColliderHandle, &'a Collider) -> bool,
+    ) {
+        let aabb = shape.compute_aabb(shape_pos);
+
+        for handle in self.interferences_with_predicate(|node_aabb| node_aabb.intersects(&aabb)) {
+            if let Some(collider) = colliders.get(handle) {
+                if !groups.test(collider.collision_groups) {
+                    continue;
+                }
+
+                if crate::geometry::intersection_test(
+                    shape_pos,
+                    shape,
+                    collider.position(),
+                    collider.shape(),
+                ) && !callback(handle, collider)
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Find the first collider hit by a shape moving with a constant linear velocity.
+    ///
+    /// Returns the handle of the collider hit first, the collider itself, and the time of impact
+    /// between the two shapes. The witness points and normals of the returned `TOI` are expressed
+    /// in the local-space of the moving shape (`witness1`, `normal1`) and of the hit collider
+    /// (`witness2`, `normal2`).
+    ///
+    /// # Parameters
+    /// - `colliders`: the set of colliders taking part in this pipeline.
+    /// - `shape_pos`: the initial position of the shape to cast.
+    /// - `shape_vel`: the constant linear velocity of the shape to cast.
+    /// - `shape`: the shape to cast.
+    /// - `max_toi`: the maximum time-of-impact that can be reported by this cast. This effectively
+    ///   limits the distance traveled by the shape to `shape_vel.norm() * max_toi`.
+    /// - `groups`: the interaction groups of the shape. Only the colliders with collision groups
+    ///   compatible with `groups` can be hit.
+    pub fn cast_shape<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        shape_pos: &Isometry<f32>,
+        shape_vel: &Vector<f32>,
+        shape: &Shape,
+        max_toi: f32,
+        groups: InteractionGroups,
+    ) -> Option<(ColliderHandle, &'a Collider, TOI)> {
+        self.cast_shape_with_filter(
+            colliders,
+            shape_pos,
+            shape_vel,
+            shape,
+            max_toi,
+            |_, collider| groups.test(collider.collision_groups),
+        )
+    }
+
+    /// Find the first collider hit by a moving shape, among the colliders accepted by `filter`.
+    ///
+    /// See `QueryPipeline::cast_shape` for the description of the other parameters.
+    pub(crate) fn cast_shape_with_filter<'a>(
+        &self,
+        colliders: &'a ColliderSet,
+        shape_pos: &Isometry<f32>,
+        shape_vel: &Vector<f32>,
+        shape: &Shape,
+        max_toi: f32,
+        filter: impl Fn(ColliderHandle, &Collider) -> bool,
+    ) -> Option<(ColliderHandle, &'a Collider, TOI)> {
+        let aabb = shape.compute_aabb(shape_pos);
+        let shift = shape_vel * max_toi;
+        let swept_aabb = aabb.merged(&AABB::new(aabb.mins + shift, aabb.maxs + shift));
+
+        let mut best = max_toi;
+        let mut result = None;
+
+        for handle in self.interferences_with_predicate(|aabb| aabb.intersects(&swept_aabb)) {
+            if let Some(collider) = colliders.get(handle) {
+                if !filter(handle, collider) {
+                    continue;
+                }
+
+                if let Some(toi) = crate::geometry::time_of_impact(
+                    shape_pos,
+                    shape_vel,
+                    shape,
+                    collider.position(),
+                    &Vector::zeros(),
+                    collider.shape(),
+                    best,
+                ) {
+                    if toi.toi <= best {
+                        best = toi.toi;
+                        result = Some((handle, collider, toi));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The handles of all the colliders with an AABB intersecting the given ray.
+    fn ray_interferences(&self, ray: &Ray, max_toi: f32) -> Vec<ColliderHandle> {
+        self.interferences_with_predicate(|aabb| {
+            aabb.intersects_ray(&Isometry::identity(), ray, max_toi)
+        })
+    }
+
+    /// The handles of all the colliders with an AABB satisfying the given predicate.
+    ///
+    /// The predicate must return `true` for any AABB containing an AABB it returns `true` for.
+    fn interferences_with_predicate(
+        &self,
+        predicate: impl FnMut(&AABB) -> bool,
+    ) -> Vec<ColliderHandle> {
+        let mut result = Vec::new();
+
+        if let Some(tree) = &self.tree {
+            let mut workspace = WAABBHierarchyIntersections::new();
+            tree.compute_interferences_with_predicate(predicate, &mut workspace);
+
+            for id in workspace.computed_interferences() {
+                // Padding leaves of the hierarchy have out-of-bounds indices.
+                if let Some(handle) = self.handles.get(*id) {
+                    result.push(*handle);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{ColliderBuilder, ColliderSet, InteractionGroups, Ray, AABB};
+    use crate::math::{Point, Vector};
+    use crate::pipeline::QueryPipeline;
+
+    #[test]
+    fn ray_casts_find_the_closest_collider() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        #[cfg(feature = "dim2")]
+        let (near, far) = (
+            RigidBodyBuilder::new_static().translation(2.0, 0.0),
+            RigidBodyBuilder::new_static().translation(5.0, 0.0),
+        );
+        #[cfg(feature = "dim3")]
+        let (near, far) = (
+            RigidBodyBuilder::new_static().translation(2.0, 0.0, 0.0),
+            RigidBodyBuilder::new_static().translation(5.0, 0.0, 0.0),
+        );
+        let near = bodies.insert(near.build());
+        let far = bodies.insert(far.build());
+        let near = colliders.insert(ColliderBuilder::ball(0.5).build(), near, &mut bodies);
+        let far = colliders.insert(ColliderBuilder::ball(0.5).build(), far, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&colliders);
+
+        let ray = Ray::new(Point::origin(), Vector::x());
+        let (handle, toi) = pipeline.cast_ray(&colliders, &ray, f32::MAX, true).unwrap();
+        assert_eq!(handle, near);
+        assert!((toi - 1.5).abs() < 1.0e-5);
+
+        let (handle, inter) = pipeline
+            .cast_ray_and_get_normal(&colliders, &ray, f32::MAX, true)
+            .unwrap();
+        assert_eq!(handle, near);
+        assert!((inter.normal + Vector::x()).norm() < 1.0e-5);
+
+        // The ray is too short to reach any collider.
+        assert!(pipeline.cast_ray(&colliders, &ray, 1.0, true).is_none());
+
+        let mut hits = Vec::new();
+        pipeline.intersections_with_ray(&colliders, &ray, f32::MAX, true, |handle, _, inter| {
+            hits.push((handle, inter.toi));
+            true
+        });
+        hits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].0, near);
+        assert_eq!(hits[1].0, far);
+        assert!((hits[1].1 - 4.5).abs() < 1.0e-5);
+
+        // The search stops as soon as the callback returns `false`.
+        let mut num_hits = 0;
+        pipeline.intersections_with_ray(&colliders, &ray, f32::MAX, true, |_, _, _| {
+            num_hits += 1;
+            false
+        });
+        assert_eq!(num_hits, 1);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn ray_casts_on_3d_polygons_only_hit_their_plane() {
+        use crate::geometry::{Polygon, Shape};
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // A square in the `z = 0` plane.
+        let vertices = vec![
+            Point::new(-1.0, -1.0, 0.0),
+            Point::new(1.0, -1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(-1.0, 1.0, 0.0),
+        ];
+        let normals = vec![-Vector::y(), Vector::x(), Vector::y(), -Vector::x()];
+        let polygon = Shape::Polygon(Polygon::new(vertices, normals));
+        let body = bodies.insert(RigidBodyBuilder::new_static().build());
+        let handle = colliders.insert(ColliderBuilder::new(polygon).build(), body, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&colliders);
+
+        // Crossing the plane inside of the polygon.
+        let ray = Ray::new(Point::new(0.5, 0.5, 5.0), -Vector::z());
+        let (hit, inter) = pipeline
+            .cast_ray_and_get_normal(&colliders, &ray, f32::MAX, true)
+            .unwrap();
+        assert_eq!(hit, handle);
+        assert!((inter.toi - 5.0).abs() < 1.0e-5);
+        assert!((inter.normal - Vector::z()).norm() < 1.0e-5);
+
+        // Crossing the plane outside of the polygon.
+        let ray = Ray::new(Point::new(1.5, 0.5, 5.0), -Vector::z());
+        assert!(pipeline
+            .cast_ray(&colliders, &ray, f32::MAX, true)
+            .is_none());
+
+        // Parallel to the plane but off of it.
+        let ray = Ray::new(Point::new(-5.0, 0.0, 0.5), Vector::x());
+        assert!(pipeline
+            .cast_ray(&colliders, &ray, f32::MAX, true)
+            .is_none());
+    }
+
+    #[test]
+    fn queries_filter_colliders_by_groups() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let body = bodies.insert(RigidBodyBuilder::new_static().build());
+        let group1 = InteractionGroups::new(0b01, 0b01);
+        let group2 = InteractionGroups::new(0b10, 0b10);
+        let ball1 = ColliderBuilder::ball(1.0).collision_groups(group1).build();
+        let ball2 = ColliderBuilder::ball(0.5).collision_groups(group2).build();
+        let ball1 = colliders.insert(ball1, body, &mut bodies);
+        let ball2 = colliders.insert(ball2, body, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&colliders);
+
+        let point = Point::from(Vector::x() * 0.25);
+        let mut hits = Vec::new();
+        pipeline.intersections_with_point(&colliders, &point, group2, |handle, _| {
+            hits.push(handle);
+            true
+        });
+        assert_eq!(hits, vec![ball2]);
+
+        let mut hits = Vec::new();
+        pipeline.intersections_with_point(
+            &colliders,
+            &point,
+            InteractionGroups::all(),
+            |handle, _| {
+                hits.push(handle);
+                true
+            },
+        );
+        assert_eq!(hits.len(), 2);
+
+        let (handle, _, proj, _) = pipeline
+            .project_point(&colliders, &point, false, group1)
+            .unwrap();
+        assert_eq!(handle, ball1);
+        assert!((na::distance(&point, &proj.point) - 0.75).abs() < 1.0e-5);
+
+        let aabb = AABB::new(point, point);
+        let mut hits = Vec::new();
+        pipeline.intersections_with_aabb(&colliders, &aabb, group1, |handle, _| {
+            hits.push(handle);
+            true
+        });
+        assert_eq!(hits, vec![ball1]);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn point_queries_support_shapes_without_ncollide_equivalent() {
+        use crate::geometry::{Polygon, Shape};
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // A square in the `z = 0` plane, and a cylinder far from it.
+        let vertices = vec![
+            Point::new(-1.0, -1.0, 0.0),
+            Point::new(1.0, -1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(-1.0, 1.0, 0.0),
+        ];
+        let normals = vec![-Vector::y(), Vector::x(), Vector::y(), -Vector::x()];
+        let polygon = Shape::Polygon(Polygon::new(vertices, normals));
+        let body = bodies.insert(RigidBodyBuilder::new_static().build());
+        let polygon = colliders.insert(ColliderBuilder::new(polygon).build(), body, &mut bodies);
+        let body = RigidBodyBuilder::new_static().translation(10.0, 0.0, 0.0);
+        let body = bodies.insert(body.build());
+        let cylinder = ColliderBuilder::cylinder(1.0, 0.5).build();
+        let cylinder = colliders.insert(cylinder, body, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&colliders);
+
+        let point = Point::new(0.5, 0.5, 2.0);
+        let (handle, _, proj, _) = pipeline
+            .project_point(&colliders, &point, false, InteractionGroups::all())
+            .unwrap();
+        assert_eq!(handle, polygon);
+        assert!((proj.point - Point::new(0.5, 0.5, 0.0)).norm() < 1.0e-4);
+
+        let mut hits = Vec::new();
+        let point = Point::new(10.2, 0.5, 0.0);
+        pipeline.intersections_with_point(
+            &colliders,
+            &point,
+            InteractionGroups::all(),
+            |handle, _| {
+                hits.push(handle);
+                true
+            },
+        );
+        assert_eq!(hits, vec![cylinder]);
+    }
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn shape_intersections_with_cylinders_and_cones() {
+        use crate::geometry::{Ball, Cylinder, Shape};
+        use crate::math::Isometry;
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let body = bodies.insert(RigidBodyBuilder::new_static().build());
+        let cylinder = ColliderBuilder::cylinder(1.0, 0.5).build();
+        let cylinder = colliders.insert(cylinder, body, &mut bodies);
+        let body = RigidBodyBuilder::new_static().translation(3.0, 0.0, 0.0);
+        let body = bodies.insert(body.build());
+        let cone = colliders.insert(ColliderBuilder::cone(1.0, 0.5).build(), body, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&colliders);
+
+        let intersections = |pos: Isometry<f32>, shape: &Shape| {
+            let mut hits = Vec::new();
+            pipeline.intersections_with_shape(
+                &colliders,
+                &pos,
+                shape,
+                InteractionGroups::all(),
+                |handle, _| {
+                    hits.push(handle);
+                    true
+                },
+            );
+            hits
+        };
+
+        let ball = Shape::Ball(Ball::new(0.5));
+        let hits = intersections(Isometry::translation(0.9, 0.0, 0.0), &ball);
+        assert_eq!(hits, vec![cylinder]);
+
+        // The cone has a radius close to 0.25 around mid-height, so this thin cylinder only
+        // overlaps its AABB.
+        let shape = Shape::Cylinder(Cylinder::new(0.1, 0.5));
+        let hits = intersections(Isometry::translation(2.15, 0.0, 0.0), &shape);
+        assert!(hits.is_empty());
+
+        let hits = intersections(Isometry::translation(2.4, 0.0, 0.0), &shape);
+        assert_eq!(hits, vec![cone]);
+    }
+
+    #[test]
+    fn shape_casts_hit_trimeshes_and_heightfields() {
+        use crate::geometry::Shape;
+        use crate::math::Isometry;
+        use na::Point3;
+
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // A trimesh and a heightfield side by side, both with their top at `y = 0`.
+        #[cfg(feature = "dim2")]
+        let (trimesh, heightfield_body, heightfield) = (
+            ColliderBuilder::trimesh(
+                vec![
+                    Point::new(-5.0, -1.0),
+                    Point::new(-1.0, -1.0),
+                    Point::new(-1.0, 0.0),
+                    Point::new(-5.0, 0.0),
+                ],
+                vec![Point3::new(0, 1, 2), Point3::new(0, 2, 3)],
+            ),
+            RigidBodyBuilder::new_static().translation(3.0, 0.0),
+            ColliderBuilder::heightfield(na::DVector::zeros(3), Vector::new(4.0, 1.0)),
+        );
+        #[cfg(feature = "dim3")]
+        let (trimesh, heightfield_body, heightfield) = (
+            ColliderBuilder::trimesh(
+                vec![
+                    Point::new(-5.0, 0.0, -2.0),
+                    Point::new(-1.0, 0.0, -2.0),
+                    Point::new(-1.0, 0.0, 2.0),
+                    Point::new(-5.0, 0.0, 2.0),
+                ],
+                vec![Point3::new(0, 2, 1), Point3::new(0, 3, 2)],
+            ),
+            RigidBodyBuilder::new_static().translation(3.0, 0.0, 0.0),
+            ColliderBuilder::heightfield(na::DMatrix::zeros(3, 3), Vector::new(4.0, 1.0, 4.0)),
+        );
+        let body = bodies.insert(RigidBodyBuilder::new_static().build());
+        let trimesh = colliders.insert(trimesh.build(), body, &mut bodies);
+        let body = bodies.insert(heightfield_body.build());
+        let heightfield = colliders.insert(heightfield.build(), body, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&colliders);
+
+        // A unit square or cube, which has no ncollide shape equivalent.
+        #[cfg(feature = "dim2")]
+        let shape = {
+            use crate::geometry::Polygon;
+            let vertices = vec![
+                Point::new(-0.5, -0.5),
+                Point::new(0.5, -0.5),
+                Point::new(0.5, 0.5),
+                Point::new(-0.5, 0.5),
+            ];
+            let normals = vec![-Vector::y(), Vector::x(), Vector::y(), -Vector::x()];
+            Shape::Polygon(Polygon::new(vertices, normals))
+        };
+        #[cfg(feature = "dim3")]
+        let shape = {
+            use crate::geometry::ConvexPolyhedron;
+            let mut vertices = Vec::new();
+            for i in 0..8 {
+                let corner = Vector::new((i & 1) as f32, ((i >> 1) & 1) as f32, (i >> 2) as f32);
+                vertices.push(Point::from(corner - Vector::repeat(0.5)));
+            }
+            Shape::ConvexPolyhedron(ConvexPolyhedron::from_points(&vertices).unwrap())
+        };
+
+        let cast_down = |x: f32, filter: &dyn Fn(_, &_) -> bool| {
+            let mut pos = Isometry::identity();
+            pos.translation.vector.x = x;
+            pos.translation.vector.y = 5.0;
+            pipeline.cast_shape_with_filter(
+                &colliders,
+                &pos,
+                &-Vector::y(),
+                &shape,
+                10.0,
+                |handle, collider| filter(handle, collider),
+            )
+        };
+
+        let (handle, _, toi) = cast_down(-3.0, &|_, _| true).unwrap();
+        assert_eq!(handle, trimesh);
+        assert!((toi.toi - 4.5).abs() < 1.0e-3);
+        assert!((toi.normal1.into_inner() + Vector::y()).norm() < 1.0e-3);
+
+        let (handle, _, toi) = cast_down(3.0, &|_, _| true).unwrap();
+        assert_eq!(handle, heightfield);
+        assert!((toi.toi - 4.5).abs() < 1.0e-3);
+
+        // Nothing is hit if the filter rejects the collider below the shape.
+        assert!(cast_down(-3.0, &|handle, _| handle != trimesh).is_none());
+        assert!(cast_down(3.0, &|handle, _| handle != trimesh).is_some());
+        assert!(cast_down(0.0, &|_, _| true).is_none());
+    }
+}
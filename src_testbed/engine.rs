@@ -6,7 +6,7 @@ use kiss3d::window::Window;
 
 use na::Point3;
 
-use crate::math::Point;
+use crate::math::{Isometry, Point};
 use crate::objects::ball::Ball;
 use crate::objects::box_node::Box as BoxNode;
 use crate::objects::convex::Convex;
@@ -15,18 +15,22 @@ use crate::objects::node::{GraphicsNode, Node};
 use rapier::dynamics::{RigidBodyHandle, RigidBodySet};
 use rapier::geometry::{Collider, ColliderHandle, ColliderSet, Shape};
 //use crate::objects::capsule::Capsule;
+#[cfg(feature = "dim3")]
+use crate::objects::{cone::Cone, cylinder::Cylinder};
 //use crate::objects::convex::Convex;
 //#[cfg(feature = "fluids")]
 //use crate::objects::fluid::Fluid as FluidNode;
 //#[cfg(feature = "dim3")]
 //use crate::objects::mesh::Mesh;
 //use crate::objects::plane::Plane;
-//#[cfg(feature = "dim2")]
-//use crate::objects::polyline::Polyline;
+#[cfg(feature = "dim2")]
+use crate::objects::polyline::Polyline;
 //#[cfg(feature = "fluids")]
 //use crate::objects::FluidRenderingMode;
 use crate::objects::capsule::Capsule;
 use crate::objects::mesh::Mesh;
+#[cfg(feature = "dim3")]
+use crate::objects::{cone::Cone, cylinder::Cylinder};
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg32;
 use std::collections::HashMap;
@@ -349,27 +353,72 @@ impl GraphicsManager {
         color: Point3<f32>,
         out: &mut Vec<Node>,
     ) {
-        match collider.shape() {
-            Shape::Ball(ball) => {
-                out.push(Node::Ball(Ball::new(handle, ball.radius, color, window)))
-            }
+        self.add_shape(
+            window,
+            handle,
+            collider.shape(),
+            &Isometry::identity(),
+            color,
+            out,
+        )
+    }
+
+    fn add_shape(
+        &mut self,
+        window: &mut Window,
+        handle: ColliderHandle,
+        shape: &Shape,
+        delta: &Isometry<f32>,
+        color: Point3<f32>,
+        out: &mut Vec<Node>,
+    ) {
+        let delta = *delta;
+
+        match shape {
+            Shape::Ball(ball) => out.push(Node::Ball(Ball::new(
+                handle,
+                delta,
+                ball.radius,
+                color,
+                window,
+            ))),
             Shape::Polygon(poly) => out.push(Node::Convex(Convex::new(
                 handle,
+                delta,
                 poly.vertices().to_vec(),
                 color,
                 window,
             ))),
             Shape::Cuboid(cuboid) => out.push(Node::Box(BoxNode::new(
                 handle,
+                delta,
                 cuboid.half_extents,
                 color,
                 window,
             ))),
-            Shape::Capsule(capsule) => {
-                out.push(Node::Capsule(Capsule::new(handle, capsule, color, window)))
+            Shape::Capsule(capsule) => out.push(Node::Capsule(Capsule::new(
+                handle, delta, capsule, color, window,
+            ))),
+            #[cfg(feature = "dim2")]
+            Shape::Segment(segment) => out.push(Node::Polyline(Polyline::new(
+                handle,
+                delta,
+                vec![segment.a, segment.b],
+                vec![na::Point2::new(0, 1)],
+                color,
+                window,
+            ))),
+            #[cfg(feature = "dim3")]
+            Shape::Segment(segment) => {
+                // Render segments as capsules with a zero radius.
+                let capsule = rapier::geometry::Capsule::new(segment.a, segment.b, 0.0);
+                out.push(Node::Capsule(Capsule::new(
+                    handle, delta, &capsule, color, window,
+                )))
             }
             Shape::Triangle(triangle) => out.push(Node::Mesh(Mesh::new(
                 handle,
+                delta,
                 vec![triangle.a, triangle.b, triangle.c],
                 vec![Point3::new(0, 1, 2)],
                 color,
@@ -377,6 +426,7 @@ impl GraphicsManager {
             ))),
             Shape::Trimesh(trimesh) => out.push(Node::Mesh(Mesh::new(
                 handle,
+                delta,
                 trimesh.vertices().to_vec(),
                 trimesh
                     .indices()
@@ -392,6 +442,63 @@ impl GraphicsManager {
                 color,
                 window,
             ))),
+            #[cfg(feature = "dim2")]
+            Shape::Polyline(polyline) => out.push(Node::Polyline(Polyline::new(
+                handle,
+                delta,
+                polyline.vertices().to_vec(),
+                polyline.indices().to_vec(),
+                color,
+                window,
+            ))),
+            Shape::Voxels(voxels) => {
+                // Render each voxel as a box. The nodes are created in the order of the ids
+                // of the voxels.
+                let half_extents = voxels.voxel_shape().half_extents;
+
+                for (_, key) in voxels.iter() {
+                    out.push(Node::Box(BoxNode::new(
+                        handle,
+                        delta * voxels.voxel_position(&key),
+                        half_extents,
+                        color,
+                        window,
+                    )))
+                }
+            }
+            Shape::Compound(compound) => {
+                for (sub_delta, sub_shape) in compound.shapes() {
+                    self.add_shape(window, handle, sub_shape, &(delta * sub_delta), color, out)
+                }
+            }
+            Shape::RoundShape(round) => match &*round.base_shape {
+                // Render rounded cuboids as slightly larger boxes.
+                Shape::Cuboid(cuboid) => out.push(Node::Box(BoxNode::new(
+                    handle,
+                    delta,
+                    cuboid.half_extents.add_scalar(round.border_radius),
+                    color,
+                    window,
+                ))),
+                base_shape => self.add_shape(window, handle, base_shape, &delta, color, out),
+            },
+            #[cfg(feature = "dim3")]
+            Shape::ConvexPolyhedron(poly) => out.push(Node::Mesh(Mesh::new(
+                handle,
+                delta,
+                poly.vertices().to_vec(),
+                poly.indices().iter().map(|idx| na::convert(*idx)).collect(),
+                color,
+                window,
+            ))),
+            #[cfg(feature = "dim3")]
+            Shape::Cylinder(cylinder) => out.push(Node::Cylinder(Cylinder::new(
+                handle, delta, cylinder, color, window,
+            ))),
+            #[cfg(feature = "dim3")]
+            Shape::Cone(cone) => {
+                out.push(Node::Cone(Cone::new(handle, delta, cone, color, window)))
+            }
         }
     }
 
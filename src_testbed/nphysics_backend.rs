@@ -1,3 +1,5 @@
+#[cfg(feature = "dim2")]
+use ncollide::shape::Polyline;
 use ncollide::shape::{Ball, Capsule, Cuboid, ShapeHandle};
 use nphysics::force_generator::DefaultForceGeneratorSet;
 use nphysics::joint::{
@@ -16,7 +18,10 @@ use rapier::geometry::{Collider, ColliderSet, Shape};
 use rapier::math::{Isometry, Vector};
 use std::collections::HashMap;
 #[cfg(feature = "dim3")]
-use {ncollide::shape::TriMesh, nphysics::joint::BallConstraint};
+use {
+    ncollide::shape::{Cone, ConvexHull, Cylinder, TriMesh},
+    nphysics::joint::BallConstraint,
+};
 
 pub struct NPhysicsWorld {
     rapier2nphysics: HashMap<RigidBodyHandle, DefaultBodyHandle>,
@@ -92,6 +97,18 @@ impl NPhysicsWorld {
                         RevoluteConstraint::new(b1, b2, params.local_anchor1, params.local_anchor2);
                     nphysics_joints.insert(c);
                 }
+                #[cfg(feature = "dim2")]
+                JointParams::RevoluteJoint(params) => {
+                    let mut c =
+                        RevoluteConstraint::new(b1, b2, params.local_anchor1, params.local_anchor2);
+
+                    if params.limits_enabled {
+                        c.enable_min_angle(params.limits[0]);
+                        c.enable_max_angle(params.limits[1]);
+                    }
+
+                    nphysics_joints.insert(c);
+                }
                 #[cfg(feature = "dim3")]
                 JointParams::RevoluteJoint(params) => {
                     let c = RevoluteConstraint::new(
@@ -120,6 +137,9 @@ impl NPhysicsWorld {
 
                     nphysics_joints.insert(c);
                 }
+                JointParams::GenericJoint(_) | JointParams::DistanceJoint(_) => {
+                    eprintln!("Creating joint unknown to the nphysics backend.")
+                }
             }
         }
 
@@ -187,7 +207,19 @@ fn nphysics_collider_from_rapier_collider(
             pos = capsule.transform_wrt_y();
             ShapeHandle::new(Capsule::new(capsule.half_height(), capsule.radius))
         }
+        Shape::Segment(segment) => ShapeHandle::new(*segment),
         Shape::HeightField(heightfield) => ShapeHandle::new(heightfield.clone()),
+        #[cfg(feature = "dim2")]
+        Shape::Polyline(polyline) => ShapeHandle::new(Polyline::new(
+            polyline.vertices().to_vec(),
+            Some(
+                polyline
+                    .indices()
+                    .iter()
+                    .map(|idx| na::convert(*idx))
+                    .collect(),
+            ),
+        )),
         #[cfg(feature = "dim3")]
         Shape::Trimesh(trimesh) => ShapeHandle::new(TriMesh::new(
             trimesh.vertices().to_vec(),
@@ -198,6 +230,19 @@ fn nphysics_collider_from_rapier_collider(
                 .collect(),
             None,
         )),
+        #[cfg(feature = "dim3")]
+        Shape::ConvexPolyhedron(poly) => {
+            ShapeHandle::new(ConvexHull::try_from_points(poly.vertices())?)
+        }
+        #[cfg(feature = "dim3")]
+        Shape::Cylinder(cylinder) => ShapeHandle::new(Cylinder::new(
+            cylinder.half_height - margin,
+            cylinder.radius - margin,
+        )),
+        #[cfg(feature = "dim3")]
+        Shape::Cone(cone) => {
+            ShapeHandle::new(Cone::new(cone.half_height - margin, cone.radius - margin))
+        }
         _ => return None,
     };
 
@@ -13,11 +13,13 @@ pub struct Convex {
     base_color: Point3<f32>,
     gfx: GraphicsNode,
     body: ColliderHandle,
+    delta: Isometry<f32>,
 }
 
 impl Convex {
     pub fn new(
         body: ColliderHandle,
+        delta: Isometry<f32>,
         vertices: Vec<Point<f32>>,
         color: Point3<f32>,
         window: &mut Window,
@@ -32,6 +34,7 @@ impl Convex {
             base_color: color,
             gfx: node,
             body,
+            delta,
         };
 
         // res.gfx.set_texture_from_file(&Path::new("media/kitten.png"), "kitten");
@@ -59,7 +62,7 @@ impl Convex {
             colliders,
             self.body,
             &self.color,
-            &Isometry::identity(),
+            &self.delta,
         );
     }
 
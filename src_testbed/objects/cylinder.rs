@@ -0,0 +1,74 @@
+use crate::objects::node::{self, GraphicsNode};
+use kiss3d::window;
+use na::Point3;
+use rapier::geometry::{self, ColliderHandle, ColliderSet};
+use rapier::math::Isometry;
+
+pub struct Cylinder {
+    color: Point3<f32>,
+    base_color: Point3<f32>,
+    gfx: GraphicsNode,
+    collider: ColliderHandle,
+    delta: Isometry<f32>,
+}
+
+impl Cylinder {
+    pub fn new(
+        collider: ColliderHandle,
+        delta: Isometry<f32>,
+        cylinder: &geometry::Cylinder,
+        color: Point3<f32>,
+        window: &mut window::Window,
+    ) -> Cylinder {
+        let r = cylinder.radius;
+        let h = cylinder.half_height * 2.0;
+        let node = window.add_cylinder(r, h);
+
+        let mut res = Cylinder {
+            color,
+            base_color: color,
+            gfx: node,
+            collider,
+            delta,
+        };
+
+        res.gfx.set_color(color.x, color.y, color.z);
+        res
+    }
+
+    pub fn select(&mut self) {
+        self.color = Point3::new(1.0, 0.0, 0.0);
+    }
+
+    pub fn unselect(&mut self) {
+        self.color = self.base_color;
+    }
+
+    pub fn update(&mut self, colliders: &ColliderSet) {
+        node::update_scene_node(
+            &mut self.gfx,
+            colliders,
+            self.collider,
+            &self.color,
+            &self.delta,
+        );
+    }
+
+    pub fn set_color(&mut self, color: Point3<f32>) {
+        self.gfx.set_color(color.x, color.y, color.z);
+        self.color = color;
+        self.base_color = color;
+    }
+
+    pub fn scene_node(&self) -> &GraphicsNode {
+        &self.gfx
+    }
+
+    pub fn scene_node_mut(&mut self) -> &mut GraphicsNode {
+        &mut self.gfx
+    }
+
+    pub fn object(&self) -> ColliderHandle {
+        self.collider
+    }
+}
@@ -11,11 +11,13 @@ pub struct Mesh {
     base_color: Point3<f32>,
     gfx: GraphicsNode,
     collider: ColliderHandle,
+    delta: Isometry<f32>,
 }
 
 impl Mesh {
     pub fn new(
         collider: ColliderHandle,
+        delta: Isometry<f32>,
         vertices: Vec<Point<f32>>,
         indices: Vec<Point3<u32>>,
         color: Point3<f32>,
@@ -47,6 +49,7 @@ impl Mesh {
             base_color: color,
             gfx,
             collider,
+            delta,
         };
 
         res.gfx.enable_backface_culling(false);
@@ -74,7 +77,7 @@ impl Mesh {
             colliders,
             self.collider,
             &self.color,
-            &Isometry::identity(),
+            &self.delta,
         );
 
         // // Update if some deformation occurred.
@@ -1,10 +1,14 @@
 pub mod ball;
 pub mod box_node;
 pub mod capsule;
+#[cfg(feature = "dim3")]
+pub mod cone;
 pub mod convex;
+#[cfg(feature = "dim3")]
+pub mod cylinder;
 pub mod heightfield;
 pub mod mesh;
 pub mod node;
 //pub mod plane;
-//#[cfg(feature = "dim2")]
-//pub mod polyline;
+#[cfg(feature = "dim2")]
+pub mod polyline;
@@ -1,12 +1,16 @@
 use crate::objects::ball::Ball;
 use crate::objects::box_node::Box;
 use crate::objects::capsule::Capsule;
+#[cfg(feature = "dim3")]
+use crate::objects::cone::Cone;
 use crate::objects::convex::Convex;
+#[cfg(feature = "dim3")]
+use crate::objects::cylinder::Cylinder;
 use crate::objects::heightfield::HeightField;
 use crate::objects::mesh::Mesh;
 //use crate::objects::plane::Plane;
-//#[cfg(feature = "dim2")]
-//use crate::objects::polyline::Polyline;
+#[cfg(feature = "dim2")]
+use crate::objects::polyline::Polyline;
 use kiss3d::window::Window;
 use na::Point3;
 
@@ -24,10 +28,14 @@ pub enum Node {
     Box(Box),
     HeightField(HeightField),
     Capsule(Capsule),
-    //    #[cfg(feature = "dim2")]
-    //    Polyline(Polyline),
+    #[cfg(feature = "dim2")]
+    Polyline(Polyline),
     Mesh(Mesh),
     Convex(Convex),
+    #[cfg(feature = "dim3")]
+    Cylinder(Cylinder),
+    #[cfg(feature = "dim3")]
+    Cone(Cone),
 }
 
 impl Node {
@@ -38,10 +46,14 @@ impl Node {
             Node::Box(ref mut n) => n.select(),
             Node::Capsule(ref mut n) => n.select(),
             Node::HeightField(ref mut n) => n.select(),
-            //            #[cfg(feature = "dim2")]
-            //            Node::Polyline(ref mut n) => n.select(),
+            #[cfg(feature = "dim2")]
+            Node::Polyline(ref mut n) => n.select(),
             Node::Mesh(ref mut n) => n.select(),
             Node::Convex(ref mut n) => n.select(),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref mut n) => n.select(),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref mut n) => n.select(),
         }
     }
 
@@ -52,10 +64,14 @@ impl Node {
             Node::Box(ref mut n) => n.unselect(),
             Node::Capsule(ref mut n) => n.unselect(),
             Node::HeightField(ref mut n) => n.unselect(),
-            //            #[cfg(feature = "dim2")]
-            //            Node::Polyline(ref mut n) => n.unselect(),
+            #[cfg(feature = "dim2")]
+            Node::Polyline(ref mut n) => n.unselect(),
             Node::Mesh(ref mut n) => n.unselect(),
             Node::Convex(ref mut n) => n.unselect(),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref mut n) => n.unselect(),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref mut n) => n.unselect(),
         }
     }
 
@@ -66,17 +82,21 @@ impl Node {
             Node::Box(ref mut n) => n.update(colliders),
             Node::Capsule(ref mut n) => n.update(colliders),
             Node::HeightField(ref mut n) => n.update(colliders),
-            //            #[cfg(feature = "dim2")]
-            //            Node::Polyline(ref mut n) => n.update(colliders),
+            #[cfg(feature = "dim2")]
+            Node::Polyline(ref mut n) => n.update(colliders),
             Node::Mesh(ref mut n) => n.update(colliders),
             Node::Convex(ref mut n) => n.update(colliders),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref mut n) => n.update(colliders),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref mut n) => n.update(colliders),
         }
     }
 
     #[cfg(feature = "dim2")]
     pub fn draw(&mut self, window: &mut Window) {
         match *self {
-            //                    Node::Polyline(ref mut n) => n.draw(_window),
+            Node::Polyline(ref mut n) => n.draw(window),
             Node::HeightField(ref mut n) => n.draw(window),
             //            Node::Plane(ref mut n) => n.draw(_window),
             _ => {}
@@ -97,6 +117,10 @@ impl Node {
             Node::HeightField(ref n) => Some(n.scene_node()),
             Node::Mesh(ref n) => Some(n.scene_node()),
             Node::Convex(ref n) => Some(n.scene_node()),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref n) => Some(n.scene_node()),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref n) => Some(n.scene_node()),
             #[cfg(feature = "dim2")]
             _ => None,
         }
@@ -113,6 +137,10 @@ impl Node {
             Node::HeightField(ref mut n) => Some(n.scene_node_mut()),
             Node::Mesh(ref mut n) => Some(n.scene_node_mut()),
             Node::Convex(ref mut n) => Some(n.scene_node_mut()),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref mut n) => Some(n.scene_node_mut()),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref mut n) => Some(n.scene_node_mut()),
             #[cfg(feature = "dim2")]
             _ => None,
         }
@@ -125,10 +153,14 @@ impl Node {
             Node::Box(ref n) => n.object(),
             Node::Capsule(ref n) => n.object(),
             Node::HeightField(ref n) => n.object(),
-            //            #[cfg(feature = "dim2")]
-            //            Node::Polyline(ref n) => n.object(),
+            #[cfg(feature = "dim2")]
+            Node::Polyline(ref n) => n.object(),
             Node::Mesh(ref n) => n.object(),
             Node::Convex(ref n) => n.object(),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref n) => n.object(),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref n) => n.object(),
         }
     }
 
@@ -139,10 +171,14 @@ impl Node {
             Node::Box(ref mut n) => n.set_color(color),
             Node::Capsule(ref mut n) => n.set_color(color),
             Node::HeightField(ref mut n) => n.set_color(color),
-            //            #[cfg(feature = "dim2")]
-            //            Node::Polyline(ref mut n) => n.set_color(color),
+            #[cfg(feature = "dim2")]
+            Node::Polyline(ref mut n) => n.set_color(color),
             Node::Mesh(ref mut n) => n.set_color(color),
             Node::Convex(ref mut n) => n.set_color(color),
+            #[cfg(feature = "dim3")]
+            Node::Cylinder(ref mut n) => n.set_color(color),
+            #[cfg(feature = "dim3")]
+            Node::Cone(ref mut n) => n.set_color(color),
         }
     }
 }
@@ -1,38 +1,36 @@
 use kiss3d::window::Window;
-use na::{Isometry2, Point2, Point3};
-use ncollide2d::shape;
-use nphysics2d::object::{ColliderAnchor, DefaultColliderHandle, DefaultColliderSet};
+use na::{Point2, Point3};
+use rapier::geometry::{ColliderHandle, ColliderSet};
+use rapier::math::{Isometry, Point};
 
 pub struct Polyline {
     color: Point3<f32>,
     base_color: Point3<f32>,
-    vertices: Vec<Point2<f32>>,
-    indices: Vec<Point2<usize>>,
-    collider: DefaultColliderHandle,
-    pos: Isometry2<f32>,
+    vertices: Vec<Point<f32>>,
+    indices: Vec<Point2<u32>>,
+    collider: ColliderHandle,
+    delta: Isometry<f32>,
+    pos: Isometry<f32>,
 }
 
 impl Polyline {
     pub fn new(
-        collider: DefaultColliderHandle,
-        colliders: &DefaultColliderSet<f32>,
-        _: Isometry2<f32>,
-        vertices: Vec<Point2<f32>>,
-        indices: Vec<Point2<usize>>,
+        collider: ColliderHandle,
+        delta: Isometry<f32>,
+        vertices: Vec<Point<f32>>,
+        indices: Vec<Point2<u32>>,
         color: Point3<f32>,
         _: &mut Window,
     ) -> Polyline {
-        let mut res = Polyline {
+        Polyline {
             color,
-            pos: Isometry2::identity(),
             base_color: color,
             vertices,
             indices,
             collider,
-        };
-
-        res.update(colliders);
-        res
+            delta,
+            pos: delta,
+        }
     }
 
     pub fn select(&mut self) {
@@ -48,31 +46,20 @@ impl Polyline {
         self.base_color = color;
     }
 
-    pub fn update(&mut self, colliders: &DefaultColliderSet<f32>) {
-        // Update if some deformation occurred.
-        // FIXME: don't update if it did not move.
-        if let Some(c) = colliders.get(self.collider) {
-            self.pos = *c.position();
-            if let ColliderAnchor::OnDeformableBody { .. } = c.anchor() {
-                let shape = c.shape().as_shape::<shape::Polyline<f32>>().unwrap();
-                self.vertices = shape.points().to_vec();
-                self.indices.clear();
-
-                for e in shape.edges() {
-                    self.indices.push(e.indices);
-                }
-            }
+    pub fn update(&mut self, colliders: &ColliderSet) {
+        if let Some(co) = colliders.get(self.collider) {
+            self.pos = co.position() * self.delta;
         }
     }
 
-    pub fn object(&self) -> DefaultColliderHandle {
+    pub fn object(&self) -> ColliderHandle {
         self.collider
     }
 
     pub fn draw(&mut self, window: &mut Window) {
         for idx in &self.indices {
-            let p1 = self.pos * self.vertices[idx.x];
-            let p2 = self.pos * self.vertices[idx.y];
+            let p1 = self.pos * self.vertices[idx.x as usize];
+            let p2 = self.pos * self.vertices[idx.y as usize];
             window.draw_planar_line(&p1, &p2, &self.color)
         }
     }
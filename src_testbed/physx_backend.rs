@@ -367,6 +367,9 @@ impl PhysxWorld {
                             &frame2 as *const _,
                         );
                     }
+                    JointParams::GenericJoint(_) | JointParams::DistanceJoint(_) => {
+                        eprintln!("Creating joint unknown to the PhysX backend.")
+                    }
                 }
             }
         }
@@ -19,7 +19,7 @@ use kiss3d::text::Font;
 use kiss3d::window::{State, Window};
 use na::{self, Point2, Point3, Vector3};
 use rapier::dynamics::{
-    ActivationStatus, IntegrationParameters, JointSet, RigidBodyHandle, RigidBodySet,
+    ActivationStatus, IntegrationParameters, JointEvent, JointSet, RigidBodyHandle, RigidBodySet,
 };
 use rapier::geometry::{BroadPhase, ColliderSet, ContactEvent, NarrowPhase, ProximityEvent};
 use rapier::math::Vector;
@@ -163,12 +163,14 @@ impl PhysicsSnapshot {
 pub struct PhysicsEvents {
     pub contact_events: Receiver<ContactEvent>,
     pub proximity_events: Receiver<ProximityEvent>,
+    pub joint_events: Receiver<JointEvent>,
 }
 
 impl PhysicsEvents {
     fn poll_all(&self) {
         while let Ok(_) = self.contact_events.try_recv() {}
         while let Ok(_) = self.proximity_events.try_recv() {}
+        while let Ok(_) = self.joint_events.try_recv() {}
     }
 }
 
@@ -323,10 +325,13 @@ impl Testbed {
         let joints = JointSet::new();
         let contact_channel = crossbeam::channel::unbounded();
         let proximity_channel = crossbeam::channel::unbounded();
-        let event_handler = ChannelEventCollector::new(proximity_channel.0, contact_channel.0);
+        let joint_channel = crossbeam::channel::unbounded();
+        let event_handler = ChannelEventCollector::new(proximity_channel.0, contact_channel.0)
+            .with_joint_event_sender(joint_channel.0);
         let events = PhysicsEvents {
             contact_events: contact_channel.1,
             proximity_events: proximity_channel.1,
+            joint_events: joint_channel.1,
         };
 
         Testbed {
@@ -659,6 +664,7 @@ impl Testbed {
                                             bodies,
                                             colliders,
                                             joints,
+                                            &(),
                                             event_handler,
                                         );
                                     });
@@ -673,6 +679,7 @@ impl Testbed {
                                     &mut self.bodies,
                                     &mut self.colliders,
                                     &mut self.joints,
+                                    &(),
                                     &self.event_handler,
                                 );
 
@@ -1404,6 +1411,7 @@ impl State for Testbed {
                                 bodies,
                                 colliders,
                                 joints,
+                                &(),
                                 event_handler,
                             );
                         });
@@ -1418,6 +1426,7 @@ impl State for Testbed {
                         &mut self.bodies,
                         &mut self.colliders,
                         &mut self.joints,
+                        &(),
                         &self.event_handler,
                     );
 